@@ -1,7 +1,7 @@
 # {{crate}}
 
 [![crates.io](https://img.shields.io/crates/v/scapegoat.svg)](https://crates.io/crates/scapegoat)
-![MSRV 1.55+](https://img.shields.io/badge/rustc-1.55+-yellow.svg)
+![MSRV 1.81+](https://img.shields.io/badge/rustc-1.81+-yellow.svg)
 [![docs.rs](https://docs.rs/scapegoat/badge.svg)](https://docs.rs/scapegoat/)
 [![GitHub Actions](https://github.com/tnballo/scapegoat/workflows/test/badge.svg)](https://github.com/tnballo/scapegoat/actions)
 [![License: MIT](https://img.shields.io/badge/License-MIT-brightgreen.svg)](https://github.com/tnballo/scapegoat/blob/master/LICENSE)
@@ -4,6 +4,7 @@ use std::convert::TryInto;
 use criterion::{criterion_group, criterion_main, Criterion};
 use scapegoat::SgSet;
 
+#[allow(dead_code)]
 mod test_data;
 use test_data::{RAND_10_000, SEQ_10_000};
 
@@ -4,6 +4,7 @@ use std::iter::FromIterator;
 use criterion::{criterion_group, criterion_main, Criterion};
 use scapegoat::SgSet;
 
+#[allow(dead_code)]
 mod test_data;
 use test_data::{
     RAND_100, RAND_10_000, RAND_1_000, SGS_100_RAND, SGS_10_000_RAND, SGS_1_000_RAND, STD_100_RAND,
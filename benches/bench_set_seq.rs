@@ -4,6 +4,7 @@ use std::iter::FromIterator;
 use criterion::{criterion_group, criterion_main, Criterion};
 use scapegoat::SgSet;
 
+#[allow(dead_code)]
 mod test_data;
 use test_data::{
     SEQ_100, SEQ_10_000, SEQ_1_000, SGS_100_SEQ, SGS_10_000_SEQ, SGS_1_000_SEQ, STD_100_SEQ,
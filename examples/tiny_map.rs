@@ -3,20 +3,34 @@ use scapegoat::SgMap;
 
 fn main() {
     let tiny_map: SgMap<u8, u8, 128> = SgMap::new();
+    let tiny_map_size = size_of_val(&tiny_map);
+    println!("SgMap<u8, u8, 128>: {} bytes", tiny_map_size);
 
     // Default configuration
     #[cfg(target_pointer_width = "64")]
     #[cfg(not(feature = "fast_rebalance"))]
     #[cfg(not(feature = "low_mem_insert"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
+    #[cfg(not(feature = "alloc"))]
+    #[cfg(not(feature = "telemetry"))]
     {
-        assert_eq!(size_of_val(&tiny_map), 1_608);
+        assert_eq!(tiny_map_size, 1_104);
     }
 
     // Optimizing for low stack footprint
     #[cfg(target_pointer_width = "64")]
     #[cfg(not(feature = "fast_rebalance"))]
     #[cfg(feature = "low_mem_insert")]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
+    #[cfg(not(feature = "alloc"))]
+    #[cfg(not(feature = "telemetry"))]
     {
-        assert_eq!(size_of_val(&tiny_map), 1_352);
+        assert_eq!(tiny_map_size, 848);
     }
 }
@@ -0,0 +1,229 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::sync::atomic::Ordering;
+
+use crate::map::SgMap;
+use crate::map_types::Iter;
+use crate::tree::SgError;
+
+// Atomic Cell Trait ---------------------------------------------------------------------------------------------------
+
+/// Interface over `core::sync::atomic` types, for use as [`SgAtomicMap`] values.
+pub trait AtomicCell: Default {
+    /// Underlying `Copy` value type (e.g. `u32` for `AtomicU32`).
+    type Value: Copy;
+
+    /// Create a new cell.
+    fn new(val: Self::Value) -> Self;
+
+    /// Consume the cell, returning its value.
+    fn into_value(self) -> Self::Value;
+
+    /// Load the current value.
+    fn load(&self, order: Ordering) -> Self::Value;
+
+    /// Store a new value.
+    fn store(&self, val: Self::Value, order: Ordering);
+
+    /// Store a new value, returning the previous one.
+    fn swap(&self, val: Self::Value, order: Ordering) -> Self::Value;
+}
+
+macro_rules! impl_atomic_cell {
+    ( $( $width:literal => $( $atomic:ident($val:ty) ),+ );+ $(;)? ) => {
+        $(
+            $(
+                #[cfg(target_has_atomic = $width)]
+                impl AtomicCell for core::sync::atomic::$atomic {
+                    type Value = $val;
+
+                    fn new(val: $val) -> Self {
+                        core::sync::atomic::$atomic::new(val)
+                    }
+
+                    fn into_value(self) -> $val {
+                        self.into_inner()
+                    }
+
+                    fn load(&self, order: Ordering) -> $val {
+                        self.load(order)
+                    }
+
+                    fn store(&self, val: $val, order: Ordering) {
+                        self.store(val, order)
+                    }
+
+                    fn swap(&self, val: $val, order: Ordering) -> $val {
+                        self.swap(val, order)
+                    }
+                }
+            )+
+        )+
+    };
+}
+
+impl_atomic_cell! {
+    "8" => AtomicBool(bool), AtomicU8(u8), AtomicI8(i8);
+    "16" => AtomicU16(u16), AtomicI16(i16);
+    "32" => AtomicU32(u32), AtomicI32(i32);
+    "64" => AtomicU64(u64), AtomicI64(i64);
+    "ptr" => AtomicUsize(usize), AtomicIsize(isize);
+}
+
+// Atomic Map ----------------------------------------------------------------------------------------------------------
+
+/// Ordered map with atomic values: values can be updated through a shared reference, without locking.
+///
+/// Structural changes (insertion and removal of keys) require `&mut self`, e.g. a single owner.
+/// Value reads and writes only require `&self`, so they can happen concurrently from other cores or interrupt handlers.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use scapegoat::SgAtomicMap;
+/// use std::thread;
+///
+/// let mut counters = SgAtomicMap::<&str, AtomicU32, 10>::new();
+/// counters.insert("rx", 0);
+/// counters.insert("tx", 0);
+///
+/// thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             counters.get("rx").unwrap().fetch_add(1, Ordering::Relaxed);
+///         });
+///     }
+/// });
+///
+/// assert!(counters.store("tx", 7, Ordering::Relaxed));
+/// assert_eq!(counters.load("rx", Ordering::Relaxed), Some(4));
+/// assert_eq!(counters.load("tx", Ordering::Relaxed), Some(7));
+/// ```
+pub struct SgAtomicMap<K: Ord, A: AtomicCell, const N: usize> {
+    map: SgMap<K, A, N>,
+}
+
+impl<K: Ord, A: AtomicCell, const N: usize> SgAtomicMap<K, A, N> {
+    /// Makes a new, empty `SgAtomicMap`.
+    pub fn new() -> Self {
+        SgAtomicMap { map: SgMap::new() }
+    }
+
+    /// Total capacity, e.g. maximum number of map pairs.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Insert a key-value pair into the map, returning the old value if the key was present.
+    /// Panics if capacity is exceeded.
+    pub fn insert(&mut self, key: K, val: A::Value) -> Option<A::Value> {
+        self.map.insert(key, A::new(val)).map(A::into_value)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if capacity is exceeded, else the `Ok` contains the old value, if any.
+    pub fn try_insert(&mut self, key: K, val: A::Value) -> Result<Option<A::Value>, SgError> {
+        self.map
+            .try_insert(key, A::new(val))
+            .map(|opt_old| opt_old.map(A::into_value))
+    }
+
+    /// Removes a key from the map, returning its last value if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<A::Value>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(key).map(A::into_value)
+    }
+
+    /// Returns a reference to the atomic cell for the key, e.g. for read-modify-write operations.
+    pub fn get<Q>(&self, key: &Q) -> Option<&A>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Load the value for the key, if present.
+    pub fn load<Q>(&self, key: &Q, order: Ordering) -> Option<A::Value>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key).map(|cell| cell.load(order))
+    }
+
+    /// Store a value for an existing key, through a shared reference.
+    /// Returns `false`, storing nothing, if the key isn't present (insertion requires `&mut self`).
+    pub fn store<Q>(&self, key: &Q, val: A::Value, order: Ordering) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.map.get(key) {
+            Some(cell) => {
+                cell.store(val, order);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Store a value for an existing key, through a shared reference, returning the previous value.
+    /// Returns `None`, storing nothing, if the key isn't present.
+    pub fn swap<Q>(&self, key: &Q, val: A::Value, order: Ordering) -> Option<A::Value>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key).map(|cell| cell.swap(val, order))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, A, N> {
+        self.map.iter()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+}
+
+impl<K: Ord, A: AtomicCell, const N: usize> Default for SgAtomicMap<K, A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, A, const N: usize> Debug for SgAtomicMap<K, A, N>
+where
+    K: Ord + Debug,
+    A: AtomicCell + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
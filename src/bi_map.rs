@@ -0,0 +1,199 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+// Pairs displaced by an insert: the one holding the new left value, then the one holding the new right value
+type Displaced<L, R> = (Option<(L, R)>, Option<(L, R)>);
+
+/// Bidirectional one-to-one map, up to `N` left-right pairs.
+///
+/// Both sides are unique and looked up in `O(log n)`: a pair is stored in a left-to-right and a right-to-left map,
+/// each holding its own copy of both values. Inserting a pair displaces any pair sharing either side.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgBiMap;
+///
+/// // Pin number <-> peripheral
+/// let mut pins = SgBiMap::<u8, &str, 8>::new();
+/// pins.insert(4, "uart_tx");
+/// pins.insert(5, "uart_rx");
+///
+/// assert_eq!(pins.get_by_left(&4), Some(&"uart_tx"));
+/// assert_eq!(pins.get_by_right("uart_rx"), Some(&5));
+///
+/// // Re-map a peripheral, displacing its old pin
+/// assert_eq!(pins.insert(6, "uart_tx"), (None, Some((4, "uart_tx"))));
+/// assert!(!pins.contains_left(&4));
+/// assert!(pins.iter().eq([(&5, &"uart_rx"), (&6, &"uart_tx")]));
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SgBiMap<L: Ord, R: Ord, const N: usize> {
+    left_to_right: SgMap<L, R, N>,
+    right_to_left: SgMap<R, L, N>,
+}
+
+impl<L: Ord + Clone, R: Ord + Clone, const N: usize> SgBiMap<L, R, N> {
+    /// Makes a new, empty `SgBiMap`.
+    pub fn new() -> Self {
+        SgBiMap {
+            left_to_right: SgMap::new(),
+            right_to_left: SgMap::new(),
+        }
+    }
+
+    /// Number of left-right pairs.
+    pub fn len(&self) -> usize {
+        self.left_to_right.len()
+    }
+
+    /// Returns `true` if the map contains no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.left_to_right.is_empty()
+    }
+
+    /// Maximum number of pairs, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.left_to_right.capacity()
+    }
+
+    /// Remove all pairs.
+    pub fn clear(&mut self) {
+        self.left_to_right.clear();
+        self.right_to_left.clear();
+    }
+
+    /// Insert a left-right pair, returning the displaced pairs:
+    /// the one holding `left`, then the one holding `right` (`None` if both are the same pair).
+    ///
+    /// # Panics
+    ///
+    /// Panics if neither side is present and the map is full.
+    /// Use [`try_insert`][SgBiMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, left: L, right: R) -> Displaced<L, R> {
+        match self.try_insert(left, right) {
+            Ok(displaced) => displaced,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Insert a left-right pair, returning the displaced pairs:
+    /// the one holding `left`, then the one holding `right` (`None` if both are the same pair).
+    /// Returns `Err`, leaving the map unchanged, if neither side is present and the map is full.
+    pub fn try_insert(&mut self, left: L, right: R) -> Result<Displaced<L, R>, SgError> {
+        if !self.contains_left(&left)
+            && !self.contains_right(&right)
+            && (self.len() >= self.left_to_right.max_len())
+        {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let by_left = self.remove_by_left(&left);
+        let by_right = self.remove_by_right(&right);
+
+        self.left_to_right.insert(left.clone(), right.clone());
+        self.right_to_left.insert(right, left);
+
+        Ok((by_left, by_right))
+    }
+
+    /// Get the right value paired with a left value, if any.
+    pub fn get_by_left<Q>(&self, left: &Q) -> Option<&R>
+    where
+        L: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.left_to_right.get(left)
+    }
+
+    /// Get the left value paired with a right value, if any.
+    pub fn get_by_right<Q>(&self, right: &Q) -> Option<&L>
+    where
+        R: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.right_to_left.get(right)
+    }
+
+    /// Returns `true` if a pair holds the left value.
+    pub fn contains_left<Q>(&self, left: &Q) -> bool
+    where
+        L: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.left_to_right.contains_key(left)
+    }
+
+    /// Returns `true` if a pair holds the right value.
+    pub fn contains_right<Q>(&self, right: &Q) -> bool
+    where
+        R: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.right_to_left.contains_key(right)
+    }
+
+    /// Remove the pair holding a left value, returning it if present.
+    pub fn remove_by_left<Q>(&mut self, left: &Q) -> Option<(L, R)>
+    where
+        L: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (left, right) = self.left_to_right.remove_entry(left)?;
+        self.right_to_left.remove(&right);
+        Some((left, right))
+    }
+
+    /// Remove the pair holding a right value, returning it if present.
+    pub fn remove_by_right<Q>(&mut self, right: &Q) -> Option<(L, R)>
+    where
+        R: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (right, left) = self.right_to_left.remove_entry(right)?;
+        self.left_to_right.remove(&left);
+        Some((left, right))
+    }
+
+    /// Gets an iterator over the pairs, in ascending order of left value.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&L, &R)> + ExactSizeIterator + '_ {
+        self.left_to_right.iter()
+    }
+
+    /// Gets an iterator over the pairs, in ascending order of right value.
+    pub fn iter_by_right(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (&L, &R)> + ExactSizeIterator + '_ {
+        self.right_to_left.iter().map(|(right, left)| (left, right))
+    }
+}
+
+impl<L, R, const N: usize> Debug for SgBiMap<L, R, N>
+where
+    L: Ord + Clone + Debug,
+    R: Ord + Clone + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<L: Ord + Clone, R: Ord + Clone, const N: usize> Extend<(L, R)> for SgBiMap<L, R, N> {
+    fn extend<I: IntoIterator<Item = (L, R)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(left, right)| {
+            self.insert(left, right);
+        });
+    }
+}
+
+impl<L: Ord + Clone, R: Ord + Clone, const N: usize> FromIterator<(L, R)> for SgBiMap<L, R, N> {
+    fn from_iter<I: IntoIterator<Item = (L, R)>>(iter: I) -> Self {
+        let mut bi_map = SgBiMap::new();
+        bi_map.extend(iter);
+        bi_map
+    }
+}
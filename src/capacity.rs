@@ -0,0 +1,78 @@
+use crate::map::SgMap;
+use crate::set::SgSet;
+
+/// Common interface for fixed-capacity collections.
+///
+/// Lets generic code be written over "any bounded collection" - implemented here for
+/// [`SgMap`][crate::map::SgMap] and [`SgSet`][crate::set::SgSet], and implementable by downstream crates
+/// for other stack-allocated collections.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{CapacityBound, SgMap, SgSet};
+///
+/// fn headroom<C: CapacityBound>(c: &C) -> usize {
+///     C::CAPACITY - c.len()
+/// }
+///
+/// let mut map = SgMap::<u8, u8, 10>::new();
+/// let mut set = SgSet::<u8, 4>::new();
+///
+/// map.insert(1, 1);
+/// set.insert(1);
+///
+/// assert_eq!(headroom(&map), 9);
+/// assert_eq!(headroom(&set), 3);
+/// ```
+pub trait CapacityBound {
+    /// Compile-time capacity, e.g. maximum number of items.
+    const CAPACITY: usize;
+
+    /// Returns the number of items currently stored.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if no items are stored.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if no more items can be stored.
+    fn is_full(&self) -> bool {
+        self.len() >= Self::CAPACITY
+    }
+}
+
+impl<K: Ord, V, const N: usize> CapacityBound for SgMap<K, V, N> {
+    const CAPACITY: usize = N;
+
+    fn len(&self) -> usize {
+        SgMap::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SgMap::is_empty(self)
+    }
+
+    // Accounts for runtime limit, see `SgMap::set_max_len`
+    fn is_full(&self) -> bool {
+        SgMap::is_full(self)
+    }
+}
+
+impl<T: Ord, const N: usize> CapacityBound for SgSet<T, N> {
+    const CAPACITY: usize = N;
+
+    fn len(&self) -> usize {
+        SgSet::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SgSet::is_empty(self)
+    }
+
+    // Accounts for runtime limit, see `SgSet::set_max_len`
+    fn is_full(&self) -> bool {
+        SgSet::is_full(self)
+    }
+}
@@ -0,0 +1,257 @@
+use core::fmt::{self, Debug};
+
+use crate::map::SgMap;
+use crate::map_types::Iter;
+use crate::tree::SgError;
+
+// Direct Key Trait ----------------------------------------------------------------------------------------------------
+
+/// Keys from a small, finite domain, each with an order-preserving index into `0..DOMAIN`.
+/// Allows [`SgDirectMap`] to use a direct-indexed table instead of a tree.
+pub trait DirectKey: Ord + Default + Copy {
+    /// Number of distinct keys.
+    const DOMAIN: usize;
+
+    /// Index of the key, in `0..DOMAIN`. Must preserve key order.
+    fn to_index(self) -> usize;
+
+    /// Key for an index in `0..DOMAIN`, inverse of [`to_index`][DirectKey::to_index].
+    fn from_index(idx: usize) -> Self;
+}
+
+impl DirectKey for u8 {
+    const DOMAIN: usize = 256;
+
+    fn to_index(self) -> usize {
+        usize::from(self)
+    }
+
+    fn from_index(idx: usize) -> Self {
+        idx as u8
+    }
+}
+
+impl DirectKey for i8 {
+    const DOMAIN: usize = 256;
+
+    // Flip sign bit, so `i8::MIN` maps to `0`
+    fn to_index(self) -> usize {
+        usize::from((self as u8) ^ 0x80)
+    }
+
+    fn from_index(idx: usize) -> Self {
+        ((idx as u8) ^ 0x80) as i8
+    }
+}
+
+impl DirectKey for bool {
+    const DOMAIN: usize = 2;
+
+    fn to_index(self) -> usize {
+        usize::from(self)
+    }
+
+    fn from_index(idx: usize) -> Self {
+        idx != 0
+    }
+}
+
+// Direct Map ----------------------------------------------------------------------------------------------------------
+
+#[allow(clippy::large_enum_variant)] // Stack-only, can't box. Variant sizes depend on `N`, only one is ever used.
+enum Storage<K: DirectKey, V, const N: usize> {
+    Direct { slots: [Option<V>; N], len: usize },
+    Tree(SgMap<K, V, N>),
+}
+
+/// Ordered map for small key domains (e.g. `u8` opcodes).
+/// If capacity `N` covers every possible key, entries live in a direct-indexed table with `O(1)` operations.
+/// Otherwise, it falls back to a [`SgMap`][crate::map::SgMap].
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgDirectMap;
+///
+/// // Capacity covers all `u8` keys, direct-indexed
+/// let mut handlers = SgDirectMap::<u8, u16, 256>::new();
+/// assert!(handlers.is_direct());
+///
+/// handlers.insert(0xfe, 0x8000);
+/// handlers.insert(0x01, 0x4000);
+/// assert_eq!(handlers.get(&0xfe), Some(&0x8000));
+/// assert!(handlers.iter().map(|(op, _)| op).eq([0x01, 0xfe]));
+///
+/// // Capacity doesn't cover all keys, tree-backed with the same API
+/// let mut sparse = SgDirectMap::<u8, &str, 8>::new();
+/// assert!(!sparse.is_direct());
+/// sparse.insert(0x10, "ld");
+/// assert_eq!(sparse.get(&0x10), Some(&"ld"));
+/// ```
+pub struct SgDirectMap<K: DirectKey, V, const N: usize> {
+    storage: Storage<K, V, N>,
+}
+
+impl<K: DirectKey, V, const N: usize> SgDirectMap<K, V, N> {
+    /// Makes a new, empty `SgDirectMap`.
+    pub fn new() -> Self {
+        let storage = match N >= K::DOMAIN {
+            true => Storage::Direct {
+                slots: core::array::from_fn(|_| None),
+                len: 0,
+            },
+            false => Storage::Tree(SgMap::new()),
+        };
+
+        SgDirectMap { storage }
+    }
+
+    /// Returns `true` if entries are direct-indexed, e.g. capacity covers the entire key domain.
+    pub fn is_direct(&self) -> bool {
+        matches!(self.storage, Storage::Direct { .. })
+    }
+
+    /// Total capacity, e.g. maximum number of map pairs.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Insert a key-value pair into the map, returning the old value if the key was present.
+    /// Panics if capacity is exceeded.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match &mut self.storage {
+            Storage::Direct { slots, len } => {
+                let old = slots[key.to_index()].replace(val);
+                if old.is_none() {
+                    *len += 1;
+                }
+                old
+            }
+            Storage::Tree(map) => map.insert(key, val),
+        }
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if capacity is exceeded, else the `Ok` contains the old value, if any.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        match &mut self.storage {
+            Storage::Direct { .. } => Ok(self.insert(key, val)),
+            Storage::Tree(map) => map.try_insert(key, val),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match &self.storage {
+            Storage::Direct { slots, .. } => slots[key.to_index()].as_ref(),
+            Storage::Tree(map) => map.get(key),
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match &mut self.storage {
+            Storage::Direct { slots, .. } => slots[key.to_index()].as_mut(),
+            Storage::Tree(map) => map.get_mut(key),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match &mut self.storage {
+            Storage::Direct { slots, len } => {
+                let old = slots[key.to_index()].take();
+                if old.is_some() {
+                    *len -= 1;
+                }
+                old
+            }
+            Storage::Tree(map) => map.remove(key),
+        }
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> DirectIter<'_, K, V, N> {
+        let inner = match &self.storage {
+            Storage::Direct { slots, .. } => DirectIterInner::Direct(slots.iter().enumerate()),
+            Storage::Tree(map) => DirectIterInner::Tree(map.iter()),
+        };
+
+        DirectIter { inner }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Direct { len, .. } => *len,
+            Storage::Tree(map) => map.len(),
+        }
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        match &mut self.storage {
+            Storage::Direct { slots, len } => {
+                slots.iter_mut().for_each(|slot| *slot = None);
+                *len = 0;
+            }
+            Storage::Tree(map) => map.clear(),
+        }
+    }
+}
+
+impl<K: DirectKey, V, const N: usize> Default for SgDirectMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> Debug for SgDirectMap<K, V, N>
+where
+    K: DirectKey + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Iterator ------------------------------------------------------------------------------------------------------------
+
+enum DirectIterInner<'a, K: DirectKey, V, const N: usize> {
+    Direct(core::iter::Enumerate<core::slice::Iter<'a, Option<V>>>),
+    Tree(Iter<'a, K, V, N>),
+}
+
+/// Ordered iterator over the entries of an [`SgDirectMap`], created by [`SgDirectMap::iter`].
+/// Yields keys by value, since direct-indexed entries don't store them.
+pub struct DirectIter<'a, K: DirectKey, V, const N: usize> {
+    inner: DirectIterInner<'a, K, V, N>,
+}
+
+impl<'a, K: DirectKey, V, const N: usize> Iterator for DirectIter<'a, K, V, N> {
+    type Item = (K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            DirectIterInner::Direct(slots) => slots
+                .find_map(|(idx, opt_val)| opt_val.as_ref().map(|val| (K::from_index(idx), val))),
+            DirectIterInner::Tree(iter) => iter.next().map(|(k, v)| (*k, v)),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Display};
+
+/// Fixed-point number key, raw integer `R` with `FRAC` fractional bits (e.g. Q16.16 is `FixedPoint<i32, 16>`).
+///
+/// Fixed-point values order exactly like their raw integers, so this ordering is correct for any `FRAC`
+/// and maps keyed by `FixedPoint` can be queried with a raw integer.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{SgMap, Q16_16};
+///
+/// // Setpoint -> gain schedule
+/// let mut schedule = SgMap::<Q16_16, u8, 10>::new();
+/// schedule.insert(Q16_16::from_f32(-0.5), 1);
+/// schedule.insert(Q16_16::from_int(2), 3);
+/// schedule.insert(Q16_16::from_f32(1.25), 2);
+///
+/// // Lookup by raw integer, e.g. straight from a control loop register
+/// assert_eq!(schedule.get(&0x0002_0000), Some(&3));
+/// assert_eq!(schedule.get(&Q16_16::from_f32(1.25).raw()), Some(&2));
+///
+/// // Largest setpoint at or below 1.5
+/// let (k, v) = schedule.range(..=Q16_16::from_f32(1.5)).next_back().unwrap();
+/// assert_eq!((k.to_f32(), *v), (1.25, 2));
+/// ```
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FixedPoint<R, const FRAC: u32>(R);
+
+/// Signed Q16.16 fixed-point key.
+pub type Q16_16 = FixedPoint<i32, 16>;
+
+/// Signed Q8.8 fixed-point key.
+pub type Q8_8 = FixedPoint<i16, 8>;
+
+/// Signed Q1.15 fixed-point key, e.g. normalized DSP samples.
+pub type Q1_15 = FixedPoint<i16, 15>;
+
+/// Signed Q1.31 fixed-point key.
+pub type Q1_31 = FixedPoint<i32, 31>;
+
+/// Unsigned Q16.16 fixed-point key.
+pub type UQ16_16 = FixedPoint<u32, 16>;
+
+macro_rules! impl_fixed_point {
+    ( $( $raw:ty ),+ $(,)? ) => {
+        $(
+            impl<const FRAC: u32> FixedPoint<$raw, FRAC> {
+                /// Wrap a raw fixed-point integer.
+                pub const fn from_raw(raw: $raw) -> Self {
+                    FixedPoint(raw)
+                }
+
+                /// Underlying raw fixed-point integer.
+                pub const fn raw(self) -> $raw {
+                    self.0
+                }
+
+                /// Convert from an integer, wrapping if out of range.
+                pub const fn from_int(int: $raw) -> Self {
+                    FixedPoint(int.wrapping_shl(FRAC))
+                }
+
+                /// Integer part, rounding toward negative infinity.
+                pub const fn to_int(self) -> $raw {
+                    self.0 >> FRAC
+                }
+
+                /// Convert from a float, saturating if out of range and truncating excess precision.
+                pub fn from_f32(val: f32) -> Self {
+                    FixedPoint((val * ((1_u64 << FRAC) as f32)) as $raw)
+                }
+
+                /// Convert to a float, may lose precision.
+                pub fn to_f32(self) -> f32 {
+                    (self.0 as f32) / ((1_u64 << FRAC) as f32)
+                }
+            }
+
+            impl<const FRAC: u32> Borrow<$raw> for FixedPoint<$raw, FRAC> {
+                fn borrow(&self) -> &$raw {
+                    &self.0
+                }
+            }
+
+            impl<const FRAC: u32> From<FixedPoint<$raw, FRAC>> for f32 {
+                fn from(fixed: FixedPoint<$raw, FRAC>) -> f32 {
+                    fixed.to_f32()
+                }
+            }
+
+            impl<const FRAC: u32> Debug for FixedPoint<$raw, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{:?} (raw {:#x})", self.to_f32(), self.0)
+                }
+            }
+
+            impl<const FRAC: u32> Display for FixedPoint<$raw, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    Display::fmt(&self.to_f32(), f)
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_point!(i8, i16, i32, i64, u8, u16, u32, u64);
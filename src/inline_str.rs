@@ -0,0 +1,187 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::tree::SgError;
+
+/// String key stored inline, up to `CAP` bytes of UTF-8 (default 23, max 255), with no indirection.
+///
+/// Ordering, equality, and hashing match those of [`str`], so maps keyed by `InlineStr` can be queried with a plain `&str`.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use scapegoat::{InlineStr, SgError, SgMap};
+///
+/// let mut sensors = SgMap::<InlineStr, u16, 10>::new();
+/// sensors.insert(InlineStr::try_from("temp.core").unwrap(), 41);
+/// sensors.insert("temp.board".parse().unwrap(), 37);
+///
+/// assert_eq!(sensors.get("temp.core"), Some(&41));
+/// assert_eq!(sensors.first_key_value().map(|(k, _)| k.as_str()), Some("temp.board"));
+///
+/// // Strings longer than the inline capacity are rejected, not truncated
+/// assert_eq!(InlineStr::<4>::try_from("temp.core"), Err(SgError::StackCapacityExceeded));
+/// ```
+#[derive(Clone, Copy)]
+pub struct InlineStr<const CAP: usize = 23> {
+    bytes: [u8; CAP],
+    len: u8,
+}
+
+impl<const CAP: usize> InlineStr<CAP> {
+    /// Makes a new, empty `InlineStr`.
+    /// Panics if `CAP` exceeds 255.
+    pub fn new() -> Self {
+        assert!(
+            CAP <= (u8::MAX as usize),
+            "Inline string capacity exceeded!"
+        );
+        InlineStr {
+            bytes: [0; CAP],
+            len: 0,
+        }
+    }
+
+    /// Maximum length, in bytes.
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Length, in bytes.
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns `true` if the string is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Extracts a string slice containing the entire string.
+    pub fn as_str(&self) -> &str {
+        match core::str::from_utf8(&self.bytes[..self.len()]) {
+            Ok(s) => s,
+            // Contents are only ever copied from a `&str`
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Append a string slice.
+    /// Returns `Err`, leaving the string unchanged, if the result would exceed capacity.
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), SgError> {
+        let old_len = self.len();
+        let new_len = old_len + s.len();
+        if new_len > CAP {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        self.bytes[old_len..new_len].copy_from_slice(s.as_bytes());
+        self.len = new_len as u8;
+        Ok(())
+    }
+
+    /// Clears the string.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const CAP: usize> Default for InlineStr<CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> TryFrom<&str> for InlineStr<CAP> {
+    type Error = SgError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut inline = Self::new();
+        inline.try_push_str(s)?;
+        Ok(inline)
+    }
+}
+
+impl<const CAP: usize> FromStr for InlineStr<CAP> {
+    type Err = SgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl<const CAP: usize> Deref for InlineStr<CAP> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const CAP: usize> AsRef<str> for InlineStr<CAP> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const CAP: usize> Borrow<str> for InlineStr<CAP> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const CAP: usize> PartialEq for InlineStr<CAP> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const CAP: usize> Eq for InlineStr<CAP> {}
+
+impl<const CAP: usize> PartialEq<str> for InlineStr<CAP> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const CAP: usize> PartialEq<&str> for InlineStr<CAP> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const CAP: usize> Ord for InlineStr<CAP> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const CAP: usize> PartialOrd for InlineStr<CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const CAP: usize> Hash for InlineStr<CAP> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<const CAP: usize> Debug for InlineStr<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const CAP: usize> Display for InlineStr<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
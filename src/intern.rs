@@ -0,0 +1,176 @@
+use core::fmt::{self, Debug};
+
+use tinyvec::ArrayVec;
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+use crate::tree::{Idx, SgError};
+
+/// Handle to a string interned by a [`SgInterner`].
+///
+/// Comparison is by integer, in interning order, not by string contents.
+/// A symbol is only meaningful to the interner that issued it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(Idx);
+
+impl Symbol {
+    /// Position in interning order, starting at zero.
+    pub const fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Map keyed by [`Symbol`]s, for string-keyed maps that share an [`SgInterner`].
+/// Each key costs one integer per instance, and key comparisons don't touch string contents.
+pub type SgSymbolMap<V, const N: usize> = SgMap<Symbol, V, N>;
+
+/// Fixed-capacity string interner: up to `N` distinct strings, totaling up to `BYTES` bytes.
+///
+/// Each distinct string is stored once. Interning the same string again returns the same [`Symbol`],
+/// so maps (e.g. [`SgSymbolMap`]) with many duplicate string keys across instances share storage.
+/// Lookup is by hash, an [`SgSet`] of `(hash, symbol)` pairs, with string contents only compared on hash match.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{SgInterner, SgSymbolMap};
+///
+/// let mut interner = SgInterner::<256, 16>::new();
+/// let mut node_a = SgSymbolMap::<u16, 8>::new();
+/// let mut node_b = SgSymbolMap::<u16, 8>::new();
+///
+/// node_a.insert(interner.intern("temp.core").unwrap(), 41);
+/// node_b.insert(interner.intern("temp.core").unwrap(), 39);
+/// node_b.insert(interner.intern("temp.board").unwrap(), 37);
+///
+/// // "temp.core" stored once, shared by both maps
+/// assert_eq!(interner.len(), 2);
+/// assert_eq!(interner.bytes_used(), "temp.core".len() + "temp.board".len());
+///
+/// let core = interner.get("temp.core").unwrap();
+/// assert_eq!(node_a.get(&core), Some(&41));
+/// assert_eq!(node_b.get(&core), Some(&39));
+/// assert_eq!(interner.resolve(core), Some("temp.core"));
+/// ```
+pub struct SgInterner<const BYTES: usize, const N: usize> {
+    bytes: ArrayVec<[u8; BYTES]>,
+    ends: ArrayVec<[u32; N]>,
+    index: SgSet<(u64, Symbol), N>,
+}
+
+impl<const BYTES: usize, const N: usize> SgInterner<BYTES, N> {
+    /// Makes a new, empty interner.
+    /// Panics if `BYTES` exceeds `u32::MAX`.
+    pub fn new() -> Self {
+        assert!(
+            BYTES <= (u32::MAX as usize),
+            "Interner byte capacity exceeded!"
+        );
+        SgInterner {
+            bytes: ArrayVec::new(),
+            ends: ArrayVec::new(),
+            index: SgSet::new(),
+        }
+    }
+
+    /// Number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if no strings are interned.
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Maximum number of distinct strings, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Total length of interned strings, in bytes.
+    pub fn bytes_used(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Maximum total length of interned strings, in bytes, e.g. `BYTES`.
+    pub fn byte_capacity(&self) -> usize {
+        BYTES
+    }
+
+    /// Intern a string, returning its symbol.
+    /// Returns `Err` if the string is new and either capacity would be exceeded.
+    pub fn intern(&mut self, s: &str) -> Result<Symbol, SgError> {
+        let hash = fnv1a(s.as_bytes());
+        if let Some(sym) = self.find(hash, s) {
+            return Ok(sym);
+        }
+
+        if (self.ends.len() == N) || (self.bytes.len() + s.len() > BYTES) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let sym = Symbol(self.ends.len() as Idx);
+        self.bytes.extend_from_slice(s.as_bytes());
+        self.ends.push(self.bytes.len() as u32);
+        self.index.insert((hash, sym));
+        Ok(sym)
+    }
+
+    /// Get the symbol of an already-interned string, without interning it.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.find(fnv1a(s.as_bytes()), s)
+    }
+
+    /// Get the string a symbol was interned from.
+    /// Returns `None` if the symbol wasn't issued by this interner (or one with fewer strings).
+    pub fn resolve(&self, sym: Symbol) -> Option<&str> {
+        let end = *self.ends.get(sym.index())? as usize;
+        let start = match sym.index().checked_sub(1) {
+            Some(prev) => self.ends[prev] as usize,
+            None => 0,
+        };
+
+        match core::str::from_utf8(&self.bytes[start..end]) {
+            Ok(s) => Some(s),
+            // Contents are only ever copied from a `&str`
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Forget all interned strings.
+    /// Previously issued symbols become invalid.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.ends.clear();
+        self.index.clear();
+    }
+
+    fn find(&self, hash: u64, s: &str) -> Option<Symbol> {
+        self.index
+            .range((hash, Symbol(0))..=(hash, Symbol(Idx::MAX)))
+            .map(|(_, sym)| *sym)
+            .find(|sym| self.resolve(*sym) == Some(s))
+    }
+}
+
+impl<const BYTES: usize, const N: usize> Default for SgInterner<BYTES, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BYTES: usize, const N: usize> Debug for SgInterner<BYTES, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.len()).filter_map(|i| self.resolve(Symbol(i as Idx))))
+            .finish()
+    }
+}
+
+// 64-bit FNV-1a, deterministic and `no_std`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,326 @@
+use core::fmt::{self, Debug};
+use core::iter::{self, FromIterator};
+use core::ops::{
+    Bound::{Excluded, Unbounded},
+    Range,
+};
+
+use smallnum::SmallUnsigned;
+
+use crate::map::SgMap;
+use crate::tree::{Idx, NodeArena, SgError, SgVec};
+
+/// Map from possibly overlapping half-open intervals to values, up to `N` intervals.
+///
+/// Each node is augmented with the maximum endpoint in its subtree, so stabbing and overlap queries skip
+/// subtrees that end too early. Intervals are ordered by start, then end.
+/// Inserting an interval already present replaces its value. Empty intervals (`start >= end`) are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgIntervalMap;
+///
+/// // Reservation window -> owner
+/// let mut slots = SgIntervalMap::<u32, &str, 8>::new();
+/// slots.insert(10..20, "dma");
+/// slots.insert(15..30, "irq");
+/// slots.insert(40..50, "log");
+///
+/// assert!(slots.query_point(&17).eq([(10..20, &"dma"), (15..30, &"irq")]));
+/// assert!(slots.query_point(&35).eq([]));
+/// assert!(slots.query_overlap(25..45).eq([(15..30, &"irq"), (40..50, &"log")]));
+///
+/// assert_eq!(slots.remove(&(15..30)), Some("irq"));
+/// assert!(slots.query_point(&17).eq([(10..20, &"dma")]));
+/// ```
+#[derive(Default, Clone)]
+pub struct SgIntervalMap<K: Ord, V, const N: usize> {
+    // (Start, end) -> (maximum end within node's subtree, value)
+    map: SgMap<(K, K), (K, V), N>,
+}
+
+impl<K: Ord + Copy, V, const N: usize> SgIntervalMap<K, V, N> {
+    /// Makes a new, empty `SgIntervalMap`.
+    pub fn new() -> Self {
+        SgIntervalMap { map: SgMap::new() }
+    }
+
+    /// Number of stored intervals.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of intervals, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all intervals.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Map an interval to a value, returning the previous value if the interval was already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is new and the map is full.
+    /// Use [`try_insert`][SgIntervalMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, interval: Range<K>, val: V) -> Option<V> {
+        match self.try_insert(interval, val) {
+            Ok(opt_val) => opt_val,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Map an interval to a value, returning the previous value if the interval was already present.
+    /// Returns `Err`, leaving the map unchanged, if the interval is new and the map is full.
+    pub fn try_insert(&mut self, interval: Range<K>, val: V) -> Result<Option<V>, SgError> {
+        let key = (interval.start, interval.end);
+        if key.0 >= key.1 {
+            return Ok(None);
+        }
+
+        if !self.map.contains_key(&key) && (self.map.len() >= self.map.max_len()) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let (opt_old, _, opt_rebuild_depth) = self
+            .map
+            .bst
+            .internal_balancing_insert::<Idx>(key, (key.1, val));
+
+        // Ancestors above a rebuilt subtree keep their shape, only the subtree itself needs a full pass
+        let path = self.search_path(&key);
+        if let Some(depth) = opt_rebuild_depth {
+            self.refresh_subtree(path[depth].usize());
+        }
+        self.refresh_path(&path);
+
+        Ok(opt_old.map(|(_, old_val)| old_val))
+    }
+
+    /// Unmap an interval, returning its value if it was present.
+    pub fn remove(&mut self, interval: &Range<K>) -> Option<V> {
+        let key = (interval.start, interval.end);
+
+        // Removal may move the successor up, re-linking its former parent
+        let opt_succ = self
+            .map
+            .range((Excluded(&key), Unbounded))
+            .next()
+            .map(|(succ, _)| *succ);
+        let opt_succ_parent = opt_succ.and_then(|succ| {
+            let path = self.search_path(&succ);
+            path.len()
+                .checked_sub(2)
+                .map(|depth| *self.map.bst.arena.key(path[depth].usize()))
+        });
+
+        let rebal_cnt = self.map.bst.rebal_cnt();
+        let (_, val) = self.map.remove(&key)?;
+
+        match (
+            self.map.bst.rebal_cnt() != rebal_cnt,
+            self.map.bst.opt_root_idx,
+        ) {
+            (true, Some(root_idx)) => self.refresh_subtree(root_idx),
+            _ => {
+                for moved in iter::once(key).chain(opt_succ).chain(opt_succ_parent) {
+                    let path = self.search_path(&moved);
+                    self.refresh_path(&path);
+                }
+            }
+        }
+
+        Some(val)
+    }
+
+    /// Get the value of an exact interval, if present.
+    pub fn get(&self, interval: &Range<K>) -> Option<&V> {
+        self.map
+            .get(&(interval.start, interval.end))
+            .map(|(_, val)| val)
+    }
+
+    /// Get a mutable reference to the value of an exact interval, if present.
+    pub fn get_mut(&mut self, interval: &Range<K>) -> Option<&mut V> {
+        self.map
+            .get_mut(&(interval.start, interval.end))
+            .map(|(_, val)| val)
+    }
+
+    /// Returns `true` if the exact interval is present.
+    pub fn contains(&self, interval: &Range<K>) -> bool {
+        self.map.contains_key(&(interval.start, interval.end))
+    }
+
+    /// Gets an iterator over the intervals containing the point, and their values, in ascending order.
+    pub fn query_point<'a>(&'a self, point: &K) -> impl Iterator<Item = (Range<K>, &'a V)> + 'a {
+        let point = *point;
+        self.search(point, move |start| *start <= point)
+    }
+
+    /// Gets an iterator over the intervals overlapping the query interval, and their values, in ascending order.
+    /// An empty query interval overlaps nothing.
+    pub fn query_overlap(&self, query: Range<K>) -> impl Iterator<Item = (Range<K>, &V)> + '_ {
+        let Range { start, end } = query;
+        let is_empty = start >= end;
+        self.search(start, move |other_start| !is_empty && (*other_start < end))
+    }
+
+    /// Gets an iterator over the intervals and their values, in ascending order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Range<K>, &V)> + ExactSizeIterator + '_ {
+        self.map
+            .iter()
+            .map(|((start, end), (_, val))| (*start..*end, val))
+    }
+
+    // In-order traversal yielding intervals that end past `min_end` and whose start satisfies `starts_in`.
+    // `starts_in` must be downward closed, so the first start failing it ends the traversal.
+    fn search<F: Fn(&K) -> bool>(
+        &self,
+        min_end: K,
+        starts_in: F,
+    ) -> impl Iterator<Item = (Range<K>, &V)> + '_ {
+        let arena = &self.map.bst.arena;
+        let reaches = |opt_idx: Option<usize>| opt_idx.filter(|idx| arena.val(*idx).0 > min_end);
+
+        let mut matches = SgVec::<[Idx; N]>::default();
+        let mut stack = SgVec::<[Idx; N]>::default();
+        let mut opt_idx = reaches(self.map.bst.opt_root_idx);
+
+        loop {
+            while let Some(idx) = opt_idx {
+                stack.push(Idx::checked_from(idx));
+                opt_idx = reaches(arena.left_idx(idx));
+            }
+
+            let idx = match stack.pop() {
+                Some(idx) => idx.usize(),
+                None => break,
+            };
+
+            let (start, end) = arena.key(idx);
+            if !starts_in(start) {
+                break;
+            }
+            if *end > min_end {
+                matches.push(Idx::checked_from(idx));
+            }
+
+            opt_idx = reaches(arena.right_idx(idx));
+        }
+
+        matches.into_iter().map(move |idx| {
+            let ((start, end), (_, val)) = arena.get(idx.usize());
+            (*start..*end, val)
+        })
+    }
+
+    // Arena indexes from the root to the key's node, or to its would-be parent if absent.
+    fn search_path(&self, key: &(K, K)) -> SgVec<[Idx; N]> {
+        let mut path = SgVec::<[Idx; N]>::default();
+        if let Some(idx) = self.map.bst.internal_search(&mut path, key).node_idx() {
+            path.push(Idx::checked_from(idx));
+        }
+
+        path
+    }
+
+    // Recompute maximum ends bottom-up along a path, including each node's children.
+    // Covers nodes moved off the path by a single or double rotation, whose own children are unchanged.
+    fn refresh_path(&mut self, path: &[Idx]) {
+        for idx in path.iter().rev() {
+            let idx = idx.usize();
+            let arena = &self.map.bst.arena;
+            let children = [arena.left_idx(idx), arena.right_idx(idx)];
+            for child_idx in children.iter().flatten() {
+                self.refresh_node(*child_idx);
+            }
+            self.refresh_node(idx);
+        }
+    }
+
+    // Recompute maximum ends for every node in a subtree, e.g. after it's rebuilt.
+    fn refresh_subtree(&mut self, idx: usize) {
+        let arena = &self.map.bst.arena;
+        let mut level_order = SgVec::<[Idx; N]>::default();
+        level_order.push(Idx::checked_from(idx));
+
+        let mut next = 0;
+        while let Some(idx) = level_order.get(next).map(|idx| idx.usize()) {
+            for child_idx in [arena.left_idx(idx), arena.right_idx(idx)].iter().flatten() {
+                level_order.push(Idx::checked_from(*child_idx));
+            }
+            next += 1;
+        }
+
+        // Children before parents
+        for idx in level_order.iter().rev() {
+            self.refresh_node(idx.usize());
+        }
+    }
+
+    // Recompute a node's maximum end, from its own and its children's (assumed current).
+    fn refresh_node(&mut self, idx: usize) {
+        let arena = &self.map.bst.arena;
+        let max_end = [arena.left_idx(idx), arena.right_idx(idx)]
+            .iter()
+            .flatten()
+            .map(|child_idx| arena.val(*child_idx).0)
+            .fold(arena.key(idx).1, K::max);
+
+        self.map.bst.arena.get_mut(idx).1 .0 = max_end;
+    }
+}
+
+impl<K, V, const N: usize> PartialEq for SgIntervalMap<K, V, N>
+where
+    K: Ord + Copy,
+    V: PartialEq,
+{
+    // Maximum ends depend on tree shape, only intervals and values matter
+    fn eq(&self, other: &Self) -> bool {
+        (self.len() == other.len()) && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V, const N: usize> Eq for SgIntervalMap<K, V, N>
+where
+    K: Ord + Copy,
+    V: Eq,
+{
+}
+
+impl<K, V, const N: usize> Debug for SgIntervalMap<K, V, N>
+where
+    K: Ord + Copy + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Copy, V, const N: usize> Extend<(Range<K>, V)> for SgIntervalMap<K, V, N> {
+    fn extend<I: IntoIterator<Item = (Range<K>, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(interval, val)| {
+            self.insert(interval, val);
+        });
+    }
+}
+
+impl<K: Ord + Copy, V, const N: usize> FromIterator<(Range<K>, V)> for SgIntervalMap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = (Range<K>, V)>>(iter: I) -> Self {
+        let mut interval_map = SgIntervalMap::new();
+        interval_map.extend(iter);
+        interval_map
+    }
+}
@@ -2,7 +2,7 @@
 Ordered set and map data structures via an arena-based [scapegoat tree](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) (memory-efficient, self-balancing binary search tree).
 
 * Embedded-friendly: `#![no_std]` by default.
-* Safe: `#![forbid(unsafe_code)]`, including all dependencies.
+* Safe: `#![forbid(unsafe_code)]`, including all dependencies (with default features).
 * Validated via [differential fuzzing](https://tiemoko.com/blog/diff-fuzz/), against the standard library's `BTreeSet` and `BTreeMap`.
 
 ### About
@@ -31,7 +31,7 @@ Strives for three properties:
 
 Other features:
 
-* **Generic:** map keys and set elements can be any type that implements traits [`Ord`](https://doc.rust-lang.org/std/cmp/trait.Ord.html) and [`Default`](https://doc.rust-lang.org/std/default/trait.Default.html).
+* **Generic:** map keys and set elements can be any type that implements trait [`Ord`](https://doc.rust-lang.org/std/cmp/trait.Ord.html). Map values can be any type.
 * **Arbitrarily mutable:** elements can be inserted and removed, map values can be mutated. Safely.
 
 ### Usage
@@ -115,13 +115,20 @@ let big_map: SgMap<u64, u64, 2_048> = SgMap::new(); // 2,048 item capacity
 #[cfg(target_pointer_width = "64")]
 #[cfg(not(feature = "low_mem_insert"))]
 #[cfg(not(feature = "fast_rebalance"))]
+#[cfg(not(feature = "avl"))]
+#[cfg(not(feature = "cacheline_nodes"))]
+#[cfg(not(feature = "split_values"))]
+#[cfg(not(feature = "yield_hook"))]
+#[cfg(not(feature = "alloc"))]
+#[cfg(not(feature = "telemetry"))]
 {
-    assert_eq!(size_of_val(&small_map), 2_680); // 2.7 KB
-    assert_eq!(size_of_val(&big_map), 53_328);  // 53.3 KB
+    assert_eq!(size_of_val(&small_map), 2_696); // 2.7 KB
+    assert_eq!(size_of_val(&big_map), 53_344);  // 53.3 KB
 }
 ```
 
-The maximum supported capacity is `65_535` (e.g. `0xffff` or [`u16::MAX`](https://doc.rust-lang.org/std/primitive.u16.html#associatedconstant.MAX)) items.
+The maximum supported capacity is `65_534` (e.g. `0xfffe` or [`u16::MAX`](https://doc.rust-lang.org/std/primitive.u16.html#associatedconstant.MAX) - 1) items, the all-ones index is reserved to encode an absent child link.
+Internal node links use this fixed index width, [`Idx`], regardless of capacity - so node layout is identical across instances.
 Please note:
 
 * For embedded platforms, stack size limit (bound by available RAM) is indicated in the manufacturer's datasheet.
@@ -137,18 +144,31 @@ Please note:
 
 For advanced configuration options, please see [the documentation here](https://github.com/tnballo/scapegoat/blob/master/CONFIG.md).
 
+### Minimum Supported Rust Version
+
+The MSRV is 1.81 (previously 1.55), declared via `rust-version` in `Cargo.toml`.
+Default features need 1.77, the `rayon` feature needs 1.80, and the `rkyv` feature needs 1.81 (as do `rkyv` and its dependencies).
+
 ### Trusted Dependencies
 
-This library has three dependencies, each of which have no dependencies of their own (e.g. exactly three total dependencies).
+With default features, this library has three dependencies, each of which have no dependencies of their own (e.g. exactly three total dependencies).
 
 * [`tinyvec`](https://crates.io/crates/tinyvec) - `#![no_std]`, `#![forbid(unsafe_code)]` alternative to `Vec`.
 * [`micromath`](https://crates.io/crates/micromath) - `#![no_std]`, `#![forbid(unsafe_code)]` floating point approximations.
 * [`smallnum`](https://crates.io/crates/smallnum) - `#![no_std]`, `#![forbid(unsafe_code)]` integer abstraction.
 
-Because this library and all dependencies are `#![forbid(unsafe_code)]`, no 3rd-party `unsafe` code is introduced into your project.
+Because this library and all default dependencies are `#![forbid(unsafe_code)]`, no 3rd-party `unsafe` code is introduced into your project.
 This maximizes **static guarantees** for memory safety (enforced via Rust's type system).
 Robustness and correctness properties beyond memory safety are **validated dynamically**, via differential fuzzing.
 
+The dependency guarantee covers default features only. These optional features pull in dependencies that do contain `unsafe` code:
+
+* `rayon` - [`rayon`](https://crates.io/crates/rayon) and its dependencies (e.g. `crossbeam`), for parallel iteration.
+* `critical-section` - [`critical-section`](https://crates.io/crates/critical-section), whose platform implementations mask interrupts or take locks.
+* `serde` - [`serde`](https://crates.io/crates/serde), for serialization.
+* `rkyv` - [`rkyv`](https://crates.io/crates/rkyv) and its dependencies (e.g. `bytecheck`, `rancor`), for zero-copy archives.
+* `postcard` - [`postcard`](https://crates.io/crates/postcard) and `serde` (which it enables), for compact images.
+
 ### Additional Considerations
 
 **General Goals**
@@ -179,6 +199,13 @@ Space complexity is always `O(n)`. Time complexity:
 | `remove` | `O(log n)` | Amortized `O(log n)` |
 | `first` | `O(1)` | `O(1)` |
 | `last` | `O(1)` | `O(1)` |
+| `range` | `O(log n + k)` | `O(log n + k)` |
+| `from_sorted_iter` | `O(n)` | `O(n)` |
+| `iter_mut` | `O(n log n)` | `O(n log n)` |
+| `range_mut` | `O(n log n)` | `O(n log n)` |
+
+`iter_mut` and `range_mut` sort the arena by key first, so the entries they yield occupy contiguous slots that safe Rust can borrow mutably.
+That sort costs `O(n log n)` for the whole tree, regardless of range size.
 
 **Memory Footprint Demos**
 
@@ -198,18 +225,33 @@ Licensed under the [MIT license](https://github.com/tnballo/scapegoat/blob/maste
 // Production
 #![forbid(unsafe_code)]
 #![cfg_attr(not(any(test, fuzzing)), no_std)]
-#![cfg_attr(not(any(test, fuzzing)), deny(missing_docs))]
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/tnballo/scapegoat/master/img/scapegoat.svg"
 )]
 #![deny(missing_docs)]
 
+#[cfg(feature = "model")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 // Only expose arena internals for fuzzing harness
 #[cfg(fuzzing)]
 pub use crate::tree::{Arena, Node, NodeGetHelper, NodeRebuildHelper};
 
 mod tree;
-pub use crate::tree::SgError;
+#[cfg(feature = "telemetry")]
+pub use crate::tree::TreeStats;
+#[cfg(feature = "yield_hook")]
+pub use crate::tree::YieldHook;
+pub use crate::tree::{
+    ArenaFragmentation, ArenaLayout, BalanceParams, BalancePolicy, DefaultBalancePolicy,
+    DeletionCountPolicy, ExtendReport, HeightBalancePolicy, Idx, InvariantError, MaintenanceBudget,
+    MaxDepthPolicy, NeverRebalancePolicy, RawNode, RawPartsError, SgError, WeightBalancePolicy,
+};
+#[cfg(feature = "stats")]
+pub use crate::tree::{TreeDisplay, TreeShape};
 
 mod map;
 pub use crate::map::SgMap;
@@ -223,6 +265,104 @@ pub use crate::set::SgSet;
 /// [`SgSet`][crate::set::SgSet]'s iterator return types.
 pub mod set_types;
 
+mod capacity;
+pub use crate::capacity::CapacityBound;
+
+mod natural;
+pub use crate::natural::{natural_cmp, NaturalKey};
+
+mod inline_str;
+pub use crate::inline_str::InlineStr;
+
+mod fixed;
+pub use crate::fixed::{FixedPoint, Q16_16, Q1_15, Q1_31, Q8_8, UQ16_16};
+
+mod atomic;
+pub use crate::atomic::{AtomicCell, SgAtomicMap};
+
+mod direct;
+pub use crate::direct::{DirectIter, DirectKey, SgDirectMap};
+
+mod watch;
+pub use crate::watch::{SgWatchMap, WatchEvent, WatchFn, WatchId};
+
+mod pool;
+pub use crate::pool::{PooledMap, SgArenaPool};
+
+mod intern;
+pub use crate::intern::{SgInterner, SgSymbolMap, Symbol};
+
+mod range_set;
+pub use crate::range_set::SgRangeSet;
+
+mod range_map;
+pub use crate::range_map::SgRangeMap;
+
+mod multi_map;
+pub use crate::multi_map::SgMultiMap;
+
+mod multi_set;
+pub use crate::multi_set::SgMultiSet;
+
+mod interval_map;
+pub use crate::interval_map::SgIntervalMap;
+
+mod bi_map;
+pub use crate::bi_map::SgBiMap;
+
+mod lru_cache;
+pub use crate::lru_cache::SgLruCache;
+
+mod ttl_map;
+pub use crate::ttl_map::SgTtlMap;
+
+mod priority_queue;
+pub use crate::priority_queue::SgPriorityQueue;
+
+mod versioned_map;
+pub use crate::versioned_map::{SgVersionedMap, Version, VersionView};
+
+mod tx_map;
+pub use crate::tx_map::SgTxMap;
+
+/// Versioned binary image format and write-ahead log for [`SgMap`][crate::map::SgMap] and [`SgSet`][crate::set::SgSet] persistence.
+pub mod persist;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+pub use crate::serde_impl::{serde_pairs, Pairs};
+
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+#[cfg(feature = "rayon")]
+pub use crate::rayon_impl::{MapParIter, MapParIterMut, SetParIter};
+
+#[cfg(feature = "critical-section")]
+mod map_cell;
+#[cfg(feature = "critical-section")]
+pub use crate::map_cell::{SgMapCell, StaticSgMap};
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv_impl::{ArchivedEntries, SgMapArchive, SgSetArchive};
+
+#[cfg(feature = "std")]
+mod sharded;
+#[cfg(feature = "std")]
+pub use crate::sharded::{SgShardedMap, ShardedIter, ShardedReadGuard};
+
+#[cfg(feature = "std")]
+mod seqlock;
+#[cfg(feature = "std")]
+pub use crate::seqlock::SgSeqLockMap;
+
+/// Operation vocabulary and reference model for differential/property testing, requires feature `model`.
+#[cfg(feature = "model")]
+pub mod model;
+
 // Initialization convenience macros.
 mod macros;
+#[allow(unused_imports)] // Macros are exported at the crate root, kept for compatibility
 pub use macros::*;
@@ -0,0 +1,238 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter;
+use core::mem;
+
+use smallnum::SmallUnsigned;
+
+use crate::map::SgMap;
+use crate::tree::{Idx, NodeArena};
+
+/// Fixed-capacity least-recently-used (LRU) cache, up to `N` entries.
+///
+/// Entries are threaded onto an intrusive doubly-linked recency list, by arena index, so lookups stay `O(log n)`
+/// and recency updates `O(1)`. Inserting a new key into a full cache evicts the least-recently-used entry.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgLruCache;
+///
+/// let mut cache = SgLruCache::<u32, &str, 2>::new();
+/// cache.insert(1, "one");
+/// cache.insert(2, "two");
+///
+/// // Lookup marks `1` as most recently used, so `2` is evicted next
+/// assert_eq!(cache.get(&1), Some(&"one"));
+/// assert_eq!(cache.insert(3, "three"), Some((2, "two")));
+///
+/// assert!(cache.iter().eq([(&3, &"three"), (&1, &"one")]));
+/// assert_eq!(cache.peek_lru(), Some((&1, &"one")));
+/// ```
+#[derive(Default, Clone)]
+pub struct SgLruCache<K: Ord, V, const N: usize> {
+    // Key -> (value, less recent neighbor, more recent neighbor), neighbors linked by arena index
+    map: SgMap<K, (V, Option<Idx>, Option<Idx>), N>,
+    opt_lru_idx: Option<Idx>,
+    opt_mru_idx: Option<Idx>,
+}
+
+impl<K: Ord, V, const N: usize> SgLruCache<K, V, N> {
+    /// Makes a new, empty `SgLruCache`.
+    pub fn new() -> Self {
+        SgLruCache {
+            map: SgMap::new(),
+            opt_lru_idx: None,
+            opt_mru_idx: None,
+        }
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of entries, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.opt_lru_idx = None;
+        self.opt_mru_idx = None;
+    }
+
+    /// Insert a key-value pair as the most recently used entry, returning the entry pushed out:
+    /// the key's previous entry if present, else the least-recently-used entry if the cache was full.
+    pub fn insert(&mut self, key: K, val: V) -> Option<(K, V)> {
+        if let Some(idx) = self.index_of(&key) {
+            let old_key = self.map.bst.arena.replace_key(idx, key);
+            let old_val = mem::replace(&mut self.map.bst.arena.get_mut(idx).1 .0, val);
+            self.touch(idx);
+            return Some((old_key, old_val));
+        }
+
+        if self.map.max_len() == 0 {
+            return Some((key, val));
+        }
+
+        let opt_evicted = match self.map.len() >= self.map.max_len() {
+            true => self.pop_lru(),
+            false => None,
+        };
+
+        let (_, idx, _) = self
+            .map
+            .bst
+            .internal_balancing_insert::<Idx>(key, (val, None, None));
+        self.push_mru(idx);
+
+        opt_evicted
+    }
+
+    /// Get a key's value, marking it most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.index_of(key)?;
+        self.touch(idx);
+        Some(&self.map.bst.arena.val(idx).0)
+    }
+
+    /// Get a mutable reference to a key's value, marking it most recently used.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.index_of(key)?;
+        self.touch(idx);
+        Some(&mut self.map.bst.arena.get_mut(idx).1 .0)
+    }
+
+    /// Get a key's value, without changing its recency.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key).map(|(val, _, _)| val)
+    }
+
+    /// Returns `true` if the key is cached, without changing its recency.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Remove a key, returning its value if present.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.index_of(key)?;
+        self.remove_idx(idx).map(|(_, val)| val)
+    }
+
+    /// Get the least-recently-used entry, the next to be evicted, without changing its recency.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        let (key, (val, _, _)) = self.map.bst.arena.get(self.opt_lru_idx?.usize());
+        Some((key, val))
+    }
+
+    /// Remove and return the least-recently-used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let idx = self.opt_lru_idx?.usize();
+        self.remove_idx(idx)
+    }
+
+    /// Gets an iterator over the entries, from most to least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let arena = &self.map.bst.arena;
+        iter::successors(self.opt_mru_idx, move |idx| arena.val(idx.usize()).1).map(move |idx| {
+            let (key, (val, _, _)) = arena.get(idx.usize());
+            (key, val)
+        })
+    }
+
+    // Arena index of a key's node, if present.
+    fn index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.bst.internal_get::<Q, Idx>(None, key).node_idx()
+    }
+
+    // Unlink then free a node.
+    fn remove_idx(&mut self, idx: usize) -> Option<(K, V)> {
+        self.unlink(idx);
+        self.map
+            .bst
+            .priv_remove_by_idx(idx)
+            .map(|(key, (val, _, _))| (key, val))
+    }
+
+    // Move a linked node to the most recently used end.
+    fn touch(&mut self, idx: usize) {
+        if self.opt_mru_idx.map(|mru_idx| mru_idx.usize()) != Some(idx) {
+            self.unlink(idx);
+            self.push_mru(idx);
+        }
+    }
+
+    // Link an unlinked node at the most recently used end.
+    fn push_mru(&mut self, idx: usize) {
+        let arena = &mut self.map.bst.arena;
+        let (_, links) = arena.get_mut(idx);
+        links.1 = self.opt_mru_idx;
+        links.2 = None;
+
+        match self.opt_mru_idx {
+            Some(mru_idx) => arena.get_mut(mru_idx.usize()).1 .2 = Some(Idx::checked_from(idx)),
+            None => self.opt_lru_idx = Some(Idx::checked_from(idx)),
+        }
+
+        self.opt_mru_idx = Some(Idx::checked_from(idx));
+    }
+
+    // Unlink a node, joining its neighbors.
+    fn unlink(&mut self, idx: usize) {
+        let arena = &mut self.map.bst.arena;
+        let (_, (_, opt_less_idx, opt_more_idx)) = arena.get(idx);
+        let (opt_less_idx, opt_more_idx) = (*opt_less_idx, *opt_more_idx);
+
+        match opt_less_idx {
+            Some(less_idx) => arena.get_mut(less_idx.usize()).1 .2 = opt_more_idx,
+            None => self.opt_lru_idx = opt_more_idx,
+        }
+
+        match opt_more_idx {
+            Some(more_idx) => arena.get_mut(more_idx.usize()).1 .1 = opt_less_idx,
+            None => self.opt_mru_idx = opt_less_idx,
+        }
+    }
+}
+
+impl<K, V, const N: usize> Debug for SgLruCache<K, V, N>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -1,13 +1,25 @@
 use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
-use core::iter::FromIterator;
-use core::ops::{Index, RangeBounds};
+use core::hash::{Hash, Hasher};
+use core::iter::{Copied, FromIterator, Rev};
+use core::ops::{Bound, Index, RangeBounds};
 
 use crate::map_types::{
-    Entry, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, OccupiedEntry, OccupiedError,
-    Range, RangeMut, VacantEntry, Values, ValuesMut,
+    Cursor, CursorMut, Entry, ExtractIf, InsertMany, IntoIter, IntoKeys, IntoValues, Iter, IterMut,
+    IterRanked, Keys, OccupiedEntry, OccupiedError, PopWhile, Range, RangeMut, VacantEntry, Values,
+    ValuesMut,
 };
-use crate::tree::{node::NodeGetHelper, Idx, SgError, SgTree};
+#[cfg(feature = "telemetry")]
+use crate::tree::TreeStats;
+#[cfg(feature = "yield_hook")]
+use crate::tree::YieldHook;
+use crate::tree::{
+    ArenaFragmentation, ArenaLayout, BalancePolicy, CursorPos, DefaultBalancePolicy, ExtendReport,
+    Idx, InvariantError, MaintenanceBudget, RawNode, RawPartsError, SgError, SgTree, SgVec,
+};
+#[cfg(feature = "stats")]
+use crate::tree::{TreeDisplay, TreeShape};
 
 /// Safe, fallible, embedded-friendly ordered map.
 ///
@@ -17,6 +29,7 @@ use crate::tree::{node::NodeGetHelper, Idx, SgError, SgTree};
 /// * [`try_append`][crate::map::SgMap::try_append]
 /// * [`try_extend`][crate::map::SgMap::try_extend]
 /// * [`try_from_iter`][crate::map::SgMap::try_from_iter]
+/// * [`try_from_results`][crate::map::SgMap::try_from_results]
 ///
 /// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented because it would collide with the blanket implementation.
 /// See [this open GitHub issue](https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839) from 2018,
@@ -26,12 +39,12 @@ use crate::tree::{node::NodeGetHelper, Idx, SgError, SgTree};
 ///
 /// The majority of API examples and descriptions are adapted or directly copied from the standard library's [`BTreeMap`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html).
 /// The goal is to offer embedded developers familiar, ergonomic APIs on resource constrained systems that otherwise don't get the luxury of dynamic collections.
-#[derive(Default, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub struct SgMap<K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) bst: SgTree<K, V, N>,
+#[derive(Clone)]
+pub struct SgMap<K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
+    pub(crate) bst: SgTree<K, V, N, P>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
+impl<K: Ord, V, const N: usize> SgMap<K, V, N> {
     /// Makes a new, empty `SgMap`.
     ///
     /// # Examples
@@ -47,6 +60,228 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         SgMap { bst: SgTree::new() }
     }
 
+    /// Makes a new, empty `SgMap` with capacity chosen at runtime, in place of const `N`.
+    /// Const `N` becomes the number of items stored inline, storage beyond it is heap-allocated up front.
+    /// Requires feature `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cap` exceeds the maximum supported capacity (`65_534`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 0>::with_capacity(1_000);
+    /// assert_eq!(map.capacity(), 1_000);
+    ///
+    /// for i in 0..1_000 {
+    ///     map.insert(i, i * 2);
+    /// }
+    ///
+    /// assert!(map.is_full());
+    /// assert_eq!(map.get(&999), Some(&1_998));
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_capacity(cap: usize) -> Self {
+        SgMap {
+            bst: SgTree::with_capacity(cap),
+        }
+    }
+
+    /// Attempt conversion from an iterator.
+    /// Will fail if iterator length exceeds `u16::MAX - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// const CAPACITY_1: usize = 1_000;
+    /// let vec: Vec<(usize, usize)> = (0..CAPACITY_1).map(|n|(n, n)).collect();
+    /// assert!(SgMap::<usize, usize, CAPACITY_1>::try_from_iter(vec.into_iter()).is_ok());
+    ///
+    /// const CAPACITY_2: usize = (u16::MAX as usize) + 1;
+    /// #[cfg(not(feature = "cacheline_nodes"))] // Result would exceed main thread stack
+    /// {
+    ///     let vec: Vec<(usize, usize)> = (0..CAPACITY_2).map(|n|(n, n)).collect();
+    ///     assert_eq!(
+    ///         SgMap::<usize, usize, CAPACITY_2>::try_from_iter(vec.into_iter()),
+    ///         Err(SgError::MaximumCapacityExceeded)
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryFromIterator` trait in `core`/`std`.
+    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Result<Self, SgError> {
+        match iter.len() <= SgTree::<K, V, N>::max_capacity() {
+            true => Ok(SgMap::from_iter(iter)),
+            false => Err(SgError::MaximumCapacityExceeded),
+        }
+    }
+
+    /// Build a map from entries already sorted by key, in linear time.
+    /// The arena is filled in input order, then linked into a perfectly balanced tree - no per-entry search or
+    /// rebalance, unlike [`from_iter`][SgMap::from_iter].
+    ///
+    /// Returns [`SgError::UnsortedInput`] if keys aren't strictly ascending (including duplicates), or
+    /// [`SgError::StackCapacityExceeded`] if there are more than `N` entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let map = SgMap::<_, _, 10>::from_sorted_iter((0..8).map(|k| (k, k * 10))).unwrap();
+    /// assert_eq!(map.len(), 8);
+    /// assert_eq!(map[&7], 70);
+    ///
+    /// assert_eq!(
+    ///     SgMap::<_, _, 10>::from_sorted_iter([(1, "a"), (3, "c"), (2, "b")]),
+    ///     Err(SgError::UnsortedInput)
+    /// );
+    /// assert_eq!(
+    ///     SgMap::<_, _, 4>::from_sorted_iter((0..5).map(|k| (k, k))),
+    ///     Err(SgError::StackCapacityExceeded)
+    /// );
+    /// ```
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Result<Self, SgError> {
+        SgTree::from_sorted_iter(iter).map(|bst| SgMap { bst })
+    }
+
+    /// Attempt conversion from an iterator of `Result`s, stopping at the first `Err`.
+    /// Capacity overflow is converted into the caller's error type, via `From<SgError>`.
+    ///
+    /// Unlike `collect::<Result<SgMap<_, _, N>, _>>()`, which panics if capacity is exceeded,
+    /// this supports `?`-style error flow for both item and capacity errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    /// use core::num::ParseIntError;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum ConfigError {
+    ///     BadValue,
+    ///     TooManyEntries,
+    /// }
+    ///
+    /// impl From<ParseIntError> for ConfigError {
+    ///     fn from(_: ParseIntError) -> Self {
+    ///         ConfigError::BadValue
+    ///     }
+    /// }
+    ///
+    /// impl From<SgError> for ConfigError {
+    ///     fn from(_: SgError) -> Self {
+    ///         ConfigError::TooManyEntries
+    ///     }
+    /// }
+    ///
+    /// fn parse<const N: usize>(lines: &[&str]) -> Result<SgMap<u8, u32, N>, ConfigError> {
+    ///     SgMap::try_from_results(lines.iter().enumerate().map(|(i, line)| {
+    ///         Ok::<_, ConfigError>((i as u8, line.parse::<u32>()?))
+    ///     }))
+    /// }
+    ///
+    /// assert_eq!(parse::<3>(&["1", "2", "3"]).unwrap().len(), 3);
+    /// assert_eq!(parse::<3>(&["1", "x", "3"]).unwrap_err(), ConfigError::BadValue);
+    /// assert_eq!(parse::<2>(&["1", "2", "3"]).unwrap_err(), ConfigError::TooManyEntries);
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryFromIterator` trait in `core`/`std`.
+    pub fn try_from_results<I, E>(iter: I) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<(K, V), E>>,
+        E: From<SgError>,
+    {
+        let mut map = SgMap::new();
+        for item in iter {
+            let (key, val) = item?;
+            map.try_insert(key, val)?;
+        }
+
+        Ok(map)
+    }
+
+    /// Bulk load a pre-built binary search tree, e.g. from codegen or FFI.
+    /// `nodes` are arena slots (`None` for unused slots) and `opt_root` is the root's slot index.
+    ///
+    /// The structure is validated (in-bounds links, each node reachable exactly once, strictly ascending keys),
+    /// then nodes are moved into the map's arena and relinked into a balanced tree, in `O(n)`.
+    /// The given shape and slot positions aren't kept: this is a validated bulk load, not zero-copy adoption.
+    /// Returns `Err` describing the first problem found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{RawNode, RawPartsError, SgMap};
+    ///
+    /// //     20
+    /// //    /  \
+    /// //  10    30
+    /// let nodes = [
+    ///     Some(RawNode { key: 20, val: "b", left: Some(2), right: Some(1) }),
+    ///     Some(RawNode { key: 30, val: "c", left: None, right: None }),
+    ///     Some(RawNode { key: 10, val: "a", left: None, right: None }),
+    ///     None,
+    /// ];
+    ///
+    /// let map = SgMap::<_, _, 10>::from_raw_parts(nodes.clone(), Some(0)).unwrap();
+    /// assert!(map.into_iter().eq([(10, "a"), (20, "b"), (30, "c")]));
+    ///
+    /// // Left and right swapped, not a search tree
+    /// let mut bad_nodes = nodes;
+    /// bad_nodes[0].as_mut().unwrap().left = Some(1);
+    /// bad_nodes[0].as_mut().unwrap().right = Some(2);
+    /// assert_eq!(
+    ///     SgMap::<_, _, 10>::from_raw_parts(bad_nodes, Some(0)),
+    ///     Err(RawPartsError::Unordered { idx: 0 })
+    /// );
+    /// ```
+    pub fn from_raw_parts<I>(nodes: I, opt_root: Option<Idx>) -> Result<Self, RawPartsError>
+    where
+        I: IntoIterator<Item = Option<RawNode<K, V>>>,
+    {
+        SgTree::from_raw_parts(nodes, opt_root).map(|bst| SgMap { bst })
+    }
+}
+
+impl<K: Ord, V, const N: usize, P: BalancePolicy> SgMap<K, V, N, P> {
+    /// Makes a new, empty `SgMap` rebalanced per `policy`, e.g. to plug in an alternative algorithm.
+    /// [`new`][SgMap::new] uses [`DefaultBalancePolicy`][crate::DefaultBalancePolicy].
+    ///
+    /// The policy is unused if feature `weight_balanced` or `avl` is enabled (rotations ignore policies).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaxDepthPolicy, SgMap};
+    ///
+    /// let mut map = SgMap::<_, _, 100, _>::with_balance_policy(MaxDepthPolicy::new(12));
+    ///
+    /// for i in 0..100 {
+    ///     map.insert(i, i * 2);
+    /// }
+    /// assert!(map.iter().map(|(k, _)| *k).eq(0..100));
+    /// ```
+    pub fn with_balance_policy(policy: P) -> Self {
+        SgMap {
+            bst: SgTree::with_balance_policy(policy),
+        }
+    }
+
     /// The [original scapegoat tree paper's](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) alpha, `a`, can be chosen in the range `0.5 <= a < 1.0`.
     /// `a` tunes how "aggressively" the data structure self-balances.
     /// It controls the trade-off between total rebuild time and maximum height guarantees.
@@ -57,7 +292,8 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
     ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
     ///
-    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range),
+    /// or if feature `weight_balanced` or `avl` is enabled (rotations ignore `a`).
     ///
     /// # Examples
     ///
@@ -67,6 +303,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// let mut map: SgMap<isize, isize, 10> = SgMap::new();
     ///
     /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
     /// assert!(map.set_rebal_param(2.0, 3.0).is_ok());
     /// ```
     #[doc(alias = "rebalance")]
@@ -75,6 +312,39 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         self.bst.set_rebal_param(alpha_num, alpha_denom)
     }
 
+    /// Fixed-point equivalent of [`set_rebal_param`][SgMap::set_rebal_param], for targets without an FPU:
+    /// alpha is `alpha_num / alpha_denom`, validated with integer arithmetic only.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range),
+    /// or if feature `weight_balanced` or `avl` is enabled (rotations ignore `a`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut map: SgMap<isize, isize, 10> = SgMap::new();
+    ///
+    /// // Set 3/4, e.g. `a = 0.75`, for cheaper insertions.
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// # {
+    /// assert!(map.set_rebalance_factor(3, 4).is_ok());
+    /// assert_eq!(map.rebal_param(), (3.0, 4.0));
+    ///
+    /// assert_eq!(map.set_rebalance_factor(1, 3), Err(SgError::RebalanceFactorOutOfRange));
+    /// assert_eq!(map.set_rebalance_factor(4, 4), Err(SgError::RebalanceFactorOutOfRange));
+    /// assert_eq!(map.set_rebalance_factor(0, 0), Err(SgError::RebalanceFactorOutOfRange));
+    /// # }
+    /// ```
+    #[doc(alias = "alpha")]
+    pub fn set_rebalance_factor(
+        &mut self,
+        alpha_num: u16,
+        alpha_denom: u16,
+    ) -> Result<(), SgError> {
+        self.bst.set_rebalance_factor(alpha_num, alpha_denom)
+    }
+
     /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
     /// See [the corresponding setter method][SgMap::set_rebal_param] for more details.
     ///
@@ -86,6 +356,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// let mut map: SgMap<isize, isize, 10> = SgMap::new();
     ///
     /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
     /// assert!(map.set_rebal_param(2.0, 3.0).is_ok());
     ///
     /// // Get the currently set value
@@ -97,6 +368,263 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         self.bst.rebal_param()
     }
 
+    /// Replace the policy deciding when and where to rebalance, e.g. to retune a [`MaxDepthPolicy`][crate::MaxDepthPolicy].
+    /// The policy type is fixed when the map is made, see [`with_balance_policy`][SgMap::with_balance_policy].
+    ///
+    /// Returns `Err` if feature `weight_balanced` or `avl` is enabled (rotations ignore policies).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaxDepthPolicy, SgMap};
+    ///
+    /// let mut map = SgMap::<_, _, 100, _>::with_balance_policy(MaxDepthPolicy::new(16));
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// assert!(map.set_balance_policy(MaxDepthPolicy::new(8)).is_ok());
+    ///
+    /// for i in 0..100 {
+    ///     map.insert(i, i * 2);
+    /// }
+    /// assert!(map.iter().map(|(k, _)| *k).eq(0..100));
+    /// ```
+    pub fn set_balance_policy(&mut self, policy: P) -> Result<(), SgError> {
+        self.bst.set_balance_policy(policy)
+    }
+
+    /// Enable or disable automatic scapegoat rebuilds on insertion and removal.
+    /// Enabled by default.
+    ///
+    /// Batch loaders can disable rebuilds, trading temporary imbalance (slower lookups) for ingest throughput,
+    /// then call [`rebalance`][SgMap::rebalance] once at the end.
+    /// Has no effect if feature `weight_balanced` or `avl` is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 1000>::new();
+    /// map.set_auto_rebalance(false);
+    /// assert!(!map.auto_rebalance());
+    ///
+    /// for i in 0..1000 {
+    ///     map.insert(i, i);
+    /// }
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// assert!(!map.is_balanced());
+    ///
+    /// map.rebalance();
+    /// assert!(map.is_balanced());
+    /// map.set_auto_rebalance(true);
+    /// ```
+    pub fn set_auto_rebalance(&mut self, enabled: bool) {
+        self.bst.set_auto_rebalance(enabled)
+    }
+
+    /// Returns `true` if automatic scapegoat rebuilds are enabled.
+    /// See [`set_auto_rebalance`][SgMap::set_auto_rebalance] for details.
+    pub fn auto_rebalance(&self) -> bool {
+        self.bst.auto_rebalance()
+    }
+
+    /// Rebuild the entire map into a perfectly balanced tree, in `O(n)` time.
+    /// Intended for use after a bulk ingest with [automatic rebuilds][SgMap::set_auto_rebalance] disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 1000> = SgMap::new();
+    /// map.set_auto_rebalance(false);
+    /// map.extend((0..1000).map(|i| (i, i)));
+    ///
+    /// map.rebalance();
+    /// assert!(map.is_balanced());
+    /// assert_eq!(map.len(), 1000);
+    /// ```
+    pub fn rebalance(&mut self) {
+        self.bst.rebalance()
+    }
+
+    /// Returns `true` if the map is alpha-height-balanced: no element is deeper than `log_(1/a)(len)`,
+    /// for [rebalance parameter][SgMap::set_rebal_param] `a`. Runs in `O(n)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map: SgMap<_, _, 1000> = (0..1000).map(|i| (i, i)).collect();
+    /// assert!(map.is_balanced());
+    /// ```
+    pub fn is_balanced(&self) -> bool {
+        self.bst.is_balanced()
+    }
+
+    /// Check internal invariants, returning a description of the first violation found:
+    ///
+    /// * Binary search tree ordering, and cached minimum/maximum.
+    /// * Arena link consistency: no cycles, no dangling indexes, no leaked nodes, free slots disjoint from reachable ones.
+    /// * Cached per-node metadata, if features `fast_rebalance` or `avl` are enabled.
+    /// * Balance: loose alpha-height-balance, per the [original paper](https://people.csail.mit.edu/rivest/pubs/GR93.pdf),
+    ///   if automatic rebuilds are on and the [balance policy][SgMap::set_balance_policy] guarantees it
+    ///   (or subtree balance, if feature `weight_balanced` or `avl` is enabled).
+    ///
+    /// Runs in `O(n)` time. Intended for fuzzing and debugging, a violation indicates a bug.
+    /// Checked against the current rebalance parameter, so call [`rebalance`][SgMap::rebalance] after making it stricter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 100> = (0..100).map(|i| (i, i)).collect();
+    /// assert_eq!(map.validate(), Ok(()));
+    ///
+    /// map.retain(|k, _| k % 2 == 0);
+    /// assert_eq!(map.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        self.bst.validate()
+    }
+
+    /// Panics with a description of the first violated internal invariant, if any.
+    /// See [`validate`][SgMap::validate] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map: SgMap<_, _, 100> = (0..100).map(|i| (i, i)).collect();
+    /// map.assert_invariants();
+    /// ```
+    pub fn assert_invariants(&self) {
+        self.bst.assert_invariants()
+    }
+
+    /// Set (or clear, if `None`) a hook called periodically during long internal operations:
+    /// full rebuilds, bulk builds, and arena compaction.
+    /// Intended for cooperative async/RTOS environments, e.g. to pet a watchdog or yield to an executor.
+    ///
+    /// The hook is a plain function pointer, so it can't borrow or capture state (use a `static`).
+    /// It's called between nodes, not re-entrantly, and must not panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::{AtomicUsize, Ordering};
+    /// use scapegoat::{SgMap, YieldHook};
+    ///
+    /// static PETS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// fn pet_watchdog() {
+    ///     PETS.fetch_add(1, Ordering::Relaxed);
+    /// }
+    ///
+    /// let mut map: SgMap<_, _, 100> = (0..100).map(|k| (k, k)).collect();
+    /// map.set_yield_hook(Some(YieldHook::new(pet_watchdog, 16)));
+    ///
+    /// // Bulk removal rebuilds the whole tree
+    /// map.retain(|k, _| k % 2 == 0);
+    /// assert!(PETS.load(Ordering::Relaxed) > 0);
+    /// ```
+    #[cfg(feature = "yield_hook")]
+    pub fn set_yield_hook(&mut self, opt_hook: Option<YieldHook>) {
+        self.bst.set_yield_hook(opt_hook)
+    }
+
+    /// Get the hook called periodically during long internal operations, if any.
+    #[cfg(feature = "yield_hook")]
+    pub fn yield_hook(&self) -> Option<YieldHook> {
+        self.bst.yield_hook()
+    }
+
+    /// Opportunistically perform pending structural work (rebuild, arena compaction), if it fits within `budget`.
+    /// Returns `true` if no work remains pending.
+    ///
+    /// Intended to be called from a dedicated slot in a periodic loop (e.g. once per frame), so that
+    /// structural work happens there instead of inside later removals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaintenanceBudget, SgMap};
+    ///
+    /// let mut map: SgMap<_, _, 100> = (0..100).map(|x| (x, x)).collect();
+    /// map.retain(|k, _| k % 3 == 0);
+    ///
+    /// // Too little budget to do anything
+    /// assert!(!map.maintenance(MaintenanceBudget::nodes(10)));
+    ///
+    /// // Enough budget for all pending work
+    /// assert!(map.maintenance(MaintenanceBudget::nodes(100)));
+    /// assert!(map.maintenance(MaintenanceBudget::nodes(0)));
+    /// ```
+    pub fn maintenance(&mut self, budget: MaintenanceBudget) -> bool {
+        self.bst.maintenance(budget)
+    }
+
+    /// Report arena slot usage: free slots left by removals, longest contiguous live run, and live-node spread.
+    /// Intended for deciding when [`maintenance`][SgMap::maintenance] (which compacts the arena) is worth its cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaintenanceBudget, SgMap};
+    ///
+    /// let mut map: SgMap<_, _, 100> = (0..100).map(|x| (x, x)).collect();
+    /// assert!(map.fragmentation().is_compact());
+    ///
+    /// map.retain(|k, _| k % 3 == 0);
+    /// let frag = map.fragmentation();
+    /// assert_eq!(frag.live(), 34);
+    /// assert_eq!(frag.free_slots(), 66);
+    /// assert_eq!(frag.longest_live_run(), 1);
+    ///
+    /// map.maintenance(MaintenanceBudget::unlimited());
+    /// assert!(map.fragmentation().is_compact());
+    /// assert_eq!(map.fragmentation().longest_live_run(), 34);
+    /// ```
+    pub fn fragmentation(&self) -> ArenaFragmentation {
+        self.bst.fragmentation()
+    }
+
+    /// Set the placement of live nodes within the arena, applied by the next [`maintenance`][SgMap::maintenance].
+    /// Defaults to [`ArenaLayout::InOrder`], e.g. sorted by key.
+    ///
+    /// For large, read-heavy maps, [`ArenaLayout::VanEmdeBoas`] cuts cache misses per lookup.
+    /// Maintenance then rebuilds the whole tree before placing nodes, so budget for twice the length.
+    /// Mutable iteration re-sorts the arena by key, undoing any other layout until the next maintenance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{ArenaLayout, MaintenanceBudget, SgMap};
+    ///
+    /// let mut map: SgMap<_, _, 1000> = (0..1000).map(|x| (x, x)).collect();
+    /// map.set_arena_layout(ArenaLayout::VanEmdeBoas);
+    /// assert_eq!(map.arena_layout(), ArenaLayout::VanEmdeBoas);
+    ///
+    /// // Rebuild plus placement
+    /// assert!(!map.maintenance(MaintenanceBudget::nodes(1000)));
+    /// assert!(map.maintenance(MaintenanceBudget::nodes(2000)));
+    ///
+    /// // Same contents, different arena order
+    /// assert_eq!(map.get(&500), Some(&500));
+    /// assert!(map.keys().copied().eq(0..1000));
+    /// ```
+    pub fn set_arena_layout(&mut self, layout: ArenaLayout) {
+        self.bst.set_arena_layout(layout)
+    }
+
+    /// Get the placement of live nodes within the arena.
+    /// See [`set_arena_layout`][SgMap::set_arena_layout] for details.
+    pub fn arena_layout(&self) -> ArenaLayout {
+        self.bst.arena_layout()
+    }
+
     /// Total capacity, e.g. maximum number of map pairs.
     ///
     /// # Examples
@@ -112,7 +640,258 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         self.bst.capacity()
     }
 
-    /// Gets an iterator over the keys of the map, in sorted order.
+    /// Set a runtime limit on the number of map pairs, at or below the const capacity `N`.
+    /// Once reached, insertion of a new key behaves as though stack capacity were exhausted
+    /// (e.g. [`try_insert`][SgMap::try_insert] returns `Err`, [`insert`][SgMap::insert] panics).
+    ///
+    /// Returns `Err` if `limit` exceeds [`capacity`][SgMap::capacity] or is less than the current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut map = SgMap::<usize, &str, 10>::new();
+    ///
+    /// assert!(map.set_max_len(1).is_ok());
+    /// assert_eq!(map.max_len(), 1);
+    /// assert_eq!(map.capacity(), 10);
+    ///
+    /// map.insert(1, "a");
+    /// assert!(map.is_full());
+    /// assert_eq!(map.try_insert(2, "b"), Err(SgError::StackCapacityExceeded));
+    ///
+    /// assert_eq!(map.set_max_len(0), Err(SgError::StackCapacityExceeded));
+    /// assert_eq!(map.set_max_len(11), Err(SgError::MaximumCapacityExceeded));
+    /// ```
+    pub fn set_max_len(&mut self, limit: usize) -> Result<(), SgError> {
+        self.bst.set_max_len(limit)
+    }
+
+    /// Get the runtime limit on the number of map pairs.
+    /// Equal to [`capacity`][SgMap::capacity] unless set via [`set_max_len`][SgMap::set_max_len].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<usize, &str, 10>::new();
+    /// assert_eq!(map.max_len(), map.capacity());
+    ///
+    /// assert!(map.set_max_len(5).is_ok());
+    /// assert_eq!(map.max_len(), 5);
+    /// ```
+    pub fn max_len(&self) -> usize {
+        self.bst.max_len()
+    }
+
+    /// Fill `buf` with node counts per depth (root is depth `0`), returning the tree's height.
+    /// Depths at or beyond `buf.len()` aren't recorded, in which case the returned height exceeds `buf.len()`.
+    /// Requires feature `stats`.
+    ///
+    /// Useful for visualizing how far the tree drifts from perfect balance between rebuilds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map: SgMap<_, _, 10> = (0..7).map(|x| (x, x)).collect();
+    /// let mut hist = [0; 8];
+    ///
+    /// let height = map.depth_histogram(&mut hist);
+    /// assert!(height <= hist.len());
+    /// assert_eq!(hist.iter().sum::<u32>() as usize, map.len());
+    /// assert_eq!(hist[0], 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn depth_histogram(&self, buf: &mut [u32]) -> usize {
+        self.bst.depth_histogram(buf)
+    }
+
+    /// Get the number of levels in the tree, e.g. key comparisons for a worst-case lookup. Zero if empty.
+    /// Requires feature `stats`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 100> = SgMap::new();
+    /// assert_eq!(map.height(), 0);
+    ///
+    /// map.insert(1, 1);
+    /// assert_eq!(map.height(), 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn height(&self) -> usize {
+        self.bst.height()
+    }
+
+    /// Get the sum of every element's depth (root is depth `0`).
+    /// A successful lookup averages `internal_path_length / len + 1` key comparisons.
+    /// Requires feature `stats`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// // Perfectly balanced: 1 root, 2 at depth 1, 4 at depth 2
+    /// let mut map: SgMap<_, _, 100> = (0..7).map(|x| (x, x)).collect();
+    /// map.rebalance();
+    /// assert_eq!(map.internal_path_length(), 2 + (4 * 2));
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn internal_path_length(&self) -> usize {
+        self.bst.internal_path_length()
+    }
+
+    /// Report structural metrics in a single traversal: height, internal path length, fully occupied levels,
+    /// and slack relative to the alpha-height bound. See [`TreeShape`] for details.
+    /// Requires feature `stats`.
+    ///
+    /// Intended for reasoning about worst-case lookup latency on real data, e.g. to validate a choice of
+    /// [rebalance parameter][SgMap::set_rebal_param]. Per-level counts are available via
+    /// [`depth_histogram`][SgMap::depth_histogram].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 100> = (0..15).map(|x| (x, x)).collect();
+    /// map.rebalance();
+    /// let shape = map.shape();
+    ///
+    /// assert_eq!(shape.len(), 15);
+    /// assert_eq!(shape.height(), shape.optimal_height());
+    /// assert_eq!(shape.full_levels(), 4);
+    /// assert!(shape.balance_slack() >= 0);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn shape(&self) -> TreeShape {
+        self.bst.shape()
+    }
+
+    /// Write the tree's structure as a [Graphviz](https://graphviz.org/) DOT digraph, e.g. for rendering with `dot -Tsvg`.
+    /// Nodes are labeled with key (`Debug` formatted) and arena index, edges with `L` or `R` for left and right child.
+    /// Requires feature `stats`.
+    ///
+    /// Intended for debugging rebalance logic, or attaching a reproducible tree shape to an issue report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 10> = SgMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let mut dot = String::new();
+    /// map.to_dot(&mut dot).unwrap();
+    ///
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("label=\"1\\n#1\""));
+    /// assert!(dot.contains("n0 -> n1 [label=\"L\"];"));
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn to_dot<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result
+    where
+        K: Debug,
+    {
+        self.bst.to_dot(writer)
+    }
+
+    /// Render the tree's structure sideways as indented ASCII, like `cargo tree --charset ascii`.
+    /// One key (`Debug` formatted) per line, children prefixed with `L` or `R`. Empty trees render as nothing.
+    /// Requires feature `stats`.
+    ///
+    /// Intended for unit-test failure messages and interactive exploration. For larger trees, see
+    /// [`to_dot`][SgMap::to_dot].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 10> = SgMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// map.insert(3, "c");
+    ///
+    /// assert_eq!(
+    ///     format!("{}", map.display_tree()),
+    ///     "2\n\
+    ///      |-- L: 1\n\
+    ///      `-- R: 3\n"
+    /// );
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn display_tree(&self) -> TreeDisplay<'_, K, V, N>
+    where
+        K: Debug,
+    {
+        self.bst.display_tree()
+    }
+
+    /// Get instrumentation counters: rebuilds, nodes touched per rebuild, maximum insertion depth,
+    /// and comparisons per lookup. See [`TreeStats`] for details.
+    /// Requires feature `telemetry`.
+    ///
+    /// Counters are updated inline, without an external profiler, e.g. to tune [`set_rebal_param`][SgMap::set_rebal_param]
+    /// for a firmware image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 100> = SgMap::new();
+    /// map.extend((0..100).map(|x| (x, x)));
+    /// map.rebalance();
+    /// assert!(map.stats().rebuilds() > 0);
+    /// assert_eq!(map.stats().max_rebuild_nodes(), 100);
+    /// assert!(map.stats().max_rebuild_nodes() <= map.stats().rebuild_nodes());
+    ///
+    /// map.reset_stats();
+    /// map.get(&50);
+    /// assert_eq!(map.stats().lookups(), 1);
+    /// assert!(map.stats().lookup_cmps() >= 1);
+    /// ```
+    #[cfg(feature = "telemetry")]
+    pub fn stats(&self) -> TreeStats {
+        self.bst.stats()
+    }
+
+    /// Zero all instrumentation counters, see [`stats`][SgMap::stats].
+    /// Requires feature `telemetry`.
+    #[cfg(feature = "telemetry")]
+    pub fn reset_stats(&mut self) {
+        self.bst.reset_stats()
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(2, "b");
+    /// a.insert(1, "a");
+    ///
+    /// let keys: Vec<_> = a.keys().cloned().collect();
+    /// assert_eq!(keys, [1, 2]);
+    /// ```
+    pub fn keys(&self) -> Keys<'_, K, V, N> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Gets an iterator over copies of the keys of the map, in sorted order.
     ///
     /// # Examples
     ///
@@ -123,11 +902,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// a.insert(2, "b");
     /// a.insert(1, "a");
     ///
-    /// let keys: Vec<_> = a.keys().cloned().collect();
-    /// assert_eq!(keys, [1, 2]);
+    /// assert_eq!(a.keys_copied().sum::<u32>(), 3);
     /// ```
-    pub fn keys(&self) -> Keys<'_, K, V, N> {
-        Keys { inner: self.iter() }
+    pub fn keys_copied(&self) -> Copied<Keys<'_, K, V, N>>
+    where
+        K: Copy,
+    {
+        self.keys().copied()
     }
 
     /// Creates a consuming iterator visiting all the keys, in sorted order.
@@ -170,6 +951,27 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         Values { inner: self.iter() }
     }
 
+    /// Gets an iterator over copies of the values of the map, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert("b", 2);
+    /// a.insert("a", 1);
+    ///
+    /// let values: Vec<u32> = a.values_copied().collect();
+    /// assert_eq!(values, [1, 2]);
+    /// ```
+    pub fn values_copied(&self) -> Copied<Values<'_, K, V, N>>
+    where
+        V: Copy,
+    {
+        self.values().copied()
+    }
+
     /// Creates a consuming iterator visiting all the values, in order by key.
     /// The map cannot be used after calling this.
     /// The iterator element type is `V`.
@@ -218,6 +1020,14 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     }
 
     /// Moves all elements from `other` into `self`, leaving `other` empty.
+    /// If a key from `other` is already present in `self`, the entry from `other` replaces it.
+    ///
+    /// Both maps are merged in `O(n + m)` and `self` is rebuilt once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combined length exceeds capacity. Neither map is modified in that case.
+    /// Use [`try_append`][SgMap::try_append] to handle this without panicking.
     ///
     /// # Examples
     ///
@@ -245,11 +1055,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// assert_eq!(a[&4], "e");
     /// assert_eq!(a[&5], "f");
     /// ```
-    pub fn append(&mut self, other: &mut SgMap<K, V, N>) {
+    pub fn append(&mut self, other: &mut SgMap<K, V, N, P>) {
         self.bst.append(&mut other.bst);
     }
 
     /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    /// If the combined length would exceed capacity, returns [`SgError::StackCapacityExceeded`]
+    /// and leaves both maps unchanged.
     ///
     /// # Examples
     ///
@@ -300,7 +1112,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// // Can still replace existing pairs
     /// assert!(a.try_append(&mut d).is_ok());
     /// ```
-    pub fn try_append(&mut self, other: &mut SgMap<K, V, N>) -> Result<(), SgError> {
+    pub fn try_append(&mut self, other: &mut SgMap<K, V, N, P>) -> Result<(), SgError> {
         self.bst.try_append(&mut other.bst)
     }
 
@@ -333,7 +1145,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
     /// * `None` if the map did not have this key present.
     /// * The old value if the map did have this key present (both the value and key are updated,
-    /// this accommodates types that can be `==` without being identical).
+    ///   this accommodates types that can be `==` without being identical).
     ///
     /// ### Warning
     ///
@@ -391,6 +1203,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// The semantics and return type of this API match `BTreeMap`'s nightly [`try_insert`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html#method.try_insert), *NOT* the other `try_*` APIs in this crate.
     /// For a fallible insert, use [`try_insert`][`SgMap::try_insert`].
     ///
+    /// # Panics
+    ///
+    /// Panics if the key is new and the map is full.
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -406,7 +1222,11 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// assert_eq!(err.entry.get(), &"a");
     /// assert_eq!(err.value, "b");
     /// ```
-    pub fn try_insert_std(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V, N>>
+    pub fn try_insert_std(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<&mut V, OccupiedError<'_, K, V, N, P>>
     where
         K: Ord,
     {
@@ -418,62 +1238,83 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
 
     /// Attempt to extend a collection with the contents of an iterator.
     ///
+    /// Items are inserted in iteration order, stopping at the first new key that doesn't fit.
+    /// Returns an [`ExtendReport`][crate::ExtendReport] of inserted and updated counts:
+    /// `Ok` if the iterator was exhausted, `Err` if capacity stopped it early.
+    /// In the latter case, the `Err` also hands back the item that didn't fit.
+    /// Items stored before the stop remain in the map.
+    ///
     /// # Examples
     ///
     /// ```
-    /// use core::iter::FromIterator;
-    /// use scapegoat::{SgMap, SgError};
+    /// use scapegoat::SgMap;
     ///
     /// let mut a = SgMap::<_, _, 2>::new();
-    /// let mut b = SgMap::<_, _, 3>::from_iter([(1, "a"), (2, "b"), (3, "c")]);
-    /// let mut c = SgMap::<_, _, 2>::from_iter([(1, "a"), (2, "b")]);
+    /// a.insert(1, "a");
+    ///
+    /// let batch = [(1, "A"), (2, "b"), (3, "c")];
     ///
-    /// // Too big
-    /// assert_eq!(a.try_extend(b.into_iter()), Err(SgError::StackCapacityExceeded));
+    /// // Too big, stops at `(3, "c")`
+    /// let (report, rejected) = a.try_extend(batch).unwrap_err();
+    /// assert_eq!(rejected, (3, "c"));
+    /// assert_eq!(report.updated(), 1);
+    /// assert_eq!(report.inserted(), 1);
+    /// assert_eq!(a.get(&1), Some(&"A"));
     ///
-    /// // Fits
-    /// assert!(a.try_extend(c.into_iter()).is_ok());
+    /// // Make room, then resume
+    /// a.pop_first();
+    /// let report = a.try_extend(batch[report.consumed()..].iter().copied()).unwrap();
+    /// assert_eq!(report.inserted(), 1);
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [(2, "b"), (3, "c")]);
     /// ```
     ///
     /// ### Note
     ///
     /// There is no `TryExtend` trait in `core`/`std`.
-    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+    ///
+    /// Breaking change since `2.3`: this method used to return `Result<(), SgError>`,
+    /// and only accepted an [`ExactSizeIterator`][core::iter::ExactSizeIterator].
+    pub fn try_extend<I: IntoIterator<Item = (K, V)>>(
         &mut self,
         iter: I,
-    ) -> Result<(), SgError> {
+    ) -> Result<ExtendReport, (ExtendReport, (K, V))> {
         self.bst.try_extend(iter)
     }
 
-    /// Attempt conversion from an iterator.
-    /// Will fail if iterator length exceeds `u16::MAX`.
+    /// Insert each key-value pair of an iterator, lazily, yielding the result of each insertion in turn.
+    /// Per item: `Ok(None)` if the key was new, `Ok(Some(old_val))` if it replaced an existing value,
+    /// or `Err` with the rejected key-value pair if the map was full. Unlike [`try_extend`][SgMap::try_extend], a failed item doesn't stop the batch,
+    /// so every record can be accounted for.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::{SgMap, SgError};
     ///
-    /// const CAPACITY_1: usize = 1_000;
-    /// let vec: Vec<(usize, usize)> = (0..CAPACITY_1).map(|n|(n, n)).collect();
-    /// assert!(SgMap::<usize, usize, CAPACITY_1>::try_from_iter(vec.into_iter()).is_ok());
+    /// let mut a = SgMap::<_, _, 2>::new();
+    /// a.insert(1, "a");
+    ///
+    /// let results: Vec<_> = a
+    ///     .insert_many([(2, "b"), (3, "c"), (1, "A")])
+    ///     .collect();
     ///
-    /// const CAPACITY_2: usize = (u16::MAX as usize) + 1;
-    /// let vec: Vec<(usize, usize)> = (0..CAPACITY_2).map(|n|(n, n)).collect();
     /// assert_eq!(
-    ///     SgMap::<usize, usize, CAPACITY_2>::try_from_iter(vec.into_iter()),
-    ///     Err(SgError::MaximumCapacityExceeded)
+    ///     results,
+    ///     [
+    ///         Ok(None),
+    ///         Err((SgError::StackCapacityExceeded, (3, "c"))),
+    ///         Ok(Some("a"))
+    ///     ]
     /// );
+    /// assert!(a.into_iter().eq([(1, "A"), (2, "b")]));
     /// ```
-    ///
-    /// ### Note
-    ///
-    /// There is no `TryFromIterator` trait in `core`/`std`.
-    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+    pub fn insert_many<I: IntoIterator<Item = (K, V)>>(
+        &mut self,
         iter: I,
-    ) -> Result<Self, SgError> {
-        match iter.len() <= SgTree::<K, V, N>::max_capacity() {
-            true => Ok(SgMap::from_iter(iter)),
-            false => Err(SgError::MaximumCapacityExceeded),
+    ) -> InsertMany<'_, K, V, N, I::IntoIter, P> {
+        InsertMany {
+            table: self,
+            iter: iter.into_iter(),
         }
     }
 
@@ -493,121 +1334,371 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     ///     println!("{}: {}", key, value);
     /// }
     ///
-    /// let (first_key, first_value) = map.iter().next().unwrap();
-    /// assert_eq!((*first_key, *first_value), (1, "a"));
+    /// let (first_key, first_value) = map.iter().next().unwrap();
+    /// assert_eq!((*first_key, *first_value), (1, "a"));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V, N> {
+        Iter::new(self)
+    }
+
+    /// Gets a double-ended iterator over the entries of the map, sorted by key, with each entry's rank (e.g. in-order
+    /// index, `0` for the first entry). Ranks are tracked during the walk and stay correct when reversed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map = SgMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    ///
+    /// let mut ranked = map.iter_ranked();
+    /// assert_eq!(ranked.next(), Some((0, &10, &"a")));
+    /// assert_eq!(ranked.next_back(), Some((2, &30, &"c")));
+    /// assert_eq!(ranked.next(), Some((1, &20, &"b")));
+    /// assert_eq!(ranked.next(), None);
+    ///
+    /// let last_page: Vec<_> = map.iter_ranked().rev().take(2).map(|(rank, _, _)| rank).collect();
+    /// assert_eq!(last_page, [2, 1]);
+    /// ```
+    pub fn iter_ranked(&self) -> IterRanked<'_, K, V, N, P> {
+        IterRanked::new(self)
+    }
+
+    /// Returns the first entry, in key order, whose value satisfies `pred`.
+    /// Walks in-order and stops at the first match, so is `O(n)` in the worst case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let states = SgMap::from([(7, "idle"), (3, "busy"), (5, "busy")]);
+    ///
+    /// assert_eq!(states.find_by_value(|s| *s == "busy"), Some((&3, &"busy")));
+    /// assert_eq!(states.find_by_value(|s| *s == "down"), None);
+    /// ```
+    pub fn find_by_value<F>(&self, mut pred: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&V) -> bool,
+    {
+        self.iter().find(|(_, v)| pred(v))
+    }
+
+    /// Gets an iterator over all entries, in key order, whose value satisfies `pred`.
+    /// Matches are found lazily, so stopping early (e.g. via [`Iterator::take`]) short-circuits the walk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let states = SgMap::from([(7, "idle"), (3, "busy"), (5, "busy")]);
+    ///
+    /// let busy: Vec<_> = states.find_all_by_value(|s| *s == "busy").map(|(k, _)| *k).collect();
+    /// assert_eq!(busy, [3, 5]);
+    /// ```
+    pub fn find_all_by_value<'a, F>(
+        &'a self,
+        mut pred: F,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        F: FnMut(&V) -> bool + 'a,
+    {
+        self.iter().filter(move |(_, v)| pred(v))
+    }
+
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// // Add 10 to the value if the key isn't "a"
+    /// for (key, value) in map.iter_mut() {
+    ///     if key != &"a" {
+    ///         *value += 10;
+    ///     }
+    /// }
+    ///
+    /// let (second_key, second_value) = map.iter().skip(1).next().unwrap();
+    /// assert_eq!((*second_key, *second_value), ("b", 12));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, N> {
+        IterMut::new(self)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
+    /// assert_eq!(map.remove_entry(&1), None);
+    /// ```
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.bst.remove_entry(key)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
+    /// The elements are visited in ascending key order.
+    /// Removed elements free their arena slots in place, and the tree is rebuilt at most once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x*10)).collect();
+    /// // Keep only the elements with even-numbered keys.
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.bst.retain(|k, v| f(k, v));
+    }
+
+    /// Creates an iterator that visits all elements in ascending key order, and lazily removes those for which
+    /// `pred` returns `true`. Elements for which `pred` returns `false` are kept, and may be mutated.
+    ///
+    /// Each removal leaves the map valid and balanced, so dropping the iterator early is safe:
+    /// unvisited elements are simply kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x)).collect();
+    /// let evens: Vec<_> = map.extract_if(|k, _v| k % 2 == 0).collect();
+    /// assert_eq!(evens, [(0, 0), (2, 2), (4, 4), (6, 6)]);
+    /// assert!(map.into_iter().eq(vec![(1, 1), (3, 3), (5, 5), (7, 7)]));
+    ///
+    /// // Stopping early keeps the rest
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x)).collect();
+    /// assert_eq!(map.extract_if(|k, _v| k % 2 == 0).next(), Some((0, 0)));
+    /// assert_eq!(map.len(), 7);
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, N, F, P>
+    where
+        K: Ord,
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Splits the collection into two at the given key. Returns everything after the given key,
+    /// including the key.
+    /// Both halves are rebuilt once, in linear time, from the already-sorted elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(17, "d");
+    /// a.insert(41, "e");
+    ///
+    /// let b = a.split_off(&3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    ///
+    /// assert_eq!(a[&1], "a");
+    /// assert_eq!(a[&2], "b");
+    ///
+    /// assert_eq!(b[&3], "c");
+    /// assert_eq!(b[&17], "d");
+    /// assert_eq!(b[&41], "e");
+    /// ```
+    pub fn split_off<Q>(&mut self, key: &Q) -> SgMap<K, V, N, P>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        SgMap {
+            bst: self.bst.split_off(key),
+        }
+    }
+
+    /// Removes the `k` smallest entries from the map, returning them as a new map.
+    /// If `k` exceeds the map's length, all entries are removed.
+    ///
+    /// Unlike calling [`pop_first`][SgMap::pop_first] `k` times, the map is rebuilt only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 10> = (1..=5).map(|x| (x, x * 10)).collect();
+    ///
+    /// let smallest = map.take_smallest(2);
+    /// assert!(smallest.into_iter().eq([(1, 10), (2, 20)]));
+    /// assert!(map.into_iter().eq([(3, 30), (4, 40), (5, 50)]));
+    /// ```
+    pub fn take_smallest(&mut self, k: usize) -> SgMap<K, V, N, P> {
+        SgMap {
+            bst: self.bst.take_smallest(k),
+        }
+    }
+
+    /// Removes the `k` largest entries from the map, returning them as a new map.
+    /// If `k` exceeds the map's length, all entries are removed.
+    ///
+    /// Unlike calling [`pop_last`][SgMap::pop_last] `k` times, the map is rebuilt only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 10> = (1..=5).map(|x| (x, x * 10)).collect();
+    ///
+    /// let largest = map.take_largest(2);
+    /// assert!(largest.into_iter().eq([(4, 40), (5, 50)]));
+    /// assert!(map.into_iter().eq([(1, 10), (2, 20), (3, 30)]));
+    /// ```
+    pub fn take_largest(&mut self, k: usize) -> SgMap<K, V, N, P> {
+        SgMap {
+            bst: self.bst.take_largest(k),
+        }
+    }
+
+    /// Removes the `n` smallest entries from the map, yielding them in ascending order.
+    /// If `n` exceeds the map's length, all entries are removed.
+    ///
+    /// Entries are removed up front, with a single rebuild, e.g. for bounded batch handoff between tasks.
+    /// This is [`take_smallest`][SgMap::take_smallest], consumed as an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut queue: SgMap<_, _, 10> = (1..=5).map(|x| (x, x * 10)).collect();
+    ///
+    /// assert!(queue.drain_front(2).eq([(1, 10), (2, 20)]));
+    /// assert_eq!(queue.first_key(), Some(&3));
     /// ```
-    pub fn iter(&self) -> Iter<'_, K, V, N> {
-        Iter::new(self)
+    pub fn drain_front(&mut self, n: usize) -> IntoIter<K, V, N> {
+        self.take_smallest(n).into_iter()
     }
 
-    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    /// Removes the `n` largest entries from the map, yielding them in descending order (largest first).
+    /// If `n` exceeds the map's length, all entries are removed.
+    ///
+    /// Entries are removed up front, with a single rebuild, e.g. for bounded batch handoff between tasks.
+    /// This is [`take_largest`][SgMap::take_largest], consumed as a reversed iterator.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
-    ///
-    /// // Add 10 to the value if the key isn't "a"
-    /// for (key, value) in map.iter_mut() {
-    ///     if key != &"a" {
-    ///         *value += 10;
-    ///     }
-    /// }
+    /// let mut queue: SgMap<_, _, 10> = (1..=5).map(|x| (x, x * 10)).collect();
     ///
-    /// let (second_key, second_value) = map.iter().skip(1).next().unwrap();
-    /// assert_eq!((*second_key, *second_value), ("b", 12));
+    /// assert!(queue.drain_back(2).eq([(5, 50), (4, 40)]));
+    /// assert_eq!(queue.last_key(), Some(&3));
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, N> {
-        IterMut::new(self)
+    pub fn drain_back(&mut self, n: usize) -> Rev<IntoIter<K, V, N>> {
+        self.take_largest(n).into_iter().rev()
     }
 
-    /// Removes a key from the map, returning the stored key and value if the key
-    /// was previously in the map.
+    /// Splits the map into two at the given in-order index (rank), e.g. to partition work by count.
+    /// The map keeps its first `rank` entries, the rest are returned as a new map.
+    /// If `rank` exceeds the map's length, the returned map is empty.
     ///
-    /// The key may be any borrowed form of the map's key type, but the ordering
-    /// on the borrowed form *must* match the ordering on the key type.
+    /// Both maps are rebuilt once, in bulk.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
-    /// assert_eq!(map.remove_entry(&1), None);
+    /// let mut map: SgMap<_, _, 10> = (1..=5).map(|x| (x * 10, x)).collect();
+    ///
+    /// let rest = map.split_at_rank(3);
+    /// assert!(map.into_iter().eq([(10, 1), (20, 2), (30, 3)]));
+    /// assert!(rest.into_iter().eq([(40, 4), (50, 5)]));
     /// ```
-    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        self.bst.remove_entry(key)
+    pub fn split_at_rank(&mut self, rank: usize) -> SgMap<K, V, N, P> {
+        SgMap {
+            bst: self.bst.split_at_rank(rank),
+        }
     }
 
-    /// Retains only the elements specified by the predicate.
+    /// Returns the number of keys in the map less than `key`, e.g. `key`'s in-order index (rank) if present.
     ///
-    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
-    /// The elements are visited in ascending key order.
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// Runs in `O(log n)` with feature `fast_rebalance`, which tracks subtree sizes, and `O(n)` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x*10)).collect();
-    /// // Keep only the elements with even-numbered keys.
-    /// map.retain(|&k, _| k % 2 == 0);
-    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// let scores: SgMap<_, _, 10> = [(70, "ann"), (85, "bob"), (92, "cy")].iter().cloned().collect();
+    /// assert_eq!(scores.rank(&85), 1);
+    /// assert_eq!(scores.rank(&90), 2);
+    /// assert_eq!(scores.rank(&0), 0);
     /// ```
-    pub fn retain<F>(&mut self, mut f: F)
+    pub fn rank<Q>(&self, key: &Q) -> usize
     where
-        K: Ord,
-        F: FnMut(&K, &mut V) -> bool,
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
     {
-        self.bst.retain(|k, v| f(k, v));
+        self.bst.rank(key)
     }
 
-    /// Splits the collection into two at the given key. Returns everything after the given key,
-    /// including the key.
+    /// Returns the key-value pair at the given in-order index (rank), e.g. `select(0)` is the first pair.
+    /// Returns `None` if `rank` is out of bounds.
+    ///
+    /// Runs in `O(log n)` with feature `fast_rebalance`, which tracks subtree sizes, and `O(n)` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(1, "a");
-    /// a.insert(2, "b");
-    /// a.insert(3, "c");
-    /// a.insert(17, "d");
-    /// a.insert(41, "e");
-    ///
-    /// let b = a.split_off(&3);
-    ///
-    /// assert_eq!(a.len(), 2);
-    /// assert_eq!(b.len(), 3);
+    /// let scores: SgMap<_, _, 10> = (1..=9).map(|x| (x * 10, x)).collect();
     ///
-    /// assert_eq!(a[&1], "a");
-    /// assert_eq!(a[&2], "b");
-    ///
-    /// assert_eq!(b[&3], "c");
-    /// assert_eq!(b[&17], "d");
-    /// assert_eq!(b[&41], "e");
+    /// // 90th percentile
+    /// let p90 = (scores.len() * 9) / 10;
+    /// assert_eq!(scores.select(p90), Some((&90, &9)));
+    /// assert_eq!(scores.select(scores.len()), None);
     /// ```
-    pub fn split_off<Q>(&mut self, key: &Q) -> SgMap<K, V, N>
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        SgMap {
-            bst: self.bst.split_off(key),
-        }
+    pub fn select(&self, rank: usize) -> Option<(&K, &V)> {
+        self.bst.select(rank)
     }
 
     /// Removes a key from the map, returning the value at the key if the key
@@ -705,7 +1796,82 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         self.bst.get_mut(key)
     }
 
+    /// Returns mutable references to the values corresponding to several distinct keys at once.
+    /// Each absent key yields `None`, in the same position.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any two keys are equal.
+    /// See [`try_get_many_mut`][SgMap::try_get_many_mut] for a fallible variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut balances = SgMap::<_, _, 10>::new();
+    /// balances.insert("alice", 100);
+    /// balances.insert("bob", 20);
+    ///
+    /// if let [Some(from), Some(to), None] = balances.get_many_mut(["alice", "bob", "carol"]) {
+    ///     *from -= 30;
+    ///     *to += 30;
+    /// }
+    /// assert_eq!(balances["alice"], 70);
+    /// assert_eq!(balances["bob"], 50);
+    /// ```
+    pub fn get_many_mut<Q, const M: usize>(&mut self, keys: [&Q; M]) -> [Option<&mut V>; M]
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.bst.get_many_mut(keys)
+    }
+
+    /// Fallible variant of [`get_many_mut`][SgMap::get_many_mut]:
+    /// returns `Err` instead of panicking if any two keys are equal.
+    ///
+    /// Keys are compared pairwise to rule out duplicates, e.g. `O(M^2)` comparisons on top of `M` lookups.
+    /// Intended for a handful of keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgError, SgMap};
+    ///
+    /// let mut balances = SgMap::<_, _, 10>::new();
+    /// balances.insert("alice", 100);
+    /// balances.insert("bob", 20);
+    ///
+    /// assert_eq!(
+    ///     balances.try_get_many_mut(["alice", "alice"]),
+    ///     Err(SgError::DuplicateKeys)
+    /// );
+    ///
+    /// if let Ok([Some(from), Some(to)]) = balances.try_get_many_mut(["alice", "bob"]) {
+    ///     *from -= 30;
+    ///     *to += 30;
+    /// }
+    /// assert_eq!(balances["alice"], 70);
+    /// assert_eq!(balances["bob"], 50);
+    /// ```
+    pub fn try_get_many_mut<Q, const M: usize>(
+        &mut self,
+        keys: [&Q; M],
+    ) -> Result<[Option<&mut V>; M], SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.bst.try_get_many_mut(keys)
+    }
+
     /// Clears the map, removing all elements.
+    /// Runs in constant time if neither key nor value type has drop glue (see [`core::mem::needs_drop`]),
+    /// otherwise is linear in the number of arena slots used since the last clear.
     ///
     /// # Examples
     ///
@@ -842,6 +2008,40 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         self.bst.pop_first()
     }
 
+    /// Lazily removes and returns the first elements in the map, in ascending order, for as long as `pred` holds.
+    /// Stops at the first element for which `pred` returns `false`, leaving it (and every greater element) in place.
+    /// Dropping the iterator early leaves unvisited elements in place.
+    ///
+    /// # Examples
+    ///
+    /// Draining every expired deadline from a timer queue.
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut timers = SgMap::<u32, &str, 10>::new();
+    /// timers.insert(10, "blink");
+    /// timers.insert(25, "poll");
+    /// timers.insert(40, "flush");
+    ///
+    /// let now = 30;
+    /// let expired: Vec<_> = timers.pop_first_while(|deadline, _| *deadline <= now).collect();
+    /// assert_eq!(expired, [(10, "blink"), (25, "poll")]);
+    /// assert_eq!(timers.first_key_value(), Some((&40, &"flush")));
+    /// ```
+    pub fn pop_first_while<F>(&mut self, pred: F) -> PopWhile<'_, K, V, N, F, P>
+    where
+        K: Ord,
+        F: FnMut(&K, &V) -> bool,
+    {
+        PopWhile {
+            table: self,
+            pred,
+            from_back: false,
+            done: false,
+        }
+    }
+
     /// Returns a reference to the last key-value pair in the map.
     /// The key in this pair is the maximum key in the map.
     ///
@@ -906,6 +2106,37 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         self.bst.pop_last()
     }
 
+    /// Lazily removes and returns the last elements in the map, in descending order, for as long as `pred` holds.
+    /// Stops at the first element for which `pred` returns `false`, leaving it (and every lesser element) in place.
+    /// Dropping the iterator early leaves unvisited elements in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// let popped: Vec<_> = map.pop_last_while(|k, _| *k > 1).collect();
+    /// assert_eq!(popped, [(3, "c"), (2, "b")]);
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn pop_last_while<F>(&mut self, pred: F) -> PopWhile<'_, K, V, N, F, P>
+    where
+        K: Ord,
+        F: FnMut(&K, &V) -> bool,
+    {
+        PopWhile {
+            table: self,
+            pred,
+            from_back: true,
+            done: false,
+        }
+    }
+
     /// Returns the number of elements in the map.
     ///
     /// # Examples
@@ -940,14 +2171,21 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     ///
     /// assert_eq!(count["a"], 3);
     /// ```
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
-        let ngh: NodeGetHelper<Idx> = self.bst.internal_get(None, &key);
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N, P> {
+        // Search path is kept, so a vacant insert doesn't traverse the tree again
+        let mut path = SgVec::new();
+        let ngh = self.bst.internal_search(&mut path, &key);
         match ngh.node_idx() {
             Some(node_idx) => Entry::Occupied(OccupiedEntry {
                 node_idx,
                 table: self,
             }),
-            None => Entry::Vacant(VacantEntry { key, table: self }),
+            None => Entry::Vacant(VacantEntry {
+                key,
+                path,
+                ngh,
+                table: self,
+            }),
         }
     }
 
@@ -970,7 +2208,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// assert_eq!(*map.get(&1).unwrap(), "first");
     /// assert_eq!(*map.get(&2).unwrap(), "b");
     /// ```
-    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N>> {
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N, P>> {
         if self.is_empty() {
             return None;
         }
@@ -1001,7 +2239,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// assert_eq!(*map.get(&1).unwrap(), "a");
     /// assert_eq!(*map.get(&2).unwrap(), "last");
     /// ```
-    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N>> {
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N, P>> {
         if self.is_empty() {
             return None;
         }
@@ -1020,6 +2258,9 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
     /// range from 4 to 10.
     ///
+    /// Descends directly to the range's lower bound and stops past its upper bound,
+    /// so runtime is `O(log n + k)` for `k` elements in range.
+    ///
     /// # Panics
     ///
     /// Panics if range `start > end`.
@@ -1042,7 +2283,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// }
     /// assert_eq!(Some((&5, &"b")), map.range(4..).next());
     /// ```
-    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V, N>
+    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V, N, P>
     where
         T: Ord + ?Sized,
         K: Borrow<T> + Ord,
@@ -1062,6 +2303,9 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
     /// range from 4 to 10.
     ///
+    /// Like [`iter_mut`][SgMap::iter_mut], the arena is sorted first, so in-range entries are contiguous.
+    /// That makes this `O(n log n)` even for a small range, prefer [`get_mut`][SgMap::get_mut] for a few keys.
+    ///
     /// # Panics
     ///
     /// Panics if range `start > end`.
@@ -1097,23 +2341,163 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
         SgTree::<K, V, N>::assert_valid_range(&range);
         RangeMut::new(self, &range)
     }
+
+    /// Returns a [`Cursor`] pointing at the first entry above `bound`,
+    /// or at the ghost position if there is none.
+    ///
+    /// For `Bound::Included(k)` that's the first key `>= k`, for `Bound::Excluded(k)` the first key `> k`,
+    /// and for `Bound::Unbounded` the first entry. Runs in `O(log n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use scapegoat::SgMap;
+    ///
+    /// let map: SgMap<_, _, 10> = [(10, "a"), (20, "b"), (30, "c")].iter().cloned().collect();
+    ///
+    /// let mut cursor = map.lower_bound(Bound::Included(&15));
+    /// assert_eq!(cursor.key_value(), Some((&20, &"b")));
+    /// assert_eq!(cursor.peek_prev(), Some((&10, &"a")));
+    ///
+    /// cursor.move_next();
+    /// assert_eq!(cursor.key(), Some(&30));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.key(), None); // Ghost position
+    ///
+    /// assert_eq!(map.lower_bound(Bound::Excluded(&30)).key(), None);
+    /// ```
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, N, P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Cursor::new(self, CursorPos::lower_bound(&self.bst, bound))
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first entry above `bound`,
+    /// or at the ghost position if there is none. See [`lower_bound`][SgMap::lower_bound].
+    ///
+    /// # Examples
+    ///
+    /// Sweeping a sorted schedule in place, merging adjacent slots.
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut slots: SgMap<u32, u32, 10> = [(0, 5), (5, 5), (12, 3)].iter().cloned().collect();
+    ///
+    /// let mut cursor = slots.lower_bound_mut(Bound::Unbounded);
+    /// while let Some((&start, &len)) = cursor.key_value() {
+    ///     match cursor.peek_next() {
+    ///         Some((&next_start, &next_len)) if start + len == next_start => {
+    ///             *cursor.value_mut().unwrap() += next_len;
+    ///             cursor.move_next();
+    ///             cursor.remove_current_and_move_back();
+    ///         }
+    ///         _ => cursor.move_next(),
+    ///     }
+    /// }
+    ///
+    /// assert!(slots.into_iter().eq([(0, 10), (12, 3)]));
+    /// ```
+    pub fn lower_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, N, P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let pos = CursorPos::lower_bound(&self.bst, bound);
+        CursorMut::new(self, pos)
+    }
+
+    /// Returns a [`Cursor`] pointing at the last entry below `bound`,
+    /// or at the ghost position if there is none.
+    ///
+    /// For `Bound::Included(k)` that's the last key `<= k`, for `Bound::Excluded(k)` the last key `< k`,
+    /// and for `Bound::Unbounded` the last entry. Runs in `O(log n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use scapegoat::SgMap;
+    ///
+    /// let map: SgMap<_, _, 10> = [(10, "a"), (20, "b"), (30, "c")].iter().cloned().collect();
+    ///
+    /// let mut cursor = map.upper_bound(Bound::Excluded(&20));
+    /// assert_eq!(cursor.key_value(), Some((&10, &"a")));
+    ///
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), None); // Ghost position
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), Some(&30)); // Wraps to last
+    /// ```
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, N, P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Cursor::new(self, CursorPos::upper_bound(&self.bst, bound))
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last entry below `bound`,
+    /// or at the ghost position if there is none. See [`upper_bound`][SgMap::upper_bound].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 10> = [(10, "a"), (30, "c")].iter().cloned().collect();
+    ///
+    /// let mut cursor = map.upper_bound_mut(Bound::Included(&25));
+    /// assert_eq!(cursor.key(), Some(&10));
+    /// assert!(cursor.insert_after(20, "b").is_ok());
+    /// assert!(cursor.insert_after(40, "d").is_err()); // Out of order
+    ///
+    /// assert!(map.into_iter().eq([(10, "a"), (20, "b"), (30, "c")]));
+    /// ```
+    pub fn upper_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, N, P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let pos = CursorPos::upper_bound(&self.bst, bound);
+        CursorMut::new(self, pos)
+    }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 // Debug
-impl<K: Default, V: Default, const N: usize> Debug for SgMap<K, V, N>
+impl<K, V, const N: usize, P> Debug for SgMap<K, V, N, P>
 where
     K: Ord + Debug,
     V: Debug,
+    P: BalancePolicy,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.bst.iter()).finish()
     }
 }
 
+// Default
+impl<K, V, const N: usize, P> Default for SgMap<K, V, N, P>
+where
+    K: Ord,
+    P: BalancePolicy + Default,
+{
+    fn default() -> Self {
+        SgMap {
+            bst: SgTree::default(),
+        }
+    }
+}
+
 // From array.
-impl<K: Default, V: Default, const N: usize> From<[(K, V); N]> for SgMap<K, V, N>
+impl<K, V, const N: usize> From<[(K, V); N]> for SgMap<K, V, N>
 where
     K: Ord,
 {
@@ -1139,10 +2523,11 @@ where
 }
 
 // Indexing
-impl<K: Default, V: Default, Q, const N: usize> Index<&Q> for SgMap<K, V, N>
+impl<K, V, Q, const N: usize, P> Index<&Q> for SgMap<K, V, N, P>
 where
     K: Borrow<Q> + Ord,
     Q: Ord + ?Sized,
+    P: BalancePolicy,
 {
     type Output = V;
 
@@ -1157,21 +2542,23 @@ where
 }
 
 // Construct from iterator.
-impl<K: Default, V: Default, const N: usize> FromIterator<(K, V)> for SgMap<K, V, N>
+impl<K, V, const N: usize, P> FromIterator<(K, V)> for SgMap<K, V, N, P>
 where
     K: Ord,
+    P: BalancePolicy + Default,
 {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
-        let mut sgm = SgMap::new();
-        sgm.bst = SgTree::from_iter(iter);
-        sgm
+        SgMap {
+            bst: SgTree::from_iter(iter),
+        }
     }
 }
 
 // Extension from iterator.
-impl<K: Default, V: Default, const N: usize> Extend<(K, V)> for SgMap<K, V, N>
+impl<K, V, const N: usize, P> Extend<(K, V)> for SgMap<K, V, N, P>
 where
     K: Ord,
+    P: BalancePolicy,
 {
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         self.bst.extend(iter);
@@ -1179,20 +2566,78 @@ where
 }
 
 // Extension from reference iterator.
-impl<'a, K: Default, V: Default, const N: usize> Extend<(&'a K, &'a V)> for SgMap<K, V, N>
+impl<'a, K, V, const N: usize, P> Extend<(&'a K, &'a V)> for SgMap<K, V, N, P>
 where
     K: Ord + Copy,
     V: Copy,
+    P: BalancePolicy,
 {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
     }
 }
 
+// PartialEq
+impl<K, V, const N: usize, P> PartialEq for SgMap<K, V, N, P>
+where
+    K: Ord + PartialEq,
+    V: PartialEq,
+    P: BalancePolicy,
+{
+    fn eq(&self, other: &SgMap<K, V, N, P>) -> bool {
+        self.bst == other.bst
+    }
+}
+
+// Eq
+impl<K, V, const N: usize, P> Eq for SgMap<K, V, N, P>
+where
+    K: Ord + Eq,
+    V: Eq,
+    P: BalancePolicy,
+{
+}
+
+// PartialOrd
+impl<K, V, const N: usize, P> PartialOrd for SgMap<K, V, N, P>
+where
+    K: Ord + PartialOrd,
+    V: PartialOrd,
+    P: BalancePolicy,
+{
+    fn partial_cmp(&self, other: &SgMap<K, V, N, P>) -> Option<Ordering> {
+        self.bst.partial_cmp(&other.bst)
+    }
+}
+
+// Ord
+impl<K, V, const N: usize, P> Ord for SgMap<K, V, N, P>
+where
+    K: Ord,
+    V: Ord,
+    P: BalancePolicy,
+{
+    fn cmp(&self, other: &SgMap<K, V, N, P>) -> Ordering {
+        self.bst.cmp(&other.bst)
+    }
+}
+
+// Hash
+impl<K, V, const N: usize, P> Hash for SgMap<K, V, N, P>
+where
+    K: Ord + Hash,
+    V: Hash,
+    P: BalancePolicy,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bst.hash(state);
+    }
+}
+
 // General Iterators ---------------------------------------------------------------------------------------------------
 
 // Reference iterator
-impl<'a, K: Ord + Default, V: Default, const N: usize> IntoIterator for &'a SgMap<K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> IntoIterator for &'a SgMap<K, V, N, P> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V, N>;
 
@@ -1202,7 +2647,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> IntoIterator for &'a SgMa
 }
 
 // Consuming iterator
-impl<K: Ord + Default, V: Default, const N: usize> IntoIterator for SgMap<K, V, N> {
+impl<K: Ord, V, const N: usize, P: BalancePolicy> IntoIterator for SgMap<K, V, N, P> {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V, N>;
 
@@ -0,0 +1,214 @@
+use core::borrow::Borrow;
+use core::cell::RefCell;
+
+use critical_section::{CriticalSection, Mutex};
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// [`SgMap`][crate::map::SgMap] shareable between interrupt handlers and the main loop, requires feature
+/// `critical-section`.
+///
+/// The map is only reachable within a critical section, via closure-based access, so a `static` cell needs no
+/// `unsafe` or hand-rolled locking. The map itself is created on first access, keeping construction `const`.
+/// That first access also initializes all `N` arena slots inside its critical section: call
+/// [`init`][SgMapCell::init] at startup to pay this cost up front, instead of in the first interrupt handler.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMapCell;
+///
+/// // ADC channel -> latest sample
+/// static SAMPLES: SgMapCell<u8, u16, 8> = SgMapCell::new();
+///
+/// fn on_adc_interrupt(channel: u8, sample: u16) {
+///     SAMPLES.with(|map| {
+///         map.insert(channel, sample);
+///     });
+/// }
+///
+/// on_adc_interrupt(0, 512);
+/// on_adc_interrupt(1, 1023);
+///
+/// assert_eq!(SAMPLES.with(|map| map.get(&1).copied()), Some(1023));
+/// assert_eq!(SAMPLES.with(|map| map.len()), 2);
+/// ```
+pub struct SgMapCell<K: Ord, V, const N: usize> {
+    // `None` until first access, since `SgMap::new` isn't `const`
+    inner: Mutex<RefCell<Option<SgMap<K, V, N>>>>,
+}
+
+impl<K: Ord, V, const N: usize> SgMapCell<K, V, N> {
+    /// Makes a new `SgMapCell`, holding an empty map. Usable in a `static` initializer.
+    pub const fn new() -> Self {
+        SgMapCell {
+            inner: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Create the map now, if not already created, so later accesses don't pay its `O(N)` initialization.
+    /// Enters a critical section for the duration, so call it before interrupts that use the cell are enabled.
+    pub fn init(&self) {
+        critical_section::with(|cs| {
+            let mut opt_map = self.inner.borrow_ref_mut(cs);
+            if opt_map.is_none() {
+                *opt_map = Some(SgMap::new());
+            }
+        })
+    }
+
+    /// Enter a critical section and call `f` with the map, returning its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is already borrowed, e.g. if called from within another access to the same cell.
+    pub fn with<R, F: FnOnce(&mut SgMap<K, V, N>) -> R>(&self, f: F) -> R {
+        critical_section::with(|cs| self.with_cs(cs, f))
+    }
+
+    /// Call `f` with the map from within an existing critical section, returning its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is already borrowed, e.g. if called from within another access to the same cell.
+    pub fn with_cs<R, F: FnOnce(&mut SgMap<K, V, N>) -> R>(
+        &self,
+        cs: CriticalSection<'_>,
+        f: F,
+    ) -> R {
+        let mut opt_map = self.inner.borrow_ref_mut(cs);
+        f(opt_map.get_or_insert_with(SgMap::new))
+    }
+
+    /// Get the map through exclusive access to the cell, no critical section required.
+    pub fn get_mut(&mut self) -> &mut SgMap<K, V, N> {
+        self.inner
+            .get_mut()
+            .get_mut()
+            .get_or_insert_with(SgMap::new)
+    }
+
+    /// Consume the cell, returning the map.
+    pub fn into_inner(self) -> SgMap<K, V, N> {
+        self.inner.into_inner().into_inner().unwrap_or_default()
+    }
+}
+
+impl<K: Ord, V, const N: usize> Default for SgMapCell<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global ordered map for interrupt contexts, requires feature `critical-section`.
+///
+/// A thin wrapper over [`SgMapCell`] exposing only non-panicking operations: each method enters its own critical
+/// section and returns owned values, so no caller closure runs while the map is borrowed.
+/// Trait impls still do: `K::cmp` for every lookup and `V::clone` for [`get`][StaticSgMap::get] run inside the
+/// critical section, so keep them cheap (and non-reentrant). The map is created on first access, call
+/// [`init`][StaticSgMap::init] at startup to keep its `O(N)` initialization out of interrupt handlers.
+/// For compound operations (e.g. read-modify-write under one critical section), use [`as_cell`][StaticSgMap::as_cell].
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{SgError, StaticSgMap};
+///
+/// // Config key -> value, shared by the main loop and interrupt handlers
+/// static CONFIG: StaticSgMap<u8, u32, 2> = StaticSgMap::new();
+///
+/// fn on_uart_interrupt(baud: u32) {
+///     let _ = CONFIG.try_insert(0, baud);
+/// }
+///
+/// assert_eq!(CONFIG.try_insert(1, 8), Ok(None));
+/// on_uart_interrupt(115_200);
+///
+/// assert_eq!(CONFIG.get(&0), Some(115_200));
+/// assert_eq!(CONFIG.try_insert(2, 1), Err(SgError::StackCapacityExceeded));
+/// assert_eq!(CONFIG.remove(&1), Some(8));
+/// assert_eq!(CONFIG.len(), 1);
+/// ```
+pub struct StaticSgMap<K: Ord, V, const N: usize> {
+    cell: SgMapCell<K, V, N>,
+}
+
+impl<K: Ord, V, const N: usize> StaticSgMap<K, V, N> {
+    /// Makes a new, empty `StaticSgMap`. Usable in a `static` initializer.
+    pub const fn new() -> Self {
+        StaticSgMap {
+            cell: SgMapCell::new(),
+        }
+    }
+
+    /// Create the map now, if not already created, see [`SgMapCell::init`].
+    pub fn init(&self) {
+        self.cell.init()
+    }
+
+    /// Insert a key-value pair, see [`SgMap::try_insert`][crate::map::SgMap::try_insert].
+    /// Returns `Err` if the map is full and the key isn't present.
+    pub fn try_insert(&self, key: K, val: V) -> Result<Option<V>, SgError> {
+        self.cell.with(|map| map.try_insert(key, val))
+    }
+
+    /// Returns a copy of the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        V: Clone,
+    {
+        self.cell.with(|map| map.get(key).cloned())
+    }
+
+    /// Returns `true` if the map contains a value for the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.cell.with(|map| map.contains_key(key))
+    }
+
+    /// Removes a key from the map, returning its value if the key was present.
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.cell.with(|map| map.remove(key))
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.cell.with(|map| map.len())
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.cell.with(|map| map.is_empty())
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&self) {
+        self.cell.with(|map| map.clear())
+    }
+
+    /// Get the underlying cell, for closure-based access.
+    pub fn as_cell(&self) -> &SgMapCell<K, V, N> {
+        &self.cell
+    }
+
+    /// Consume the wrapper, returning the map.
+    pub fn into_inner(self) -> SgMap<K, V, N> {
+        self.cell.into_inner()
+    }
+}
+
+impl<K: Ord, V, const N: usize> Default for StaticSgMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
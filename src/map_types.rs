@@ -1,13 +1,13 @@
 use core::borrow::Borrow;
 use core::fmt;
-use core::iter::{FusedIterator, Peekable};
+use core::iter::FusedIterator;
 use core::ops::RangeBounds;
 
-use tinyvec::ArrayVec;
-
 use crate::map::SgMap;
+use crate::tree::node::NodeGetHelper;
 use crate::tree::{
-    Idx, IntoIter as TreeIntoIter, Iter as TreeIter, IterMut as TreeIterMut, SmallNode,
+    BalancePolicy, CursorPos, DefaultBalancePolicy, Idx, IntoIter as TreeIntoIter,
+    Iter as TreeIter, IterMut as TreeIterMut, NodeArena, SgError, SgVec,
 };
 
 // General Iterators ---------------------------------------------------------------------------------------------------
@@ -17,20 +17,20 @@ use crate::tree::{
 /// This `struct` is created by the [`iter`][crate::map::SgMap::iter] method on [`SgMap`][crate::map::SgMap].
 /// documentation for more.
 ///
-pub struct Iter<'a, T: Ord + Default, V: Default, const N: usize> {
+pub struct Iter<'a, T: Ord, V, const N: usize> {
     ref_iter: TreeIter<'a, T, V, N>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iter<'a, K, V, N> {
     /// Construct reference iterator.
-    pub(crate) fn new(map: &'a SgMap<K, V, N>) -> Self {
+    pub(crate) fn new<P: BalancePolicy>(map: &'a SgMap<K, V, N, P>) -> Self {
         Iter {
             ref_iter: TreeIter::new(&map.bst),
         }
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for Iter<'a, K, V, N> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -38,32 +38,107 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Iter<'a, K,
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for Iter<'a, K, V, N> {
     fn len(&self) -> usize {
         self.ref_iter.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Iter<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for Iter<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> FusedIterator for Iter<'a, K, V, N> {}
+
+/// An iterator over the entries of a [`SgMap`][crate::map::SgMap], with each entry's rank (in-order index).
+///
+/// This `struct` is created by the [`iter_ranked`][crate::map::SgMap::iter_ranked] method on
+/// [`SgMap`][crate::map::SgMap]. See its documentation for more.
+pub struct IterRanked<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
+    table: &'a SgMap<K, V, N, P>,
+    node_idx_iter: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
+    front_rank: usize,
+    back_rank: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> IterRanked<'a, K, V, N, P> {
+    /// Construct ranked reference iterator.
+    pub(crate) fn new(map: &'a SgMap<K, V, N, P>) -> Self {
+        let sorted_idxs = match map.bst.opt_root_idx {
+            Some(root_idx) => map.bst.flatten_subtree_to_sorted_idxs(root_idx),
+            None => SgVec::new(),
+        };
+
+        IterRanked {
+            table: map,
+            front_rank: 0,
+            back_rank: sorted_idxs.len(),
+            node_idx_iter: sorted_idxs.into_iter(),
+        }
+    }
+
+    fn to_ranked_ref(&self, rank: usize, idx: usize) -> (usize, &'a K, &'a V) {
+        let (key, val) = self.table.bst.arena.get(idx);
+        (rank, key, val)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> Iterator for IterRanked<'a, K, V, N, P> {
+    type Item = (usize, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_idx = self.node_idx_iter.next()?;
+        let rank = self.front_rank;
+        self.front_rank += 1;
+        Some(self.to_ranked_ref(rank, node_idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> DoubleEndedIterator
+    for IterRanked<'a, K, V, N, P>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node_idx = self.node_idx_iter.next_back()?;
+        self.back_rank -= 1;
+        Some(self.to_ranked_ref(self.back_rank, node_idx))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> ExactSizeIterator
+    for IterRanked<'a, K, V, N, P>
+{
+    fn len(&self) -> usize {
+        self.back_rank - self.front_rank
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> FusedIterator for IterRanked<'a, K, V, N, P> {}
 
 /// An owning iterator over the entries of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`into_iter`][crate::map::SgMap::into_iter] method on [`SgMap`][crate::map::SgMap].
 /// documentation for more.
-pub struct IntoIter<K: Ord + Default, V: Default, const N: usize> {
+pub struct IntoIter<K: Ord, V, const N: usize> {
     cons_iter: TreeIntoIter<K, V, N>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize> IntoIter<K, V, N> {
     /// Construct owning iterator.
-    pub(crate) fn new(map: SgMap<K, V, N>) -> Self {
+    pub(crate) fn new<P: BalancePolicy>(map: SgMap<K, V, N, P>) -> Self {
         IntoIter {
             cons_iter: TreeIntoIter::new(map.bst),
         }
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize> Iterator for IntoIter<K, V, N> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -71,32 +146,38 @@ impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoIter<K, V, N
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {
     fn len(&self) -> usize {
         self.cons_iter.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoIter<K, V, N> {}
+impl<K: Ord, V, const N: usize> DoubleEndedIterator for IntoIter<K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cons_iter.next_back()
+    }
+}
+
+impl<K: Ord, V, const N: usize> FusedIterator for IntoIter<K, V, N> {}
 
 /// An mutable iterator over the entries of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`iter_mut`][crate::map::SgMap::iter_mut] method on [`SgMap`][crate::map::SgMap].
 /// documentation for more.
-pub struct IterMut<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct IterMut<'a, K: Ord, V, const N: usize> {
     mut_iter: TreeIterMut<'a, K, V, N>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> IterMut<'a, K, V, N> {
     /// Construct owning iterator.
-    pub(crate) fn new(map: &'a mut SgMap<K, V, N>) -> Self {
+    pub(crate) fn new<P: BalancePolicy>(map: &'a mut SgMap<K, V, N, P>) -> Self {
         IterMut {
             mut_iter: TreeIterMut::new(&mut map.bst),
         }
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for IterMut<'a, K, V, N> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -104,13 +185,19 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for IterMut<'a,
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for IterMut<'a, K, V, N> {
     fn len(&self) -> usize {
         self.mut_iter.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for IterMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for IterMut<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.mut_iter.next_back()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> FusedIterator for IterMut<'a, K, V, N> {}
 
 // Key Iterators -------------------------------------------------------------------------------------------------------
 
@@ -120,11 +207,11 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for IterMut
 ///
 /// This `struct` is created by the [`keys`][crate::map::SgMap::keys] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct Keys<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct Keys<'a, K: Ord, V, const N: usize> {
     pub(crate) inner: Iter<'a, K, V, N>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Keys<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for Keys<'a, K, V, N> {
     type Item = &'a K;
 
     fn next(&mut self) -> Option<&'a K> {
@@ -132,23 +219,29 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Keys<'a, K,
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Keys<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for Keys<'a, K, V, N> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Keys<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for Keys<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> FusedIterator for Keys<'a, K, V, N> {}
 
 /// An owning iterator over the keys of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`into_keys`][crate::map::SgMap::into_keys] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct IntoKeys<K: Ord + Default, V: Default, const N: usize> {
+pub struct IntoKeys<K: Ord, V, const N: usize> {
     pub(crate) inner: IntoIter<K, V, N>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoKeys<K, V, N> {
+impl<K: Ord, V, const N: usize> Iterator for IntoKeys<K, V, N> {
     type Item = K;
 
     fn next(&mut self) -> Option<K> {
@@ -156,13 +249,19 @@ impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoKeys<K, V, N
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoKeys<K, V, N> {
+impl<K: Ord, V, const N: usize> ExactSizeIterator for IntoKeys<K, V, N> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoKeys<K, V, N> {}
+impl<K: Ord, V, const N: usize> DoubleEndedIterator for IntoKeys<K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<K: Ord, V, const N: usize> FusedIterator for IntoKeys<K, V, N> {}
 
 // Value Iterators -----------------------------------------------------------------------------------------------------
 
@@ -172,11 +271,11 @@ impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoKeys<K,
 ///
 /// This `struct` is created by the [`values`][crate::map::SgMap::values] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct Values<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct Values<'a, K: Ord, V, const N: usize> {
     pub(crate) inner: Iter<'a, K, V, N>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Values<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for Values<'a, K, V, N> {
     type Item = &'a V;
 
     fn next(&mut self) -> Option<&'a V> {
@@ -184,23 +283,29 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Values<'a, K
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Values<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for Values<'a, K, V, N> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Values<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for Values<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> FusedIterator for Values<'a, K, V, N> {}
 
 /// An owning iterator over the values of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`into_values`][crate::map::SgMap::into_values] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct IntoValues<K: Ord + Default, V: Default, const N: usize> {
+pub struct IntoValues<K: Ord, V, const N: usize> {
     pub(crate) inner: IntoIter<K, V, N>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoValues<K, V, N> {
+impl<K: Ord, V, const N: usize> Iterator for IntoValues<K, V, N> {
     type Item = V;
 
     fn next(&mut self) -> Option<V> {
@@ -208,23 +313,29 @@ impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoValues<K, V,
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoValues<K, V, N> {
+impl<K: Ord, V, const N: usize> ExactSizeIterator for IntoValues<K, V, N> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoValues<K, V, N> {}
+impl<K: Ord, V, const N: usize> DoubleEndedIterator for IntoValues<K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<K: Ord, V, const N: usize> FusedIterator for IntoValues<K, V, N> {}
 
 /// A mutable iterator over the values of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`values_mut`][crate::map::SgMap::values_mut] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct ValuesMut<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct ValuesMut<'a, K: Ord, V, const N: usize> {
     pub(crate) inner: IterMut<'a, K, V, N>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for ValuesMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for ValuesMut<'a, K, V, N> {
     type Item = &'a mut V;
 
     fn next(&mut self) -> Option<&'a mut V> {
@@ -232,29 +343,33 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for ValuesMut<'a
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator
-    for ValuesMut<'a, K, V, N>
-{
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for ValuesMut<'a, K, V, N> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for ValuesMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for ValuesMut<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> FusedIterator for ValuesMut<'a, K, V, N> {}
 
 // Entry APIs ----------------------------------------------------------------------------------------------------------
 
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This `enum` is constructed from the [`SgMap::entry`] method on [`SgMap`].
-pub enum Entry<'a, K: Ord + Default, V: Default, const N: usize> {
+pub enum Entry<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
     /// A vacant entry.
-    Vacant(VacantEntry<'a, K, V, N>),
+    Vacant(VacantEntry<'a, K, V, N, P>),
     /// An occupied entry.
-    Occupied(OccupiedEntry<'a, K, V, N>),
+    Occupied(OccupiedEntry<'a, K, V, N, P>),
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> Entry<'a, K, V, N, P> {
     /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable
     /// reference to the value in the entry.
     ///
@@ -361,7 +476,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
     ///    .or_insert(42);
     /// assert_eq!(map["poneyland"], 43);
     /// ```
-    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Entry<'a, K, V, N> {
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Entry<'a, K, V, N, P> {
         match self {
             Entry::Occupied(mut entry) => {
                 f(entry.get_mut());
@@ -384,7 +499,10 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
     ///
     /// assert_eq!(map["poneyland"], None);
     /// ```
-    pub fn or_default(self) -> &'a mut V {
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
         match self {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => entry.insert(Default::default()),
@@ -394,12 +512,14 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
 
 /// A view into a vacant entry in a [`SgMap`][crate::map::SgMap].
 /// It is part of the [`Entry`] enum.
-pub struct VacantEntry<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct VacantEntry<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
     pub(super) key: K,
-    pub(super) table: &'a mut SgMap<K, V, N>,
+    pub(super) path: SgVec<[Idx; N]>,
+    pub(super) ngh: NodeGetHelper<Idx>,
+    pub(super) table: &'a mut SgMap<K, V, N, P>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> VacantEntry<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> VacantEntry<'a, K, V, N, P> {
     /// Gets a reference to the key that would be used when inserting a value
     /// through the [`VacantEntry`][crate::map_types::VacantEntry].
     ///
@@ -450,23 +570,27 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> VacantEntry<'a, K, V, N>
     /// assert_eq!(map["poneyland"], 37);
     /// ```
     pub fn insert(self, value: V) -> &'a mut V {
-        let (_, new_node_idx) = self
+        if self.table.bst.is_full() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+
+        let new_node_idx = self
             .table
             .bst
-            .internal_balancing_insert::<Idx>(self.key, value);
+            .internal_insert_vacant(&self.path, self.ngh, self.key, value);
 
-        self.table.bst.arena[new_node_idx].get_mut().1
+        self.table.bst.arena.get_mut(new_node_idx).1
     }
 }
 
 /// A view into an occupied entry in a [`SgMap`][crate::map::SgMap].
 /// It is part of the [`Entry`] enum.
-pub struct OccupiedEntry<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct OccupiedEntry<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
     pub(super) node_idx: usize,
-    pub(super) table: &'a mut SgMap<K, V, N>,
+    pub(super) table: &'a mut SgMap<K, V, N, P>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> OccupiedEntry<'a, K, V, N, P> {
     /// Gets a reference to the key in the entry.
     ///
     /// # Examples
@@ -479,7 +603,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N
     /// assert_eq!(map.entry("poneyland").key(), &"poneyland");
     /// ```
     pub fn key(&self) -> &K {
-        self.table.bst.arena[self.node_idx].key()
+        self.table.bst.arena.key(self.node_idx)
     }
 
     /// Gets a reference to the value in the entry.
@@ -498,7 +622,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N
     /// }
     /// ```
     pub fn get(&self) -> &V {
-        self.table.bst.arena[self.node_idx].val()
+        self.table.bst.arena.val(self.node_idx)
     }
 
     /// Gets a mutable reference to the value in the entry.
@@ -528,7 +652,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N
     /// assert_eq!(map["poneyland"], 24);
     /// ```
     pub fn get_mut(&mut self) -> &mut V {
-        self.table.bst.arena[self.node_idx].get_mut().1
+        self.table.bst.arena.get_mut(self.node_idx).1
     }
 
     /// Converts the entry into a mutable reference to its value.
@@ -553,7 +677,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N
     /// assert_eq!(map["poneyland"], 22);
     /// ```
     pub fn into_mut(self) -> &'a mut V {
-        self.table.bst.arena[self.node_idx].get_mut().1
+        self.table.bst.arena.get_mut(self.node_idx).1
     }
 
     /// Sets the value of the entry with the `OccupiedEntry`'s key,
@@ -628,15 +752,15 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N
 /// The error returned by [`try_insert_std`](SgMap::try_insert_std) when the key already exists.
 ///
 /// Contains the occupied entry, and the value that was not inserted.
-pub struct OccupiedError<'a, K: 'a + Ord + Default, V: 'a + Default, const N: usize> {
+pub struct OccupiedError<'a, K: 'a + Ord, V: 'a, const N: usize, P = DefaultBalancePolicy> {
     /// The entry in the map that was already occupied.
-    pub entry: OccupiedEntry<'a, K, V, N>,
+    pub entry: OccupiedEntry<'a, K, V, N, P>,
     /// The value which was not inserted, because the entry was already occupied.
     pub value: V,
 }
 
-impl<K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize> fmt::Debug
-    for OccupiedError<'_, K, V, N>
+impl<K: fmt::Debug + Ord, V: fmt::Debug, const N: usize, P: BalancePolicy> fmt::Debug
+    for OccupiedError<'_, K, V, N, P>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("OccupiedError")
@@ -647,8 +771,8 @@ impl<K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize> fmt
     }
 }
 
-impl<'a, K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize> fmt::Display
-    for OccupiedError<'a, K, V, N>
+impl<'a, K: fmt::Debug + Ord, V: fmt::Debug, const N: usize, P: BalancePolicy> fmt::Display
+    for OccupiedError<'a, K, V, N, P>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -661,25 +785,389 @@ impl<'a, K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize>
     }
 }
 
+// Batch Insert --------------------------------------------------------------------------------------------------------
+
+/// A lazy iterator inserting each entry of another iterator into a [`SgMap`], yielding a per-entry result.
+///
+/// This `struct` is created by the [`insert_many`][crate::map::SgMap::insert_many] method on
+/// [`SgMap`][crate::map::SgMap]. See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct InsertMany<'a, K: Ord, V, const N: usize, I, P = DefaultBalancePolicy> {
+    pub(crate) table: &'a mut SgMap<K, V, N, P>,
+    pub(crate) iter: I,
+}
+
+impl<'a, K, V, const N: usize, I, P: BalancePolicy> Iterator for InsertMany<'a, K, V, N, I, P>
+where
+    K: Ord,
+    I: Iterator<Item = (K, V)>,
+{
+    type Item = Result<Option<V>, (SgError, (K, V))>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, val) = self.iter.next()?;
+        match self.table.contains_key(&key) || !self.table.is_full() {
+            true => Some(Ok(self.table.insert(key, val))),
+            false => Some(Err((SgError::StackCapacityExceeded, (key, val)))),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V, const N: usize, I, P: BalancePolicy> ExactSizeIterator
+    for InsertMany<'a, K, V, N, I, P>
+where
+    K: Ord,
+    I: ExactSizeIterator<Item = (K, V)>,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K, V, const N: usize, I, P: BalancePolicy> FusedIterator for InsertMany<'a, K, V, N, I, P>
+where
+    K: Ord,
+    I: FusedIterator<Item = (K, V)>,
+{
+}
+
+// Pop While -----------------------------------------------------------------------------------------------------------
+
+/// A lazy iterator removing extremal entries of a [`SgMap`] for as long as a predicate holds.
+///
+/// This `struct` is created by the [`pop_first_while`][crate::map::SgMap::pop_first_while] and
+/// [`pop_last_while`][crate::map::SgMap::pop_last_while] methods on [`SgMap`][crate::map::SgMap].
+/// See their documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PopWhile<'a, K: Ord, V, const N: usize, F, P = DefaultBalancePolicy> {
+    pub(crate) table: &'a mut SgMap<K, V, N, P>,
+    pub(crate) pred: F,
+    pub(crate) from_back: bool,
+    pub(crate) done: bool,
+}
+
+impl<'a, K, V, const N: usize, F, P: BalancePolicy> Iterator for PopWhile<'a, K, V, N, F, P>
+where
+    K: Ord,
+    F: FnMut(&K, &V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let opt_extremal = match self.from_back {
+            true => self.table.last_key_value(),
+            false => self.table.first_key_value(),
+        };
+
+        match opt_extremal {
+            Some((key, val)) if (self.pred)(key, val) => match self.from_back {
+                true => self.table.pop_last(),
+                false => self.table.pop_first(),
+            },
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.done {
+            true => (0, Some(0)),
+            false => (0, Some(self.table.len())),
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, F, P: BalancePolicy> FusedIterator for PopWhile<'a, K, V, N, F, P>
+where
+    K: Ord,
+    F: FnMut(&K, &V) -> bool,
+{
+}
+
+// Extract If ----------------------------------------------------------------------------------------------------------
+
+/// A lazy iterator removing the entries of a [`SgMap`] for which a predicate holds.
+///
+/// This `struct` is created by the [`extract_if`][crate::map::SgMap::extract_if] method on
+/// [`SgMap`][crate::map::SgMap]. See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIf<'a, K: Ord, V, const N: usize, F, P = DefaultBalancePolicy> {
+    table: &'a mut SgMap<K, V, N, P>,
+    node_idx_iter: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
+    pred: F,
+}
+
+impl<'a, K: Ord, V, const N: usize, F, P: BalancePolicy> ExtractIf<'a, K, V, N, F, P> {
+    /// Construct extracting iterator.
+    pub(crate) fn new(map: &'a mut SgMap<K, V, N, P>, pred: F) -> Self {
+        // Removal doesn't relocate surviving nodes, so these indexes stay valid
+        let sorted_idxs = match map.bst.opt_root_idx {
+            Some(root_idx) => map.bst.flatten_subtree_to_sorted_idxs(root_idx),
+            None => SgVec::new(),
+        };
+
+        ExtractIf {
+            table: map,
+            node_idx_iter: sorted_idxs.into_iter(),
+            pred,
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, F, P: BalancePolicy> Iterator for ExtractIf<'a, K, V, N, F, P>
+where
+    K: Ord,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for node_idx in self.node_idx_iter.by_ref() {
+            let (key, val) = self.table.bst.arena.get_mut(node_idx);
+            if (self.pred)(key, val) {
+                return self.table.bst.priv_remove_by_idx(node_idx);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.node_idx_iter.len()))
+    }
+}
+
+impl<'a, K, V, const N: usize, F, P: BalancePolicy> FusedIterator for ExtractIf<'a, K, V, N, F, P>
+where
+    K: Ord,
+    F: FnMut(&K, &mut V) -> bool,
+{
+}
+
+// Cursors -------------------------------------------------------------------------------------------------------------
+
+/// A cursor over a [`SgMap`], pointing at an entry or at a "ghost" position logically past both ends.
+/// Stepping between adjacent entries is amortized `O(1)`, without a search from the root.
+///
+/// This `struct` is created by the [`lower_bound`][crate::map::SgMap::lower_bound] and
+/// [`upper_bound`][crate::map::SgMap::upper_bound] methods on [`SgMap`][crate::map::SgMap].
+/// See their documentation for more.
+pub struct Cursor<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
+    table: &'a SgMap<K, V, N, P>,
+    pos: CursorPos<N>,
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> Cursor<'a, K, V, N, P> {
+    /// Construct cursor.
+    pub(crate) fn new(table: &'a SgMap<K, V, N, P>, pos: CursorPos<N>) -> Self {
+        Cursor { table, pos }
+    }
+
+    /// Returns the key-value pair the cursor points at, or `None` at the ghost position.
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.pos.curr().map(|idx| self.table.bst.arena.get(idx))
+    }
+
+    /// Returns the key the cursor points at, or `None` at the ghost position.
+    pub fn key(&self) -> Option<&'a K> {
+        self.key_value().map(|(k, _)| k)
+    }
+
+    /// Returns the value the cursor points at, or `None` at the ghost position.
+    pub fn value(&self) -> Option<&'a V> {
+        self.key_value().map(|(_, v)| v)
+    }
+
+    /// Moves to the next entry. From the last entry, moves to the ghost position.
+    /// From the ghost position, moves to the first entry.
+    pub fn move_next(&mut self) {
+        self.pos.move_next(&self.table.bst);
+    }
+
+    /// Moves to the previous entry. From the first entry, moves to the ghost position.
+    /// From the ghost position, moves to the last entry.
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(&self.table.bst);
+    }
+
+    /// Returns the entry after the cursor, without moving.
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let table = self.table;
+        self.pos
+            .next_idx(&table.bst)
+            .map(|idx| table.bst.arena.get(idx))
+    }
+
+    /// Returns the entry before the cursor, without moving.
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let table = self.table;
+        self.pos
+            .prev_idx(&table.bst)
+            .map(|idx| table.bst.arena.get(idx))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> Clone for Cursor<'a, K, V, N, P> {
+    fn clone(&self) -> Self {
+        Cursor {
+            table: self.table,
+            pos: self.pos.clone(),
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, P: BalancePolicy> fmt::Debug for Cursor<'a, K, V, N, P>
+where
+    K: fmt::Debug + Ord,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.key_value()).finish()
+    }
+}
+
+/// A cursor over a [`SgMap`] with editing operations: value mutation, and insertion/removal at the cursor.
+///
+/// This `struct` is created by the [`lower_bound_mut`][crate::map::SgMap::lower_bound_mut] and
+/// [`upper_bound_mut`][crate::map::SgMap::upper_bound_mut] methods on [`SgMap`][crate::map::SgMap].
+/// See their documentation for more.
+pub struct CursorMut<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
+    table: &'a mut SgMap<K, V, N, P>,
+    pos: CursorPos<N>,
+}
+
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> CursorMut<'a, K, V, N, P> {
+    /// Construct mutable cursor.
+    pub(crate) fn new(table: &'a mut SgMap<K, V, N, P>, pos: CursorPos<N>) -> Self {
+        CursorMut { table, pos }
+    }
+
+    /// Returns the key-value pair the cursor points at, or `None` at the ghost position.
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.pos.curr().map(|idx| self.table.bst.arena.get(idx))
+    }
+
+    /// Returns the key and mutable value the cursor points at, or `None` at the ghost position.
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        let arena = &mut self.table.bst.arena;
+        self.pos.curr().map(move |idx| arena.get_mut(idx))
+    }
+
+    /// Returns the key the cursor points at, or `None` at the ghost position.
+    pub fn key(&self) -> Option<&K> {
+        self.key_value().map(|(k, _)| k)
+    }
+
+    /// Returns the value the cursor points at, or `None` at the ghost position.
+    pub fn value(&self) -> Option<&V> {
+        self.key_value().map(|(_, v)| v)
+    }
+
+    /// Returns the mutable value the cursor points at, or `None` at the ghost position.
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.key_value_mut().map(|(_, v)| v)
+    }
+
+    /// Moves to the next entry. From the last entry, moves to the ghost position.
+    /// From the ghost position, moves to the first entry.
+    pub fn move_next(&mut self) {
+        self.pos.move_next(&self.table.bst);
+    }
+
+    /// Moves to the previous entry. From the first entry, moves to the ghost position.
+    /// From the ghost position, moves to the last entry.
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(&self.table.bst);
+    }
+
+    /// Returns the entry after the cursor, without moving.
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        self.pos
+            .next_idx(&self.table.bst)
+            .map(|idx| self.table.bst.arena.get(idx))
+    }
+
+    /// Returns the entry before the cursor, without moving.
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        self.pos
+            .prev_idx(&self.table.bst)
+            .map(|idx| self.table.bst.arena.get(idx))
+    }
+
+    /// Returns a read-only cursor at the same position, borrowing this one.
+    pub fn as_cursor(&self) -> Cursor<'_, K, V, N, P> {
+        Cursor::new(self.table, self.pos.clone())
+    }
+
+    /// Inserts an entry immediately after the cursor, without moving.
+    /// At the ghost position, the entry is inserted as the first entry.
+    ///
+    /// Returns [`SgError::UnsortedInput`] if `key` doesn't belong between the cursor and the next entry,
+    /// or [`SgError::StackCapacityExceeded`] if the map is full. The map is unchanged on error.
+    pub fn insert_after(&mut self, key: K, val: V) -> Result<(), SgError> {
+        self.pos.insert_after(&mut self.table.bst, key, val)
+    }
+
+    /// Inserts an entry immediately before the cursor, without moving.
+    /// At the ghost position, the entry is inserted as the last entry.
+    ///
+    /// Returns [`SgError::UnsortedInput`] if `key` doesn't belong between the previous entry and the cursor,
+    /// or [`SgError::StackCapacityExceeded`] if the map is full. The map is unchanged on error.
+    pub fn insert_before(&mut self, key: K, val: V) -> Result<(), SgError> {
+        self.pos.insert_before(&mut self.table.bst, key, val)
+    }
+
+    /// Removes the entry the cursor points at, moving to the next entry (or the ghost position if it was last).
+    /// Returns `None`, without moving, at the ghost position.
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        self.pos.remove_current(&mut self.table.bst)
+    }
+
+    /// Removes the entry the cursor points at, moving to the previous entry (or the ghost position if it was
+    /// first). Returns `None`, without moving, at the ghost position.
+    pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
+        self.pos.remove_current_and_move_back(&mut self.table.bst)
+    }
+}
+
+impl<'a, K, V, const N: usize, P: BalancePolicy> fmt::Debug for CursorMut<'a, K, V, N, P>
+where
+    K: fmt::Debug + Ord,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.key_value()).finish()
+    }
+}
+
 // Range APIs ----------------------------------------------------------------------------------------------------------
 
 /// An iterator over a sub-range of entries in a [`SgMap`].
 ///
 /// This `struct` is created by the [`range`][`crate::map::SgMap::range`] method on [`SgMap`][crate::map::SgMap]. See its
 /// documentation for more.
-pub struct Range<'a, K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) table: &'a SgMap<K, V, N>,
-    pub(crate) node_idx_iter: <ArrayVec<[usize; N]> as IntoIterator>::IntoIter,
+pub struct Range<'a, K: Ord, V, const N: usize, P = DefaultBalancePolicy> {
+    pub(crate) table: &'a SgMap<K, V, N, P>,
+    pub(crate) node_idx_iter: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Range<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> Range<'a, K, V, N, P> {
     fn to_node_ref(&self, idx: usize) -> (&'a K, &'a V) {
-        let node = &self.table.bst.arena[idx];
-        (node.key(), node.val())
+        self.table.bst.arena.get(idx)
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Range<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> Iterator for Range<'a, K, V, N, P> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -688,14 +1176,16 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Range<'a, K,
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> DoubleEndedIterator for Range<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> DoubleEndedIterator
+    for Range<'a, K, V, N, P>
+{
     fn next_back(&mut self) -> Option<Self::Item> {
         let node_idx = self.node_idx_iter.next_back()?;
         Some(self.to_node_ref(node_idx))
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Range<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, P: BalancePolicy> FusedIterator for Range<'a, K, V, N, P> {}
 
 /// A mutable iterator over a sub-range of entries in a [`SgMap`].
 ///
@@ -703,158 +1193,58 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Range<'
 /// documentation for more.
 ///
 /// [`range_mut`]: SgMap::range_mut
-pub struct RangeMut<'a, K: Ord + Default, V: Default, const N: usize> {
-    inner: RangeMutPeekable<'a, K, V, N>,
-    last: Option<RangeMutLast<'a, K, V, N>>,
-    total_cnt: usize,
-    spent_cnt: usize,
+pub struct RangeMut<'a, K: Ord, V, const N: usize> {
+    inner: TreeIterMut<'a, K, V, N>,
 }
 
-type RangeMutLast<'a, K, V, const N: usize> =
-    <Peekable<TreeIterMut<'a, K, V, N>> as Iterator>::Item;
-
-type RangeMutPeekable<'a, K, V, const N: usize> = Peekable<TreeIterMut<'a, K, V, N>>;
-
 impl<'a, K, V, const N: usize> RangeMut<'a, K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     // Constructor
-    pub(crate) fn new<T, R>(map: &'a mut SgMap<K, V, N>, range: &R) -> Self
+    pub(crate) fn new<T, R, P>(map: &'a mut SgMap<K, V, N, P>, range: &R) -> Self
     where
+        P: BalancePolicy,
         T: Ord + ?Sized,
-        K: Borrow<T> + Ord + Default,
+        K: Borrow<T> + Ord,
         R: RangeBounds<T>,
     {
-        let len = RangeMut::compute_len(map, range);
-
-        let (iter, last) = RangeMut::init_iter_mut(map, range);
         Self {
-            inner: iter,
-            last,
-            total_cnt: len,
-            spent_cnt: 0,
-        }
-    }
-
-    // Compute amount of items to return
-    fn compute_len<T, R>(map: &SgMap<K, V, N>, range: &R) -> usize
-    where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord + Default,
-        R: RangeBounds<T>,
-    {
-        let mut peekable = map.bst.iter().peekable();
-        let mut len = 0;
-
-        // Advance immutable iter to start
-        while let Some(node) = peekable.peek() {
-            if range.contains(node.0.borrow()) {
-                break;
-            }
-
-            peekable.next();
+            inner: map.bst.range_iter_mut(range),
         }
-
-        // Count remaining
-        for node in peekable {
-            if range.contains(node.0.borrow()) {
-                len += 1;
-            } else {
-                break;
-            }
-        }
-
-        len
-    }
-
-    // Prepare mutable iterator to return first item in range
-    fn init_iter_mut<T, R>(
-        map: &'a mut SgMap<K, V, N>,
-        range: &R,
-    ) -> (
-        RangeMutPeekable<'a, K, V, N>,
-        Option<RangeMutLast<'a, K, V, N>>,
-    )
-    where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord + Default,
-        R: RangeBounds<T>,
-    {
-        let mut peekable = map.bst.iter_mut().peekable();
-        let mut last = None;
-
-        // Advance mutable iter to start
-        while let Some(node) = peekable.peek() {
-            if range.contains(node.0.borrow()) {
-                break;
-            }
-
-            peekable.next();
-        }
-
-        while let Some(node) = peekable.next_back() {
-            if range.contains(node.0.borrow()) {
-                last = Some(node);
-                break;
-            }
-        }
-
-        (peekable, last)
     }
 }
 
 impl<'a, K, V, const N: usize> Iterator for RangeMut<'a, K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.spent_cnt < self.total_cnt {
-            self.spent_cnt += 1;
-            match self.inner.next() {
-                Some(node) => Some(node),
-                None => self.last.take(),
-            }
-        } else {
-            None
-        }
+        self.inner.next()
     }
 }
 
 impl<'a, K, V, const N: usize> DoubleEndedIterator for RangeMut<'a, K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.spent_cnt < self.total_cnt {
-            self.spent_cnt += 1;
-            match self.last.take() {
-                Some(node) => Some(node),
-                None => self.inner.next_back(),
-            }
-        } else {
-            None
-        }
+        self.inner.next_back()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for RangeMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> FusedIterator for RangeMut<'a, K, V, N> {}
 
 /*
 // TODO: does commit to this interface limit potential optimizations?
 impl<'a, K, V, const N: usize> ExactSizeIterator for RangeMut<'a, K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+        self.inner.len()
     }
 }
 */
@@ -0,0 +1,322 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+// Operation Vocabulary ------------------------------------------------------------------------------------------------
+
+/// A single [`SgMap`][crate::map::SgMap] API call, with its arguments.
+///
+/// Executed against an [`SgMap`][crate::map::SgMap] by [`apply`] and against a [`RefModel`] by [`apply_model`].
+/// Both return an [`OpOutput`] that must be equal for the same sequence of operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op<K, V> {
+    /// [`SgMap::insert`][crate::map::SgMap::insert], panics if capacity is exceeded.
+    Insert {
+        /// Key to insert.
+        key: K,
+        /// Value to insert.
+        val: V,
+    },
+    /// [`SgMap::try_insert`][crate::map::SgMap::try_insert].
+    TryInsert {
+        /// Key to insert.
+        key: K,
+        /// Value to insert.
+        val: V,
+    },
+    /// [`SgMap::remove`][crate::map::SgMap::remove].
+    Remove {
+        /// Key to remove.
+        key: K,
+    },
+    /// [`SgMap::remove_entry`][crate::map::SgMap::remove_entry].
+    RemoveEntry {
+        /// Key to remove.
+        key: K,
+    },
+    /// [`SgMap::get`][crate::map::SgMap::get].
+    Get {
+        /// Key to look up.
+        key: K,
+    },
+    /// [`SgMap::get_key_value`][crate::map::SgMap::get_key_value].
+    GetKeyValue {
+        /// Key to look up.
+        key: K,
+    },
+    /// [`SgMap::get_mut`][crate::map::SgMap::get_mut], overwriting the value if present.
+    GetMut {
+        /// Key to look up.
+        key: K,
+        /// Replacement value.
+        val: V,
+    },
+    /// [`SgMap::contains_key`][crate::map::SgMap::contains_key].
+    ContainsKey {
+        /// Key to look up.
+        key: K,
+    },
+    /// [`SgMap::first_key_value`][crate::map::SgMap::first_key_value].
+    FirstKeyValue,
+    /// [`SgMap::last_key_value`][crate::map::SgMap::last_key_value].
+    LastKeyValue,
+    /// [`SgMap::pop_first`][crate::map::SgMap::pop_first].
+    PopFirst,
+    /// [`SgMap::pop_last`][crate::map::SgMap::pop_last].
+    PopLast,
+    /// [`SgMap::len`][crate::map::SgMap::len].
+    Len,
+    /// [`SgMap::is_empty`][crate::map::SgMap::is_empty].
+    IsEmpty,
+    /// [`SgMap::is_full`][crate::map::SgMap::is_full].
+    IsFull,
+    /// [`SgMap::clear`][crate::map::SgMap::clear].
+    Clear,
+    /// [`SgMap::retain`][crate::map::SgMap::retain], keeping only keys less than `key`.
+    RetainLessThan {
+        /// Exclusive upper bound of retained keys.
+        key: K,
+    },
+    /// [`SgMap::split_off`][crate::map::SgMap::split_off].
+    SplitOff {
+        /// Key to split at.
+        key: K,
+    },
+    /// [`SgMap::take_smallest`][crate::map::SgMap::take_smallest].
+    TakeSmallest {
+        /// Number of entries to take.
+        k: usize,
+    },
+    /// [`SgMap::take_largest`][crate::map::SgMap::take_largest].
+    TakeLargest {
+        /// Number of entries to take.
+        k: usize,
+    },
+    /// [`SgMap::range`][crate::map::SgMap::range], with an inclusive range. Bounds are swapped if reversed.
+    RangeInclusive {
+        /// One range bound.
+        start: K,
+        /// The other range bound.
+        end: K,
+    },
+    /// [`SgMap::iter`][crate::map::SgMap::iter].
+    Iter,
+}
+
+/// The observable result of executing an [`Op`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpOutput<K, V> {
+    /// No return value.
+    Unit,
+    /// A boolean query result.
+    Bool(bool),
+    /// A length.
+    Len(usize),
+    /// A value, if any.
+    Value(Option<V>),
+    /// A key-value pair, if any.
+    KeyValue(Option<(K, V)>),
+    /// Result of a fallible insert.
+    TryInsert(Result<Option<V>, SgError>),
+    /// Ordered entries, e.g. from iteration or a split.
+    Entries(Vec<(K, V)>),
+}
+
+// Reference Model -----------------------------------------------------------------------------------------------------
+
+/// Reference model of an [`SgMap`][crate::map::SgMap] with capacity `N`, backed by the standard library's
+/// [`BTreeMap`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefModel<K: Ord, V, const N: usize> {
+    /// Backing map.
+    pub map: BTreeMap<K, V>,
+}
+
+impl<K: Ord, V, const N: usize> RefModel<K, V, N> {
+    /// Makes a new, empty `RefModel`.
+    pub fn new() -> Self {
+        RefModel {
+            map: BTreeMap::new(),
+        }
+    }
+}
+
+// Executors -----------------------------------------------------------------------------------------------------------
+
+/// Execute `op` against `map`, returning its observable result.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::model::{apply, apply_model, Op, OpOutput, RefModel};
+/// use scapegoat::SgMap;
+///
+/// let mut map = SgMap::<u8, u8, 4>::new();
+/// let mut model = RefModel::<u8, u8, 4>::new();
+///
+/// let ops = [
+///     Op::Insert { key: 1, val: 10 },
+///     Op::Insert { key: 2, val: 20 },
+///     Op::Remove { key: 1 },
+///     Op::Iter,
+/// ];
+///
+/// for op in ops {
+///     assert_eq!(apply(&mut map, op.clone()), apply_model(&mut model, op));
+/// }
+///
+/// assert_eq!(apply(&mut map, Op::Len), OpOutput::Len(1));
+/// ```
+pub fn apply<K, V, const N: usize>(map: &mut SgMap<K, V, N>, op: Op<K, V>) -> OpOutput<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    match op {
+        Op::Insert { key, val } => OpOutput::Value(map.insert(key, val)),
+        Op::TryInsert { key, val } => OpOutput::TryInsert(map.try_insert(key, val)),
+        Op::Remove { key } => OpOutput::Value(map.remove(&key)),
+        Op::RemoveEntry { key } => OpOutput::KeyValue(map.remove_entry(&key)),
+        Op::Get { key } => OpOutput::Value(map.get(&key).cloned()),
+        Op::GetKeyValue { key } => {
+            OpOutput::KeyValue(map.get_key_value(&key).map(|(k, v)| (k.clone(), v.clone())))
+        }
+        Op::GetMut { key, val } => {
+            OpOutput::Value(map.get_mut(&key).map(|v| core::mem::replace(v, val)))
+        }
+        Op::ContainsKey { key } => OpOutput::Bool(map.contains_key(&key)),
+        Op::FirstKeyValue => {
+            OpOutput::KeyValue(map.first_key_value().map(|(k, v)| (k.clone(), v.clone())))
+        }
+        Op::LastKeyValue => {
+            OpOutput::KeyValue(map.last_key_value().map(|(k, v)| (k.clone(), v.clone())))
+        }
+        Op::PopFirst => OpOutput::KeyValue(map.pop_first()),
+        Op::PopLast => OpOutput::KeyValue(map.pop_last()),
+        Op::Len => OpOutput::Len(map.len()),
+        Op::IsEmpty => OpOutput::Bool(map.is_empty()),
+        Op::IsFull => OpOutput::Bool(map.is_full()),
+        Op::Clear => {
+            map.clear();
+            OpOutput::Unit
+        }
+        Op::RetainLessThan { key } => {
+            map.retain(|k, _| *k < key);
+            OpOutput::Unit
+        }
+        Op::SplitOff { key } => OpOutput::Entries(map.split_off(&key).into_iter().collect()),
+        Op::TakeSmallest { k } => OpOutput::Entries(map.take_smallest(k).into_iter().collect()),
+        Op::TakeLargest { k } => OpOutput::Entries(map.take_largest(k).into_iter().collect()),
+        Op::RangeInclusive { start, end } => {
+            let (start, end) = if start <= end {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            OpOutput::Entries(
+                map.range(start..=end)
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+            )
+        }
+        Op::Iter => OpOutput::Entries(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+    }
+}
+
+/// Execute `op` against `model`, returning its observable result.
+/// See [`apply`] for an example.
+pub fn apply_model<K, V, const N: usize>(
+    model: &mut RefModel<K, V, N>,
+    op: Op<K, V>,
+) -> OpOutput<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    let map = &mut model.map;
+    match op {
+        Op::Insert { key, val } => {
+            if !map.contains_key(&key) && (map.len() >= N) {
+                panic!("Stack-storage capacity exceeded!");
+            }
+            OpOutput::Value(map.insert(key, val))
+        }
+        Op::TryInsert { key, val } => {
+            if !map.contains_key(&key) && (map.len() >= N) {
+                OpOutput::TryInsert(Err(SgError::StackCapacityExceeded))
+            } else {
+                OpOutput::TryInsert(Ok(map.insert(key, val)))
+            }
+        }
+        Op::Remove { key } => OpOutput::Value(map.remove(&key)),
+        Op::RemoveEntry { key } => OpOutput::KeyValue(map.remove_entry(&key)),
+        Op::Get { key } => OpOutput::Value(map.get(&key).cloned()),
+        Op::GetKeyValue { key } => {
+            OpOutput::KeyValue(map.get_key_value(&key).map(|(k, v)| (k.clone(), v.clone())))
+        }
+        Op::GetMut { key, val } => {
+            OpOutput::Value(map.get_mut(&key).map(|v| core::mem::replace(v, val)))
+        }
+        Op::ContainsKey { key } => OpOutput::Bool(map.contains_key(&key)),
+        Op::FirstKeyValue => {
+            OpOutput::KeyValue(map.iter().next().map(|(k, v)| (k.clone(), v.clone())))
+        }
+        Op::LastKeyValue => {
+            OpOutput::KeyValue(map.iter().next_back().map(|(k, v)| (k.clone(), v.clone())))
+        }
+        Op::PopFirst => {
+            let opt_key = map.keys().next().cloned();
+            OpOutput::KeyValue(opt_key.and_then(|k| map.remove_entry(&k)))
+        }
+        Op::PopLast => {
+            let opt_key = map.keys().next_back().cloned();
+            OpOutput::KeyValue(opt_key.and_then(|k| map.remove_entry(&k)))
+        }
+        Op::Len => OpOutput::Len(map.len()),
+        Op::IsEmpty => OpOutput::Bool(map.is_empty()),
+        Op::IsFull => OpOutput::Bool(map.len() == N),
+        Op::Clear => {
+            map.clear();
+            OpOutput::Unit
+        }
+        Op::RetainLessThan { key } => {
+            map.retain(|k, _| *k < key);
+            OpOutput::Unit
+        }
+        Op::SplitOff { key } => OpOutput::Entries(map.split_off(&key).into_iter().collect()),
+        Op::TakeSmallest { k } => {
+            let taken_keys: Vec<K> = map.keys().take(k).cloned().collect();
+            OpOutput::Entries(
+                taken_keys
+                    .iter()
+                    .filter_map(|k| map.remove_entry(k))
+                    .collect(),
+            )
+        }
+        Op::TakeLargest { k } => {
+            let mut taken_keys: Vec<K> = map.keys().rev().take(k).cloned().collect();
+            taken_keys.reverse();
+            OpOutput::Entries(
+                taken_keys
+                    .iter()
+                    .filter_map(|k| map.remove_entry(k))
+                    .collect(),
+            )
+        }
+        Op::RangeInclusive { start, end } => {
+            let (start, end) = if start <= end {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            OpOutput::Entries(
+                map.range(start..=end)
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+            )
+        }
+        Op::Iter => OpOutput::Entries(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+    }
+}
@@ -0,0 +1,217 @@
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use tinyvec::ArrayVec;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Ordered map permitting duplicate keys, up to `N` key-value pairs.
+///
+/// Values sharing a key are kept in insertion order.
+/// Each pair is stored with a per-key sequence number, so lookups clone the key to build search bounds.
+/// Every method taking `key: &K` clones it twice, and once more per value removed by `remove_all`,
+/// so keys should be cheap to clone (e.g. integers, `&str`, or [`InlineStr`][crate::InlineStr]).
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMultiMap;
+///
+/// // Timestamp -> event
+/// let mut events = SgMultiMap::<u32, &str, 8>::new();
+/// events.insert(20, "irq");
+/// events.insert(10, "boot");
+/// events.insert(20, "dma");
+///
+/// assert!(events.get_all(&20).eq([&"irq", &"dma"]));
+/// assert_eq!(events.count(&20), 2);
+/// assert!(events.iter().eq([(&10, &"boot"), (&20, &"irq"), (&20, &"dma")]));
+///
+/// assert_eq!(events.remove(&20), Some("irq"));
+/// assert!(events.get_all(&20).eq([&"dma"]));
+/// ```
+#[derive(Default, Clone)]
+pub struct SgMultiMap<K: Ord, V, const N: usize> {
+    // (Key, per-key sequence number) -> value
+    map: SgMap<(K, usize), V, N>,
+}
+
+impl<K: Ord + Clone, V, const N: usize> SgMultiMap<K, V, N> {
+    /// Makes a new, empty `SgMultiMap`.
+    pub fn new() -> Self {
+        SgMultiMap { map: SgMap::new() }
+    }
+
+    /// Number of key-value pairs, counting each duplicate key.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of key-value pairs, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all key-value pairs.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Insert a key-value pair, after any existing values for the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full.
+    /// Use [`try_insert`][SgMultiMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) {
+        if self.try_insert(key, val).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Insert a key-value pair, after any existing values for the key.
+    /// Returns `Err`, leaving the map unchanged, if the map is full.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<(), SgError> {
+        if self.map.len() >= self.map.max_len() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let opt_last = self.seqs(&key).next_back();
+        let seq = match opt_last {
+            Some(last) => match last.checked_add(1) {
+                Some(seq) => seq,
+                None => self.renumber(&key),
+            },
+            None => 0,
+        };
+
+        self.map.insert((key, seq), val);
+        Ok(())
+    }
+
+    /// Get the first value inserted for the key, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_all(key).next()
+    }
+
+    /// Gets an iterator over every value for the key, in insertion order.
+    pub fn get_all(&self, key: &K) -> impl DoubleEndedIterator<Item = &V> + '_ {
+        self.map
+            .range((key.clone(), 0)..=(key.clone(), usize::MAX))
+            .map(|(_, val)| val)
+    }
+
+    /// Gets a mutable iterator over every value for the key, in insertion order.
+    ///
+    /// Built on [`SgMap::range_mut`], which sorts the whole arena first, e.g. `O(n log n)` for any key.
+    pub fn get_all_mut(&mut self, key: &K) -> impl DoubleEndedIterator<Item = &mut V> + '_ {
+        self.map
+            .range_mut((key.clone(), 0)..=(key.clone(), usize::MAX))
+            .map(|(_, val)| val)
+    }
+
+    /// Returns `true` if the map contains at least one value for the key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of values for the key.
+    pub fn count(&self, key: &K) -> usize {
+        self.get_all(key).count()
+    }
+
+    /// Remove the first value inserted for the key, returning it if present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let seq = self.seqs(key).next()?;
+        self.map.remove(&(key.clone(), seq))
+    }
+
+    /// Remove every value for the key, returning the number removed.
+    pub fn remove_all(&mut self, key: &K) -> usize {
+        let seqs = self.seqs(key).collect::<ArrayVec<[usize; N]>>();
+        for seq in seqs.iter() {
+            self.map.remove(&(key.clone(), *seq));
+        }
+
+        seqs.len()
+    }
+
+    /// Gets an iterator over the key-value pairs, sorted by key, then insertion order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator + '_ {
+        self.map.iter().map(|((key, _), val)| (key, val))
+    }
+
+    /// Gets an iterator over the keys, sorted and including duplicates.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    // Sequence numbers of the key's values, ascending.
+    fn seqs<'a>(&'a self, key: &K) -> impl DoubleEndedIterator<Item = usize> + 'a {
+        self.map
+            .range((key.clone(), 0)..=(key.clone(), usize::MAX))
+            .map(|((_, seq), _)| *seq)
+    }
+
+    // Re-number the key's values from zero, preserving order, and return the next free sequence number.
+    // Only reached once a key's sequence numbers are exhausted, e.g. after `usize::MAX` inserts.
+    fn renumber(&mut self, key: &K) -> usize {
+        let seqs = self.seqs(key).collect::<ArrayVec<[usize; N]>>();
+        for (new_seq, old_seq) in seqs.iter().enumerate() {
+            if let Some(val) = self.map.remove(&(key.clone(), *old_seq)) {
+                self.map.insert((key.clone(), new_seq), val);
+            }
+        }
+
+        seqs.len()
+    }
+}
+
+impl<K, V, const N: usize> PartialEq for SgMultiMap<K, V, N>
+where
+    K: Ord + Clone,
+    V: PartialEq,
+{
+    // Sequence numbers are an implementation detail, only order matters
+    fn eq(&self, other: &Self) -> bool {
+        (self.len() == other.len()) && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V, const N: usize> Eq for SgMultiMap<K, V, N>
+where
+    K: Ord + Clone,
+    V: Eq,
+{
+}
+
+impl<K, V, const N: usize> Debug for SgMultiMap<K, V, N>
+where
+    K: Ord + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Clone, V, const N: usize> Extend<(K, V)> for SgMultiMap<K, V, N> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(key, val)| self.insert(key, val));
+    }
+}
+
+impl<K: Ord + Clone, V, const N: usize> FromIterator<(K, V)> for SgMultiMap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut multi_map = SgMultiMap::new();
+        multi_map.extend(iter);
+        multi_map
+    }
+}
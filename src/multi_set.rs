@@ -0,0 +1,182 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Counting multiset (e.g. a histogram), up to `N` distinct elements.
+///
+/// Duplicates share a single entry, so capacity bounds distinct elements, not the total count.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMultiSet;
+///
+/// let mut hist = SgMultiSet::<char, 8>::new();
+/// "hello".chars().for_each(|c| {
+///     hist.insert(c);
+/// });
+///
+/// assert_eq!(hist.count(&'l'), 2);
+/// assert_eq!(hist.len(), 5);
+/// assert_eq!(hist.distinct_len(), 4);
+/// assert!(hist.iter().eq([(&'e', 1), (&'h', 1), (&'l', 2), (&'o', 1)]));
+///
+/// assert_eq!(hist.remove(&'l'), 1);
+/// assert_eq!(hist.remove(&'h'), 0);
+/// assert!(!hist.contains(&'h'));
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SgMultiSet<T: Ord, const N: usize> {
+    // Element -> count, never zero
+    map: SgMap<T, usize, N>,
+    total: usize,
+}
+
+impl<T: Ord, const N: usize> SgMultiSet<T, N> {
+    /// Makes a new, empty `SgMultiSet`.
+    pub fn new() -> Self {
+        SgMultiSet {
+            map: SgMap::new(),
+            total: 0,
+        }
+    }
+
+    /// Total number of elements, counting each duplicate.
+    pub fn len(&self) -> usize {
+        self.total
+    }
+
+    /// Number of distinct elements.
+    pub fn distinct_len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of distinct elements, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all elements.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.total = 0;
+    }
+
+    /// Add one occurrence of an element, returning its new count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is new and the set is full.
+    /// Use [`try_insert`][SgMultiSet::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, elem: T) -> usize {
+        match self.try_insert(elem) {
+            Ok(cnt) => cnt,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Add one occurrence of an element, returning its new count.
+    /// Returns `Err`, leaving the set unchanged, if the element is new and the set is full.
+    pub fn try_insert(&mut self, elem: T) -> Result<usize, SgError> {
+        let cnt = match self.map.get_mut(&elem) {
+            Some(cnt) => {
+                *cnt += 1;
+                *cnt
+            }
+            None => {
+                self.map.try_insert(elem, 1)?;
+                1
+            }
+        };
+
+        self.total += 1;
+        Ok(cnt)
+    }
+
+    /// Remove one occurrence of an element, returning its remaining count.
+    /// The element's entry is removed once its count reaches zero.
+    pub fn remove<Q>(&mut self, elem: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let cnt = match self.map.get_mut(elem) {
+            Some(cnt) => {
+                *cnt -= 1;
+                self.total -= 1;
+                *cnt
+            }
+            None => return 0,
+        };
+
+        if cnt == 0 {
+            self.map.remove(elem);
+        }
+
+        cnt
+    }
+
+    /// Remove every occurrence of an element, returning the number removed.
+    pub fn remove_all<Q>(&mut self, elem: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let cnt = self.map.remove(elem).unwrap_or(0);
+        self.total -= cnt;
+        cnt
+    }
+
+    /// Number of occurrences of an element, zero if absent.
+    pub fn count<Q>(&self, elem: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(elem).copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if the set contains at least one occurrence of the element.
+    pub fn contains<Q>(&self, elem: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(elem)
+    }
+
+    /// Gets an iterator over the distinct elements and their counts, in ascending order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&T, usize)> + ExactSizeIterator + '_ {
+        self.map.iter().map(|(elem, cnt)| (elem, *cnt))
+    }
+}
+
+impl<T: Ord + Debug, const N: usize> Debug for SgMultiSet<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const N: usize> Extend<T> for SgMultiSet<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|elem| {
+            self.insert(elem);
+        });
+    }
+}
+
+impl<T: Ord, const N: usize> FromIterator<T> for SgMultiSet<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut multi_set = SgMultiSet::new();
+        multi_set.extend(iter);
+        multi_set
+    }
+}
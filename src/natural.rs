@@ -0,0 +1,131 @@
+use core::cmp::Ordering;
+
+/// Compare two strings in "natural" order: embedded runs of ASCII digits are compared by numeric value.
+///
+/// So `"fw-9" < "fw-10"`, whereas plain lexicographic order would have `"fw-10" < "fw-9"`.
+/// Strings that are naturally equal but differ textually (e.g. zero padding, `"v01"` vs `"v1"`)
+/// fall back to lexicographic order, so the result is a total order consistent with `str` equality.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use scapegoat::natural_cmp;
+///
+/// assert_eq!(natural_cmp("fw-9", "fw-10"), Ordering::Less);
+/// assert_eq!(natural_cmp("v1.10.0", "v1.9.3"), Ordering::Greater);
+/// assert_eq!(natural_cmp("v1", "v1"), Ordering::Equal);
+/// ```
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_rest, mut b_rest) = (a.as_bytes(), b.as_bytes());
+
+    loop {
+        match (a_rest.first(), b_rest.first()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_byte), Some(b_byte)) if a_byte.is_ascii_digit() && b_byte.is_ascii_digit() => {
+                let (a_num, a_tail) = split_digits(a_rest);
+                let (b_num, b_tail) = split_digits(b_rest);
+
+                // Longer significant digit run is larger, equal length compares digit-wise
+                let (a_sig, b_sig) = (trim_leading_zeros(a_num), trim_leading_zeros(b_num));
+                match a_sig.len().cmp(&b_sig.len()).then_with(|| a_sig.cmp(b_sig)) {
+                    Ordering::Equal => {
+                        a_rest = a_tail;
+                        b_rest = b_tail;
+                    }
+                    ord => return ord,
+                }
+            }
+            (Some(a_byte), Some(b_byte)) => match a_byte.cmp(b_byte) {
+                Ordering::Equal => {
+                    a_rest = &a_rest[1..];
+                    b_rest = &b_rest[1..];
+                }
+                ord => return ord,
+            },
+        }
+    }
+}
+
+// Split a byte slice into a leading run of ASCII digits and the remainder.
+fn split_digits(bytes: &[u8]) -> (&[u8], &[u8]) {
+    let digit_cnt = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    bytes.split_at(digit_cnt)
+}
+
+// Strip leading ASCII zeros from a digit run.
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let zero_cnt = digits.iter().take_while(|b| **b == b'0').count();
+    &digits[zero_cnt..]
+}
+
+/// Key wrapper that orders strings naturally, see [`natural_cmp`].
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{NaturalKey, SgMap};
+///
+/// let mut fw_images = SgMap::<NaturalKey<&str>, u32, 10>::new();
+/// fw_images.insert(NaturalKey("fw-10"), 0xb00);
+/// fw_images.insert(NaturalKey("fw-9"), 0xa00);
+/// fw_images.insert(NaturalKey("fw-100"), 0xc00);
+///
+/// assert!(fw_images
+///     .keys()
+///     .map(|k| k.0)
+///     .eq(["fw-9", "fw-10", "fw-100"]));
+///
+/// assert_eq!(fw_images.last_key_value(), Some((&NaturalKey("fw-100"), &0xc00)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct NaturalKey<S>(pub S);
+
+impl<S: AsRef<str> + Eq> Ord for NaturalKey<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_cmp(self.0.as_ref(), other.0.as_ref())
+    }
+}
+
+impl<S: AsRef<str> + Eq> PartialOrd for NaturalKey<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Test ----------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::natural_cmp;
+    use core::cmp::Ordering;
+
+    #[test]
+    fn test_natural_cmp() {
+        let sorted = [
+            "", "0", "01", "1", "2", "9", "010", "10", "99", "100", "a", "a1", "a2", "a10", "a10b",
+            "a10c", "ab", "fw-9", "fw-10", "fw-10.1", "fw-10.02", "fw-10.10",
+        ];
+
+        for (i, a) in sorted.iter().enumerate() {
+            for (j, b) in sorted.iter().enumerate() {
+                assert_eq!(natural_cmp(a, b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_natural_cmp_large_numbers() {
+        // No integer parsing, so no overflow
+        assert_eq!(
+            natural_cmp("v340282366920938463463374607431768211456", "v9"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            natural_cmp("v000000000000000000000001", "v2"),
+            Ordering::Less
+        );
+    }
+}
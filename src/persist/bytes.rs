@@ -0,0 +1,89 @@
+use core::mem::size_of;
+
+/// Types with a fixed-size, little-endian byte encoding, for use with the binary persistence format
+/// (e.g. [`SgMap::to_bytes`][crate::map::SgMap::to_bytes]).
+pub trait FixedBytes: Sized {
+    /// Encoded size, in bytes.
+    const SIZE: usize;
+
+    /// Encode into `buf`, which is exactly [`SIZE`][FixedBytes::SIZE] bytes long.
+    fn write_bytes(&self, buf: &mut [u8]);
+
+    /// Decode from `buf`, which is exactly [`SIZE`][FixedBytes::SIZE] bytes long.
+    /// Returns `None` if the bytes aren't a valid encoding.
+    fn read_bytes(buf: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_fixed_bytes_int {
+    ( $( $int:ty ),+ $(,)? ) => {
+        $(
+            impl FixedBytes for $int {
+                const SIZE: usize = size_of::<$int>();
+
+                fn write_bytes(&self, buf: &mut [u8]) {
+                    buf.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_bytes(buf: &[u8]) -> Option<Self> {
+                    let mut bytes = [0; size_of::<$int>()];
+                    bytes.copy_from_slice(buf);
+                    Some(<$int>::from_le_bytes(bytes))
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_bytes_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl FixedBytes for bool {
+    const SIZE: usize = 1;
+
+    fn write_bytes(&self, buf: &mut [u8]) {
+        buf[0] = u8::from(*self);
+    }
+
+    fn read_bytes(buf: &[u8]) -> Option<Self> {
+        match buf[0] {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl FixedBytes for char {
+    const SIZE: usize = 4;
+
+    fn write_bytes(&self, buf: &mut [u8]) {
+        u32::from(*self).write_bytes(buf);
+    }
+
+    fn read_bytes(buf: &[u8]) -> Option<Self> {
+        u32::read_bytes(buf).and_then(char::from_u32)
+    }
+}
+
+impl FixedBytes for () {
+    const SIZE: usize = 0;
+
+    fn write_bytes(&self, _buf: &mut [u8]) {}
+
+    fn read_bytes(_buf: &[u8]) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl<const M: usize> FixedBytes for [u8; M] {
+    const SIZE: usize = M;
+
+    fn write_bytes(&self, buf: &mut [u8]) {
+        buf.copy_from_slice(self);
+    }
+
+    fn read_bytes(buf: &[u8]) -> Option<Self> {
+        let mut bytes = [0; M];
+        bytes.copy_from_slice(buf);
+        Some(bytes)
+    }
+}
@@ -0,0 +1,204 @@
+use core::convert::TryFrom;
+
+use postcard::ser_flavors::Size;
+use serde::{Deserialize, Serialize};
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+
+use super::image::check_len;
+use super::PersistError;
+
+// Header --------------------------------------------------------------------------------------------------------------
+
+/// Magic number prefixing every compact image.
+pub const COMPACT_MAGIC: [u8; 4] = *b"SGPC";
+
+/// Current compact format version, images with any other version are rejected.
+///
+/// Layout, as a postcard-encoded tuple: magic (4 bytes), format version (1 byte), capacity (varint `u32`),
+/// then the map or set in its serde representation (varint length, then items in ascending order).
+/// Any postcard implementation can decode it, e.g. as `([u8; 4], u8, u32, BTreeMap<K, V>)`.
+pub const COMPACT_VERSION: u8 = 1;
+
+// Magic and version bytes, the only fixed-size part of the header.
+const FIXED_HEADER_LEN: usize = 5;
+
+// Envelope for a map or set of capacity `N`.
+fn envelope<T, const N: usize>(value: &T) -> ([u8; 4], u8, u32, &T) {
+    (COMPACT_MAGIC, COMPACT_VERSION, N as u32, value)
+}
+
+fn encoded_len<T: Serialize, const N: usize>(value: &T) -> Result<usize, PersistError> {
+    postcard::serialize_with_flavor(&envelope::<T, N>(value), Size::default())
+        .map_err(|_| PersistError::Malformed)
+}
+
+// Write an envelope for `value` into `buf`, returning bytes written.
+fn encode<T: Serialize, const N: usize>(value: &T, buf: &mut [u8]) -> Result<usize, PersistError> {
+    let available = buf.len();
+    match postcard::to_slice(&envelope::<T, N>(value), buf) {
+        Ok(written) => Ok(written.len()),
+        Err(postcard::Error::SerializeBufferFull) => Err(PersistError::BufferTooSmall {
+            needed: encoded_len::<T, N>(value)?,
+            available,
+        }),
+        Err(_) => Err(PersistError::Malformed),
+    }
+}
+
+// Validate the header, then decode the value into capacity `N`, returning it and its encoded item count.
+fn decode<'de, T: Deserialize<'de>, const N: usize>(
+    buf: &'de [u8],
+) -> Result<(T, usize), PersistError> {
+    check_len(buf, FIXED_HEADER_LEN)?;
+
+    if buf[0..4] != COMPACT_MAGIC {
+        return Err(PersistError::BadMagic);
+    }
+
+    if buf[4] != COMPACT_VERSION {
+        return Err(PersistError::UnsupportedVersion {
+            found: buf[4],
+            supported: COMPACT_VERSION,
+        });
+    }
+
+    let (image_capacity, body) = postcard::take_from_bytes::<u32>(&buf[FIXED_HEADER_LEN..])
+        .map_err(|_| PersistError::Malformed)?;
+
+    // Checked up front, so capacity errors aren't reported as malformed data
+    let (image_len, _) =
+        postcard::take_from_bytes::<u64>(body).map_err(|_| PersistError::Malformed)?;
+    if image_len > (N as u64) {
+        return Err(PersistError::CapacityExceeded {
+            image_capacity: image_capacity as usize,
+            image_len: usize::try_from(image_len).unwrap_or(usize::MAX),
+            capacity: N,
+        });
+    }
+
+    let value = postcard::from_bytes(body).map_err(|_| PersistError::Malformed)?;
+    Ok((value, image_len as usize))
+}
+
+// Map Encoding --------------------------------------------------------------------------------------------------------
+
+impl<K: Ord, V, const N: usize> SgMap<K, V, N> {
+    /// Size of this map's compact image, in bytes.
+    /// Returns `Err` if a key or value's serde implementation fails.
+    pub fn compact_len(&self) -> Result<usize, PersistError>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        encoded_len::<Self, N>(self)
+    }
+
+    /// Encode the map into `buf` as a compact, postcard-compatible image, returning the number of bytes written.
+    /// Requires feature `postcard`.
+    ///
+    /// Unlike [`to_bytes`][SgMap::to_bytes], keys and values can be any serde type and integers are varint-encoded,
+    /// so images of small values take less space (e.g. in EEPROM or flash).
+    /// The image is prefixed with a versioned header, see [`COMPACT_VERSION`][crate::persist::COMPACT_VERSION].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use scapegoat::persist::PersistError;
+    ///
+    /// // Setting name -> value
+    /// let settings = SgMap::<&str, u32, 4>::from([
+    ///     ("addr", 7),
+    ///     ("baud", 115_200),
+    ///     ("retries", 3),
+    ///     ("timeout_ms", 250),
+    /// ]);
+    /// let mut eeprom = [0xff; 64];
+    ///
+    /// let written = settings.to_compact_bytes(&mut eeprom).unwrap();
+    /// assert_eq!(written, settings.compact_len().unwrap());
+    /// assert_eq!(SgMap::<&str, u32, 4>::from_compact_bytes(&eeprom).as_ref(), Ok(&settings));
+    ///
+    /// // Failures are reported, never panics
+    /// assert!(matches!(
+    ///     settings.to_compact_bytes(&mut [0; 8]),
+    ///     Err(PersistError::BufferTooSmall { needed, available: 8 }) if needed == written
+    /// ));
+    /// assert!(matches!(
+    ///     SgMap::<&str, u32, 2>::from_compact_bytes(&eeprom),
+    ///     Err(PersistError::CapacityExceeded { image_len: 4, capacity: 2, .. })
+    /// ));
+    /// assert_eq!(SgMap::<&str, u32, 4>::from_compact_bytes(&[0xff; 64]), Err(PersistError::BadMagic));
+    /// ```
+    pub fn to_compact_bytes(&self, buf: &mut [u8]) -> Result<usize, PersistError>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        encode::<Self, N>(self, buf)
+    }
+
+    /// Decode a map from a compact image written by [`to_compact_bytes`][SgMap::to_compact_bytes].
+    /// Keys and values may borrow from `buf` (e.g. `&str`).
+    /// Returns `Err` if the image is incompatible (e.g. different format version, or too many items for this
+    /// capacity) or malformed. Trailing bytes are ignored.
+    pub fn from_compact_bytes<'de>(buf: &'de [u8]) -> Result<Self, PersistError>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        match decode::<Self, N>(buf)? {
+            // Images are written without duplicate keys
+            (map, image_len) if map.len() == image_len => Ok(map),
+            _ => Err(PersistError::Malformed),
+        }
+    }
+}
+
+// Set Encoding --------------------------------------------------------------------------------------------------------
+
+impl<T: Ord, const N: usize> SgSet<T, N> {
+    /// Size of this set's compact image, in bytes.
+    /// Returns `Err` if an element's serde implementation fails.
+    pub fn compact_len(&self) -> Result<usize, PersistError>
+    where
+        T: Serialize,
+    {
+        encoded_len::<Self, N>(self)
+    }
+
+    /// Encode the set into `buf` as a compact, postcard-compatible image, returning the number of bytes written.
+    /// Requires feature `postcard`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let channels = SgSet::<u16, 3>::from([1, 6, 11]);
+    /// let mut flash = [0; 16];
+    ///
+    /// assert_eq!(channels.to_compact_bytes(&mut flash), Ok(10));
+    /// assert_eq!(SgSet::<u16, 3>::from_compact_bytes(&flash), Ok(channels));
+    /// ```
+    pub fn to_compact_bytes(&self, buf: &mut [u8]) -> Result<usize, PersistError>
+    where
+        T: Serialize,
+    {
+        encode::<Self, N>(self, buf)
+    }
+
+    /// Decode a set from a compact image written by [`to_compact_bytes`][SgSet::to_compact_bytes].
+    /// Returns `Err` if the image is incompatible or malformed. Trailing bytes are ignored.
+    pub fn from_compact_bytes<'de>(buf: &'de [u8]) -> Result<Self, PersistError>
+    where
+        T: Deserialize<'de>,
+    {
+        match decode::<Self, N>(buf)? {
+            (set, image_len) if set.len() == image_len => Ok(set),
+            _ => Err(PersistError::Malformed),
+        }
+    }
+}
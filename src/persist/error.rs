@@ -0,0 +1,147 @@
+use core::fmt;
+
+use crate::tree::SgError;
+
+/// Errors for binary image and write-ahead log encoding and decoding.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum PersistError {
+    /// Buffer is shorter than the image requires.
+    BufferTooSmall {
+        /// Bytes required.
+        needed: usize,
+        /// Bytes available.
+        available: usize,
+    },
+
+    /// Image or log doesn't start with its magic number ([`FORMAT_MAGIC`][super::FORMAT_MAGIC] or
+    /// [`WAL_MAGIC`][super::WAL_MAGIC]), e.g. uninitialized or foreign data.
+    BadMagic,
+
+    /// Image was written by an unsupported format version.
+    UnsupportedVersion {
+        /// Version in the image.
+        found: u8,
+        /// Version supported by this library.
+        supported: u8,
+    },
+
+    /// Image was written with a different arena index width.
+    IndexWidthMismatch {
+        /// Index width in the image, in bytes.
+        found: u8,
+        /// Index width of this library, in bytes.
+        expected: u8,
+    },
+
+    /// Image was written for different key or value types.
+    ItemSizeMismatch {
+        /// Key and value sizes in the image, in bytes.
+        found: (u16, u16),
+        /// Key and value sizes of the loading type, in bytes.
+        expected: (u16, u16),
+    },
+
+    /// Paged image was written with a different page size.
+    PageSizeMismatch {
+        /// Page size in the image, in bytes.
+        found: usize,
+        /// Page size of the loading store, in bytes.
+        expected: usize,
+    },
+
+    /// Image holds more items than the loading type's capacity.
+    CapacityExceeded {
+        /// Capacity the image was written with.
+        image_capacity: usize,
+        /// Number of items in the image.
+        image_len: usize,
+        /// Capacity of the loading type.
+        capacity: usize,
+    },
+
+    /// Item at the given index is invalid or out of order (e.g. corrupt data).
+    InvalidItem {
+        /// Index of the invalid item.
+        idx: usize,
+    },
+
+    /// Compact image isn't a valid encoding of the item types (e.g. truncated or corrupt), or an item's serde
+    /// implementation failed.
+    Malformed,
+
+    /// Write-ahead log has no free record slots, take a snapshot and [checkpoint][super::Wal::checkpoint] it.
+    LogFull {
+        /// Record slots in the log.
+        slots: usize,
+    },
+
+    /// Map operation failed (e.g. capacity exceeded while logging or replaying an insert).
+    Storage(SgError),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::BufferTooSmall { needed, available } => write!(
+                f,
+                "buffer too small: need {} bytes, have {}",
+                needed, available
+            ),
+            PersistError::BadMagic => write!(f, "not an image: bad magic number"),
+            PersistError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported format version {} (supported: {})",
+                found, supported
+            ),
+            PersistError::IndexWidthMismatch { found, expected } => write!(
+                f,
+                "index width mismatch: image uses {} bytes, expected {}",
+                found, expected
+            ),
+            PersistError::ItemSizeMismatch { found, expected } => write!(
+                f,
+                "key/value size mismatch: image uses {:?} bytes, expected {:?}",
+                found, expected
+            ),
+            PersistError::PageSizeMismatch { found, expected } => write!(
+                f,
+                "page size mismatch: image uses {} bytes, expected {}",
+                found, expected
+            ),
+            PersistError::CapacityExceeded {
+                image_capacity,
+                image_len,
+                capacity,
+            } => write!(
+                f,
+                "image holds {} items (written with capacity {}), exceeds capacity {}",
+                image_len, image_capacity, capacity
+            ),
+            PersistError::InvalidItem { idx } => {
+                write!(f, "invalid or out-of-order item at index {}", idx)
+            }
+            PersistError::Malformed => write!(f, "malformed compact image"),
+            PersistError::LogFull { slots } => {
+                write!(f, "write-ahead log full: all {} slots pending", slots)
+            }
+            PersistError::Storage(err) => write!(f, "map operation failed: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistError::Storage(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SgError> for PersistError {
+    fn from(err: SgError) -> Self {
+        PersistError::Storage(err)
+    }
+}
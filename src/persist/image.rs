@@ -0,0 +1,247 @@
+use core::mem::size_of;
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+use crate::tree::{Idx, SgTree};
+
+use super::{FixedBytes, PersistError};
+
+// Header --------------------------------------------------------------------------------------------------------------
+
+/// Magic number prefixing every encoded image.
+pub const FORMAT_MAGIC: [u8; 4] = *b"SGTB";
+
+/// Current binary format version, images with any other version are rejected.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Size of the image header, in bytes.
+///
+/// Layout (multi-byte fields little-endian):
+/// magic (4), format version (1), index width (1), key size (2), value size (2), capacity (4), length (4).
+pub const HEADER_LEN: usize = 18;
+
+// Header fields for an image of `len` items of type `(K, V)` with capacity `N`.
+struct Header {
+    idx_width: u8,
+    key_size: u16,
+    val_size: u16,
+    capacity: u32,
+    len: u32,
+}
+
+impl Header {
+    fn new<K: FixedBytes, V: FixedBytes, const N: usize>(len: usize) -> Self {
+        Header {
+            idx_width: size_of::<Idx>() as u8,
+            key_size: K::SIZE as u16,
+            val_size: V::SIZE as u16,
+            capacity: N as u32,
+            len: len as u32,
+        }
+    }
+
+    fn write(&self, buf: &mut [u8]) {
+        buf[0..4].copy_from_slice(&FORMAT_MAGIC);
+        buf[4] = FORMAT_VERSION;
+        buf[5] = self.idx_width;
+        self.key_size.write_bytes(&mut buf[6..8]);
+        self.val_size.write_bytes(&mut buf[8..10]);
+        self.capacity.write_bytes(&mut buf[10..14]);
+        self.len.write_bytes(&mut buf[14..18]);
+    }
+
+    // Parse and validate a header for loading items of type `(K, V)` into capacity `N`.
+    fn read<K: FixedBytes, V: FixedBytes, const N: usize>(
+        buf: &[u8],
+    ) -> Result<Self, PersistError> {
+        check_len(buf, HEADER_LEN)?;
+
+        if buf[0..4] != FORMAT_MAGIC {
+            return Err(PersistError::BadMagic);
+        }
+
+        if buf[4] != FORMAT_VERSION {
+            return Err(PersistError::UnsupportedVersion {
+                found: buf[4],
+                supported: FORMAT_VERSION,
+            });
+        }
+
+        let expected = Header::new::<K, V, N>(0);
+        let found = Header {
+            idx_width: buf[5],
+            key_size: u16::read_bytes(&buf[6..8]).unwrap_or_default(),
+            val_size: u16::read_bytes(&buf[8..10]).unwrap_or_default(),
+            capacity: u32::read_bytes(&buf[10..14]).unwrap_or_default(),
+            len: u32::read_bytes(&buf[14..18]).unwrap_or_default(),
+        };
+
+        if found.idx_width != expected.idx_width {
+            return Err(PersistError::IndexWidthMismatch {
+                found: found.idx_width,
+                expected: expected.idx_width,
+            });
+        }
+
+        if (found.key_size, found.val_size) != (expected.key_size, expected.val_size) {
+            return Err(PersistError::ItemSizeMismatch {
+                found: (found.key_size, found.val_size),
+                expected: (expected.key_size, expected.val_size),
+            });
+        }
+
+        if (found.len as usize) > N {
+            return Err(PersistError::CapacityExceeded {
+                image_capacity: found.capacity as usize,
+                image_len: found.len as usize,
+                capacity: N,
+            });
+        }
+
+        Ok(found)
+    }
+}
+
+// Write header and items of `tree` into `buf`, returning bytes written.
+fn encode<K, V, const N: usize>(
+    tree: &SgTree<K, V, N>,
+    buf: &mut [u8],
+) -> Result<usize, PersistError>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+{
+    let item_size = K::SIZE + V::SIZE;
+    let needed = HEADER_LEN + tree.len() * item_size;
+    check_len(buf, needed)?;
+
+    Header::new::<K, V, N>(tree.len()).write(buf);
+    for (idx, (k, v)) in tree.iter().enumerate() {
+        let start = HEADER_LEN + idx * item_size;
+        let (key_bytes, val_bytes) = buf[start..(start + item_size)].split_at_mut(K::SIZE);
+        k.write_bytes(key_bytes);
+        v.write_bytes(val_bytes);
+    }
+
+    Ok(needed)
+}
+
+pub(super) fn check_len(buf: &[u8], needed: usize) -> Result<(), PersistError> {
+    match buf.len() >= needed {
+        true => Ok(()),
+        false => Err(PersistError::BufferTooSmall {
+            needed,
+            available: buf.len(),
+        }),
+    }
+}
+
+// Map Encoding --------------------------------------------------------------------------------------------------------
+
+impl<K, V, const N: usize> SgMap<K, V, N>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+{
+    /// Size of this map's binary image, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        HEADER_LEN + self.len() * (K::SIZE + V::SIZE)
+    }
+
+    /// Encode the map into `buf` as a binary image, returning the number of bytes written.
+    /// The image is prefixed with a versioned header, see [`HEADER_LEN`][crate::persist::HEADER_LEN].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::SgMap;
+    /// use scapegoat::persist::PersistError;
+    ///
+    /// let map = SgMap::<u16, u32, 8>::from_iter([(1, 10), (2, 20)]);
+    /// let mut flash = [0; 64];
+    ///
+    /// let written = map.to_bytes(&mut flash).unwrap();
+    /// assert_eq!(written, map.encoded_len());
+    /// assert_eq!(SgMap::<u16, u32, 8>::from_bytes(&flash), Ok(map));
+    ///
+    /// // Incompatible images are rejected
+    /// assert!(matches!(
+    ///     SgMap::<u16, u64, 8>::from_bytes(&flash),
+    ///     Err(PersistError::ItemSizeMismatch { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     SgMap::<u16, u32, 1>::from_bytes(&flash),
+    ///     Err(PersistError::CapacityExceeded { .. })
+    /// ));
+    /// assert_eq!(SgMap::<u16, u32, 8>::from_bytes(&[0xff; 64]), Err(PersistError::BadMagic));
+    /// ```
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize, PersistError> {
+        encode::<K, V, N>(&self.bst, buf)
+    }
+
+    /// Decode a map from a binary image written by [`to_bytes`][SgMap::to_bytes].
+    /// Returns `Err` if the image is incompatible (e.g. different format version, index width, item sizes,
+    /// or too many items for this capacity) or corrupt.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, PersistError> {
+        let header = Header::read::<K, V, N>(buf)?;
+        let item_size = K::SIZE + V::SIZE;
+        check_len(buf, HEADER_LEN + (header.len as usize) * item_size)?;
+
+        let mut map = SgMap::new();
+        for idx in 0..(header.len as usize) {
+            let start = HEADER_LEN + idx * item_size;
+            let (key_bytes, val_bytes) = buf[start..(start + item_size)].split_at(K::SIZE);
+
+            let invalid = PersistError::InvalidItem { idx };
+            let key = K::read_bytes(key_bytes).ok_or(invalid)?;
+            let val = V::read_bytes(val_bytes).ok_or(invalid)?;
+
+            // Images are written in strictly ascending key order
+            if map.last_key().is_some_and(|last| *last >= key) {
+                return Err(invalid);
+            }
+
+            map.insert(key, val);
+        }
+
+        Ok(map)
+    }
+}
+
+// Set Encoding --------------------------------------------------------------------------------------------------------
+
+impl<T, const N: usize> SgSet<T, N>
+where
+    T: Ord + FixedBytes,
+{
+    /// Size of this set's binary image, in bytes.
+    pub fn encoded_len(&self) -> usize {
+        HEADER_LEN + self.len() * T::SIZE
+    }
+
+    /// Encode the set into `buf` as a binary image, returning the number of bytes written.
+    /// The image is prefixed with a versioned header, see [`HEADER_LEN`][crate::persist::HEADER_LEN].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::SgSet;
+    ///
+    /// let set = SgSet::<i32, 8>::from_iter([-1, 0, 1]);
+    /// let mut flash = [0; 64];
+    ///
+    /// assert_eq!(set.to_bytes(&mut flash), Ok(set.encoded_len()));
+    /// assert_eq!(SgSet::<i32, 8>::from_bytes(&flash), Ok(set));
+    /// ```
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<usize, PersistError> {
+        encode::<T, (), N>(&self.bst, buf)
+    }
+
+    /// Decode a set from a binary image written by [`to_bytes`][SgSet::to_bytes].
+    /// Returns `Err` if the image is incompatible or corrupt.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, PersistError> {
+        SgMap::<T, (), N>::from_bytes(buf).map(SgSet::from)
+    }
+}
@@ -0,0 +1,19 @@
+mod bytes;
+pub use bytes::FixedBytes;
+
+mod error;
+pub use error::PersistError;
+
+mod image;
+pub use image::{FORMAT_MAGIC, FORMAT_VERSION, HEADER_LEN};
+
+mod wal;
+pub use wal::{Wal, WAL_HEADER_LEN, WAL_MAGIC, WAL_VERSION};
+
+mod paged;
+pub use paged::{PageStore, PAGED_HEADER_LEN, PAGED_MAGIC, PAGED_VERSION};
+
+#[cfg(feature = "postcard")]
+mod compact;
+#[cfg(feature = "postcard")]
+pub use compact::{COMPACT_MAGIC, COMPACT_VERSION};
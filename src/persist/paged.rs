@@ -0,0 +1,351 @@
+use core::mem::size_of;
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+use crate::tree::{Idx, SgTree};
+
+use super::{FixedBytes, PersistError};
+
+// Paged Format --------------------------------------------------------------------------------------------------------
+
+/// Magic number prefixing every paged image.
+pub const PAGED_MAGIC: [u8; 4] = *b"SGPG";
+
+/// Current paged image format version, images with any other version are rejected.
+pub const PAGED_VERSION: u8 = 1;
+
+/// Size of the paged image header, stored alone in page `0`, in bytes.
+///
+/// Layout (multi-byte fields little-endian):
+/// magic (4), format version (1), index width (1), key size (2), value size (2), capacity (4), page size (4).
+/// Arena slots follow in pages `1..`, each slot an occupied flag (1), key, and value, never straddling pages.
+pub const PAGED_HEADER_LEN: usize = 18;
+
+/// Page-granular storage backing a paged image, e.g. flash or EEPROM sectors of `PAGE` bytes.
+pub trait PageStore<const PAGE: usize> {
+    /// Storage error, must be able to represent image format errors.
+    type Error: From<PersistError>;
+
+    /// Number of pages available.
+    fn page_count(&self) -> usize;
+
+    /// Read page `idx` into `page`.
+    fn read_page(&mut self, idx: usize, page: &mut [u8; PAGE]) -> Result<(), Self::Error>;
+
+    /// Write `page` to page `idx`.
+    fn write_page(&mut self, idx: usize, page: &[u8; PAGE]) -> Result<(), Self::Error>;
+}
+
+/// In-memory page store, e.g. for testing or staging a RAM mirror.
+impl<const PAGE: usize, const PAGES: usize> PageStore<PAGE> for [[u8; PAGE]; PAGES] {
+    type Error = PersistError;
+
+    fn page_count(&self) -> usize {
+        PAGES
+    }
+
+    fn read_page(&mut self, idx: usize, page: &mut [u8; PAGE]) -> Result<(), Self::Error> {
+        page.copy_from_slice(&self[idx]);
+        Ok(())
+    }
+
+    fn write_page(&mut self, idx: usize, page: &[u8; PAGE]) -> Result<(), Self::Error> {
+        self[idx].copy_from_slice(page);
+        Ok(())
+    }
+}
+
+// Page geometry for items of type `(K, V)` and capacity `cap`.
+struct Geometry {
+    slot_len: usize,
+    slots_per_page: usize,
+    page_cnt: usize,
+}
+
+impl Geometry {
+    fn new<K: FixedBytes, V: FixedBytes, const PAGE: usize>(
+        cap: usize,
+    ) -> Result<Self, PersistError> {
+        let slot_len = 1 + K::SIZE + V::SIZE;
+        let slots_per_page = PAGE / slot_len;
+        if (PAGE < PAGED_HEADER_LEN) || (slots_per_page == 0) {
+            return Err(PersistError::BufferTooSmall {
+                needed: slot_len.max(PAGED_HEADER_LEN),
+                available: PAGE,
+            });
+        }
+
+        Ok(Geometry {
+            slot_len,
+            slots_per_page,
+            page_cnt: 1 + cap.div_ceil(slots_per_page),
+        })
+    }
+
+    fn check_store<S: PageStore<PAGE>, const PAGE: usize>(
+        &self,
+        store: &S,
+    ) -> Result<(), PersistError> {
+        match store.page_count() >= self.page_cnt {
+            true => Ok(()),
+            false => Err(PersistError::BufferTooSmall {
+                needed: self.page_cnt * PAGE,
+                available: store.page_count() * PAGE,
+            }),
+        }
+    }
+}
+
+// Save `tree` to `store`, writing only pages whose stored contents differ. Returns number of pages written.
+fn save<K, V, S, const N: usize, const PAGE: usize>(
+    tree: &SgTree<K, V, N>,
+    store: &mut S,
+) -> Result<usize, S::Error>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+    S: PageStore<PAGE>,
+{
+    let geo = Geometry::new::<K, V, PAGE>(N)?;
+    geo.check_store(store)?;
+
+    let mut page = [0; PAGE];
+    let mut stored = [0; PAGE];
+    let mut written = 0;
+
+    // Header page
+    page[0..4].copy_from_slice(&PAGED_MAGIC);
+    page[4] = PAGED_VERSION;
+    page[5] = size_of::<Idx>() as u8;
+    (K::SIZE as u16).write_bytes(&mut page[6..8]);
+    (V::SIZE as u16).write_bytes(&mut page[8..10]);
+    (N as u32).write_bytes(&mut page[10..14]);
+    (PAGE as u32).write_bytes(&mut page[14..18]);
+    written += sync_page(store, 0, &page, &mut stored)?;
+
+    // Slot pages
+    let mut slots = tree.arena_slots();
+    for page_idx in 1..geo.page_cnt {
+        page.iter_mut().for_each(|b| *b = 0);
+        for slot in page.chunks_exact_mut(geo.slot_len).take(geo.slots_per_page) {
+            if let Some(Some((k, v))) = slots.next() {
+                slot[0] = 1;
+                k.write_bytes(&mut slot[1..(1 + K::SIZE)]);
+                v.write_bytes(&mut slot[(1 + K::SIZE)..]);
+            }
+        }
+
+        written += sync_page(store, page_idx, &page, &mut stored)?;
+    }
+
+    Ok(written)
+}
+
+// Write `page` to page `idx` of `store` if its contents differ, returning the number of pages written.
+fn sync_page<S: PageStore<PAGE>, const PAGE: usize>(
+    store: &mut S,
+    idx: usize,
+    page: &[u8; PAGE],
+    stored: &mut [u8; PAGE],
+) -> Result<usize, S::Error> {
+    store.read_page(idx, stored)?;
+    if stored == page {
+        return Ok(0);
+    }
+
+    store.write_page(idx, page)?;
+    Ok(1)
+}
+
+// Load a map from a paged image in `store`.
+fn load<K, V, S, const N: usize, const PAGE: usize>(
+    store: &mut S,
+) -> Result<SgMap<K, V, N>, S::Error>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+    S: PageStore<PAGE>,
+{
+    Geometry::new::<K, V, PAGE>(N)?;
+    if store.page_count() == 0 {
+        return Err(PersistError::BufferTooSmall {
+            needed: PAGE,
+            available: 0,
+        }
+        .into());
+    }
+
+    let mut page = [0; PAGE];
+    store.read_page(0, &mut page)?;
+
+    if page[0..4] != PAGED_MAGIC {
+        return Err(PersistError::BadMagic.into());
+    }
+
+    if page[4] != PAGED_VERSION {
+        return Err(PersistError::UnsupportedVersion {
+            found: page[4],
+            supported: PAGED_VERSION,
+        }
+        .into());
+    }
+
+    let idx_width = size_of::<Idx>() as u8;
+    if page[5] != idx_width {
+        return Err(PersistError::IndexWidthMismatch {
+            found: page[5],
+            expected: idx_width,
+        }
+        .into());
+    }
+
+    let found = (
+        u16::read_bytes(&page[6..8]).unwrap_or_default(),
+        u16::read_bytes(&page[8..10]).unwrap_or_default(),
+    );
+    let expected = (K::SIZE as u16, V::SIZE as u16);
+    if found != expected {
+        return Err(PersistError::ItemSizeMismatch { found, expected }.into());
+    }
+
+    let page_size = u32::read_bytes(&page[14..18]).unwrap_or_default() as usize;
+    if page_size != PAGE {
+        return Err(PersistError::PageSizeMismatch {
+            found: page_size,
+            expected: PAGE,
+        }
+        .into());
+    }
+
+    // Image capacity may differ, so long as the items fit
+    let image_capacity = u32::read_bytes(&page[10..14]).unwrap_or_default() as usize;
+    let geo = Geometry::new::<K, V, PAGE>(image_capacity)?;
+    geo.check_store(store)?;
+
+    let mut map = SgMap::new();
+    let mut slot_idx = 0;
+    for page_idx in 1..geo.page_cnt {
+        store.read_page(page_idx, &mut page)?;
+        for slot in page.chunks_exact(geo.slot_len).take(geo.slots_per_page) {
+            let invalid = PersistError::InvalidItem { idx: slot_idx };
+            match slot[0] {
+                0 => {}
+                1 => {
+                    let key = K::read_bytes(&slot[1..(1 + K::SIZE)]).ok_or(invalid)?;
+                    let val = V::read_bytes(&slot[(1 + K::SIZE)..]).ok_or(invalid)?;
+                    if map.contains_key(&key) {
+                        return Err(invalid.into());
+                    }
+
+                    if map.try_insert(key, val).is_err() {
+                        return Err(PersistError::CapacityExceeded {
+                            image_capacity,
+                            image_len: map.len() + 1,
+                            capacity: map.max_len(),
+                        }
+                        .into());
+                    }
+                }
+                _ => return Err(invalid.into()),
+            }
+
+            slot_idx += 1;
+        }
+    }
+
+    Ok(map)
+}
+
+// Map Paging ----------------------------------------------------------------------------------------------------------
+
+impl<K, V, const N: usize> SgMap<K, V, N>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+{
+    /// Number of `PAGE`-byte pages a paged image of this map type occupies, header page included.
+    /// Returns `Err` if a page can't hold the header or a single slot.
+    pub fn paged_len<const PAGE: usize>() -> Result<usize, PersistError> {
+        Geometry::new::<K, V, PAGE>(N).map(|geo| geo.page_cnt)
+    }
+
+    /// Save the map to `store` as a paged image, returning the number of pages written.
+    ///
+    /// The image mirrors the internal arena, so an entry keeps its position across saves.
+    /// Each page is compared against the store and only changed (dirty) pages are rewritten,
+    /// e.g. inserting or updating one entry typically rewrites a single page, reducing flash wear.
+    /// Loading compacts the arena, so the first save after [`load_pages`][SgMap::load_pages]
+    /// may rewrite pages that held removed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// // 16 pages of 32 bytes, e.g. EEPROM sectors
+    /// let mut eeprom = [[0xff; 32]; 16];
+    ///
+    /// let mut map = SgMap::<u16, u32, 32>::new();
+    /// for i in 0..32 {
+    ///     map.insert(i, 0);
+    /// }
+    /// assert_eq!(SgMap::<u16, u32, 32>::paged_len::<32>(), Ok(9));
+    /// assert_eq!(map.save_pages(&mut eeprom), Ok(9));
+    ///
+    /// // Unchanged map, nothing to write
+    /// assert_eq!(map.save_pages(&mut eeprom), Ok(0));
+    ///
+    /// // Single update, single page write
+    /// map.insert(17, 0xbeef);
+    /// assert_eq!(map.save_pages(&mut eeprom), Ok(1));
+    ///
+    /// assert_eq!(SgMap::<u16, u32, 32>::load_pages(&mut eeprom), Ok(map));
+    /// ```
+    pub fn save_pages<S, const PAGE: usize>(&self, store: &mut S) -> Result<usize, S::Error>
+    where
+        S: PageStore<PAGE>,
+    {
+        save::<K, V, S, N, PAGE>(&self.bst, store)
+    }
+
+    /// Load a map from a paged image written by [`save_pages`][SgMap::save_pages].
+    /// Returns `Err` if the image is incompatible (e.g. different format version, index width, item or page sizes,
+    /// or too many items for this capacity) or corrupt.
+    pub fn load_pages<S, const PAGE: usize>(store: &mut S) -> Result<Self, S::Error>
+    where
+        S: PageStore<PAGE>,
+    {
+        load::<K, V, S, N, PAGE>(store)
+    }
+}
+
+// Set Paging ----------------------------------------------------------------------------------------------------------
+
+impl<T, const N: usize> SgSet<T, N>
+where
+    T: Ord + FixedBytes,
+{
+    /// Number of `PAGE`-byte pages a paged image of this set type occupies, header page included.
+    /// Returns `Err` if a page can't hold the header or a single slot.
+    pub fn paged_len<const PAGE: usize>() -> Result<usize, PersistError> {
+        Geometry::new::<T, (), PAGE>(N).map(|geo| geo.page_cnt)
+    }
+
+    /// Save the set to `store` as a paged image, returning the number of pages written.
+    /// Only changed pages are rewritten, see [`SgMap::save_pages`][crate::map::SgMap::save_pages].
+    pub fn save_pages<S, const PAGE: usize>(&self, store: &mut S) -> Result<usize, S::Error>
+    where
+        S: PageStore<PAGE>,
+    {
+        save::<T, (), S, N, PAGE>(&self.bst, store)
+    }
+
+    /// Load a set from a paged image written by [`save_pages`][SgSet::save_pages].
+    /// Returns `Err` if the image is incompatible or corrupt.
+    pub fn load_pages<S, const PAGE: usize>(store: &mut S) -> Result<Self, S::Error>
+    where
+        S: PageStore<PAGE>,
+    {
+        load::<T, (), S, N, PAGE>(store).map(SgSet::from)
+    }
+}
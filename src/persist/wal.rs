@@ -0,0 +1,368 @@
+use core::marker::PhantomData;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+use super::image::check_len;
+use super::{FixedBytes, PersistError};
+
+// Log Format ----------------------------------------------------------------------------------------------------------
+
+/// Magic number prefixing every write-ahead log.
+pub const WAL_MAGIC: [u8; 4] = *b"SGWL";
+
+/// Current write-ahead log format version, logs with any other version are rejected.
+pub const WAL_VERSION: u8 = 1;
+
+/// Size of the write-ahead log header, in bytes.
+///
+/// Layout (multi-byte fields little-endian):
+/// magic (4), format version (1), key size (2), value size (2), base sequence number (4).
+/// Fixed-size record slots follow the header, see [`Wal::record_len`].
+pub const WAL_HEADER_LEN: usize = 13;
+
+const OP_INSERT: u8 = 1;
+const OP_REMOVE: u8 = 2;
+
+// Record layout: sequence number (4), opcode (1), key, value (zeroed for removals), checksum (2).
+const RECORD_OVERHEAD: usize = 4 + 1 + 2;
+
+fn record_len<K: FixedBytes, V: FixedBytes>() -> usize {
+    RECORD_OVERHEAD + K::SIZE + V::SIZE
+}
+
+// Decoded log record.
+enum Op<K, V> {
+    Insert(K, V),
+    Remove(K),
+}
+
+impl<K, V> Op<K, V> {
+    fn key(&self) -> &K {
+        match self {
+            Op::Insert(key, _) | Op::Remove(key) => key,
+        }
+    }
+}
+
+// Fletcher-16 checksum, detects records torn by power loss mid-write.
+fn checksum(bytes: &[u8]) -> u16 {
+    let (mut sum1, mut sum2) = (0_u16, 0_u16);
+    for b in bytes {
+        sum1 = (sum1 + u16::from(*b)) % 255;
+        sum2 = (sum2 + sum1) % 255;
+    }
+
+    (sum2 << 8) | sum1
+}
+
+// Validate the header of log `buf` for items of type `(K, V)`, returning the base sequence number and slot count.
+fn read_header<K: FixedBytes, V: FixedBytes>(buf: &[u8]) -> Result<(u32, usize), PersistError> {
+    check_len(buf, WAL_HEADER_LEN + record_len::<K, V>())?;
+
+    if buf[0..4] != WAL_MAGIC {
+        return Err(PersistError::BadMagic);
+    }
+
+    if buf[4] != WAL_VERSION {
+        return Err(PersistError::UnsupportedVersion {
+            found: buf[4],
+            supported: WAL_VERSION,
+        });
+    }
+
+    let found = (
+        u16::read_bytes(&buf[5..7]).unwrap_or_default(),
+        u16::read_bytes(&buf[7..9]).unwrap_or_default(),
+    );
+    let expected = (K::SIZE as u16, V::SIZE as u16);
+    if found != expected {
+        return Err(PersistError::ItemSizeMismatch { found, expected });
+    }
+
+    let base_seq = u32::read_bytes(&buf[9..13]).unwrap_or_default();
+    let slots = (buf.len() - WAL_HEADER_LEN) / record_len::<K, V>();
+
+    Ok((base_seq, slots))
+}
+
+// Visit every committed record of log `buf` in order, returning the next free sequence number.
+// The scan stops at the first slot that doesn't hold the expected, intact record (e.g. end of log or torn write).
+fn scan<K, V, F>(buf: &[u8], mut visit: F) -> Result<u32, PersistError>
+where
+    K: FixedBytes,
+    V: FixedBytes,
+    F: FnMut(Op<K, V>) -> Result<(), PersistError>,
+{
+    let (base_seq, slots) = read_header::<K, V>(buf)?;
+    let record_len = record_len::<K, V>();
+
+    let mut seq = base_seq;
+    for idx in 0..slots {
+        let start = WAL_HEADER_LEN + ((seq as usize) % slots) * record_len;
+        let record = &buf[start..(start + record_len)];
+        let (body, sum_bytes) = record.split_at(record_len - 2);
+
+        if u16::read_bytes(sum_bytes) != Some(checksum(body))
+            || u32::read_bytes(&body[0..4]) != Some(seq)
+        {
+            break;
+        }
+
+        let invalid = PersistError::InvalidItem { idx };
+        let key_bytes = &body[5..(5 + K::SIZE)];
+        let val_bytes = &body[(5 + K::SIZE)..];
+        let op = match body[4] {
+            OP_INSERT => Op::Insert(
+                K::read_bytes(key_bytes).ok_or(invalid)?,
+                V::read_bytes(val_bytes).ok_or(invalid)?,
+            ),
+            OP_REMOVE => Op::Remove(K::read_bytes(key_bytes).ok_or(invalid)?),
+            _ => break,
+        };
+
+        visit(op)?;
+        seq = seq.wrapping_add(1);
+    }
+
+    Ok(seq)
+}
+
+// Write-ahead Log -----------------------------------------------------------------------------------------------------
+
+/// Write-ahead log of map mutations, stored in a caller-provided ring buffer (e.g. a flash journal region).
+///
+/// Each mutation is encoded into the log before it's applied, so a map can be rebuilt after power loss
+/// by loading the last snapshot (see [`SgMap::from_bytes`][crate::map::SgMap::from_bytes]) and replaying
+/// the log onto it (see [`SgMap::recover`][crate::map::SgMap::recover]).
+/// Records are checksummed, so a record torn by power loss mid-write is discarded on recovery.
+///
+/// Once all slots are pending, logging fails with [`PersistError::LogFull`].
+/// Write a new snapshot and [`checkpoint`][Wal::checkpoint] to release them.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMap;
+/// use scapegoat::persist::Wal;
+///
+/// let mut snapshot = [0; 64];
+/// let mut journal = [0; 128];
+///
+/// let mut map = SgMap::<u16, u32, 8>::new();
+/// map.to_bytes(&mut snapshot).unwrap();
+///
+/// let mut wal = Wal::new(&mut journal).unwrap();
+/// wal.insert(&mut map, 1, 10).unwrap();
+/// wal.insert(&mut map, 2, 20).unwrap();
+/// wal.remove(&mut map, &1).unwrap();
+/// // Power loss!
+///
+/// let mut recovered = SgMap::<u16, u32, 8>::from_bytes(&snapshot).unwrap();
+/// assert_eq!(recovered.recover(&journal), Ok(3));
+/// assert_eq!(recovered, map);
+/// ```
+pub struct Wal<'a, K, V> {
+    buf: &'a mut [u8],
+    base_seq: u32,
+    next_seq: u32,
+    slots: usize,
+    item: PhantomData<(K, V)>,
+}
+
+impl<'a, K, V> Wal<'a, K, V>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+{
+    /// Format `buf` as a new, empty log.
+    /// Returns `Err` if `buf` can't hold the header and at least one record.
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, PersistError> {
+        check_len(buf, WAL_HEADER_LEN + Self::record_len())?;
+
+        // Start past any sequence number a zeroed slot could match
+        let base_seq = 1;
+        buf[0..4].copy_from_slice(&WAL_MAGIC);
+        buf[4] = WAL_VERSION;
+        (K::SIZE as u16).write_bytes(&mut buf[5..7]);
+        (V::SIZE as u16).write_bytes(&mut buf[7..9]);
+        base_seq.write_bytes(&mut buf[9..13]);
+
+        // Clear records left by any previous log in this buffer
+        buf[WAL_HEADER_LEN..].iter_mut().for_each(|b| *b = 0);
+
+        let slots = (buf.len() - WAL_HEADER_LEN) / Self::record_len();
+        Ok(Wal {
+            buf,
+            base_seq,
+            next_seq: base_seq,
+            slots,
+            item: PhantomData,
+        })
+    }
+
+    /// Resume an existing log, e.g. after recovery, appending after its last intact record.
+    /// Returns `Err` if `buf` doesn't hold a compatible log.
+    pub fn open(buf: &'a mut [u8]) -> Result<Self, PersistError> {
+        let (base_seq, slots) = read_header::<K, V>(buf)?;
+        let next_seq = scan::<K, V, _>(buf, |_| Ok(()))?;
+        Ok(Wal {
+            buf,
+            base_seq,
+            next_seq,
+            slots,
+            item: PhantomData,
+        })
+    }
+
+    /// Size of a single log record, in bytes.
+    pub fn record_len() -> usize {
+        record_len::<K, V>()
+    }
+
+    /// Total number of record slots.
+    pub const fn slots(&self) -> usize {
+        self.slots
+    }
+
+    /// Number of records logged since the last checkpoint.
+    pub fn pending(&self) -> usize {
+        self.next_seq.wrapping_sub(self.base_seq) as usize
+    }
+
+    /// Log an insertion of `key` and `val`, without applying it.
+    pub fn log_insert(&mut self, key: &K, val: &V) -> Result<(), PersistError> {
+        self.append(OP_INSERT, key, Some(val))
+    }
+
+    /// Log a removal of `key`, without applying it.
+    pub fn log_remove(&mut self, key: &K) -> Result<(), PersistError> {
+        self.append(OP_REMOVE, key, None)
+    }
+
+    /// Log then apply an insertion into `map`, returning the old value if the key was present.
+    /// Returns `Err`, logging and inserting nothing, if the log or map is full.
+    pub fn insert<const N: usize>(
+        &mut self,
+        map: &mut SgMap<K, V, N>,
+        key: K,
+        val: V,
+    ) -> Result<Option<V>, PersistError> {
+        if map.is_full() && !map.contains_key(&key) {
+            return Err(PersistError::Storage(SgError::StackCapacityExceeded));
+        }
+
+        self.log_insert(&key, &val)?;
+        Ok(map.try_insert(key, val)?)
+    }
+
+    /// Log then apply a removal from `map`, returning the value at the key if the key was present.
+    /// Nothing is logged if the key isn't present.
+    pub fn remove<const N: usize>(
+        &mut self,
+        map: &mut SgMap<K, V, N>,
+        key: &K,
+    ) -> Result<Option<V>, PersistError> {
+        if !map.contains_key(key) {
+            return Ok(None);
+        }
+
+        self.log_remove(key)?;
+        Ok(map.remove(key))
+    }
+
+    /// Release all pending records, e.g. once a snapshot including their effects has been written.
+    ///
+    /// Losing power between writing the snapshot and checkpointing is safe:
+    /// recovery only applies the last record per key, so records already reflected in the snapshot are harmless.
+    /// See [`SgMap::recover`][crate::map::SgMap::recover].
+    pub fn checkpoint(&mut self) {
+        self.base_seq = self.next_seq;
+        self.base_seq.write_bytes(&mut self.buf[9..13]);
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    fn append(&mut self, op: u8, key: &K, opt_val: Option<&V>) -> Result<(), PersistError> {
+        if self.pending() >= self.slots {
+            return Err(PersistError::LogFull { slots: self.slots });
+        }
+
+        let record_len = Self::record_len();
+        let start = WAL_HEADER_LEN + ((self.next_seq as usize) % self.slots) * record_len;
+        let (body, sum_bytes) = self.buf[start..(start + record_len)].split_at_mut(record_len - 2);
+
+        self.next_seq.write_bytes(&mut body[0..4]);
+        body[4] = op;
+        key.write_bytes(&mut body[5..(5 + K::SIZE)]);
+        match opt_val {
+            Some(val) => val.write_bytes(&mut body[(5 + K::SIZE)..]),
+            None => body[(5 + K::SIZE)..].iter_mut().for_each(|b| *b = 0),
+        }
+        checksum(body).write_bytes(sum_bytes);
+
+        self.next_seq = self.next_seq.wrapping_add(1);
+        Ok(())
+    }
+}
+
+// Recovery ------------------------------------------------------------------------------------------------------------
+
+impl<K, V, const N: usize> SgMap<K, V, N>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+{
+    /// Replay every intact record of a write-ahead log (see [`Wal`][crate::persist::Wal]) onto this map,
+    /// e.g. one just loaded from the last snapshot. Returns the number of records replayed.
+    ///
+    /// Only the last record per key determines that key's final state, so earlier records are skipped.
+    /// Final removals are applied before final insertions, so replay can't transiently exceed capacity,
+    /// even onto a snapshot written after some of the records (e.g. power loss before [`Wal::checkpoint`]).
+    /// This costs `O(r^2)` record decodes for `r` pending records.
+    ///
+    /// [`Wal::checkpoint`]: crate::persist::Wal::checkpoint
+    pub fn recover(&mut self, wal_bytes: &[u8]) -> Result<usize, PersistError> {
+        // Removals first, then insertions
+        let mut replayed = 0;
+        for removals in [true, false] {
+            let mut idx = 0;
+            scan::<K, V, _>(wal_bytes, |op| {
+                idx += 1;
+                replayed = replayed.max(idx);
+                if is_superseded::<K, V>(wal_bytes, idx, op.key())? {
+                    return Ok(());
+                }
+
+                match op {
+                    Op::Insert(key, val) if !removals => {
+                        self.try_insert(key, val)?;
+                    }
+                    Op::Remove(key) if removals => {
+                        self.remove(&key);
+                    }
+                    _ => {}
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(replayed)
+    }
+}
+
+// Returns `true` if any intact record of log `buf` past the first `count` has key `key`.
+fn is_superseded<K, V>(buf: &[u8], count: usize, key: &K) -> Result<bool, PersistError>
+where
+    K: Ord + FixedBytes,
+    V: FixedBytes,
+{
+    let (mut idx, mut superseded) = (0, false);
+    scan::<K, V, _>(buf, |op| {
+        idx += 1;
+        superseded |= (idx > count) && (op.key() == key);
+        Ok(())
+    })?;
+
+    Ok(superseded)
+}
@@ -0,0 +1,113 @@
+use core::fmt::{self, Debug};
+use core::ops::{Deref, DerefMut};
+
+use crate::map::SgMap;
+
+/// Pool of `P` preallocated maps, each of capacity `N`, for short-lived scratch use.
+///
+/// Constructing a map initializes its full arena, a cost proportional to `N`.
+/// A pool pays it once: [`acquire`][SgArenaPool::acquire] hands out the pooled maps,
+/// and each is returned (emptied at a cost proportional to its length, not `N`, and with default settings)
+/// when its handle is dropped.
+///
+/// There's no interior mutability: the pool is mutably borrowed for as long as any handle is live.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgArenaPool;
+///
+/// let mut pool = SgArenaPool::<u32, u32, 1024, 2>::new();
+///
+/// for request in 0..3 {
+///     let [mut seen, mut counts] = pool.acquire();
+///     assert!(seen.is_empty() && counts.is_empty());
+///
+///     seen.insert(request, request);
+///     *counts.entry(request % 2).or_insert(0) += 1;
+///     assert_eq!(seen.len(), 1);
+/// } // Maps emptied and returned to pool
+/// ```
+pub struct SgArenaPool<K: Ord, V, const N: usize, const P: usize> {
+    maps: [SgMap<K, V, N>; P],
+}
+
+impl<K: Ord, V, const N: usize, const P: usize> SgArenaPool<K, V, N, P> {
+    /// Makes a new pool of `P` empty maps.
+    pub fn new() -> Self {
+        SgArenaPool {
+            maps: core::array::from_fn(|_| SgMap::new()),
+        }
+    }
+
+    /// Number of pooled maps, e.g. `P`.
+    pub fn pool_size(&self) -> usize {
+        P
+    }
+
+    /// Capacity of each pooled map, e.g. `N`.
+    pub fn map_capacity(&self) -> usize {
+        N
+    }
+
+    /// Hand out every pooled map, each empty.
+    /// Maps are cleared and returned to the pool when their handles are dropped.
+    pub fn acquire(&mut self) -> [PooledMap<'_, K, V, N>; P] {
+        self.maps.each_mut().map(|map| PooledMap { map })
+    }
+}
+
+impl<K: Ord, V, const N: usize, const P: usize> Default for SgArenaPool<K, V, N, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V, const N: usize, const P: usize> Debug for SgArenaPool<K, V, N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SgArenaPool")
+            .field("pool_size", &P)
+            .field("map_capacity", &N)
+            .finish()
+    }
+}
+
+/// Map borrowed from a [`SgArenaPool`], dereferences to [`SgMap`].
+/// Cleared, with every setting restored to its default, and returned to the pool on drop.
+pub struct PooledMap<'a, K: Ord, V, const N: usize> {
+    map: &'a mut SgMap<K, V, N>,
+}
+
+impl<K: Ord, V, const N: usize> Deref for PooledMap<'_, K, V, N> {
+    type Target = SgMap<K, V, N>;
+
+    fn deref(&self) -> &Self::Target {
+        self.map
+    }
+}
+
+impl<K: Ord, V, const N: usize> DerefMut for PooledMap<'_, K, V, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.map
+    }
+}
+
+impl<K: Ord, V, const N: usize> Drop for PooledMap<'_, K, V, N> {
+    fn drop(&mut self) {
+        self.map.clear();
+        self.map.bst.reset_config();
+
+        #[cfg(feature = "telemetry")]
+        self.map.reset_stats();
+    }
+}
+
+impl<K, V, const N: usize> Debug for PooledMap<'_, K, V, N>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.map, f)
+    }
+}
@@ -0,0 +1,191 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::mem;
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+use crate::tree::SgError;
+
+/// Keyed min-priority queue, up to `N` keys.
+///
+/// Each key has one priority, which can be changed or removed by key in `O(log n)`.
+/// Entries are ordered by priority, ties broken by key order.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgPriorityQueue;
+///
+/// // Task ID -> deadline tick
+/// let mut tasks = SgPriorityQueue::<u8, u32, 8>::new();
+/// tasks.push(1, 300);
+/// tasks.push(2, 100);
+/// tasks.push(3, 200);
+///
+/// assert_eq!(tasks.peek(), Some((&2, &100)));
+///
+/// // Task 1 becomes urgent
+/// assert_eq!(tasks.change_priority(&1, 50), Some(300));
+/// assert_eq!(tasks.pop(), Some((1, 50)));
+///
+/// assert_eq!(tasks.remove(&2), Some(100));
+/// assert_eq!(tasks.pop(), Some((3, 200)));
+/// assert!(tasks.is_empty());
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SgPriorityQueue<K: Ord, P: Ord, const N: usize> {
+    priorities: SgMap<K, P, N>,
+    queue: SgSet<(P, K), N>,
+}
+
+impl<K: Ord + Clone, P: Ord + Clone, const N: usize> SgPriorityQueue<K, P, N> {
+    /// Makes a new, empty `SgPriorityQueue`.
+    pub fn new() -> Self {
+        SgPriorityQueue {
+            priorities: SgMap::new(),
+            queue: SgSet::new(),
+        }
+    }
+
+    /// Number of keys queued.
+    pub fn len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    /// Returns `true` if the queue contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty()
+    }
+
+    /// Maximum number of keys, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.priorities.capacity()
+    }
+
+    /// Remove all keys.
+    pub fn clear(&mut self) {
+        self.priorities.clear();
+        self.queue.clear();
+    }
+
+    /// Queue a key with a priority, returning its previous priority if already queued.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is new and the queue is full.
+    /// Use [`try_push`][SgPriorityQueue::try_push] for a fallible alternative.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        match self.try_push(key, priority) {
+            Ok(opt_old) => opt_old,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Queue a key with a priority, returning its previous priority if already queued.
+    /// Returns `Err`, leaving the queue unchanged, if the key is new and the queue is full.
+    pub fn try_push(&mut self, key: K, priority: P) -> Result<Option<P>, SgError> {
+        if let Some(old_priority) = self.change_priority(&key, priority.clone()) {
+            return Ok(Some(old_priority));
+        }
+
+        self.priorities.try_insert(key.clone(), priority.clone())?;
+        self.queue.insert((priority, key));
+        Ok(None)
+    }
+
+    /// Get the key with the minimum priority, and its priority, without removing it.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.queue.first().map(|(priority, key)| (key, priority))
+    }
+
+    /// Remove and return the key with the minimum priority, and its priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        let (priority, key) = self.queue.pop_first()?;
+        self.priorities.remove(&key);
+        Some((key, priority))
+    }
+
+    /// Change a queued key's priority, returning the previous one.
+    /// Returns `None`, leaving the queue unchanged, if the key isn't queued.
+    pub fn change_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (key, old_priority) = self.priorities.get_key_value(key)?;
+        let mut entry = (old_priority.clone(), key.clone());
+        self.queue.remove(&entry);
+
+        let old_priority = mem::replace(&mut entry.0, priority.clone());
+        if let Some(stored) = self.priorities.get_mut::<K>(&entry.1) {
+            *stored = priority;
+        }
+        self.queue.insert(entry);
+
+        Some(old_priority)
+    }
+
+    /// Get a queued key's priority.
+    pub fn priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.priorities.get(key)
+    }
+
+    /// Returns `true` if the key is queued.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.priorities.contains_key(key)
+    }
+
+    /// Remove a key from the queue, returning its priority if queued.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (key, priority) = self.priorities.remove_entry(key)?;
+        let entry = (priority, key);
+        self.queue.remove(&entry);
+        Some(entry.0)
+    }
+
+    /// Gets an iterator over the keys and their priorities, in ascending priority order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &P)> + ExactSizeIterator + '_ {
+        self.queue.iter().map(|(priority, key)| (key, priority))
+    }
+}
+
+impl<K, P, const N: usize> Debug for SgPriorityQueue<K, P, N>
+where
+    K: Ord + Clone + Debug,
+    P: Ord + Clone + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Clone, P: Ord + Clone, const N: usize> Extend<(K, P)> for SgPriorityQueue<K, P, N> {
+    fn extend<I: IntoIterator<Item = (K, P)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(key, priority)| {
+            self.push(key, priority);
+        });
+    }
+}
+
+impl<K: Ord + Clone, P: Ord + Clone, const N: usize> FromIterator<(K, P)>
+    for SgPriorityQueue<K, P, N>
+{
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut queue = SgPriorityQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
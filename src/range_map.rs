@@ -0,0 +1,297 @@
+use core::fmt::{self, Debug};
+use core::ops::Range;
+
+use tinyvec::ArrayVec;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Map from disjoint half-open key ranges to values, up to `N` ranges.
+///
+/// Inserting a range overwrites the overlapped parts of existing ranges, trimming or splitting them.
+/// Adjoining ranges with equal values are coalesced, so each stored range is maximal.
+/// Empty ranges (`start >= end`) are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgRangeMap;
+///
+/// #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// enum Perm {
+///     #[default]
+///     Read,
+///     ReadWrite,
+/// }
+///
+/// let mut mmio = SgRangeMap::<u32, Perm, 8>::new();
+/// mmio.insert(0x1000..0x2000, Perm::ReadWrite);
+/// mmio.insert(0x1400..0x1800, Perm::Read); // Splits 0x1000..0x2000
+/// mmio.insert(0x2000..0x3000, Perm::ReadWrite); // Adjoins 0x1800..0x2000, same permissions
+///
+/// assert!(mmio.iter().eq([
+///     (0x1000..0x1400, &Perm::ReadWrite),
+///     (0x1400..0x1800, &Perm::Read),
+///     (0x1800..0x3000, &Perm::ReadWrite),
+/// ]));
+///
+/// assert_eq!(mmio.get(&0x1500), Some(&Perm::Read));
+/// assert_eq!(mmio.get(&0x3000), None);
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SgRangeMap<K: Ord, V, const N: usize> {
+    // Range start -> (range end, value)
+    map: SgMap<K, (K, V), N>,
+}
+
+impl<K: Ord + Copy, V, const N: usize> SgRangeMap<K, V, N> {
+    /// Makes a new, empty `SgRangeMap`.
+    pub fn new() -> Self {
+        SgRangeMap { map: SgMap::new() }
+    }
+
+    /// Number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of disjoint ranges, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all ranges.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Get the value of the range containing the point, if any.
+    pub fn get(&self, point: &K) -> Option<&V> {
+        self.get_key_value(point).map(|(_, val)| val)
+    }
+
+    /// Get the range containing the point, and its value, if any.
+    pub fn get_key_value(&self, point: &K) -> Option<(Range<K>, &V)> {
+        self.map
+            .range(..=*point)
+            .next_back()
+            .filter(|(_, (end, _))| point < end)
+            .map(|(start, (end, val))| (*start..*end, val))
+    }
+
+    /// Returns `true` if a stored range contains the point.
+    pub fn contains_key(&self, point: &K) -> bool {
+        self.get_key_value(point).is_some()
+    }
+
+    /// Gets an iterator over the stored ranges and their values, in ascending order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (Range<K>, &V)> + '_ {
+        self.map
+            .iter()
+            .map(|(start, (end, val))| (*start..*end, val))
+    }
+}
+
+impl<K: Ord + Copy, V: Clone + Eq, const N: usize> SgRangeMap<K, V, N> {
+    /// Map a range to a value, overwriting any overlapped parts of existing ranges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the result would need more ranges.
+    /// Use [`try_insert`][SgRangeMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, range: Range<K>, val: V) {
+        if self.try_insert(range, val).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Map a range to a value, overwriting any overlapped parts of existing ranges.
+    /// Returns `Err`, leaving the map unchanged, if the result would need more ranges than capacity.
+    pub fn try_insert(&mut self, range: Range<K>, val: V) -> Result<(), SgError> {
+        let Range {
+            start: range_start,
+            end: range_end,
+        } = range;
+
+        if range_start >= range_end {
+            return Ok(());
+        }
+
+        // Range starting before the new one and reaching its start: (start, end, has equal value)
+        let opt_pred = self
+            .map
+            .range(..range_start)
+            .next_back()
+            .filter(|(_, (end, _))| *end >= range_start)
+            .map(|(start, (end, pred_val))| (*start, *end, *pred_val == val));
+
+        // Already mapped to the same value
+        if let Some((_, end, true)) = opt_pred {
+            if end >= range_end {
+                return Ok(());
+            }
+        }
+
+        // Ranges starting within the new one, the last may extend past the new end
+        let inner_starts = self
+            .map
+            .range(range_start..range_end)
+            .map(|(start, _)| Some(*start)) // `Option` as `K` may lack `Default`
+            .collect::<ArrayVec<[Option<K>; N]>>();
+
+        let opt_inner_tail = inner_starts
+            .last()
+            .copied()
+            .flatten()
+            .and_then(|start| self.map.get(&start))
+            .filter(|(end, _)| *end > range_end)
+            .map(|(_, inner_val)| *inner_val == val);
+
+        let pred_split = matches!(opt_pred, Some((_, end, false)) if end > range_end);
+
+        // Range starting right at the new end, with an equal value
+        let opt_succ_end = match (pred_split, opt_inner_tail) {
+            (false, None) => self
+                .map
+                .get(&range_end)
+                .filter(|(_, succ_val)| *succ_val == val)
+                .map(|(end, _)| *end),
+            _ => None,
+        };
+
+        // Ranges are only added after all removals, so checking the final count suffices
+        let left_merge = matches!(opt_pred, Some((_, _, true)));
+        let added = usize::from(!left_merge)
+            + usize::from(pred_split)
+            + usize::from(opt_inner_tail == Some(false));
+        let removed = inner_starts.len() + usize::from(opt_succ_end.is_some());
+        if self.map.len() + added > self.map.max_len() + removed {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let mut new_start = range_start;
+        let mut new_end = range_end;
+        let mut opt_tail = None;
+
+        for start in inner_starts.into_iter().flatten() {
+            if let Some((end, inner_val)) = self.map.remove(&start) {
+                if end > range_end {
+                    match inner_val == val {
+                        true => new_end = end,
+                        false => opt_tail = Some((end, inner_val)),
+                    }
+                }
+            }
+        }
+
+        if let Some(end) = opt_succ_end {
+            self.map.remove(&range_end);
+            new_end = end;
+        }
+
+        match opt_pred {
+            Some((start, _, true)) => new_start = start,
+            Some((start, end, false)) if end > range_start => {
+                if let Some((pred_end, pred_val)) = self.map.get_mut(&start) {
+                    if pred_split {
+                        opt_tail = Some((*pred_end, pred_val.clone()));
+                    }
+                    *pred_end = range_start;
+                }
+            }
+            _ => {}
+        }
+
+        self.map.insert(new_start, (new_end, val));
+        if let Some(tail) = opt_tail {
+            self.map.insert(range_end, tail);
+        }
+
+        Ok(())
+    }
+
+    /// Unmap a range, trimming or splitting any ranges it overlaps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range splits a stored range in two, and the map is full.
+    /// Use [`try_remove`][SgRangeMap::try_remove] for a fallible alternative.
+    pub fn remove(&mut self, range: Range<K>) {
+        if self.try_remove(range).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Unmap a range, trimming or splitting any ranges it overlaps.
+    /// Returns `Err`, leaving the map unchanged, if the range splits a stored range in two and the map is full.
+    pub fn try_remove(&mut self, range: Range<K>) -> Result<(), SgError> {
+        let Range {
+            start: rm_start,
+            end: rm_end,
+        } = range;
+
+        if rm_start >= rm_end {
+            return Ok(());
+        }
+
+        // Range starting before the removed one, trimmed if it reaches past the removed start
+        let opt_pred_start = self
+            .map
+            .range(..rm_start)
+            .next_back()
+            .filter(|(_, (end, _))| *end > rm_start)
+            .map(|(start, (end, _))| (*start, *end > rm_end));
+
+        if let Some((_, true)) = opt_pred_start {
+            if self.map.len() >= self.map.max_len() {
+                return Err(SgError::StackCapacityExceeded);
+            }
+        }
+
+        // Ranges starting within the removed one, their remainder past the removed end is kept
+        let inner_starts = self
+            .map
+            .range(rm_start..rm_end)
+            .map(|(start, _)| Some(*start))
+            .collect::<ArrayVec<[Option<K>; N]>>();
+
+        for start in inner_starts.into_iter().flatten() {
+            if let Some((end, val)) = self.map.remove(&start) {
+                if end > rm_end {
+                    self.map.insert(rm_end, (end, val));
+                }
+            }
+        }
+
+        if let Some((start, split)) = opt_pred_start {
+            if let Some((pred_end, pred_val)) = self.map.get_mut(&start) {
+                let opt_tail = match split {
+                    true => Some((*pred_end, pred_val.clone())),
+                    false => None,
+                };
+                *pred_end = rm_start;
+
+                if let Some(tail) = opt_tail {
+                    self.map.insert(rm_end, tail);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V, const N: usize> Debug for SgRangeMap<K, V, N>
+where
+    K: Ord + Copy + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,211 @@
+use core::fmt::{self, Debug};
+use core::ops::Bound::{Excluded, Included};
+use core::ops::Range;
+
+use tinyvec::ArrayVec;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Set of disjoint, coalesced half-open key ranges, up to `N` ranges.
+///
+/// Inserting a range merges it with every range it overlaps or adjoins (e.g. `0..2` and `2..4` become `0..4`).
+/// Removing a range trims, or splits, the ranges it overlaps.
+/// Empty ranges (`start >= end`) are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgRangeSet;
+///
+/// let mut free = SgRangeSet::<u32, 8>::new();
+/// free.insert(0..16);
+/// free.insert(32..48);
+/// free.insert(16..20); // Adjoins 0..16
+/// assert!(free.iter().eq([0..20, 32..48]));
+///
+/// free.remove(4..8); // Splits 0..20
+/// assert!(free.iter().eq([0..4, 8..20, 32..48]));
+///
+/// assert!(free.contains(&10));
+/// assert!(!free.contains(&20));
+/// assert_eq!(free.get(&40), Some(32..48));
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SgRangeSet<K: Ord, const N: usize> {
+    // Range start -> range end
+    map: SgMap<K, K, N>,
+}
+
+impl<K: Ord + Copy, const N: usize> SgRangeSet<K, N> {
+    /// Makes a new, empty `SgRangeSet`.
+    pub fn new() -> Self {
+        SgRangeSet { map: SgMap::new() }
+    }
+
+    /// Number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of disjoint ranges, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all ranges.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Insert a range, merging it with any overlapping or adjoining ranges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is disjoint from (and not adjoining) every stored range, and the set is full.
+    /// Use [`try_insert`][SgRangeSet::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, range: Range<K>) {
+        if self.try_insert(range).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Insert a range, merging it with any overlapping or adjoining ranges.
+    /// Returns `Err`, leaving the set unchanged, if the range requires a new entry and the set is full.
+    pub fn try_insert(&mut self, range: Range<K>) -> Result<(), SgError> {
+        let Range {
+            start: mut new_start,
+            end: mut new_end,
+        } = range;
+
+        if new_start >= new_end {
+            return Ok(());
+        }
+
+        // Range starting at or before the new one, merged if it reaches the new start
+        let opt_pred = self
+            .map
+            .range(..=new_start)
+            .next_back()
+            .map(|(start, end)| (*start, *end))
+            .filter(|(_, end)| *end >= new_start);
+
+        // Ranges starting within, or right at the end of, the new one
+        let succ_starts = self
+            .map
+            .range((Excluded(new_start), Included(new_end)))
+            .map(|(start, _)| Some(*start)) // `Option` as `K` may lack `Default`
+            .collect::<ArrayVec<[Option<K>; N]>>();
+
+        if opt_pred.is_none() && succ_starts.is_empty() && (self.map.len() >= self.map.max_len()) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        for start in succ_starts.into_iter().flatten() {
+            if let Some(end) = self.map.remove(&start) {
+                new_end = new_end.max(end);
+            }
+        }
+
+        if let Some((start, end)) = opt_pred {
+            new_start = start;
+            new_end = new_end.max(end);
+        }
+
+        self.map.insert(new_start, new_end);
+        Ok(())
+    }
+
+    /// Remove a range, trimming or splitting any ranges it overlaps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range splits a stored range in two, and the set is full.
+    /// Use [`try_remove`][SgRangeSet::try_remove] for a fallible alternative.
+    pub fn remove(&mut self, range: Range<K>) {
+        if self.try_remove(range).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Remove a range, trimming or splitting any ranges it overlaps.
+    /// Returns `Err`, leaving the set unchanged, if the range splits a stored range in two and the set is full.
+    pub fn try_remove(&mut self, range: Range<K>) -> Result<(), SgError> {
+        let Range {
+            start: rm_start,
+            end: rm_end,
+        } = range;
+
+        if rm_start >= rm_end {
+            return Ok(());
+        }
+
+        // Range starting before the removed one, trimmed if it reaches past the removed start
+        let opt_pred = self
+            .map
+            .range(..rm_start)
+            .next_back()
+            .map(|(start, end)| (*start, *end))
+            .filter(|(_, end)| *end > rm_start);
+
+        if let Some((_, end)) = opt_pred {
+            if (end > rm_end) && (self.map.len() >= self.map.max_len()) {
+                return Err(SgError::StackCapacityExceeded);
+            }
+        }
+
+        // Ranges starting within the removed one, their remainder past the removed end is kept
+        let inner_starts = self
+            .map
+            .range(rm_start..rm_end)
+            .map(|(start, _)| Some(*start))
+            .collect::<ArrayVec<[Option<K>; N]>>();
+
+        for start in inner_starts.into_iter().flatten() {
+            if let Some(end) = self.map.remove(&start) {
+                if end > rm_end {
+                    self.map.insert(rm_end, end);
+                }
+            }
+        }
+
+        if let Some((start, end)) = opt_pred {
+            self.map.insert(start, rm_start);
+            if end > rm_end {
+                self.map.insert(rm_end, end);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a stored range contains the point.
+    pub fn contains(&self, point: &K) -> bool {
+        self.get(point).is_some()
+    }
+
+    /// Get the stored range containing the point, if any.
+    pub fn get(&self, point: &K) -> Option<Range<K>> {
+        self.map
+            .range(..=*point)
+            .next_back()
+            .filter(|(_, end)| point < *end)
+            .map(|(start, end)| *start..*end)
+    }
+
+    /// Gets an iterator over the stored ranges, in ascending order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Range<K>> + '_ {
+        self.map.iter().map(|(start, end)| *start..*end)
+    }
+}
+
+impl<K: Ord + Copy + Debug, const N: usize> Debug for SgRangeSet<K, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
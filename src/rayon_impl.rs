@@ -0,0 +1,255 @@
+use std::vec::Vec;
+
+use rayon::iter::plumbing::{
+    bridge, bridge_unindexed, Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer,
+    UnindexedProducer,
+};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+use crate::tree::{IterMut as TreeIterMut, NodeArena, SgTree};
+
+// Immutable Reference Iterators ---------------------------------------------------------------------------------------
+
+/// Parallel iterator over the entries of an [`SgMap`][crate::map::SgMap], in key order.
+/// Requires feature `rayon`.
+///
+/// Work is split at subtree boundaries: a subtree divides into its left subtree, then its root and right subtree.
+pub struct MapParIter<'a, K, V, const N: usize> {
+    bst: &'a SgTree<K, V, N>,
+}
+
+impl<'a, K: Ord + Sync, V: Sync, const N: usize> ParallelIterator for MapParIter<'a, K, V, N> {
+    type Item = (&'a K, &'a V);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let producer = SubtreeProducer {
+            bst: self.bst,
+            pieces: self
+                .bst
+                .opt_root_idx
+                .map(Piece::Subtree)
+                .into_iter()
+                .collect(),
+        };
+
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+/// Parallel iterator over the elements of an [`SgSet`][crate::set::SgSet], in order.
+/// Requires feature `rayon`.
+///
+/// Work is split at subtree boundaries: a subtree divides into its left subtree, then its root and right subtree.
+pub struct SetParIter<'a, T, const N: usize> {
+    map_par_iter: MapParIter<'a, T, (), N>,
+}
+
+impl<'a, T: Ord + Sync, const N: usize> ParallelIterator for SetParIter<'a, T, N> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.map_par_iter
+            .map(|(elem, _)| elem)
+            .drive_unindexed(consumer)
+    }
+}
+
+// In-order run of a subtree's nodes.
+#[derive(Clone, Copy)]
+enum Piece {
+    Node(usize),
+    Subtree(usize),
+}
+
+// Adjacent pieces, visited in order. Splitting expands a lone subtree into its left subtree, root, and right subtree.
+struct SubtreeProducer<'a, K, V, const N: usize> {
+    bst: &'a SgTree<K, V, N>,
+    pieces: Vec<Piece>,
+}
+
+impl<'a, K: Ord + Sync, V: Sync, const N: usize> UnindexedProducer
+    for SubtreeProducer<'a, K, V, N>
+{
+    type Item = (&'a K, &'a V);
+
+    fn split(mut self) -> (Self, Option<Self>) {
+        if let [Piece::Subtree(idx)] = self.pieces[..] {
+            let arena = &self.bst.arena;
+            self.pieces.clear();
+            self.pieces.extend(arena.left_idx(idx).map(Piece::Subtree));
+            self.pieces.push(Piece::Node(idx));
+            self.pieces.extend(arena.right_idx(idx).map(Piece::Subtree));
+        }
+
+        match self.pieces.len() {
+            0 | 1 => (self, None),
+            len => {
+                let right = SubtreeProducer {
+                    bst: self.bst,
+                    pieces: self.pieces.split_off(len / 2),
+                };
+                (self, Some(right))
+            }
+        }
+    }
+
+    fn fold_with<F: Folder<Self::Item>>(self, mut folder: F) -> F {
+        let arena = &self.bst.arena;
+        let mut idx_stack = Vec::new();
+
+        for piece in self.pieces {
+            match piece {
+                Piece::Node(idx) => folder = folder.consume(arena.get(idx)),
+                Piece::Subtree(root_idx) => {
+                    // Iterative in-order traversal
+                    let mut opt_idx = Some(root_idx);
+                    loop {
+                        while let Some(idx) = opt_idx {
+                            idx_stack.push(idx);
+                            opt_idx = arena.left_idx(idx);
+                        }
+
+                        match idx_stack.pop() {
+                            Some(idx) => {
+                                folder = folder.consume(arena.get(idx));
+                                if folder.full() {
+                                    return folder;
+                                }
+                                opt_idx = arena.right_idx(idx);
+                            }
+                            None => break,
+                        }
+                    }
+                }
+            }
+
+            if folder.full() {
+                break;
+            }
+        }
+
+        folder
+    }
+}
+
+// Mutable Reference Iterator ------------------------------------------------------------------------------------------
+
+/// Parallel iterator over the entries of an [`SgMap`][crate::map::SgMap], in key order, with mutable values.
+/// Requires feature `rayon`.
+///
+/// Sorts the arena first, like [`iter_mut`][crate::map::SgMap::iter_mut], so every subtree occupies a contiguous
+/// span of slots and work is split by index.
+pub struct MapParIterMut<'a, K, V, const N: usize> {
+    iter_mut: TreeIterMut<'a, K, V, N>,
+}
+
+impl<'a, K: Ord + Send + Sync, V: Send, const N: usize> ParallelIterator
+    for MapParIterMut<'a, K, V, N>
+{
+    type Item = (&'a K, &'a mut V);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.iter_mut.len())
+    }
+}
+
+impl<'a, K: Ord + Send + Sync, V: Send, const N: usize> IndexedParallelIterator
+    for MapParIterMut<'a, K, V, N>
+{
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.iter_mut.len()
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(SpanProducer {
+            iter_mut: self.iter_mut,
+        })
+    }
+}
+
+// Span of sorted, fully occupied arena slots.
+struct SpanProducer<'a, K, V, const N: usize> {
+    iter_mut: TreeIterMut<'a, K, V, N>,
+}
+
+impl<'a, K: Ord + Send + Sync, V: Send, const N: usize> Producer for SpanProducer<'a, K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = TreeIterMut<'a, K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.iter_mut.split_at(index);
+        (
+            SpanProducer { iter_mut: left },
+            SpanProducer { iter_mut: right },
+        )
+    }
+}
+
+// Conversions ---------------------------------------------------------------------------------------------------------
+
+impl<'a, K: Ord + Sync, V: Sync, const N: usize> IntoParallelIterator for &'a SgMap<K, V, N> {
+    type Iter = MapParIter<'a, K, V, N>;
+    type Item = (&'a K, &'a V);
+
+    fn into_par_iter(self) -> Self::Iter {
+        MapParIter { bst: &self.bst }
+    }
+}
+
+impl<'a, K: Ord + Send + Sync, V: Send, const N: usize> IntoParallelIterator
+    for &'a mut SgMap<K, V, N>
+{
+    type Iter = MapParIterMut<'a, K, V, N>;
+    type Item = (&'a K, &'a mut V);
+
+    fn into_par_iter(self) -> Self::Iter {
+        MapParIterMut {
+            iter_mut: TreeIterMut::new(&mut self.bst),
+        }
+    }
+}
+
+/// Moves entries into a `Vec` first, parallel iteration starts once they've been sorted out of the arena.
+impl<K: Ord + Send, V: Send, const N: usize> IntoParallelIterator for SgMap<K, V, N> {
+    type Iter = rayon::vec::IntoIter<(K, V)>;
+    type Item = (K, V);
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_iter().collect::<Vec<_>>().into_par_iter()
+    }
+}
+
+impl<'a, T: Ord + Sync, const N: usize> IntoParallelIterator for &'a SgSet<T, N> {
+    type Iter = SetParIter<'a, T, N>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        SetParIter {
+            map_par_iter: MapParIter { bst: &self.bst },
+        }
+    }
+}
+
+/// Moves elements into a `Vec` first, parallel iteration starts once they've been sorted out of the arena.
+impl<T: Ord + Send, const N: usize> IntoParallelIterator for SgSet<T, N> {
+    type Iter = rayon::vec::IntoIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_iter().collect::<Vec<_>>().into_par_iter()
+    }
+}
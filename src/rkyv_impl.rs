@@ -0,0 +1,418 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::ops::{Bound, RangeBounds};
+
+use rkyv::api::low::{LowDeserializer, LowValidator};
+use rkyv::bytecheck::CheckBytes;
+use rkyv::collections::util::{Entry, EntryAdapter};
+use rkyv::rancor::{Fallible, Source};
+use rkyv::ser::{Allocator, Writer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Archived, Deserialize, Place, Serialize};
+
+use smallnum::SmallUnsigned;
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+use crate::tree::{Idx, NodeArena, SgError, SgTree, SgVec};
+
+/// Archived form of an [`SgMap`][crate::map::SgMap]: its entries, sorted by key.
+pub type ArchivedEntries<K, V> = ArchivedVec<Entry<Archived<K>, Archived<V>>>;
+
+// Arena indexes of a tree's nodes, in key order.
+fn sorted_idxs<K: Ord, V, const N: usize>(bst: &SgTree<K, V, N>) -> SgVec<[Idx; N]> {
+    match bst.opt_root_idx {
+        Some(root_idx) => bst.flatten_subtree_to_sorted_idxs(root_idx),
+        None => SgVec::default(),
+    }
+}
+
+// Index of the first item not ordered before `bound`, for `Included`/`Excluded` start and end bounds.
+fn partition<T, Q, F>(items: &[T], bound: Bound<&Q>, is_start: bool, key: F) -> usize
+where
+    Q: Ord + ?Sized,
+    F: Fn(&T) -> &Q,
+{
+    match (bound, is_start) {
+        (Bound::Unbounded, true) => 0,
+        (Bound::Unbounded, false) => items.len(),
+        (Bound::Included(q), true) | (Bound::Excluded(q), false) => {
+            items.partition_point(|item| key(item) < q)
+        }
+        (Bound::Excluded(q), true) | (Bound::Included(q), false) => {
+            items.partition_point(|item| key(item) <= q)
+        }
+    }
+}
+
+// Map -----------------------------------------------------------------------------------------------------------------
+
+/// Archives as a sorted array of entries, i.e. an implicit balanced tree searched by bisection,
+/// so the archive doesn't depend on arena layout features.
+impl<K: Ord + Archive, V: Archive, const N: usize> Archive for SgMap<K, V, N> {
+    type Archived = ArchivedEntries<K, V>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<K, V, S, const N: usize> Serialize<S> for SgMap<K, V, N>
+where
+    K: Ord + Serialize<S>,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let arena = &self.bst.arena;
+        let sorted_idxs = sorted_idxs(&self.bst);
+        let entries = sorted_idxs.iter().map(|idx| {
+            let (key, val) = arena.get(idx.usize());
+            EntryAdapter::new(key, val)
+        });
+
+        ArchivedVec::serialize_from_iter::<EntryAdapter<&K, &V, K, V>, _, _>(entries, serializer)
+    }
+}
+
+/// Fails, instead of panicking, if the archive exceeds capacity.
+impl<K, V, D, const N: usize> Deserialize<SgMap<K, V, N>, D> for ArchivedEntries<K, V>
+where
+    K: Ord + Archive,
+    V: Archive,
+    Archived<K>: Deserialize<K, D>,
+    Archived<V>: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<SgMap<K, V, N>, D::Error> {
+        let mut map = SgMap::new();
+        for entry in self.iter() {
+            let key = entry.key.deserialize(deserializer)?;
+            let val = entry.value.deserialize(deserializer)?;
+            map.try_insert(key, val).map_err(D::Error::new)?;
+        }
+
+        Ok(map)
+    }
+}
+
+/// Validated, zero-copy view of an archived [`SgMap`][crate::map::SgMap], requires feature `rkyv`.
+///
+/// Lookups bisect the archived entries in place (e.g. in flash or an `mmap`), without deserialization.
+/// Construction checks the archive's bytes and that its keys are strictly ascending.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::rancor::Error;
+/// use rkyv::rend::u32_le;
+/// use scapegoat::{SgMap, SgMapArchive};
+///
+/// let settings = SgMap::<u32, u16, 3>::from([(3, 300), (1, 100), (2, 200)]);
+/// let bytes = rkyv::to_bytes::<Error>(&settings).unwrap();
+///
+/// let archive = SgMapArchive::<u32, u16>::from_bytes::<Error>(&bytes).unwrap();
+/// assert_eq!(archive.get(&u32_le::from_native(2)).map(|v| v.to_native()), Some(200));
+/// assert_eq!(archive.len(), 3);
+///
+/// let restored: SgMap<u32, u16, 3> = archive.to_map::<3, Error>().unwrap();
+/// assert_eq!(restored, settings);
+/// ```
+pub struct SgMapArchive<'a, K: Archive, V: Archive> {
+    entries: &'a ArchivedEntries<K, V>,
+}
+
+impl<'a, K: Archive, V: Archive> SgMapArchive<'a, K, V>
+where
+    Archived<K>: Ord,
+{
+    /// Access and validate an archived map at the end of `bytes`, as written by `rkyv` serialization.
+    /// Returns `Err` if the bytes are invalid or keys aren't strictly ascending.
+    pub fn from_bytes<E>(bytes: &'a [u8]) -> Result<Self, E>
+    where
+        E: Source,
+        ArchivedEntries<K, V>: for<'b> CheckBytes<LowValidator<'b, E>>,
+    {
+        Self::new(rkyv::api::low::access::<ArchivedEntries<K, V>, E>(bytes)?)
+    }
+
+    /// Validate an already-accessed archived map.
+    /// Returns `Err` if keys aren't strictly ascending.
+    pub fn new<E: Source>(entries: &'a ArchivedEntries<K, V>) -> Result<Self, E> {
+        match entries.windows(2).all(|pair| pair[0].key < pair[1].key) {
+            true => Ok(SgMapArchive { entries }),
+            false => Err(E::new(SgError::UnsortedInput)),
+        }
+    }
+
+    /// Number of archived entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the archive contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get an archived key's value.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a Archived<V>>
+    where
+        Archived<K>: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, val)| val)
+    }
+
+    /// Get an archived key-value pair.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&'a Archived<K>, &'a Archived<V>)>
+    where
+        Archived<K>: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let entries: &'a [_] = self.entries.as_slice();
+        let idx = entries
+            .binary_search_by(|entry| entry.key.borrow().cmp(key))
+            .ok()?;
+
+        Some((&entries[idx].key, &entries[idx].value))
+    }
+
+    /// Returns `true` if the archive contains the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Archived<K>: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    /// Gets an iterator over the archived entries, sorted by key.
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (&'a Archived<K>, &'a Archived<V>)> + ExactSizeIterator + 'a
+    {
+        let entries: &'a [_] = self.entries.as_slice();
+        entries.iter().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Gets an iterator over the archived entries within a key range, sorted by key.
+    pub fn range<Q, R>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (&'a Archived<K>, &'a Archived<V>)> + ExactSizeIterator + 'a
+    where
+        Archived<K>: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let entries: &'a [_] = self.entries.as_slice();
+        let start = partition(entries, range.start_bound(), true, |entry| {
+            entry.key.borrow()
+        });
+        let end = partition(entries, range.end_bound(), false, |entry| {
+            entry.key.borrow()
+        });
+
+        entries[start..end.max(start)]
+            .iter()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Deserialize into an [`SgMap`][crate::map::SgMap] with capacity `N`.
+    /// Returns `Err` if the archive exceeds capacity.
+    pub fn to_map<const N: usize, E: Source>(&self) -> Result<SgMap<K, V, N>, E>
+    where
+        K: Ord,
+        Archived<K>: Deserialize<K, LowDeserializer<E>>,
+        Archived<V>: Deserialize<V, LowDeserializer<E>>,
+    {
+        rkyv::api::low::deserialize::<SgMap<K, V, N>, E>(self.entries)
+    }
+}
+
+impl<K: Archive, V: Archive> Debug for SgMapArchive<'_, K, V>
+where
+    Archived<K>: Debug,
+    Archived<V>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|entry| (&entry.key, &entry.value)))
+            .finish()
+    }
+}
+
+// Set -----------------------------------------------------------------------------------------------------------------
+
+/// Archives as a sorted array of elements, i.e. an implicit balanced tree searched by bisection,
+/// so the archive doesn't depend on arena layout features.
+impl<T: Ord + Archive, const N: usize> Archive for SgSet<T, N> {
+    type Archived = ArchivedVec<Archived<T>>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<T, S, const N: usize> Serialize<S> for SgSet<T, N>
+where
+    T: Ord + Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let arena = &self.bst.arena;
+        let sorted_idxs = sorted_idxs(&self.bst);
+        let elems = sorted_idxs.iter().map(|idx| arena.key(idx.usize()));
+
+        ArchivedVec::serialize_from_iter::<T, _, _>(elems, serializer)
+    }
+}
+
+/// Fails, instead of panicking, if the archive exceeds capacity.
+impl<T, D, const N: usize> Deserialize<SgSet<T, N>, D> for ArchivedVec<Archived<T>>
+where
+    T: Ord + Archive,
+    Archived<T>: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<SgSet<T, N>, D::Error> {
+        let mut set = SgSet::new();
+        for elem in self.iter() {
+            set.try_insert(elem.deserialize(deserializer)?)
+                .map_err(D::Error::new)?;
+        }
+
+        Ok(set)
+    }
+}
+
+/// Validated, zero-copy view of an archived [`SgSet`][crate::set::SgSet], requires feature `rkyv`.
+///
+/// Lookups bisect the archived elements in place (e.g. in flash or an `mmap`), without deserialization.
+/// Construction checks the archive's bytes and that its elements are strictly ascending.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::rancor::Error;
+/// use rkyv::rend::u32_le;
+/// use scapegoat::{SgSet, SgSetArchive};
+///
+/// let allowed = SgSet::<u32, 3>::from([30, 10, 20]);
+/// let bytes = rkyv::to_bytes::<Error>(&allowed).unwrap();
+///
+/// let archive = SgSetArchive::<u32>::from_bytes::<Error>(&bytes).unwrap();
+/// assert!(archive.contains(&u32_le::from_native(20)));
+/// assert!(archive.iter().map(|elem| elem.to_native()).eq([10, 20, 30]));
+/// ```
+pub struct SgSetArchive<'a, T: Archive> {
+    elems: &'a ArchivedVec<Archived<T>>,
+}
+
+impl<'a, T: Archive> SgSetArchive<'a, T>
+where
+    Archived<T>: Ord,
+{
+    /// Access and validate an archived set at the end of `bytes`, as written by `rkyv` serialization.
+    /// Returns `Err` if the bytes are invalid or elements aren't strictly ascending.
+    pub fn from_bytes<E>(bytes: &'a [u8]) -> Result<Self, E>
+    where
+        E: Source,
+        ArchivedVec<Archived<T>>: for<'b> CheckBytes<LowValidator<'b, E>>,
+    {
+        Self::new(rkyv::api::low::access::<ArchivedVec<Archived<T>>, E>(
+            bytes,
+        )?)
+    }
+
+    /// Validate an already-accessed archived set.
+    /// Returns `Err` if elements aren't strictly ascending.
+    pub fn new<E: Source>(elems: &'a ArchivedVec<Archived<T>>) -> Result<Self, E> {
+        match elems.windows(2).all(|pair| pair[0] < pair[1]) {
+            true => Ok(SgSetArchive { elems }),
+            false => Err(E::new(SgError::UnsortedInput)),
+        }
+    }
+
+    /// Number of archived elements.
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    /// Returns `true` if the archive contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Returns `true` if the archive contains the value.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Archived<T>: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(value).is_some()
+    }
+
+    /// Get the archived element equal to the value.
+    pub fn get<Q>(&self, value: &Q) -> Option<&'a Archived<T>>
+    where
+        Archived<T>: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let elems: &'a [_] = self.elems.as_slice();
+        let idx = elems
+            .binary_search_by(|elem| elem.borrow().cmp(value))
+            .ok()?;
+
+        Some(&elems[idx])
+    }
+
+    /// Gets an iterator over the archived elements, in ascending order.
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &'a Archived<T>> + ExactSizeIterator + 'a {
+        let elems: &'a [_] = self.elems.as_slice();
+        elems.iter()
+    }
+
+    /// Gets an iterator over the archived elements within a range, in ascending order.
+    pub fn range<Q, R>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = &'a Archived<T>> + ExactSizeIterator + 'a
+    where
+        Archived<T>: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let elems: &'a [_] = self.elems.as_slice();
+        let start = partition(elems, range.start_bound(), true, |elem| elem.borrow());
+        let end = partition(elems, range.end_bound(), false, |elem| elem.borrow());
+
+        elems[start..end.max(start)].iter()
+    }
+
+    /// Deserialize into an [`SgSet`][crate::set::SgSet] with capacity `N`.
+    /// Returns `Err` if the archive exceeds capacity.
+    pub fn to_set<const N: usize, E: Source>(&self) -> Result<SgSet<T, N>, E>
+    where
+        T: Ord,
+        Archived<T>: Deserialize<T, LowDeserializer<E>>,
+    {
+        rkyv::api::low::deserialize::<SgSet<T, N>, E>(self.elems)
+    }
+}
+
+impl<T: Archive> Debug for SgSetArchive<'_, T>
+where
+    Archived<T>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.elems.iter()).finish()
+    }
+}
@@ -0,0 +1,149 @@
+use core::hint;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use std::sync::{Mutex, PoisonError, RwLock, TryLockError};
+
+use crate::map::SgMap;
+
+/// Read-mostly ordered map: readers never wait on the writer, retrying on a sequence counter instead.
+/// Requires feature `std`.
+///
+/// Holds two copies of an [`SgMap`][crate::map::SgMap], since safe Rust can't read one mid-mutation.
+/// A write applies its closure to the standby copy, publishes it by bumping the sequence counter, then replays the
+/// closure on the other copy. A read runs its closure on the copy published at the current sequence number,
+/// retrying if a replay has already claimed it. Writes are serialized, reads only contend on the copy's reader count.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgSeqLockMap;
+/// use std::thread;
+///
+/// // Sensor ID -> latest reading
+/// let telemetry = SgSeqLockMap::<u8, i32, 16>::new();
+///
+/// thread::scope(|s| {
+///     s.spawn(|| {
+///         for reading in 0..100 {
+///             telemetry.write(|map| map.insert(1, reading));
+///         }
+///     });
+///
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             let reading = telemetry.read(|map| map.get(&1).copied());
+///             assert!(reading.map_or(true, |r| (0..100).contains(&r)));
+///         });
+///     }
+/// });
+///
+/// assert_eq!(telemetry.version(), 100);
+/// assert_eq!(telemetry.try_read(0, |map| map.get(&1).copied()), Some(Some(99)));
+/// ```
+pub struct SgSeqLockMap<K: Ord, V, const N: usize> {
+    // Number of published writes, the current copy is `seq % 2`
+    seq: AtomicUsize,
+    copies: [RwLock<SgMap<K, V, N>>; 2],
+    writer: Mutex<()>,
+}
+
+impl<K: Ord, V, const N: usize> SgSeqLockMap<K, V, N> {
+    /// Makes a new, empty `SgSeqLockMap`.
+    pub fn new() -> Self {
+        SgSeqLockMap {
+            seq: AtomicUsize::new(0),
+            copies: [RwLock::new(SgMap::new()), RwLock::new(SgMap::new())],
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Number of writes published so far, e.g. to detect changes between reads.
+    pub fn version(&self) -> usize {
+        self.seq.load(Ordering::Acquire)
+    }
+
+    /// Call `f` with a consistent snapshot of the map, returning its result.
+    /// Spins while a write claims the snapshot's copy, use [`try_read`][SgSeqLockMap::try_read] to bound retries.
+    pub fn read<R, F: FnOnce(&SgMap<K, V, N>) -> R>(&self, f: F) -> R {
+        let mut f = Some(f);
+        loop {
+            if let Some(result) = self.read_once(&mut f) {
+                return result;
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Call `f` with a consistent snapshot of the map, returning its result.
+    /// Returns `None`, without calling `f`, if writes claimed the snapshot's copy on the first attempt and all
+    /// `max_retries` retries.
+    pub fn try_read<R, F: FnOnce(&SgMap<K, V, N>) -> R>(
+        &self,
+        max_retries: usize,
+        f: F,
+    ) -> Option<R> {
+        let mut f = Some(f);
+        for _ in 0..=max_retries {
+            if let Some(result) = self.read_once(&mut f) {
+                return Some(result);
+            }
+            hint::spin_loop();
+        }
+
+        None
+    }
+
+    /// Apply `f` to the map, returning the result of its first call.
+    ///
+    /// `f` is called once per copy, so must make the same change each time (e.g. insert a given key-value pair).
+    /// Both calls see identical maps, so fallible operations (e.g. `try_insert`) succeed or fail alike.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` does, after which the copies may differ.
+    pub fn write<R, F: FnMut(&mut SgMap<K, V, N>) -> R>(&self, mut f: F) -> R {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let seq = self.seq.load(Ordering::Acquire);
+
+        // Waits only for readers that claimed the standby copy before the last publish
+        let result = f(&mut self.copies[(seq + 1) % 2]
+            .write()
+            .unwrap_or_else(PoisonError::into_inner));
+        self.seq.store(seq + 1, Ordering::Release);
+
+        f(&mut self.copies[seq % 2]
+            .write()
+            .unwrap_or_else(PoisonError::into_inner));
+
+        result
+    }
+
+    /// Consume the `SgSeqLockMap`, returning the current copy of the map.
+    pub fn into_inner(self) -> SgMap<K, V, N> {
+        let [first, second] = self.copies;
+        let current = match self.seq.into_inner() % 2 {
+            0 => first,
+            _ => second,
+        };
+
+        current.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Single read attempt, takes `f` only on success.
+    fn read_once<R, F: FnOnce(&SgMap<K, V, N>) -> R>(&self, f: &mut Option<F>) -> Option<R> {
+        let seq = self.seq.load(Ordering::Acquire);
+        let map = match self.copies[seq % 2].try_read() {
+            Ok(map) => map,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+
+        f.take().map(|f| f(&map))
+    }
+}
+
+impl<K: Ord, V, const N: usize> Default for SgSeqLockMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
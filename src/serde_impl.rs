@@ -0,0 +1,238 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::map::SgMap;
+use crate::set::SgSet;
+
+// Map Representation (Default) ----------------------------------------------------------------------------------------
+
+// Serializes as a map, e.g. `{"k": v}` in JSON, same as `BTreeMap`.
+impl<K, V, const N: usize> Serialize for SgMap<K, V, N>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self.iter() {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+// Fails, instead of panicking, if input exceeds capacity.
+impl<'de, K, V, const N: usize> Deserialize<'de> for SgMap<K, V, N>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(SgMapVisitor(PhantomData))
+    }
+}
+
+struct SgMapVisitor<K, V, const N: usize>(PhantomData<(K, V)>);
+
+impl<'de, K, V, const N: usize> Visitor<'de> for SgMapVisitor<K, V, N>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = SgMap<K, V, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map with at most {} entries", N)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = SgMap::new();
+        while let Some((k, v)) = access.next_entry()? {
+            if map.try_insert(k, v).is_err() {
+                return Err(A::Error::invalid_length(map.len() + 1, &self));
+            }
+        }
+        Ok(map)
+    }
+}
+
+// Serializes as a sequence, e.g. `[e1, e2]` in JSON, same as `BTreeSet`.
+impl<T, const N: usize> Serialize for SgSet<T, N>
+where
+    T: Ord + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for e in self.iter() {
+            seq.serialize_element(e)?;
+        }
+        seq.end()
+    }
+}
+
+// Fails, instead of panicking, if input exceeds capacity.
+impl<'de, T, const N: usize> Deserialize<'de> for SgSet<T, N>
+where
+    T: Ord + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(SgSetVisitor(PhantomData))
+    }
+}
+
+struct SgSetVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T, const N: usize> Visitor<'de> for SgSetVisitor<T, N>
+where
+    T: Ord + Deserialize<'de>,
+{
+    type Value = SgSet<T, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence with at most {} elements", N)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut set = SgSet::new();
+        while let Some(e) = access.next_element()? {
+            if set.try_insert(e).is_err() {
+                return Err(A::Error::invalid_length(set.len() + 1, &self));
+            }
+        }
+        Ok(set)
+    }
+}
+
+// Sequence-of-Pairs Representation ------------------------------------------------------------------------------------
+
+/// Alternate [`SgMap`][crate::map::SgMap] serde representation: a sequence of key-value pairs
+/// (e.g. `[[k, v]]` in JSON) instead of a map (e.g. `{"k": v}` in JSON).
+/// Requires feature `serde`.
+///
+/// Pairs suit formats or consumers that only support string map keys, or prefer compact tuples (e.g. CBOR).
+/// Use as a field attribute, `#[serde(with = "scapegoat::serde_pairs")]`, or via [`Pairs`] directly.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMap;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Config {
+///     names: SgMap<u8, char, 4>,
+///
+///     #[serde(with = "scapegoat::serde_pairs")]
+///     ranges: SgMap<u8, char, 4>,
+/// }
+///
+/// let cfg = Config {
+///     names: SgMap::from([(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]),
+///     ranges: SgMap::from([(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]),
+/// };
+///
+/// let json = serde_json::to_string(&cfg).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"names":{"1":"a","2":"b","3":"c","4":"d"},"ranges":[[1,"a"],[2,"b"],[3,"c"],[4,"d"]]}"#
+/// );
+/// assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), cfg);
+/// ```
+pub mod serde_pairs {
+    use super::*;
+
+    /// Serialize `map` as a sequence of key-value pairs.
+    pub fn serialize<K, V, S, const N: usize>(
+        map: &SgMap<K, V, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        K: Ord + Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(map.len()))?;
+        for pair in map.iter() {
+            seq.serialize_element(&pair)?;
+        }
+        seq.end()
+    }
+
+    /// Deserialize a map from a sequence of key-value pairs.
+    /// Fails, instead of panicking, if input exceeds capacity.
+    pub fn deserialize<'de, K, V, D, const N: usize>(
+        deserializer: D,
+    ) -> Result<SgMap<K, V, N>, D::Error>
+    where
+        K: Ord + Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PairsVisitor(PhantomData))
+    }
+
+    struct PairsVisitor<K, V, const N: usize>(PhantomData<(K, V)>);
+
+    impl<'de, K, V, const N: usize> Visitor<'de> for PairsVisitor<K, V, N>
+    where
+        K: Ord + Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        type Value = SgMap<K, V, N>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a sequence of at most {} key-value pairs", N)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut map = SgMap::new();
+            while let Some((k, v)) = access.next_element()? {
+                if map.try_insert(k, v).is_err() {
+                    return Err(A::Error::invalid_length(map.len() + 1, &self));
+                }
+            }
+            Ok(map)
+        }
+    }
+}
+
+/// Wrapper selecting the [`serde_pairs`] representation for an owned [`SgMap`][crate::map::SgMap].
+/// Requires feature `serde`.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{Pairs, SgMap};
+///
+/// let map = SgMap::<_, _, 3>::from([(3, "c"), (1, "a"), (2, "b")]);
+/// let json = serde_json::to_string(&Pairs(map)).unwrap();
+/// assert_eq!(json, r#"[[1,"a"],[2,"b"],[3,"c"]]"#);
+///
+/// let Pairs(map): Pairs<SgMap<u8, &str, 3>> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(map[&2], "b");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Pairs<M>(pub M);
+
+impl<K, V, const N: usize> Serialize for Pairs<SgMap<K, V, N>>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_pairs::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, K, V, const N: usize> Deserialize<'de> for Pairs<SgMap<K, V, N>>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_pairs::deserialize(deserializer).map(Pairs)
+    }
+}
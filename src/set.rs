@@ -1,13 +1,26 @@
 use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
-use core::iter::FromIterator;
-use core::ops::RangeBounds;
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, Rev};
 use core::ops::{BitAnd, BitOr, BitXor, Sub};
+use core::ops::{Bound, RangeBounds};
 
+use crate::map::SgMap;
 use crate::set_types::{
-    Difference, Intersection, IntoIter, Iter, Range, SymmetricDifference, Union,
+    Cursor, CursorMut, Difference, ExtractIf, Intersection, IntoIter, Iter, IterRanked,
+    MergeWithIter, PopWhile, Range, SymmetricDifference, Union,
 };
-use crate::tree::{SgError, SgTree};
+#[cfg(feature = "telemetry")]
+use crate::tree::TreeStats;
+#[cfg(feature = "yield_hook")]
+use crate::tree::YieldHook;
+use crate::tree::{
+    ArenaFragmentation, ArenaLayout, BalancePolicy, CursorPos, DefaultBalancePolicy, ExtendReport,
+    InvariantError, MaintenanceBudget, NodeArena, SgError, SgTree, SgVec,
+};
+#[cfg(feature = "stats")]
+use crate::tree::{TreeDisplay, TreeShape};
 
 /// Safe, fallible, embedded-friendly ordered set.
 ///
@@ -17,7 +30,10 @@ use crate::tree::{SgError, SgTree};
 /// * [`try_append`][crate::set::SgSet::try_append]
 /// * [`try_extend`][crate::set::SgSet::try_extend]
 /// * [`try_from_iter`][crate::set::SgSet::try_from_iter]
+/// * [`try_from_results`][crate::set::SgSet::try_from_results]
 /// * [`try_replace`][crate::set::SgSet::try_replace]
+/// * [`try_get_or_insert`][crate::set::SgSet::try_get_or_insert]
+/// * [`try_get_or_insert_with`][crate::set::SgSet::try_get_or_insert_with]
 ///
 /// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented because it would collide with the blanket implementation.
 /// See [this open GitHub issue](https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839) from 2018,
@@ -27,92 +43,790 @@ use crate::tree::{SgError, SgTree};
 ///
 /// The majority of API examples and descriptions are adapted or directly copied from the standard library's [`BTreeSet`](https://doc.rust-lang.org/std/collections/struct.BTreeSet.html).
 /// The goal is to offer embedded developers familiar, ergonomic APIs on resource constrained systems that otherwise don't get the luxury of dynamic collections.
-#[derive(Default, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub struct SgSet<T: Ord + Default, const N: usize> {
-    pub(crate) bst: SgTree<T, (), N>,
+#[derive(Clone)]
+pub struct SgSet<T: Ord, const N: usize, P = DefaultBalancePolicy> {
+    pub(crate) bst: SgTree<T, (), N, P>,
 }
 
-impl<T: Ord + Default, const N: usize> SgSet<T, N> {
-    /// Makes a new, empty `SgSet`.
+impl<T: Ord, const N: usize> SgSet<T, N> {
+    /// Makes a new, empty `SgSet`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<i32, 10> = SgSet::new();
+    /// ```
+    pub fn new() -> Self {
+        SgSet { bst: SgTree::new() }
+    }
+
+    /// Makes a new, empty `SgSet` with capacity chosen at runtime, in place of const `N`.
+    /// Const `N` becomes the number of items stored inline, storage beyond it is heap-allocated up front.
+    /// Requires feature `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cap` exceeds the maximum supported capacity (`65_534`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 0>::with_capacity(1_000);
+    /// assert_eq!(set.capacity(), 1_000);
+    ///
+    /// for i in 0..1_000 {
+    ///     set.insert(i);
+    /// }
+    ///
+    /// assert!(set.is_full());
+    /// assert!(set.contains(&999));
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_capacity(cap: usize) -> Self {
+        SgSet {
+            bst: SgTree::with_capacity(cap),
+        }
+    }
+
+    /// Attempt conversion from an iterator.
+    /// Will fail if iterator length exceeds `u16::MAX - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// const CAPACITY_1: usize = 1_000;
+    /// assert!(SgSet::<_, CAPACITY_1>::try_from_iter((0..CAPACITY_1)).is_ok());
+    ///
+    /// const CAPACITY_2: usize = (u16::MAX as usize) + 1;
+    /// #[cfg(not(feature = "cacheline_nodes"))] // Result would exceed main thread stack
+    /// assert_eq!(
+    ///     SgSet::<_, CAPACITY_2>::try_from_iter((0..CAPACITY_2)),
+    ///     Err(SgError::MaximumCapacityExceeded)
+    /// );
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryFromIterator` trait in `core`/`std`.
+    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = T>>(
+        iter: I,
+    ) -> Result<Self, SgError> {
+        match iter.len() <= SgTree::<T, (), N>::max_capacity() {
+            true => Ok(SgSet::from_iter(iter)),
+            false => Err(SgError::MaximumCapacityExceeded),
+        }
+    }
+
+    /// Build a set from values already in ascending order, in linear time.
+    /// The arena is filled in input order, then linked into a perfectly balanced tree - no per-value search or
+    /// rebalance, unlike [`from_iter`][SgSet::from_iter].
+    ///
+    /// Returns [`SgError::UnsortedInput`] if values aren't strictly ascending (including duplicates), or
+    /// [`SgError::StackCapacityExceeded`] if there are more than `N` values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let set = SgSet::<_, 10>::from_sorted_iter(0..8).unwrap();
+    /// assert!(set.iter().cloned().eq(0..8));
+    ///
+    /// assert_eq!(
+    ///     SgSet::<_, 10>::from_sorted_iter([1, 2, 2]),
+    ///     Err(SgError::UnsortedInput)
+    /// );
+    /// ```
+    pub fn from_sorted_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, SgError> {
+        SgTree::from_sorted_iter(iter.into_iter().map(|e| (e, ()))).map(|bst| SgSet { bst })
+    }
+
+    /// Attempt conversion from an iterator of `Result`s, stopping at the first `Err`.
+    /// Capacity overflow is converted into the caller's error type, via `From<SgError>`.
+    ///
+    /// Unlike `collect::<Result<SgSet<_, N>, _>>()`, which panics if capacity is exceeded,
+    /// this supports `?`-style error flow for both item and capacity errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum IdError {
+    ///     Invalid,
+    ///     TooMany,
+    /// }
+    ///
+    /// impl From<SgError> for IdError {
+    ///     fn from(_: SgError) -> Self {
+    ///         IdError::TooMany
+    ///     }
+    /// }
+    ///
+    /// let ids = [3, 1, 2].iter().map(|&id| if id > 0 { Ok(id) } else { Err(IdError::Invalid) });
+    /// assert_eq!(SgSet::<u8, 3>::try_from_results(ids.clone()).unwrap().len(), 3);
+    /// assert_eq!(SgSet::<u8, 2>::try_from_results(ids).unwrap_err(), IdError::TooMany);
+    ///
+    /// let ids = [1, 0].iter().map(|&id| if id > 0 { Ok(id) } else { Err(IdError::Invalid) });
+    /// assert_eq!(SgSet::<u8, 2>::try_from_results(ids).unwrap_err(), IdError::Invalid);
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryFromIterator` trait in `core`/`std`.
+    pub fn try_from_results<I, E>(iter: I) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        E: From<SgError>,
+    {
+        let mut set = SgSet::new();
+        for item in iter {
+            set.try_insert(item?)?;
+        }
+
+        Ok(set)
+    }
+}
+
+impl<T: Ord, const N: usize, P: BalancePolicy> SgSet<T, N, P> {
+    /// Makes a new, empty `SgSet` rebalanced per `policy`, e.g. to plug in an alternative algorithm.
+    /// [`new`][SgSet::new] uses [`DefaultBalancePolicy`][crate::DefaultBalancePolicy].
+    ///
+    /// The policy is unused if feature `weight_balanced` or `avl` is enabled (rotations ignore policies).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{BalanceParams, BalancePolicy, SgSet};
+    ///
+    /// // Rebuild a subtree once it holds more than 3/4 of its parent's nodes, regardless of alpha
+    /// #[derive(Clone, Copy)]
+    /// struct ThreeQuarters;
+    ///
+    /// impl BalancePolicy for ThreeQuarters {
+    ///     fn is_scapegoat(&self, child_size: usize, parent_size: usize, _: usize, _: BalanceParams) -> bool {
+    ///         (4 * child_size) > (3 * parent_size)
+    ///     }
+    /// }
+    ///
+    /// let mut set = SgSet::<_, 100, _>::with_balance_policy(ThreeQuarters);
+    ///
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
+    /// assert!(set.iter().copied().eq(0..100));
+    /// ```
+    pub fn with_balance_policy(policy: P) -> Self {
+        SgSet {
+            bst: SgTree::with_balance_policy(policy),
+        }
+    }
+
+    /// The [original scapegoat tree paper's](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) alpha, `a`, can be chosen in the range `0.5 <= a < 1.0`.
+    /// `a` tunes how "aggressively" the data structure self-balances.
+    /// It controls the trade-off between total rebuild time and maximum height guarantees.
+    ///
+    /// * As `a` approaches `0.5`, the tree will rebalance more often. Ths means slower insertions, but faster lookups and deletions.
+    ///     * An `a` equal to `0.5` means a tree that always maintains a perfect balance (e.g."complete" binary tree, at all times).
+    ///
+    /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
+    ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range),
+    /// or if feature `weight_balanced` or `avl` is enabled (rotations ignore `a`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<isize, 10> = SgSet::new();
+    ///
+    /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// assert!(set.set_rebal_param(2.0, 3.0).is_ok());
+    /// ```
+    #[doc(alias = "rebalance")]
+    #[doc(alias = "alpha")]
+    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
+        self.bst.set_rebal_param(alpha_num, alpha_denom)
+    }
+
+    /// Fixed-point equivalent of [`set_rebal_param`][SgSet::set_rebal_param], for targets without an FPU:
+    /// alpha is `alpha_num / alpha_denom`, validated with integer arithmetic only.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range),
+    /// or if feature `weight_balanced` or `avl` is enabled (rotations ignore `a`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set: SgSet<isize, 10> = SgSet::new();
+    ///
+    /// // Set 3/4, e.g. `a = 0.75`, for cheaper insertions.
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// # {
+    /// assert!(set.set_rebalance_factor(3, 4).is_ok());
+    /// assert_eq!(set.rebal_param(), (3.0, 4.0));
+    ///
+    /// assert_eq!(set.set_rebalance_factor(1, 3), Err(SgError::RebalanceFactorOutOfRange));
+    /// assert_eq!(set.set_rebalance_factor(4, 4), Err(SgError::RebalanceFactorOutOfRange));
+    /// assert_eq!(set.set_rebalance_factor(0, 0), Err(SgError::RebalanceFactorOutOfRange));
+    /// # }
+    /// ```
+    #[doc(alias = "alpha")]
+    pub fn set_rebalance_factor(
+        &mut self,
+        alpha_num: u16,
+        alpha_denom: u16,
+    ) -> Result<(), SgError> {
+        self.bst.set_rebalance_factor(alpha_num, alpha_denom)
+    }
+
+    /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
+    /// See [the corresponding setter method][SgSet::set_rebal_param] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<isize, 10> = SgSet::new();
+    ///
+    /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// assert!(set.set_rebal_param(2.0, 3.0).is_ok());
+    ///
+    /// // Get the currently set value
+    /// assert_eq!(set.rebal_param(), (2.0, 3.0));
+    /// ```
+    #[doc(alias = "rebalance")]
+    #[doc(alias = "alpha")]
+    pub fn rebal_param(&self) -> (f32, f32) {
+        self.bst.rebal_param()
+    }
+
+    /// Replace the policy deciding when and where to rebalance, e.g. to retune a [`MaxDepthPolicy`][crate::MaxDepthPolicy].
+    /// The policy type is fixed when the set is made, see [`with_balance_policy`][SgSet::with_balance_policy].
+    ///
+    /// Returns `Err` if feature `weight_balanced` or `avl` is enabled (rotations ignore policies).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaxDepthPolicy, SgSet};
+    ///
+    /// let mut set = SgSet::<_, 100, _>::with_balance_policy(MaxDepthPolicy::new(16));
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// assert!(set.set_balance_policy(MaxDepthPolicy::new(8)).is_ok());
+    ///
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
+    /// assert!(set.iter().copied().eq(0..100));
+    /// ```
+    pub fn set_balance_policy(&mut self, policy: P) -> Result<(), SgError> {
+        self.bst.set_balance_policy(policy)
+    }
+
+    /// Enable or disable automatic scapegoat rebuilds on insertion and removal.
+    /// Enabled by default.
+    ///
+    /// Batch loaders can disable rebuilds, trading temporary imbalance (slower lookups) for ingest throughput,
+    /// then call [`rebalance`][SgSet::rebalance] once at the end.
+    /// Has no effect if feature `weight_balanced` or `avl` is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 1000>::new();
+    /// set.set_auto_rebalance(false);
+    /// assert!(!set.auto_rebalance());
+    ///
+    /// for i in 0..1000 {
+    ///     set.insert(i);
+    /// }
+    /// # #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    /// assert!(!set.is_balanced());
+    ///
+    /// set.rebalance();
+    /// assert!(set.is_balanced());
+    /// set.set_auto_rebalance(true);
+    /// ```
+    pub fn set_auto_rebalance(&mut self, enabled: bool) {
+        self.bst.set_auto_rebalance(enabled)
+    }
+
+    /// Returns `true` if automatic scapegoat rebuilds are enabled.
+    /// See [`set_auto_rebalance`][SgSet::set_auto_rebalance] for details.
+    pub fn auto_rebalance(&self) -> bool {
+        self.bst.auto_rebalance()
+    }
+
+    /// Rebuild the entire set into a perfectly balanced tree, in `O(n)` time.
+    /// Intended for use after a bulk ingest with [automatic rebuilds][SgSet::set_auto_rebalance] disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 1000> = SgSet::new();
+    /// set.set_auto_rebalance(false);
+    /// set.extend((0..1000).map(|i| i));
+    ///
+    /// set.rebalance();
+    /// assert!(set.is_balanced());
+    /// assert_eq!(set.len(), 1000);
+    /// ```
+    pub fn rebalance(&mut self) {
+        self.bst.rebalance()
+    }
+
+    /// Returns `true` if the set is alpha-height-balanced: no element is deeper than `log_(1/a)(len)`,
+    /// for [rebalance parameter][SgSet::set_rebal_param] `a`. Runs in `O(n)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<_, 1000> = (0..1000).map(|i| i).collect();
+    /// assert!(set.is_balanced());
+    /// ```
+    pub fn is_balanced(&self) -> bool {
+        self.bst.is_balanced()
+    }
+
+    /// Check internal invariants, returning a description of the first violation found:
+    ///
+    /// * Binary search tree ordering, and cached minimum/maximum.
+    /// * Arena link consistency: no cycles, no dangling indexes, no leaked nodes, free slots disjoint from reachable ones.
+    /// * Cached per-node metadata, if features `fast_rebalance` or `avl` are enabled.
+    /// * Balance: loose alpha-height-balance, per the [original paper](https://people.csail.mit.edu/rivest/pubs/GR93.pdf),
+    ///   if automatic rebuilds are on and the [balance policy][SgSet::set_balance_policy] guarantees it
+    ///   (or subtree balance, if feature `weight_balanced` or `avl` is enabled).
+    ///
+    /// Runs in `O(n)` time. Intended for fuzzing and debugging, a violation indicates a bug.
+    /// Checked against the current rebalance parameter, so call [`rebalance`][SgSet::rebalance] after making it stricter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 100> = (0..100).collect();
+    /// assert_eq!(set.validate(), Ok(()));
+    ///
+    /// set.retain(|k| k % 2 == 0);
+    /// assert_eq!(set.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        self.bst.validate()
+    }
+
+    /// Panics with a description of the first violated internal invariant, if any.
+    /// See [`validate`][SgSet::validate] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<_, 100> = (0..100).collect();
+    /// set.assert_invariants();
+    /// ```
+    pub fn assert_invariants(&self) {
+        self.bst.assert_invariants()
+    }
+
+    /// Set (or clear, if `None`) a hook called periodically during long internal operations:
+    /// full rebuilds, bulk builds, and arena compaction.
+    /// Intended for cooperative async/RTOS environments, e.g. to pet a watchdog or yield to an executor.
+    ///
+    /// The hook is a plain function pointer, so it can't borrow or capture state (use a `static`).
+    /// It's called between nodes, not re-entrantly, and must not panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::{AtomicUsize, Ordering};
+    /// use scapegoat::{SgSet, YieldHook};
+    ///
+    /// static PETS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// fn pet_watchdog() {
+    ///     PETS.fetch_add(1, Ordering::Relaxed);
+    /// }
+    ///
+    /// let mut set: SgSet<_, 100> = (0..100).collect();
+    /// set.set_yield_hook(Some(YieldHook::new(pet_watchdog, 16)));
+    ///
+    /// // Bulk removal rebuilds the whole tree
+    /// set.retain(|k| k % 2 == 0);
+    /// assert!(PETS.load(Ordering::Relaxed) > 0);
+    /// ```
+    #[cfg(feature = "yield_hook")]
+    pub fn set_yield_hook(&mut self, opt_hook: Option<YieldHook>) {
+        self.bst.set_yield_hook(opt_hook)
+    }
+
+    /// Get the hook called periodically during long internal operations, if any.
+    #[cfg(feature = "yield_hook")]
+    pub fn yield_hook(&self) -> Option<YieldHook> {
+        self.bst.yield_hook()
+    }
+
+    /// Opportunistically perform pending structural work (rebuild, arena compaction), if it fits within `budget`.
+    /// Returns `true` if no work remains pending.
+    ///
+    /// Intended to be called from a dedicated slot in a periodic loop (e.g. once per frame), so that
+    /// structural work happens there instead of inside later removals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaintenanceBudget, SgSet};
+    ///
+    /// let mut set: SgSet<_, 100> = (0..100).collect();
+    /// set.retain(|x| x % 3 == 0);
+    ///
+    /// // Too little budget to do anything
+    /// assert!(!set.maintenance(MaintenanceBudget::nodes(10)));
+    ///
+    /// // Enough budget for all pending work
+    /// assert!(set.maintenance(MaintenanceBudget::nodes(100)));
+    /// assert!(set.maintenance(MaintenanceBudget::nodes(0)));
+    /// ```
+    pub fn maintenance(&mut self, budget: MaintenanceBudget) -> bool {
+        self.bst.maintenance(budget)
+    }
+
+    /// Report arena slot usage: free slots left by removals, longest contiguous live run, and live-node spread.
+    /// Intended for deciding when [`maintenance`][SgSet::maintenance] (which compacts the arena) is worth its cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{MaintenanceBudget, SgSet};
+    ///
+    /// let mut set: SgSet<_, 100> = (0..100).collect();
+    /// set.retain(|x| x % 2 == 1);
+    /// assert_eq!(set.fragmentation().free_slots(), 50);
+    /// assert_eq!(set.fragmentation().live_spread(), 99);
+    ///
+    /// set.maintenance(MaintenanceBudget::unlimited());
+    /// assert!(set.fragmentation().is_compact());
+    /// ```
+    pub fn fragmentation(&self) -> ArenaFragmentation {
+        self.bst.fragmentation()
+    }
+
+    /// Set the placement of live nodes within the arena, applied by the next [`maintenance`][SgSet::maintenance].
+    /// Defaults to [`ArenaLayout::InOrder`], e.g. sorted by key.
+    ///
+    /// For large, read-heavy sets, [`ArenaLayout::VanEmdeBoas`] cuts cache misses per lookup.
+    /// Maintenance then rebuilds the whole tree before placing nodes, so budget for twice the length.
+    /// Mutable iteration re-sorts the arena by key, undoing any other layout until the next maintenance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{ArenaLayout, MaintenanceBudget, SgSet};
+    ///
+    /// let mut set: SgSet<_, 1000> = (0..1000).collect();
+    /// set.set_arena_layout(ArenaLayout::VanEmdeBoas);
+    /// assert_eq!(set.arena_layout(), ArenaLayout::VanEmdeBoas);
+    ///
+    /// // Rebuild plus placement
+    /// assert!(!set.maintenance(MaintenanceBudget::nodes(1000)));
+    /// assert!(set.maintenance(MaintenanceBudget::nodes(2000)));
+    ///
+    /// // Same contents, different arena order
+    /// assert_eq!(set.get(&500), Some(&500));
+    /// assert!(set.iter().copied().eq(0..1000));
+    /// ```
+    pub fn set_arena_layout(&mut self, layout: ArenaLayout) {
+        self.bst.set_arena_layout(layout)
+    }
+
+    /// Get the placement of live nodes within the arena.
+    /// See [`set_arena_layout`][SgSet::set_arena_layout] for details.
+    pub fn arena_layout(&self) -> ArenaLayout {
+        self.bst.arena_layout()
+    }
+
+    /// Total capacity, e.g. maximum number of set elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<i32, 10> = SgSet::new();
+    ///
+    /// assert!(set.capacity() == 10)
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Set a runtime limit on the number of set elements, at or below the const capacity `N`.
+    /// Once reached, insertion of a new key behaves as though stack capacity were exhausted
+    /// (e.g. [`try_insert`][SgSet::try_insert] returns `Err`, [`insert`][SgSet::insert] panics).
+    ///
+    /// Returns `Err` if `limit` exceeds [`capacity`][SgSet::capacity] or is less than the current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set = SgSet::<usize, 10>::new();
+    ///
+    /// assert!(set.set_max_len(1).is_ok());
+    /// assert_eq!(set.max_len(), 1);
+    /// assert_eq!(set.capacity(), 10);
+    ///
+    /// set.insert(1);
+    /// assert!(set.is_full());
+    /// assert_eq!(set.try_insert(2), Err(SgError::StackCapacityExceeded));
+    ///
+    /// assert_eq!(set.set_max_len(0), Err(SgError::StackCapacityExceeded));
+    /// assert_eq!(set.set_max_len(11), Err(SgError::MaximumCapacityExceeded));
+    /// ```
+    pub fn set_max_len(&mut self, limit: usize) -> Result<(), SgError> {
+        self.bst.set_max_len(limit)
+    }
+
+    /// Get the runtime limit on the number of set elements.
+    /// Equal to [`capacity`][SgSet::capacity] unless set via [`set_max_len`][SgSet::set_max_len].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<usize, 10>::new();
+    /// assert_eq!(set.max_len(), set.capacity());
+    ///
+    /// assert!(set.set_max_len(5).is_ok());
+    /// assert_eq!(set.max_len(), 5);
+    /// ```
+    pub fn max_len(&self) -> usize {
+        self.bst.max_len()
+    }
+
+    /// Fill `buf` with node counts per depth (root is depth `0`), returning the tree's height.
+    /// Depths at or beyond `buf.len()` aren't recorded, in which case the returned height exceeds `buf.len()`.
+    /// Requires feature `stats`.
+    ///
+    /// Useful for visualizing how far the tree drifts from perfect balance between rebuilds.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set: SgSet<i32, 10> = SgSet::new();
+    /// let set: SgSet<_, 10> = (0..7).collect();
+    /// let mut hist = [0; 8];
+    ///
+    /// let height = set.depth_histogram(&mut hist);
+    /// assert!(height <= hist.len());
+    /// assert_eq!(hist.iter().sum::<u32>() as usize, set.len());
+    /// assert_eq!(hist[0], 1);
     /// ```
-    pub fn new() -> Self {
-        SgSet { bst: SgTree::new() }
+    #[cfg(feature = "stats")]
+    pub fn depth_histogram(&self, buf: &mut [u32]) -> usize {
+        self.bst.depth_histogram(buf)
     }
 
-    /// The [original scapegoat tree paper's](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) alpha, `a`, can be chosen in the range `0.5 <= a < 1.0`.
-    /// `a` tunes how "aggressively" the data structure self-balances.
-    /// It controls the trade-off between total rebuild time and maximum height guarantees.
+    /// Get the number of levels in the tree, e.g. key comparisons for a worst-case lookup. Zero if empty.
+    /// Requires feature `stats`.
     ///
-    /// * As `a` approaches `0.5`, the tree will rebalance more often. Ths means slower insertions, but faster lookups and deletions.
-    ///     * An `a` equal to `0.5` means a tree that always maintains a perfect balance (e.g."complete" binary tree, at all times).
+    /// # Examples
     ///
-    /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
-    ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    /// let mut set: SgSet<_, 100> = SgSet::new();
+    /// assert_eq!(set.height(), 0);
+    ///
+    /// set.insert(1);
+    /// assert_eq!(set.height(), 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn height(&self) -> usize {
+        self.bst.height()
+    }
+
+    /// Get the sum of every element's depth (root is depth `0`).
+    /// A successful lookup averages `internal_path_length / len + 1` key comparisons.
+    /// Requires feature `stats`.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set: SgSet<isize, 10> = SgSet::new();
+    /// // Perfectly balanced: 1 root, 2 at depth 1, 4 at depth 2
+    /// let mut set: SgSet<_, 100> = (0..7).collect();
+    /// set.rebalance();
+    /// assert_eq!(set.internal_path_length(), 2 + (4 * 2));
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn internal_path_length(&self) -> usize {
+        self.bst.internal_path_length()
+    }
+
+    /// Report structural metrics in a single traversal: height, internal path length, fully occupied levels,
+    /// and slack relative to the alpha-height bound. See [`TreeShape`] for details.
+    /// Requires feature `stats`.
+    ///
+    /// Intended for reasoning about worst-case lookup latency on real data, e.g. to validate a choice of
+    /// [rebalance parameter][SgSet::set_rebal_param]. Per-level counts are available via
+    /// [`depth_histogram`][SgSet::depth_histogram].
+    ///
+    /// # Examples
     ///
-    /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
-    /// assert!(set.set_rebal_param(2.0, 3.0).is_ok());
     /// ```
-    #[doc(alias = "rebalance")]
-    #[doc(alias = "alpha")]
-    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
-        self.bst.set_rebal_param(alpha_num, alpha_denom)
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 100> = (0..15).collect();
+    /// set.rebalance();
+    /// let shape = set.shape();
+    ///
+    /// assert_eq!(shape.len(), 15);
+    /// assert_eq!(shape.height(), shape.optimal_height());
+    /// assert_eq!(shape.full_levels(), 4);
+    /// assert!(shape.balance_slack() >= 0);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn shape(&self) -> TreeShape {
+        self.bst.shape()
     }
 
-    /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
-    /// See [the corresponding setter method][SgSet::set_rebal_param] for more details.
+    /// Write the tree's structure as a [Graphviz](https://graphviz.org/) DOT digraph, e.g. for rendering with `dot -Tsvg`.
+    /// Nodes are labeled with key (`Debug` formatted) and arena index, edges with `L` or `R` for left and right child.
+    /// Requires feature `stats`.
+    ///
+    /// Intended for debugging rebalance logic, or attaching a reproducible tree shape to an issue report.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set: SgSet<isize, 10> = SgSet::new();
+    /// let mut set: SgSet<_, 10> = SgSet::new();
+    /// set.insert(2);
+    /// set.insert(1);
     ///
-    /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
-    /// assert!(set.set_rebal_param(2.0, 3.0).is_ok());
+    /// let mut dot = String::new();
+    /// set.to_dot(&mut dot).unwrap();
     ///
-    /// // Get the currently set value
-    /// assert_eq!(set.rebal_param(), (2.0, 3.0));
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("label=\"1\\n#1\""));
+    /// assert!(dot.contains("n0 -> n1 [label=\"L\"];"));
     /// ```
-    #[doc(alias = "rebalance")]
-    #[doc(alias = "alpha")]
-    pub fn rebal_param(&self) -> (f32, f32) {
-        self.bst.rebal_param()
+    #[cfg(feature = "stats")]
+    pub fn to_dot<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result
+    where
+        T: Debug,
+    {
+        self.bst.to_dot(writer)
     }
 
-    /// Total capacity, e.g. maximum number of set elements.
+    /// Render the tree's structure sideways as indented ASCII, like `cargo tree --charset ascii`.
+    /// One key (`Debug` formatted) per line, children prefixed with `L` or `R`. Empty trees render as nothing.
+    /// Requires feature `stats`.
+    ///
+    /// Intended for unit-test failure messages and interactive exploration. For larger trees, see
+    /// [`to_dot`][SgSet::to_dot].
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set: SgSet<i32, 10> = SgSet::new();
+    /// let mut set: SgSet<_, 10> = SgSet::new();
+    /// set.insert(2);
+    /// set.insert(1);
+    /// set.insert(3);
     ///
-    /// assert!(set.capacity() == 10)
+    /// assert_eq!(
+    ///     format!("{}", set.display_tree()),
+    ///     "2\n\
+    ///      |-- L: 1\n\
+    ///      `-- R: 3\n"
+    /// );
     /// ```
-    pub fn capacity(&self) -> usize {
-        self.bst.capacity()
+    #[cfg(feature = "stats")]
+    pub fn display_tree(&self) -> TreeDisplay<'_, T, (), N>
+    where
+        T: Debug,
+    {
+        self.bst.display_tree()
+    }
+
+    /// Get instrumentation counters: rebuilds, nodes touched per rebuild, maximum insertion depth,
+    /// and comparisons per lookup. See [`TreeStats`] for details.
+    /// Requires feature `telemetry`.
+    ///
+    /// Counters are updated inline, without an external profiler, e.g. to tune [`set_rebal_param`][SgSet::set_rebal_param]
+    /// for a firmware image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 100> = SgSet::new();
+    /// set.extend(0..100);
+    /// set.rebalance();
+    /// assert!(set.stats().rebuilds() > 0);
+    /// assert_eq!(set.stats().max_rebuild_nodes(), 100);
+    /// assert!(set.stats().max_rebuild_nodes() <= set.stats().rebuild_nodes());
+    ///
+    /// set.reset_stats();
+    /// set.contains(&50);
+    /// assert_eq!(set.stats().lookups(), 1);
+    /// assert!(set.stats().lookup_cmps() >= 1);
+    /// ```
+    #[cfg(feature = "telemetry")]
+    pub fn stats(&self) -> TreeStats {
+        self.bst.stats()
+    }
+
+    /// Zero all instrumentation counters, see [`stats`][SgSet::stats].
+    /// Requires feature `telemetry`.
+    #[cfg(feature = "telemetry")]
+    pub fn reset_stats(&mut self) {
+        self.bst.reset_stats()
     }
 
     /// Moves all elements from `other` into `self`, leaving `other` empty.
     ///
+    /// Both sets are merged in `O(n + m)` and `self` is rebuilt once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combined length exceeds capacity. Neither set is modified in that case.
+    /// Use [`try_append`][SgSet::try_append] to handle this without panicking.
+    ///
     /// # Examples
     ///
     /// ```
@@ -139,7 +853,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// assert!(a.contains(&4));
     /// assert!(a.contains(&5));
     /// ```
-    pub fn append(&mut self, other: &mut SgSet<T, N>)
+    pub fn append(&mut self, other: &mut SgSet<T, N, P>)
     where
         T: Ord,
     {
@@ -147,6 +861,8 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     }
 
     /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    /// If the combined length would exceed capacity, returns [`SgError::StackCapacityExceeded`]
+    /// and leaves both sets unchanged.
     ///
     /// # Examples
     ///
@@ -197,7 +913,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// // Can still replace existing pairs
     /// assert!(a.try_append(&mut d).is_ok());
     /// ```
-    pub fn try_append(&mut self, other: &mut SgSet<T, N>) -> Result<(), SgError> {
+    pub fn try_append(&mut self, other: &mut SgSet<T, N, P>) -> Result<(), SgError> {
         self.bst.try_append(&mut other.bst)
     }
 
@@ -270,67 +986,48 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
 
     /// Attempt to extend a collection with the contents of an iterator.
     ///
+    /// Elements are inserted in iteration order, stopping at the first new element that doesn't fit.
+    /// Returns an [`ExtendReport`][crate::ExtendReport] of inserted and updated (already present) counts:
+    /// `Ok` if the iterator was exhausted, `Err` if capacity stopped it early.
+    /// In the latter case, the `Err` also hands back the element that didn't fit.
+    /// Elements stored before the stop remain in the set.
+    ///
     /// # Examples
     ///
     /// ```
-    /// use core::iter::FromIterator;
-    /// use scapegoat::{SgSet, SgError};
+    /// use scapegoat::SgSet;
     ///
     /// let mut a = SgSet::<_, 2>::new();
-    /// let mut b = SgSet::<_, 3>::from_iter([1, 2, 3]);
-    /// let mut c = SgSet::<_, 2>::from_iter([1, 2]);
+    /// a.insert(1);
+    ///
+    /// let batch = [1, 2, 3];
     ///
-    /// // Too big
-    /// assert_eq!(a.try_extend(b.into_iter()), Err(SgError::StackCapacityExceeded));
+    /// // Too big, stops at `3`
+    /// let (report, rejected) = a.try_extend(batch).unwrap_err();
+    /// assert_eq!(rejected, 3);
+    /// assert_eq!(report.updated(), 1);
+    /// assert_eq!(report.inserted(), 1);
     ///
-    /// // Fits
-    /// assert!(a.try_extend(c.into_iter()).is_ok());
+    /// // Make room, then resume
+    /// a.pop_first();
+    /// let report = a.try_extend(batch[report.consumed()..].iter().copied()).unwrap();
+    /// assert_eq!(report.inserted(), 1);
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [2, 3]);
     /// ```
     ///
     /// ### Note
     ///
     /// There is no `TryExtend` trait in `core`/`std`.
-    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = T>>(
-        &mut self,
-        iter: I,
-    ) -> Result<(), SgError> {
-        // Derp :P
-        if iter.len() <= (self.capacity() - self.len()) {
-            let map: crate::SgMap<T, (), N> = iter.into_iter().map(|e| (e, ())).collect();
-            self.bst.try_extend(map.into_iter())
-        } else {
-            Err(SgError::StackCapacityExceeded)
-        }
-    }
-
-    /// Attempt conversion from an iterator.
-    /// Will fail if iterator length exceeds `u16::MAX`.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use scapegoat::{SgSet, SgError};
-    ///
-    /// const CAPACITY_1: usize = 1_000;
-    /// assert!(SgSet::<_, CAPACITY_1>::try_from_iter((0..CAPACITY_1)).is_ok());
-    ///
-    /// const CAPACITY_2: usize = (u16::MAX as usize) + 1;
-    /// assert_eq!(
-    ///     SgSet::<_, CAPACITY_2>::try_from_iter((0..CAPACITY_2)),
-    ///     Err(SgError::MaximumCapacityExceeded)
-    /// );
-    /// ```
-    ///
-    /// ### Note
     ///
-    /// There is no `TryFromIterator` trait in `core`/`std`.
-    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = T>>(
+    /// Breaking change since `2.3`: this method used to return `Result<(), SgError>`,
+    /// and only accepted an [`ExactSizeIterator`][core::iter::ExactSizeIterator].
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
         iter: I,
-    ) -> Result<Self, SgError> {
-        match iter.len() <= SgTree::<T, (), N>::max_capacity() {
-            true => Ok(SgSet::from_iter(iter)),
-            false => Err(SgError::MaximumCapacityExceeded),
-        }
+    ) -> Result<ExtendReport, (ExtendReport, T)> {
+        self.bst
+            .try_extend(iter.into_iter().map(|e| (e, ())))
+            .map_err(|(report, (e, _))| (report, e))
     }
 
     /// Gets an iterator that visits the values in the `SgSet` in ascending order.
@@ -364,71 +1061,239 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
         Iter::new(self)
     }
 
-    /// Removes a value from the set. Returns whether the value was
-    /// present in the set.
+    /// Gets a double-ended iterator over the items of the set, in ascending order, with each item's rank (e.g.
+    /// in-order index, `0` for the first item). Ranks are tracked during the walk and stay correct when reversed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set = SgSet::from(["b", "c", "a"]);
+    ///
+    /// let ranked: Vec<_> = set.iter_ranked().rev().collect();
+    /// assert_eq!(ranked, [(2, &"c"), (1, &"b"), (0, &"a")]);
+    /// ```
+    pub fn iter_ranked(&self) -> IterRanked<'_, T, N, P> {
+        IterRanked::new(self)
+    }
+
+    /// Removes a value from the set. Returns whether the value was
+    /// present in the set.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 10>::new();
+    ///
+    /// set.insert(2);
+    /// assert_eq!(set.remove(&2), true);
+    /// assert_eq!(set.remove(&2), false);
+    /// ```
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.bst.remove(value).is_some()
+    }
+
+    /// Splits the collection into two at the given value. Returns everything after the given value,
+    /// including the value.
+    /// Both halves are rebuilt once, in linear time, from the already-sorted elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a = SgSet::<_, 5>::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// a.insert(3);
+    /// a.insert(17);
+    /// a.insert(41);
+    ///
+    /// let b = a.split_off(&3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    ///
+    /// assert!(a.contains(&1));
+    /// assert!(a.contains(&2));
+    ///
+    /// assert!(b.contains(&3));
+    /// assert!(b.contains(&17));
+    /// assert!(b.contains(&41));
+    /// ```
+    pub fn split_off<Q>(&mut self, value: &Q) -> SgSet<T, N, P>
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        SgSet {
+            bst: self.bst.split_off(value),
+        }
+    }
+
+    /// Removes the `k` smallest values from the set, returning them as a new set.
+    /// If `k` exceeds the set's length, all values are removed.
+    ///
+    /// Unlike calling [`pop_first`][SgSet::pop_first] `k` times, the set is rebuilt only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::from([5, 1, 4, 2, 3]);
+    ///
+    /// let smallest = set.take_smallest(2);
+    /// assert!(smallest.into_iter().eq([1, 2]));
+    /// assert!(set.into_iter().eq([3, 4, 5]));
+    /// ```
+    pub fn take_smallest(&mut self, k: usize) -> SgSet<T, N, P> {
+        SgSet {
+            bst: self.bst.take_smallest(k),
+        }
+    }
+
+    /// Removes the `k` largest values from the set, returning them as a new set.
+    /// If `k` exceeds the set's length, all values are removed.
+    ///
+    /// Unlike calling [`pop_last`][SgSet::pop_last] `k` times, the set is rebuilt only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::from([5, 1, 4, 2, 3]);
+    ///
+    /// let largest = set.take_largest(2);
+    /// assert!(largest.into_iter().eq([4, 5]));
+    /// assert!(set.into_iter().eq([1, 2, 3]));
+    /// ```
+    pub fn take_largest(&mut self, k: usize) -> SgSet<T, N, P> {
+        SgSet {
+            bst: self.bst.take_largest(k),
+        }
+    }
+
+    /// Removes the `n` smallest values from the set, yielding them in ascending order.
+    /// If `n` exceeds the set's length, all values are removed.
+    ///
+    /// Values are removed up front, with a single rebuild, e.g. for bounded batch handoff between tasks.
+    /// This is [`take_smallest`][SgSet::take_smallest], consumed as an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = (1..=5).collect();
+    ///
+    /// assert!(set.drain_front(2).eq([1, 2]));
+    /// assert_eq!(set.first(), Some(&3));
+    /// ```
+    pub fn drain_front(&mut self, n: usize) -> IntoIter<T, N> {
+        self.take_smallest(n).into_iter()
+    }
+
+    /// Removes the `n` largest values from the set, yielding them in descending order (largest first).
+    /// If `n` exceeds the set's length, all values are removed.
+    ///
+    /// Values are removed up front, with a single rebuild, e.g. for bounded batch handoff between tasks.
+    /// This is [`take_largest`][SgSet::take_largest], consumed as a reversed iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = (1..=5).collect();
+    ///
+    /// assert!(set.drain_back(2).eq([5, 4]));
+    /// assert_eq!(set.last(), Some(&3));
+    /// ```
+    pub fn drain_back(&mut self, n: usize) -> Rev<IntoIter<T, N>> {
+        self.take_largest(n).into_iter().rev()
+    }
+
+    /// Splits the set into two at the given in-order index (rank), e.g. to partition work by count.
+    /// The set keeps its first `rank` values, the rest are returned as a new set.
+    /// If `rank` exceeds the set's length, the returned set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::from([50, 10, 40, 20, 30]);
+    ///
+    /// let rest = set.split_at_rank(2);
+    /// assert!(set.into_iter().eq([10, 20]));
+    /// assert!(rest.into_iter().eq([30, 40, 50]));
+    /// ```
+    pub fn split_at_rank(&mut self, rank: usize) -> SgSet<T, N, P> {
+        SgSet {
+            bst: self.bst.split_at_rank(rank),
+        }
+    }
+
+    /// Returns the number of values in the set less than `value`, e.g. `value`'s in-order index (rank) if present.
     ///
     /// The value may be any borrowed form of the set's value type,
     /// but the ordering on the borrowed form *must* match the
     /// ordering on the value type.
     ///
+    /// Runs in `O(log n)` with feature `fast_rebalance`, which tracks subtree sizes, and `O(n)` otherwise.
+    ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set = SgSet::<_, 10>::new();
-    ///
-    /// set.insert(2);
-    /// assert_eq!(set.remove(&2), true);
-    /// assert_eq!(set.remove(&2), false);
+    /// let set: SgSet<_, 10> = [10, 20, 30].iter().cloned().collect();
+    /// assert_eq!(set.rank(&20), 1);
+    /// assert_eq!(set.rank(&25), 2);
     /// ```
-    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    pub fn rank<Q>(&self, value: &Q) -> usize
     where
         T: Borrow<Q> + Ord,
         Q: Ord + ?Sized,
     {
-        self.bst.remove(value).is_some()
+        self.bst.rank(value)
     }
 
-    /// Splits the collection into two at the given value. Returns everything after the given value,
-    /// including the value.
+    /// Returns the value at the given in-order index (rank), e.g. `select(0)` is the first value.
+    /// Returns `None` if `rank` is out of bounds.
+    ///
+    /// Runs in `O(log n)` with feature `fast_rebalance`, which tracks subtree sizes, and `O(n)` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut a = SgSet::<_, 5>::new();
-    /// a.insert(1);
-    /// a.insert(2);
-    /// a.insert(3);
-    /// a.insert(17);
-    /// a.insert(41);
-    ///
-    /// let b = a.split_off(&3);
-    ///
-    /// assert_eq!(a.len(), 2);
-    /// assert_eq!(b.len(), 3);
-    ///
-    /// assert!(a.contains(&1));
-    /// assert!(a.contains(&2));
-    ///
-    /// assert!(b.contains(&3));
-    /// assert!(b.contains(&17));
-    /// assert!(b.contains(&41));
+    /// let set: SgSet<_, 10> = [30, 10, 20].iter().cloned().collect();
+    /// assert_eq!(set.select(0), Some(&10));
+    /// assert_eq!(set.select(2), Some(&30));
+    /// assert_eq!(set.select(3), None);
     /// ```
-    pub fn split_off<Q>(&mut self, value: &Q) -> SgSet<T, N>
-    where
-        T: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        SgSet {
-            bst: self.bst.split_off(value),
-        }
+    pub fn select(&self, rank: usize) -> Option<&T> {
+        self.bst.select(rank).map(|(k, _)| k)
     }
 
     /// Adds a value to the set, replacing the existing value, if any, that is equal to the given
     /// one. Returns the replaced value.
+    /// An equal value is swapped in place, without restructuring the tree.
     ///
     /// # Examples
     ///
@@ -446,21 +1311,184 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     where
         T: Ord,
     {
-        let removed = self.bst.remove_entry(&value).map(|(k, _)| k);
-        self.insert(value);
-        removed
+        match self.try_replace(value) {
+            Ok(opt_replaced) => opt_replaced,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
     }
 
-    // TODO: add example
     /// Attempts to add a value to the set, replacing the existing value, if any, that is equal to the given
     /// one. Returns the replaced value.
+    /// Fails only if the value is new and the set is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set = SgSet::<_, 2>::new();
+    /// assert_eq!(set.try_replace(1), Ok(None));
+    /// assert_eq!(set.try_replace(2), Ok(None));
+    /// assert_eq!(set.try_replace(2), Ok(Some(2)));
+    /// assert_eq!(set.try_replace(3), Err(SgError::StackCapacityExceeded));
+    /// ```
     pub fn try_replace(&mut self, value: T) -> Result<Option<T>, SgError>
     where
         T: Ord,
     {
-        let removed = self.bst.remove_entry(&value).map(|(k, _)| k);
-        self.try_insert(value)?;
-        Ok(removed)
+        let mut path = SgVec::new();
+        let ngh = self.bst.internal_search(&mut path, &value);
+        match ngh.node_idx() {
+            Some(idx) => {
+                let replaced = self.bst.arena.replace_key(idx, value);
+                Ok(Some(replaced))
+            }
+            None if self.bst.is_full() => Err(SgError::StackCapacityExceeded),
+            None => {
+                self.bst.internal_insert_vacant(&path, ngh, value, ());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Inserts the given value into the set if it is not present, then returns a reference to the value in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.len(), 3);
+    /// assert_eq!(set.get_or_insert(2), &2);
+    /// assert_eq!(set.get_or_insert(100), &100);
+    /// assert_eq!(set.len(), 4); // 100 was inserted
+    /// ```
+    pub fn get_or_insert(&mut self, value: T) -> &T
+    where
+        T: Ord,
+    {
+        match self.try_get_or_insert(value) {
+            Ok(value) => value,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Attempts to insert the given value into the set if it is not present, then returns a reference to the value
+    /// in the set. Fails only if the value is new and the set is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set: SgSet<_, 3> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.try_get_or_insert(2), Ok(&2));
+    /// assert_eq!(set.try_get_or_insert(4), Err(SgError::StackCapacityExceeded));
+    /// ```
+    pub fn try_get_or_insert(&mut self, value: T) -> Result<&T, SgError>
+    where
+        T: Ord,
+    {
+        let mut path = SgVec::new();
+        let ngh = self.bst.internal_search(&mut path, &value);
+        let idx = match ngh.node_idx() {
+            Some(idx) => idx,
+            None if self.bst.is_full() => return Err(SgError::StackCapacityExceeded),
+            None => self.bst.internal_insert_vacant(&path, ngh, value, ()),
+        };
+
+        Ok(self.bst.arena.key(idx))
+    }
+
+    /// Inserts a value computed from `f` into the set if the given `value` is not present,
+    /// then returns a reference to the value in the set.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set is full and the value is new, or if the value computed by `f` isn't equal to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<String, 10> = ["cat", "dog", "horse"]
+    ///     .iter()
+    ///     .map(|&pet| pet.to_owned())
+    ///     .collect();
+    ///
+    /// assert_eq!(set.len(), 3);
+    /// for &pet in &["cat", "dog", "fish"] {
+    ///     let value = set.get_or_insert_with(pet, str::to_owned);
+    ///     assert_eq!(value, pet);
+    /// }
+    /// assert_eq!(set.len(), 4); // a new "fish" was inserted
+    /// ```
+    pub fn get_or_insert_with<Q, F>(&mut self, value: &Q, f: F) -> &T
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+        F: FnOnce(&Q) -> T,
+    {
+        match self.try_get_or_insert_with(value, f) {
+            Ok(value) => value,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Attempts to insert a value computed from `f` into the set if the given `value` is not present,
+    /// then returns a reference to the value in the set. Fails only if the value is new and the set is full,
+    /// in which case `f` isn't called.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value computed by `f` isn't equal to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set: SgSet<String, 2> = SgSet::new();
+    /// assert_eq!(set.try_get_or_insert_with("cat", str::to_owned).map(|s| s.len()), Ok(3));
+    /// assert_eq!(set.try_get_or_insert_with("dog", str::to_owned).map(|s| s.len()), Ok(3));
+    /// assert_eq!(set.try_get_or_insert_with("cat", str::to_owned).map(|s| s.len()), Ok(3));
+    /// assert_eq!(
+    ///     set.try_get_or_insert_with("fish", str::to_owned),
+    ///     Err(SgError::StackCapacityExceeded)
+    /// );
+    /// ```
+    pub fn try_get_or_insert_with<Q, F>(&mut self, value: &Q, f: F) -> Result<&T, SgError>
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+        F: FnOnce(&Q) -> T,
+    {
+        let mut path = SgVec::new();
+        let ngh = self.bst.internal_search(&mut path, value);
+        let idx = match ngh.node_idx() {
+            Some(idx) => idx,
+            None if self.bst.is_full() => return Err(SgError::StackCapacityExceeded),
+            None => {
+                let new_value = f(value);
+                assert!(
+                    new_value.borrow() == value,
+                    "new value is not equal to the lookup value"
+                );
+                self.bst.internal_insert_vacant(&path, ngh, new_value, ())
+            }
+        };
+
+        Ok(self.bst.arena.key(idx))
     }
 
     /// Removes and returns the value in the set, if any, that is equal to the given one.
@@ -490,6 +1518,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     ///
     /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
     /// The elements are visited in ascending order.
+    /// Removed elements free their arena slots in place, and the tree is rebuilt at most once.
     ///
     /// # Examples
     ///
@@ -510,6 +1539,30 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
         self.bst.retain(|k, _| f(k));
     }
 
+    /// Creates an iterator that visits all values in ascending order, and lazily removes those for which
+    /// `pred` returns `true`. Values for which `pred` returns `false` are kept.
+    ///
+    /// Each removal leaves the set valid and balanced, so dropping the iterator early is safe:
+    /// unvisited values are simply kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<i32, 10> = (0..8).collect();
+    /// let evens: Vec<_> = set.extract_if(|v| v % 2 == 0).collect();
+    /// assert_eq!(evens, [0, 2, 4, 6]);
+    /// assert!(set.into_iter().eq(vec![1, 3, 5, 7]));
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, N, F, P>
+    where
+        T: Ord,
+        F: FnMut(&T) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
     /// Returns a reference to the value in the set, if any, that is equal to the given value.
     ///
     /// The value may be any borrowed form of the set's value type,
@@ -534,6 +1587,8 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     }
 
     /// Clears the set, removing all values.
+    /// Runs in constant time if the value type has no drop glue (see [`core::mem::needs_drop`]),
+    /// otherwise is linear in the number of arena slots used since the last clear.
     ///
     /// # Examples
     ///
@@ -616,6 +1671,34 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
         self.bst.pop_first().map(|(k, _)| k)
     }
 
+    /// Lazily removes and returns the first values in the set, in ascending order, for as long as `pred` holds.
+    /// Stops at the first value for which `pred` returns `false`, leaving it (and every greater value) in place.
+    /// Dropping the iterator early leaves unvisited values in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = [1, 2, 3, 4].iter().cloned().collect();
+    ///
+    /// let popped: Vec<_> = set.pop_first_while(|n| *n < 3).collect();
+    /// assert_eq!(popped, [1, 2]);
+    /// assert_eq!(set.first(), Some(&3));
+    /// ```
+    pub fn pop_first_while<F>(&mut self, pred: F) -> PopWhile<'_, T, N, F, P>
+    where
+        T: Ord,
+        F: FnMut(&T) -> bool,
+    {
+        PopWhile {
+            table: self,
+            pred,
+            from_back: false,
+            done: false,
+        }
+    }
+
     /// Returns the last/maximum value in the set, if any.
     ///
     /// # Examples
@@ -653,67 +1736,209 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// }
     /// assert!(set.is_empty());
     /// ```
-    pub fn pop_last(&mut self) -> Option<T>
+    pub fn pop_last(&mut self) -> Option<T>
+    where
+        T: Ord,
+    {
+        self.bst.pop_last().map(|(k, _)| k)
+    }
+
+    /// Lazily removes and returns the last values in the set, in descending order, for as long as `pred` holds.
+    /// Stops at the first value for which `pred` returns `false`, leaving it (and every lesser value) in place.
+    /// Dropping the iterator early leaves unvisited values in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = [1, 2, 3, 4].iter().cloned().collect();
+    ///
+    /// let popped: Vec<_> = set.pop_last_while(|n| *n > 2).collect();
+    /// assert_eq!(popped, [4, 3]);
+    /// assert_eq!(set.last(), Some(&2));
+    /// ```
+    pub fn pop_last_while<F>(&mut self, pred: F) -> PopWhile<'_, T, N, F, P>
+    where
+        T: Ord,
+        F: FnMut(&T) -> bool,
+    {
+        PopWhile {
+            table: self,
+            pred,
+            from_back: true,
+            done: false,
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut v = SgSet::<_, 10>::new();
+    /// assert_eq!(v.len(), 0);
+    /// v.insert(1);
+    /// assert_eq!(v.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the set.
+    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
+    /// yield elements from min (inclusive) to max (exclusive).
+    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
+    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
+    /// range from 4 to 10.
+    ///
+    /// Descends directly to the range's lower bound and stops past its upper bound,
+    /// so runtime is `O(log n + k)` for `k` elements in range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use core::ops::Bound::Included;
+    ///
+    /// let mut set = SgSet::<_, 5>::new();
+    /// set.insert(3);
+    /// set.insert(5);
+    /// set.insert(8);
+    /// for &elem in set.range((Included(&4), Included(&8))) {
+    ///     println!("{}", elem);
+    /// }
+    /// assert_eq!(Some(&5), set.range(4..).next());
+    /// ```
+    pub fn range<K, R>(&self, range: R) -> Range<'_, T, N, P>
+    where
+        K: Ord + ?Sized,
+        T: Borrow<K> + Ord,
+        R: RangeBounds<K>,
+    {
+        SgTree::<T, (), N>::assert_valid_range(&range);
+        Range {
+            table: self,
+            node_idx_iter: self.bst.range_search(&range).into_iter(),
+        }
+    }
+
+    /// Returns a [`Cursor`] pointing at the first value above `bound`,
+    /// or at the ghost position if there is none.
+    ///
+    /// For `Bound::Included(v)` that's the first value `>= v`, for `Bound::Excluded(v)` the first value `> v`,
+    /// and for `Bound::Unbounded` the first value. Runs in `O(log n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<_, 10> = [10, 20, 30].iter().cloned().collect();
+    ///
+    /// let mut cursor = set.lower_bound(Bound::Included(&15));
+    /// assert_eq!(cursor.get(), Some(&20));
+    /// assert_eq!(cursor.peek_prev(), Some(&10));
+    ///
+    /// cursor.move_next();
+    /// cursor.move_next();
+    /// assert_eq!(cursor.get(), None); // Ghost position
+    /// ```
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, T, N, P>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Cursor::new(self, CursorPos::lower_bound(&self.bst, bound))
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first value above `bound`,
+    /// or at the ghost position if there is none. See [`lower_bound`][SgSet::lower_bound].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = (0..8).collect();
+    ///
+    /// // Drop every other value, from 3 onward
+    /// let mut cursor = set.lower_bound_mut(Bound::Included(&3));
+    /// while cursor.remove_current().is_some() && cursor.get().is_some() {
+    ///     cursor.move_next(); // Past the last value, this would wrap around via the ghost position
+    /// }
+    ///
+    /// assert!(set.into_iter().eq([0, 1, 2, 4, 6]));
+    /// ```
+    pub fn lower_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, T, N, P>
     where
-        T: Ord,
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
     {
-        self.bst.pop_last().map(|(k, _)| k)
+        let pos = CursorPos::lower_bound(&self.bst, bound);
+        CursorMut::new(self, pos)
     }
 
-    /// Returns the number of elements in the set.
+    /// Returns a [`Cursor`] pointing at the last value below `bound`,
+    /// or at the ghost position if there is none.
+    ///
+    /// For `Bound::Included(v)` that's the last value `<= v`, for `Bound::Excluded(v)` the last value `< v`,
+    /// and for `Bound::Unbounded` the last value. Runs in `O(log n)`.
     ///
     /// # Examples
     ///
     /// ```
+    /// use core::ops::Bound;
     /// use scapegoat::SgSet;
     ///
-    /// let mut v = SgSet::<_, 10>::new();
-    /// assert_eq!(v.len(), 0);
-    /// v.insert(1);
-    /// assert_eq!(v.len(), 1);
+    /// let set: SgSet<_, 10> = [10, 20, 30].iter().cloned().collect();
+    ///
+    /// let mut cursor = set.upper_bound(Bound::Excluded(&20));
+    /// assert_eq!(cursor.get(), Some(&10));
+    /// assert_eq!(cursor.peek_next(), Some(&20));
     /// ```
-    pub fn len(&self) -> usize {
-        self.bst.len()
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, T, N, P>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Cursor::new(self, CursorPos::upper_bound(&self.bst, bound))
     }
 
-    /// Constructs a double-ended iterator over a sub-range of elements in the set.
-    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
-    /// yield elements from min (inclusive) to max (exclusive).
-    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
-    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
-    /// range from 4 to 10.
-    ///
-    /// # Panics
-    ///
-    /// Panics if range `start > end`.
-    /// Panics if range `start == end` and both bounds are `Excluded`.
+    /// Returns a [`CursorMut`] pointing at the last value below `bound`,
+    /// or at the ghost position if there is none. See [`upper_bound`][SgSet::upper_bound].
     ///
     /// # Examples
     ///
     /// ```
+    /// use core::ops::Bound;
     /// use scapegoat::SgSet;
-    /// use core::ops::Bound::Included;
     ///
-    /// let mut set = SgSet::<_, 5>::new();
-    /// set.insert(3);
-    /// set.insert(5);
-    /// set.insert(8);
-    /// for &elem in set.range((Included(&4), Included(&8))) {
-    ///     println!("{}", elem);
-    /// }
-    /// assert_eq!(Some(&5), set.range(4..).next());
+    /// let mut set: SgSet<_, 10> = [10, 30].iter().cloned().collect();
+    ///
+    /// let mut cursor = set.upper_bound_mut(Bound::Unbounded);
+    /// assert_eq!(cursor.get(), Some(&30));
+    /// assert!(cursor.insert_before(20).is_ok());
+    /// assert!(cursor.insert_before(5).is_err()); // Out of order
+    ///
+    /// assert!(set.into_iter().eq([10, 20, 30]));
     /// ```
-    pub fn range<K, R>(&self, range: R) -> Range<'_, T, N>
+    pub fn upper_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, T, N, P>
     where
-        K: Ord + ?Sized,
-        T: Borrow<K> + Ord,
-        R: RangeBounds<K>,
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
     {
-        SgTree::<T, (), N>::assert_valid_range(&range);
-        Range {
-            table: self,
-            node_idx_iter: self.bst.range_search(&range).into_iter(),
-        }
+        let pos = CursorPos::upper_bound(&self.bst, bound);
+        CursorMut::new(self, pos)
     }
 
     /// Returns an iterator over values representing set difference, e.g., values in `self` but not in `other`, in ascending order.
@@ -734,7 +1959,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let diff: Vec<_> = a.difference(&b).cloned().collect();
     /// assert_eq!(diff, [1]);
     /// ```
-    pub fn difference(&self, other: &SgSet<T, N>) -> Difference<T, N>
+    pub fn difference<'a>(&'a self, other: &'a SgSet<T, N, P>) -> Difference<'a, T, N>
     where
         T: Ord,
     {
@@ -759,15 +1984,10 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let sym_diff: Vec<_> = a.symmetric_difference(&b).cloned().collect();
     /// assert_eq!(sym_diff, [1, 3]);
     /// ```
-    ///
-    /// ### Warning
-    ///
-    /// At present, this function may panic if set capacity `N` exceeds `2048`.
-    /// The issue is that this function's returned iterator needs to be `2 * N` long to support disjoint sets,
-    /// but without unstable `feature(generic_const_exprs)` we can't compute `2 * N`.
-    /// So we use `4096` instead of `2 * N` as a workaround, hence `N` should be `<= 2048` to ensure no panic.
-    /// An `N > 2048` may or may not panic, depending on the size of sets' intersection.
-    pub fn symmetric_difference<'a>(&'a self, other: &'a SgSet<T, N>) -> SymmetricDifference<T, N>
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a SgSet<T, N, P>,
+    ) -> SymmetricDifference<'a, T, N>
     where
         T: Ord,
     {
@@ -792,7 +2012,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let intersection: Vec<_> = a.intersection(&b).cloned().collect();
     /// assert_eq!(intersection, [2]);
     /// ```
-    pub fn intersection(&self, other: &SgSet<T, N>) -> Intersection<T, N>
+    pub fn intersection<'a>(&'a self, other: &'a SgSet<T, N, P>) -> Intersection<'a, T, N>
     where
         T: Ord,
     {
@@ -815,21 +2035,114 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let union: Vec<_> = a.union(&b).cloned().collect();
     /// assert_eq!(union, [1, 2]);
     /// ```
-    ///
-    /// ### Warning
-    ///
-    /// At present, this function may panic if set capacity `N` exceeds `2048`.
-    /// The issue is that this function's returned iterator needs to be `2 * N` long to support disjoint sets,
-    /// but without unstable `feature(generic_const_exprs)` we can't compute `2 * N`.
-    /// So we use `4096` instead of `2 * N` as a workaround, hence `N` should be `<= 2048` to ensure no panic.
-    /// An `N > 2048` may or may not panic, depending on the size of sets' intersection.
-    pub fn union<'a>(&'a self, other: &'a SgSet<T, N>) -> Union<T, N>
+    pub fn union<'a>(&'a self, other: &'a SgSet<T, N, P>) -> Union<'a, T, N>
     where
         T: Ord,
     {
         Union::new(self, other)
     }
 
+    /// Lazily visits the values in both `self` and a sorted external iterator, in ascending order.
+    /// The other side is never collected, e.g. records can be streamed from flash one at a time.
+    /// Iteration stops as soon as either side is exhausted.
+    ///
+    /// `iter` must yield values in strictly ascending order, otherwise the output is unspecified.
+    /// Yields [`Merged::Both`][crate::set_types::Merged::Both], holding the set's value and the external one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use scapegoat::set_types::Merged;
+    ///
+    /// let set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    /// let streamed = [2, 3, 4].into_iter();
+    ///
+    /// let both: Vec<_> = set.intersection_with_iter(streamed).map(|m| *m.get()).collect();
+    /// assert_eq!(both, [2, 3]);
+    /// assert_eq!(set.intersection_with_iter([3]).next(), Some(Merged::Both(&3, 3)));
+    /// ```
+    pub fn intersection_with_iter<I>(&self, iter: I) -> MergeWithIter<'_, T, N, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        MergeWithIter::intersection(self, iter.into_iter())
+    }
+
+    /// Lazily visits the values in `self` but not in a sorted external iterator, in ascending order.
+    /// The other side is never collected, and is only consumed as far as the set's largest value.
+    ///
+    /// `iter` must yield values in strictly ascending order, otherwise the output is unspecified.
+    /// Yields [`Merged::Set`][crate::set_types::Merged::Set].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    ///
+    /// let diff: Vec<_> = set.difference_with_iter(2..).map(|m| *m.get()).collect();
+    /// assert_eq!(diff, [1]);
+    /// ```
+    pub fn difference_with_iter<I>(&self, iter: I) -> MergeWithIter<'_, T, N, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        MergeWithIter::difference(self, iter.into_iter())
+    }
+
+    /// Lazily visits the values in `self` or a sorted external iterator, but not both, in ascending order.
+    /// The other side is never collected.
+    ///
+    /// `iter` must yield values in strictly ascending order, otherwise the output is unspecified.
+    /// Yields [`Merged::Set`][crate::set_types::Merged::Set] and [`Merged::Iter`][crate::set_types::Merged::Iter].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use scapegoat::set_types::Merged;
+    ///
+    /// let set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    ///
+    /// let sym_diff: Vec<_> = set.symmetric_difference_with_iter([2, 4]).collect();
+    /// assert_eq!(sym_diff, [Merged::Set(&1), Merged::Set(&3), Merged::Iter(4)]);
+    /// ```
+    pub fn symmetric_difference_with_iter<I>(&self, iter: I) -> MergeWithIter<'_, T, N, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        MergeWithIter::symmetric_difference(self, iter.into_iter())
+    }
+
+    /// Lazily visits the values in `self` or a sorted external iterator, without duplicates, in ascending order.
+    /// The other side is never collected, so unlike [`union`][SgSet::union] output isn't bounded by capacity.
+    ///
+    /// `iter` must yield values in strictly ascending order, otherwise the output is unspecified.
+    /// Yields every [`Merged`][crate::set_types::Merged] variant, recording where each value came from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<_, 10> = [1, 3, 5].iter().cloned().collect();
+    ///
+    /// let union: Vec<_> = set.union_with_iter(2..=4).map(|m| *m.get()).collect();
+    /// assert_eq!(union, [1, 2, 3, 4, 5]);
+    /// ```
+    pub fn union_with_iter<I>(&self, iter: I) -> MergeWithIter<'_, T, N, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        MergeWithIter::union(self, iter.into_iter())
+    }
+
     /// Returns `true` if the set contains no elements.
     ///
     /// # Examples
@@ -878,11 +2191,12 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// b.insert(1);
     /// assert_eq!(a.is_disjoint(&b), false);
     /// ```
-    pub fn is_disjoint(&self, other: &SgSet<T, N>) -> bool
+    pub fn is_disjoint(&self, other: &SgSet<T, N, P>) -> bool
     where
         T: Ord,
     {
-        self.intersection(other).count() == 0
+        // Ordered walk, stops at the first common value
+        self.intersection(other).next().is_none()
     }
 
     /// Returns `true` if `self` is a subset of `other`, e.g., `other` contains at least all the values in `self`.
@@ -901,11 +2215,16 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// set.insert(4);
     /// assert_eq!(set.is_subset(&sup), false);
     /// ```
-    pub fn is_subset(&self, other: &SgSet<T, N>) -> bool
+    pub fn is_subset(&self, other: &SgSet<T, N, P>) -> bool
     where
         T: Ord,
     {
-        self.intersection(other).count() == self.len()
+        if self.len() > other.len() {
+            return false;
+        }
+
+        // Ordered walk, stops at the first value missing from `other`
+        self.difference(other).next().is_none()
     }
 
     /// Returns `true` if `self` is a superset of `other`, e.g., `self` contains at least all the values in `other`.
@@ -927,20 +2246,165 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// set.insert(2);
     /// assert_eq!(set.is_superset(&sub), true);
     /// ```
-    pub fn is_superset(&self, other: &SgSet<T, N>) -> bool
+    pub fn is_superset(&self, other: &SgSet<T, N, P>) -> bool
     where
         T: Ord,
     {
         other.is_subset(self)
     }
+
+    /// Returns the number of values in both `self` and `other`, e.g. the length of their intersection.
+    ///
+    /// Computed with a single simultaneous in-order walk of both sets, the intersection isn't materialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let a = SgSet::from([1, 2, 3, 4]);
+    /// let b = SgSet::from([3, 4, 5, 6]);
+    ///
+    /// assert_eq!(a.overlap_count(&b), 2);
+    /// assert_eq!(a.overlap_count(&SgSet::new()), 0);
+    /// ```
+    pub fn overlap_count(&self, other: &SgSet<T, N, P>) -> usize
+    where
+        T: Ord,
+    {
+        let mut self_iter = self.iter();
+        let mut other_iter = other.iter();
+
+        let mut opt_self = self_iter.next();
+        let mut opt_other = other_iter.next();
+
+        let mut cnt = 0;
+
+        // If either is exhausted, short-circuit.
+        while let (Some(self_val), Some(other_val)) = (opt_self, opt_other) {
+            match self_val.cmp(other_val) {
+                Ordering::Less => {
+                    opt_self = self_iter.next();
+                }
+                Ordering::Equal => {
+                    cnt += 1;
+                    opt_self = self_iter.next();
+                    opt_other = other_iter.next();
+                }
+                Ordering::Greater => {
+                    opt_other = other_iter.next();
+                }
+            }
+        }
+
+        cnt
+    }
+
+    /// Returns the [Jaccard index](https://en.wikipedia.org/wiki/Jaccard_index) of `self` and `other`
+    /// as an integer ratio, a tuple of `(intersection_len, union_len)`.
+    /// Useful on targets without hardware floating point.
+    ///
+    /// Two empty sets are considered identical, their ratio is `(0, 0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let a = SgSet::from([1, 2, 3, 4]);
+    /// let b = SgSet::from([3, 4, 5, 6]);
+    ///
+    /// assert_eq!(a.jaccard_ratio(&b), (2, 6));
+    /// ```
+    pub fn jaccard_ratio(&self, other: &SgSet<T, N, P>) -> (usize, usize)
+    where
+        T: Ord,
+    {
+        let overlap = self.overlap_count(other);
+        (overlap, self.len() + other.len() - overlap)
+    }
+
+    /// Returns the [Jaccard similarity](https://en.wikipedia.org/wiki/Jaccard_index) of `self` and `other`,
+    /// in the range `0.0..=1.0`. `1.0` means the sets are equal, `0.0` means they are disjoint.
+    ///
+    /// Two empty sets are considered identical, their similarity is `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let a = SgSet::from([1, 2, 3, 4]);
+    /// let b = SgSet::from([3, 4, 5, 6]);
+    ///
+    /// assert!((a.jaccard_similarity(&b) - (1.0 / 3.0)).abs() < f32::EPSILON);
+    /// assert_eq!(a.jaccard_similarity(&a.clone()), 1.0);
+    /// ```
+    pub fn jaccard_similarity(&self, other: &SgSet<T, N, P>) -> f32
+    where
+        T: Ord,
+    {
+        match self.jaccard_ratio(other) {
+            (_, 0) => 1.0,
+            (inter_len, union_len) => inter_len as f32 / union_len as f32,
+        }
+    }
+
+    /// Returns the number of values in `self` but not in `other`, e.g. the length of their difference.
+    ///
+    /// Computed with a single simultaneous in-order walk of both sets, the difference isn't materialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let a = SgSet::from([1, 2, 3, 4]);
+    /// let b = SgSet::from([3, 4, 5, 6]);
+    ///
+    /// assert_eq!(a.difference_len(&b), a.difference(&b).count());
+    /// assert_eq!(a.difference_len(&b), 2);
+    /// ```
+    pub fn difference_len(&self, other: &SgSet<T, N, P>) -> usize
+    where
+        T: Ord,
+    {
+        self.len() - self.overlap_count(other)
+    }
+
+    /// Returns the number of values in `self` or `other` but not both, e.g. the length of their
+    /// symmetric difference.
+    ///
+    /// Computed with a single simultaneous in-order walk of both sets, the symmetric difference isn't materialized.
+    /// Unlike [`symmetric_difference`][SgSet::symmetric_difference], this function can't panic for large `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let a = SgSet::from([1, 2, 3, 4]);
+    /// let b = SgSet::from([3, 4, 5, 6]);
+    ///
+    /// assert_eq!(a.symmetric_difference_len(&b), a.symmetric_difference(&b).count());
+    /// assert_eq!(a.symmetric_difference_len(&b), 4);
+    /// ```
+    pub fn symmetric_difference_len(&self, other: &SgSet<T, N, P>) -> usize
+    where
+        T: Ord,
+    {
+        let overlap = self.overlap_count(other);
+        (self.len() - overlap) + (other.len() - overlap)
+    }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 // Debug
-impl<T, const N: usize> Debug for SgSet<T, N>
+impl<T, const N: usize, P> Debug for SgSet<T, N, P>
 where
-    T: Ord + Default + Debug,
+    T: Ord + Debug,
+    P: BalancePolicy,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set()
@@ -949,10 +2413,23 @@ where
     }
 }
 
+// Default
+impl<T, const N: usize, P> Default for SgSet<T, N, P>
+where
+    T: Ord,
+    P: BalancePolicy + Default,
+{
+    fn default() -> Self {
+        SgSet {
+            bst: SgTree::default(),
+        }
+    }
+}
+
 // From array.
 impl<T, const N: usize> From<[T; N]> for SgSet<T, N>
 where
-    T: Ord + Default,
+    T: Ord,
 {
     /// ```
     /// use scapegoat::SgSet;
@@ -975,22 +2452,64 @@ where
     }
 }
 
+// From unit-valued map, structure-preserving.
+impl<T, const N: usize, P> From<SgMap<T, (), N, P>> for SgSet<T, N, P>
+where
+    T: Ord,
+    P: BalancePolicy,
+{
+    /// Reuses the map's tree as-is, no reinsertion or rebalancing.
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgSet};
+    ///
+    /// let map = SgMap::from([(1, ()), (2, ()), (3, ())]);
+    /// let set = SgSet::from(map);
+    /// assert_eq!(set, SgSet::from([1, 2, 3]));
+    /// ```
+    fn from(map: SgMap<T, (), N, P>) -> Self {
+        SgSet { bst: map.bst }
+    }
+}
+
+// Into unit-valued map, structure-preserving.
+impl<T, const N: usize, P> From<SgSet<T, N, P>> for SgMap<T, (), N, P>
+where
+    T: Ord,
+    P: BalancePolicy,
+{
+    /// Reuses the set's tree as-is, no reinsertion or rebalancing.
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgSet};
+    ///
+    /// let set = SgSet::from([1, 2, 3]);
+    /// let map: SgMap<_, (), 3> = set.into();
+    /// assert_eq!(map.keys().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    fn from(set: SgSet<T, N, P>) -> Self {
+        SgMap { bst: set.bst }
+    }
+}
+
 // Construct from iterator.
-impl<T, const N: usize> FromIterator<T> for SgSet<T, N>
+impl<T, const N: usize, P> FromIterator<T> for SgSet<T, N, P>
 where
-    T: Ord + Default,
+    T: Ord,
+    P: BalancePolicy + Default,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut sgs = SgSet::new();
-        sgs.bst = SgTree::from_iter(iter.into_iter().map(|e| (e, ())));
-        sgs
+        SgSet {
+            bst: SgTree::from_iter(iter.into_iter().map(|e| (e, ()))),
+        }
     }
 }
 
 // Extension from iterator.
-impl<T, const N: usize> Extend<T> for SgSet<T, N>
+impl<T, const N: usize, P> Extend<T> for SgSet<T, N, P>
 where
-    T: Ord + Default,
+    T: Ord,
+    P: BalancePolicy,
 {
     fn extend<TreeIter: IntoIterator<Item = T>>(&mut self, iter: TreeIter) {
         self.bst.extend(iter.into_iter().map(|e| (e, ())));
@@ -998,19 +2517,72 @@ where
 }
 
 // Extension from reference iterator.
-impl<'a, T, const N: usize> Extend<&'a T> for SgSet<T, N>
+impl<'a, T, const N: usize, P> Extend<&'a T> for SgSet<T, N, P>
 where
-    T: 'a + Ord + Default + Copy,
+    T: 'a + Ord + Copy,
+    P: BalancePolicy,
 {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
     }
 }
 
+// PartialEq
+impl<T, const N: usize, P> PartialEq for SgSet<T, N, P>
+where
+    T: Ord,
+    P: BalancePolicy,
+{
+    fn eq(&self, other: &SgSet<T, N, P>) -> bool {
+        self.bst == other.bst
+    }
+}
+
+// Eq
+impl<T, const N: usize, P> Eq for SgSet<T, N, P>
+where
+    T: Ord,
+    P: BalancePolicy,
+{
+}
+
+// PartialOrd
+impl<T, const N: usize, P> PartialOrd for SgSet<T, N, P>
+where
+    T: Ord,
+    P: BalancePolicy,
+{
+    fn partial_cmp(&self, other: &SgSet<T, N, P>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Ord
+impl<T, const N: usize, P> Ord for SgSet<T, N, P>
+where
+    T: Ord,
+    P: BalancePolicy,
+{
+    fn cmp(&self, other: &SgSet<T, N, P>) -> Ordering {
+        self.bst.cmp(&other.bst)
+    }
+}
+
+// Hash
+impl<T, const N: usize, P> Hash for SgSet<T, N, P>
+where
+    T: Ord + Hash,
+    P: BalancePolicy,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bst.hash(state);
+    }
+}
+
 // General Iterators ---------------------------------------------------------------------------------------------------
 
 // Reference iterator
-impl<'a, T: Ord + Default, const N: usize> IntoIterator for &'a SgSet<T, N> {
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> IntoIterator for &'a SgSet<T, N, P> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T, N>;
 
@@ -1020,7 +2592,7 @@ impl<'a, T: Ord + Default, const N: usize> IntoIterator for &'a SgSet<T, N> {
 }
 
 // Consuming iterator
-impl<T: Ord + Default, const N: usize> IntoIterator for SgSet<T, N> {
+impl<T: Ord, const N: usize, P: BalancePolicy> IntoIterator for SgSet<T, N, P> {
     type Item = T;
     type IntoIter = IntoIter<T, N>;
 
@@ -1031,7 +2603,7 @@ impl<T: Ord + Default, const N: usize> IntoIterator for SgSet<T, N> {
 
 // Operator Overloading ------------------------------------------------------------------------------------------------
 
-impl<T: Ord + Default + Clone, const N: usize> Sub<&SgSet<T, N>> for &SgSet<T, N> {
+impl<T: Ord + Clone, const N: usize> Sub<&SgSet<T, N>> for &SgSet<T, N> {
     type Output = SgSet<T, N>;
 
     /// Returns the difference of `self` and `rhs` as a new `SgSet<T, N>`.
@@ -1049,11 +2621,13 @@ impl<T: Ord + Default + Clone, const N: usize> Sub<&SgSet<T, N>> for &SgSet<T, N
     /// assert_eq!(result_vec, [1, 2]);
     /// ```
     fn sub(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.difference(rhs).cloned().collect()
+        // At most `self.len()` values, can't exceed capacity
+        SgSet::from_sorted_iter(self.difference(rhs).cloned())
+            .expect("Stack-storage capacity exceeded!")
     }
 }
 
-impl<T: Ord + Default + Clone, const N: usize> BitAnd<&SgSet<T, N>> for &SgSet<T, N> {
+impl<T: Ord + Clone, const N: usize> BitAnd<&SgSet<T, N>> for &SgSet<T, N> {
     type Output = SgSet<T, N>;
 
     /// Returns the intersection of `self` and `rhs` as a new `SgSet<T, N>`.
@@ -1071,11 +2645,13 @@ impl<T: Ord + Default + Clone, const N: usize> BitAnd<&SgSet<T, N>> for &SgSet<T
     /// assert_eq!(result_vec, [2, 3]);
     /// ```
     fn bitand(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.intersection(rhs).cloned().collect()
+        // At most `self.len()` values, can't exceed capacity
+        SgSet::from_sorted_iter(self.intersection(rhs).cloned())
+            .expect("Stack-storage capacity exceeded!")
     }
 }
 
-impl<T: Ord + Default + Clone, const N: usize> BitOr<&SgSet<T, N>> for &SgSet<T, N> {
+impl<T: Ord + Clone, const N: usize> BitOr<&SgSet<T, N>> for &SgSet<T, N> {
     type Output = SgSet<T, N>;
 
     /// Returns the union of `self` and `rhs` as a new `SgSet<T, N>`.
@@ -1092,12 +2668,27 @@ impl<T: Ord + Default + Clone, const N: usize> BitOr<&SgSet<T, N>> for &SgSet<T,
     /// let result_vec: Vec<_> = result.into_iter().collect();
     /// assert_eq!(result_vec, [1, 2, 3, 4, 5]);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the union has more than `N` values.
+    /// For a fallible alternative, build the result with [`from_sorted_iter`][SgSet::from_sorted_iter]:
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let a: SgSet<_, 3> = vec![1, 2, 3].into_iter().collect();
+    /// let b: SgSet<_, 3> = vec![4, 5, 6].into_iter().collect();
+    ///
+    /// let result = SgSet::<_, 3>::from_sorted_iter(a.union(&b).cloned());
+    /// assert_eq!(result, Err(SgError::StackCapacityExceeded));
+    /// ```
     fn bitor(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.union(rhs).cloned().collect()
+        SgSet::from_sorted_iter(self.union(rhs).cloned()).expect("Stack-storage capacity exceeded!")
     }
 }
 
-impl<T: Ord + Default + Clone, const N: usize> BitXor<&SgSet<T, N>> for &SgSet<T, N> {
+impl<T: Ord + Clone, const N: usize> BitXor<&SgSet<T, N>> for &SgSet<T, N> {
     type Output = SgSet<T, N>;
 
     /// Returns the symmetric difference of `self` and `rhs` as a new `SgSet<T, N>`.
@@ -1114,7 +2705,23 @@ impl<T: Ord + Default + Clone, const N: usize> BitXor<&SgSet<T, N>> for &SgSet<T
     /// let result_vec: Vec<_> = result.into_iter().collect();
     /// assert_eq!(result_vec, [1, 4]);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symmetric difference has more than `N` values.
+    /// For a fallible alternative, build the result with [`from_sorted_iter`][SgSet::from_sorted_iter]:
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let a: SgSet<_, 3> = vec![1, 2, 3].into_iter().collect();
+    /// let b: SgSet<_, 3> = vec![4, 5, 6].into_iter().collect();
+    ///
+    /// let result = SgSet::<_, 3>::from_sorted_iter(a.symmetric_difference(&b).cloned());
+    /// assert_eq!(result, Err(SgError::StackCapacityExceeded));
+    /// ```
     fn bitxor(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.symmetric_difference(rhs).cloned().collect()
+        SgSet::from_sorted_iter(self.symmetric_difference(rhs).cloned())
+            .expect("Stack-storage capacity exceeded!")
     }
 }
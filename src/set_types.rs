@@ -1,11 +1,13 @@
+use core::borrow::Borrow;
 use core::cmp::Ordering;
-use core::iter::FusedIterator;
+use core::fmt::{self, Debug};
+use core::iter::{FusedIterator, Peekable};
 
 use crate::set::SgSet;
-use crate::tree::{Idx, IntoIter as TreeIntoIter, Iter as TreeIter, SmallNode};
-
-use smallnum::SmallUnsigned;
-use tinyvec::{ArrayVec, ArrayVecIterator};
+use crate::tree::{
+    BalancePolicy, CursorPos, DefaultBalancePolicy, IntoIter as TreeIntoIter, Iter as TreeIter,
+    NodeArena, SgError, SgVec,
+};
 
 // General Iterators ---------------------------------------------------------------------------------------------------
 
@@ -13,20 +15,20 @@ use tinyvec::{ArrayVec, ArrayVecIterator};
 ///
 /// This `struct` is created by the [`iter`][crate::set::SgSet::iter] method on [`SgSet`][crate::set::SgSet].
 /// See its documentation for more.
-pub struct Iter<'a, T: Ord + Default, const N: usize> {
+pub struct Iter<'a, T: Ord, const N: usize> {
     ref_iter: TreeIter<'a, T, (), N>,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iter<'a, T, N> {
+impl<'a, T: Ord, const N: usize> Iter<'a, T, N> {
     /// Construct reference iterator.
-    pub(crate) fn new(set: &'a SgSet<T, N>) -> Self {
+    pub(crate) fn new<P: BalancePolicy>(set: &'a SgSet<T, N, P>) -> Self {
         Iter {
             ref_iter: TreeIter::new(&set.bst),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Iter<'a, T, N> {
+impl<'a, T: Ord, const N: usize> Iterator for Iter<'a, T, N> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -34,32 +36,98 @@ impl<'a, T: Ord + Default, const N: usize> Iterator for Iter<'a, T, N> {
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Iter<'a, T, N> {
+impl<'a, T: Ord, const N: usize> ExactSizeIterator for Iter<'a, T, N> {
     fn len(&self) -> usize {
         self.ref_iter.len()
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Iter<'a, T, N> {}
+impl<'a, T: Ord, const N: usize> DoubleEndedIterator for Iter<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord, const N: usize> FusedIterator for Iter<'a, T, N> {}
+
+/// An iterator over the items of a [`SgSet`][crate::set::SgSet], with each item's rank (in-order index).
+///
+/// This `struct` is created by the [`iter_ranked`][crate::set::SgSet::iter_ranked] method on
+/// [`SgSet`][crate::set::SgSet]. See its documentation for more.
+pub struct IterRanked<'a, T: Ord, const N: usize, P = DefaultBalancePolicy> {
+    table: &'a SgSet<T, N, P>,
+    node_idx_iter: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
+    front_rank: usize,
+    back_rank: usize,
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> IterRanked<'a, T, N, P> {
+    /// Construct ranked reference iterator.
+    pub(crate) fn new(set: &'a SgSet<T, N, P>) -> Self {
+        let sorted_idxs = match set.bst.opt_root_idx {
+            Some(root_idx) => set.bst.flatten_subtree_to_sorted_idxs(root_idx),
+            None => SgVec::new(),
+        };
+
+        IterRanked {
+            table: set,
+            front_rank: 0,
+            back_rank: sorted_idxs.len(),
+            node_idx_iter: sorted_idxs.into_iter(),
+        }
+    }
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> Iterator for IterRanked<'a, T, N, P> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_idx = self.node_idx_iter.next()?;
+        let rank = self.front_rank;
+        self.front_rank += 1;
+        Some((rank, self.table.bst.arena.key(node_idx)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> DoubleEndedIterator for IterRanked<'a, T, N, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node_idx = self.node_idx_iter.next_back()?;
+        self.back_rank -= 1;
+        Some((self.back_rank, self.table.bst.arena.key(node_idx)))
+    }
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> ExactSizeIterator for IterRanked<'a, T, N, P> {
+    fn len(&self) -> usize {
+        self.back_rank - self.front_rank
+    }
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> FusedIterator for IterRanked<'a, T, N, P> {}
 
 /// An owning iterator over the items of a [`SgSet`][crate::set::SgSet].
 ///
 /// This `struct` is created by the [`into_iter`][crate::set::SgSet::into_iter] method on [`SgSet`][crate::set::SgSet]
 /// (provided by the IntoIterator trait). See its documentation for more.
-pub struct IntoIter<T: Ord + Default, const N: usize> {
+pub struct IntoIter<T: Ord, const N: usize> {
     cons_iter: TreeIntoIter<T, (), N>,
 }
 
-impl<T: Ord + Default, const N: usize> IntoIter<T, N> {
+impl<T: Ord, const N: usize> IntoIter<T, N> {
     /// Construct owning iterator.
-    pub(crate) fn new(set: SgSet<T, N>) -> Self {
+    pub(crate) fn new<P: BalancePolicy>(set: SgSet<T, N, P>) -> Self {
         IntoIter {
             cons_iter: TreeIntoIter::new(set.bst),
         }
     }
 }
 
-impl<T: Ord + Default, const N: usize> Iterator for IntoIter<T, N> {
+impl<T: Ord, const N: usize> Iterator for IntoIter<T, N> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -67,350 +135,614 @@ impl<T: Ord + Default, const N: usize> Iterator for IntoIter<T, N> {
     }
 }
 
-impl<T: Ord + Default, const N: usize> ExactSizeIterator for IntoIter<T, N> {
+impl<T: Ord, const N: usize> ExactSizeIterator for IntoIter<T, N> {
     fn len(&self) -> usize {
         self.cons_iter.len()
     }
 }
 
-impl<T: Ord + Default, const N: usize> FusedIterator for IntoIter<T, N> {}
+impl<T: Ord, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cons_iter.next_back().map(|(k, _)| k)
+    }
+}
 
-/*
-Workaround Note:
+impl<T: Ord, const N: usize> FusedIterator for IntoIter<T, N> {}
 
-The remaining iterators in this file only store indexes into the input set(s) iterator(s) and have to
-recover set elements with `set.iter().nth(idx)`. Rather inefficient, solves a blocking problem:
-in `ArrayVecIterator<[&'a T; N]>` `Default` is not implemented for `&'a T`.
+// Set Operation Iterators ---------------------------------------------------------------------------------------------
 
-TODO: faster solution?
-*/
+// Double-ended peeking over a set's items.
+// When the inner iterator runs dry, each end falls back to the item buffered at the opposite end.
+struct DoublePeek<'a, T: Ord, const N: usize> {
+    iter: Iter<'a, T, N>,
+    front: Option<&'a T>,
+    back: Option<&'a T>,
+}
 
-// TODO: without `feature(generic_const_exprs)`, `Union` and `SymmetricDifference` cannot compute `2 * N` length
-// iterator to support disjoint sets. This is a temporary workaround, documented in external API docs.
-const PLACEHOLDER_2N: usize = 4096;
+impl<'a, T: Ord, const N: usize> DoublePeek<'a, T, N> {
+    fn new<P: BalancePolicy>(set: &'a SgSet<T, N, P>) -> Self {
+        DoublePeek {
+            iter: set.iter(),
+            front: None,
+            back: None,
+        }
+    }
 
-// Intersection Iterator -----------------------------------------------------------------------------------------------
+    fn peek(&mut self, from_back: bool) -> Option<&'a T> {
+        match from_back {
+            false => {
+                if self.front.is_none() {
+                    self.front = self.iter.next().or_else(|| self.back.take());
+                }
+                self.front
+            }
+            true => {
+                if self.back.is_none() {
+                    self.back = self.iter.next_back().or_else(|| self.front.take());
+                }
+                self.back
+            }
+        }
+    }
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Intersection`
+    fn take(&mut self, from_back: bool) -> Option<&'a T> {
+        self.peek(from_back);
+        match from_back {
+            false => self.front.take(),
+            true => self.back.take(),
+        }
+    }
 
-/// An iterator producing elements in the intersection of [`SgSet`][crate::set::SgSet]s.
-///
-/// This `struct` is created by the [`intersection`][crate::set::SgSet::difference] method on [`SgSet`][crate::set::SgSet].
-/// See its documentation for more.
-pub struct Intersection<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[Idx; N]>,
-    set_this: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
+    fn len(&self) -> usize {
+        self.iter.len() + usize::from(self.front.is_some()) + usize::from(self.back.is_some())
+    }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Intersection<'a, T, N> {
-    /// Construct `Intersection` iterator.
-    /// Values that are both in `this` and `other`.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &SgSet<T, N>) -> Self {
-        let mut self_enum_iter = this.iter().enumerate();
-        let mut other_enum_iter = other.iter().enumerate();
+// Lazy merge of two sets' in-order iterators, shared by the set operation iterators below.
+// No allocation, `O(n + m)` to exhaust from either end.
+struct SetMerge<'a, T: Ord, const N: usize> {
+    this: DoublePeek<'a, T, N>,
+    other: DoublePeek<'a, T, N>,
+    op: MergeOp,
+}
 
-        let mut opt_self = self_enum_iter.next();
-        let mut opt_other = other_enum_iter.next();
+impl<'a, T: Ord, const N: usize> SetMerge<'a, T, N> {
+    fn new<P: BalancePolicy>(
+        this: &'a SgSet<T, N, P>,
+        other: &'a SgSet<T, N, P>,
+        op: MergeOp,
+    ) -> Self {
+        SetMerge {
+            this: DoublePeek::new(this),
+            other: DoublePeek::new(other),
+            op,
+        }
+    }
 
-        let mut inter = ArrayVec::default();
-        let mut len = 0;
+    fn step(&mut self, from_back: bool) -> Option<&'a T> {
+        loop {
+            // `Less` means the item at this end is only in `this`, `Greater` only in `other`
+            let ord = match (self.this.peek(from_back), self.other.peek(from_back)) {
+                (Some(this_item), Some(other_item)) => match from_back {
+                    false => this_item.cmp(other_item),
+                    true => other_item.cmp(this_item),
+                },
+                (Some(_), None) => match self.op {
+                    MergeOp::Intersection => return None,
+                    _ => Ordering::Less,
+                },
+                (None, Some(_)) => match self.op {
+                    MergeOp::Intersection | MergeOp::Difference => return None,
+                    _ => Ordering::Greater,
+                },
+                (None, None) => return None,
+            };
 
-        // If either is shorter, short-circuit.
-        while let (Some((self_idx, self_val)), Some((_, other_val))) = (opt_self, opt_other) {
-            match self_val.cmp(other_val) {
+            match ord {
                 Ordering::Less => {
-                    opt_self = self_enum_iter.next();
-                }
-                Ordering::Equal => {
-                    inter.push(Idx::checked_from(self_idx));
-                    len += 1;
-                    opt_self = self_enum_iter.next();
-                    opt_other = other_enum_iter.next();
+                    let item = self.this.take(from_back);
+                    if self.op != MergeOp::Intersection {
+                        return item;
+                    }
                 }
                 Ordering::Greater => {
-                    opt_other = other_enum_iter.next();
+                    let item = self.other.take(from_back);
+                    if let MergeOp::SymmetricDifference | MergeOp::Union = self.op {
+                        return item;
+                    }
+                }
+                Ordering::Equal => {
+                    let item = self.this.take(from_back);
+                    self.other.take(from_back);
+                    if let MergeOp::Intersection | MergeOp::Union = self.op {
+                        return item;
+                    }
                 }
             }
         }
+    }
 
-        Intersection {
-            inner: inter.into_iter(),
-            set_this: this,
-            total_cnt: len,
-            spent_cnt: 0,
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = (self.this.len(), self.other.len());
+        let (min_len, max_len) = (this_len.min(other_len), this_len.max(other_len));
+        match self.op {
+            MergeOp::Intersection => (0, Some(min_len)),
+            MergeOp::Difference => (this_len.saturating_sub(other_len), Some(this_len)),
+            MergeOp::SymmetricDifference => (max_len - min_len, Some(this_len + other_len)),
+            MergeOp::Union => (max_len, Some(this_len + other_len)),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Intersection<'a, T, N> {
-    type Item = &'a T;
+macro_rules! set_op_iter {
+    ($name:ident, $op:expr, $desc:literal, $method:literal) => {
+        #[doc = concat!("A lazy iterator producing elements in the ", $desc, " of [`SgSet`][crate::set::SgSet]s.")]
+        ///
+        #[doc = concat!("This `struct` is created by the [`", $method, "`][crate::set::SgSet::", $method, "] method")]
+        /// on [`SgSet`][crate::set::SgSet]. See its documentation for more.
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct $name<'a, T: Ord, const N: usize> {
+            merge: SetMerge<'a, T, N>,
+        }
 
-    fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some(idx) => match self.set_this.iter().nth(idx.usize()) {
-                Some(item) => {
-                    self.spent_cnt += 1;
-                    Some(item)
+        impl<'a, T: Ord, const N: usize> $name<'a, T, N> {
+            #[doc = concat!("Construct `", stringify!($name), "` iterator.")]
+            pub(crate) fn new<P: BalancePolicy>(this: &'a SgSet<T, N, P>, other: &'a SgSet<T, N, P>) -> Self {
+                $name {
+                    merge: SetMerge::new(this, other, $op),
                 }
-                None => None,
-            },
-            None => None,
+            }
         }
-    }
-}
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Intersection<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
-    }
-}
+        impl<'a, T: Ord, const N: usize> Iterator for $name<'a, T, N> {
+            type Item = &'a T;
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Intersection<'a, T, N> {}
-
-// Difference Iterator -------------------------------------------------------------------------------------------------
+            fn next(&mut self) -> Option<&'a T> {
+                self.merge.step(false)
+            }
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Difference`
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.merge.size_hint()
+            }
+        }
 
-/// An iterator producing elements in the difference of [`SgSet`][crate::set::SgSet]s.
-///
-/// This `struct` is created by the [`difference`][crate::set::SgSet::difference] method
-/// on [`SgSet`][crate::set::SgSet]. See its documentation for more.
-pub struct Difference<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[Idx; N]>,
-    set_this: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
-}
-
-impl<'a, T: Ord + Default, const N: usize> Difference<'a, T, N> {
-    /// Construct `Difference` iterator.
-    /// Values that are in `this` but not in `other`.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &SgSet<T, N>) -> Self {
-        let mut diff = ArrayVec::default();
-        let mut len = 0;
-
-        for (idx, val) in this.iter().enumerate() {
-            if !other.contains(val) {
-                diff.push(Idx::checked_from(idx));
-                len += 1;
+        impl<'a, T: Ord, const N: usize> DoubleEndedIterator for $name<'a, T, N> {
+            fn next_back(&mut self) -> Option<&'a T> {
+                self.merge.step(true)
             }
         }
 
-        Difference {
-            inner: diff.into_iter(),
-            set_this: this,
-            total_cnt: len,
-            spent_cnt: 0,
+        impl<'a, T: Ord, const N: usize> FusedIterator for $name<'a, T, N> {}
+    };
+}
+
+set_op_iter!(
+    Intersection,
+    MergeOp::Intersection,
+    "intersection",
+    "intersection"
+);
+set_op_iter!(Difference, MergeOp::Difference, "difference", "difference");
+set_op_iter!(
+    SymmetricDifference,
+    MergeOp::SymmetricDifference,
+    "symmetric difference",
+    "symmetric_difference"
+);
+set_op_iter!(Union, MergeOp::Union, "union", "union");
+
+// Streaming Set Operations --------------------------------------------------------------------------------------------
+
+/// An item produced by merging a [`SgSet`][crate::set::SgSet] with a sorted external iterator.
+///
+/// Yielded by [`MergeWithIter`], the variant records which side(s) the item came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merged<'a, T, U> {
+    /// Only in the set.
+    Set(&'a T),
+    /// Only in the external iterator.
+    Iter(U),
+    /// In both, the set's item and the external iterator's item.
+    Both(&'a T, U),
+}
+
+impl<'a, T, U: Borrow<T>> Merged<'a, T, U> {
+    /// Borrow the merged value, from whichever side holds it (the set's, if both).
+    pub fn get(&self) -> &T {
+        match self {
+            Merged::Set(item) | Merged::Both(item, _) => item,
+            Merged::Iter(item) => item.borrow(),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Difference<'a, T, N> {
-    type Item = &'a T;
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MergeOp {
+    Intersection,
+    Difference,
+    SymmetricDifference,
+    Union,
+}
 
-    fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some(idx) => match self.set_this.iter().nth(idx.usize()) {
-                Some(item) => {
-                    self.spent_cnt += 1;
-                    Some(item)
-                }
-                None => None,
-            },
-            None => None,
+/// A lazy iterator merging a [`SgSet`][crate::set::SgSet] with a sorted external iterator.
+///
+/// This `struct` is created by the [`intersection_with_iter`][crate::set::SgSet::intersection_with_iter],
+/// [`difference_with_iter`][crate::set::SgSet::difference_with_iter],
+/// [`symmetric_difference_with_iter`][crate::set::SgSet::symmetric_difference_with_iter],
+/// and [`union_with_iter`][crate::set::SgSet::union_with_iter] methods on [`SgSet`][crate::set::SgSet].
+/// See their documentation for more.
+pub struct MergeWithIter<'a, T: Ord, const N: usize, I: Iterator> {
+    set_iter: Peekable<Iter<'a, T, N>>,
+    ext_iter: Peekable<I>,
+    op: MergeOp,
+}
+
+impl<'a, T: Ord, const N: usize, I: Iterator> MergeWithIter<'a, T, N, I>
+where
+    I::Item: Borrow<T>,
+{
+    fn new<P: BalancePolicy>(set: &'a SgSet<T, N, P>, ext_iter: I, op: MergeOp) -> Self {
+        MergeWithIter {
+            set_iter: set.iter().peekable(),
+            ext_iter: ext_iter.peekable(),
+            op,
         }
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Difference<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+    /// Construct a lazy intersection, yielding only `Merged::Both`.
+    pub(crate) fn intersection<P: BalancePolicy>(set: &'a SgSet<T, N, P>, ext_iter: I) -> Self {
+        Self::new(set, ext_iter, MergeOp::Intersection)
+    }
+
+    /// Construct a lazy difference, yielding only `Merged::Set`.
+    pub(crate) fn difference<P: BalancePolicy>(set: &'a SgSet<T, N, P>, ext_iter: I) -> Self {
+        Self::new(set, ext_iter, MergeOp::Difference)
+    }
+
+    /// Construct a lazy symmetric difference, yielding `Merged::Set` and `Merged::Iter`.
+    pub(crate) fn symmetric_difference<P: BalancePolicy>(
+        set: &'a SgSet<T, N, P>,
+        ext_iter: I,
+    ) -> Self {
+        Self::new(set, ext_iter, MergeOp::SymmetricDifference)
+    }
+
+    /// Construct a lazy union, yielding every variant.
+    pub(crate) fn union<P: BalancePolicy>(set: &'a SgSet<T, N, P>, ext_iter: I) -> Self {
+        Self::new(set, ext_iter, MergeOp::Union)
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Difference<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, I: Iterator> Iterator for MergeWithIter<'a, T, N, I>
+where
+    I::Item: Borrow<T>,
+{
+    type Item = Merged<'a, T, I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let ord = match (self.set_iter.peek(), self.ext_iter.peek()) {
+                (Some(set_item), Some(ext_item)) => (*set_item).cmp(ext_item.borrow()),
+                (Some(_), None) => match self.op {
+                    MergeOp::Intersection => return None,
+                    _ => Ordering::Less,
+                },
+                (None, Some(_)) => match self.op {
+                    // Don't drain the rest of a potentially long stream
+                    MergeOp::Intersection | MergeOp::Difference => return None,
+                    _ => Ordering::Greater,
+                },
+                (None, None) => return None,
+            };
+
+            let item = match ord {
+                Ordering::Less => Merged::Set(self.set_iter.next()?),
+                Ordering::Greater => Merged::Iter(self.ext_iter.next()?),
+                Ordering::Equal => Merged::Both(self.set_iter.next()?, self.ext_iter.next()?),
+            };
+
+            let keep = matches!(
+                (&item, self.op),
+                (_, MergeOp::Union)
+                    | (Merged::Both(..), MergeOp::Intersection)
+                    | (Merged::Set(_), MergeOp::Difference)
+                    | (
+                        Merged::Set(_) | Merged::Iter(_),
+                        MergeOp::SymmetricDifference
+                    )
+            );
+
+            if keep {
+                return Some(item);
+            }
+        }
+    }
+}
 
-// Symmetric Difference Iterator ---------------------------------------------------------------------------------------
+impl<'a, T: Ord, const N: usize, I: FusedIterator> FusedIterator for MergeWithIter<'a, T, N, I> where
+    I::Item: Borrow<T>
+{
+}
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Difference`
+// Pop While -----------------------------------------------------------------------------------------------------------
 
-/// An iterator producing elements in the symmetric difference of [`SgSet`][crate::set::SgSet]s.
+/// A lazy iterator removing extremal values of a [`SgSet`] for as long as a predicate holds.
 ///
-/// This `struct` is created by the [`symmetric_difference`][crate::set::SgSet::symmetric_difference]
-/// method on [`SgSet`][crate::set::SgSet]. See its documentation for more.
-pub struct SymmetricDifference<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[(Idx, bool); PLACEHOLDER_2N]>, // TODO: placeholder
-    set_this: &'a SgSet<T, N>,
-    set_other: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
-}
-
-impl<'a, T: Ord + Default, const N: usize> SymmetricDifference<'a, T, N> {
-    /// Construct `SymmetricDifference` iterator.
-    /// Values that are in `this` or in `other` but not in both.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &'a SgSet<T, N>) -> Self {
-        let mut sym_diff = ArrayVec::default();
-        let mut len = 0;
-
-        for (idx, val) in this.iter().enumerate() {
-            if !other.contains(val) {
-                sym_diff.push((Idx::checked_from(idx), true));
-                len += 1;
-            }
+/// This `struct` is created by the [`pop_first_while`][crate::set::SgSet::pop_first_while] and
+/// [`pop_last_while`][crate::set::SgSet::pop_last_while] methods on [`SgSet`][crate::set::SgSet].
+/// See their documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PopWhile<'a, T: Ord, const N: usize, F, P = DefaultBalancePolicy> {
+    pub(crate) table: &'a mut SgSet<T, N, P>,
+    pub(crate) pred: F,
+    pub(crate) from_back: bool,
+    pub(crate) done: bool,
+}
+
+impl<'a, T, const N: usize, F, P: BalancePolicy> Iterator for PopWhile<'a, T, N, F, P>
+where
+    T: Ord,
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
         }
 
-        for (idx, val) in other.iter().enumerate() {
-            if !this.contains(val) {
-                sym_diff.push((Idx::checked_from(idx), false));
-                len += 1;
+        let opt_extremal = match self.from_back {
+            true => self.table.last(),
+            false => self.table.first(),
+        };
+
+        match opt_extremal {
+            Some(item) if (self.pred)(item) => match self.from_back {
+                true => self.table.pop_last(),
+                false => self.table.pop_first(),
+            },
+            _ => {
+                self.done = true;
+                None
             }
         }
+    }
 
-        // Ascending order
-        sym_diff.sort_unstable_by_key(|(idx, in_this): &(Idx, bool)| match in_this {
-            true => this.iter().nth(idx.usize()),
-            false => other.iter().nth(idx.usize()),
-        });
-
-        SymmetricDifference {
-            inner: sym_diff.into_iter(),
-            set_this: this,
-            set_other: other,
-            total_cnt: len,
-            spent_cnt: 0,
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.done {
+            true => (0, Some(0)),
+            false => (0, Some(self.table.len())),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for SymmetricDifference<'a, T, N> {
-    type Item = &'a T;
+impl<'a, T, const N: usize, F, P: BalancePolicy> FusedIterator for PopWhile<'a, T, N, F, P>
+where
+    T: Ord,
+    F: FnMut(&T) -> bool,
+{
+}
 
-    fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some((idx, in_this)) => match in_this {
-                true => match self.set_this.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-                false => match self.set_other.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-            },
-            None => None,
+// Extract If ----------------------------------------------------------------------------------------------------------
+
+/// A lazy iterator removing the values of a [`SgSet`] for which a predicate holds.
+///
+/// This `struct` is created by the [`extract_if`][crate::set::SgSet::extract_if] method on
+/// [`SgSet`][crate::set::SgSet]. See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIf<'a, T: Ord, const N: usize, F, P = DefaultBalancePolicy> {
+    table: &'a mut SgSet<T, N, P>,
+    node_idx_iter: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
+    pred: F,
+}
+
+impl<'a, T: Ord, const N: usize, F, P: BalancePolicy> ExtractIf<'a, T, N, F, P> {
+    /// Construct extracting iterator.
+    pub(crate) fn new(set: &'a mut SgSet<T, N, P>, pred: F) -> Self {
+        // Removal doesn't relocate surviving nodes, so these indexes stay valid
+        let sorted_idxs = match set.bst.opt_root_idx {
+            Some(root_idx) => set.bst.flatten_subtree_to_sorted_idxs(root_idx),
+            None => SgVec::new(),
+        };
+
+        ExtractIf {
+            table: set,
+            node_idx_iter: sorted_idxs.into_iter(),
+            pred,
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for SymmetricDifference<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+impl<'a, T, const N: usize, F, P: BalancePolicy> Iterator for ExtractIf<'a, T, N, F, P>
+where
+    T: Ord,
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for node_idx in self.node_idx_iter.by_ref() {
+            if (self.pred)(self.table.bst.arena.key(node_idx)) {
+                return self.table.bst.priv_remove_by_idx(node_idx).map(|(k, _)| k);
+            }
+        }
+
+        None
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for SymmetricDifference<'a, T, N> {}
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.node_idx_iter.len()))
+    }
+}
 
-// Union Iterator ------------------------------------------------------------------------------------------------------
+impl<'a, T, const N: usize, F, P: BalancePolicy> FusedIterator for ExtractIf<'a, T, N, F, P>
+where
+    T: Ord,
+    F: FnMut(&T) -> bool,
+{
+}
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Union`
+// Cursors -------------------------------------------------------------------------------------------------------------
 
-/// An iterator producing elements in the union of [`SgSet`][crate::set::SgSet]s.
+/// A cursor over a [`SgSet`], pointing at a value or at a "ghost" position logically past both ends.
+/// Stepping between adjacent values is amortized `O(1)`, without a search from the root.
 ///
-/// This `struct` is created by the [`union`][crate::set::SgSet::difference] method on [`SgSet`][crate::set::SgSet].
-/// See its documentation for more.
-pub struct Union<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[(Idx, bool); PLACEHOLDER_2N]>,
-    set_this: &'a SgSet<T, N>,
-    set_other: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
-}
-
-impl<'a, T: Ord + Default, const N: usize> Union<'a, T, N> {
-    /// Construct `Union` iterator.
-    /// Values in `this` or `other`, without duplicates.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &'a SgSet<T, N>) -> Self {
-        let mut uni = ArrayVec::default();
-        let mut len = 0;
-
-        for (idx, _) in this.iter().enumerate() {
-            uni.push((Idx::checked_from(idx), true));
-            len += 1;
-        }
+/// This `struct` is created by the [`lower_bound`][crate::set::SgSet::lower_bound] and
+/// [`upper_bound`][crate::set::SgSet::upper_bound] methods on [`SgSet`][crate::set::SgSet].
+/// See their documentation for more.
+pub struct Cursor<'a, T: Ord, const N: usize, P = DefaultBalancePolicy> {
+    table: &'a SgSet<T, N, P>,
+    pos: CursorPos<N>,
+}
 
-        for (idx, val) in other.iter().enumerate() {
-            if !this.contains(val) {
-                uni.push((Idx::checked_from(idx), false));
-                len += 1;
-            }
-        }
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> Cursor<'a, T, N, P> {
+    /// Construct cursor.
+    pub(crate) fn new(table: &'a SgSet<T, N, P>, pos: CursorPos<N>) -> Self {
+        Cursor { table, pos }
+    }
 
-        // Ascending order
-        uni.sort_unstable_by_key(|(idx, in_this): &(Idx, bool)| match in_this {
-            true => this.iter().nth(idx.usize()),
-            false => other.iter().nth(idx.usize()),
-        });
-
-        Union {
-            inner: uni.into_iter(),
-            set_this: this,
-            set_other: other,
-            total_cnt: len,
-            spent_cnt: 0,
-        }
+    /// Returns the value the cursor points at, or `None` at the ghost position.
+    pub fn get(&self) -> Option<&'a T> {
+        let table = self.table;
+        self.pos.curr().map(|idx| table.bst.arena.key(idx))
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Union<'a, T, N> {
-    type Item = &'a T;
+    /// Moves to the next value. From the last value, moves to the ghost position.
+    /// From the ghost position, moves to the first value.
+    pub fn move_next(&mut self) {
+        self.pos.move_next(&self.table.bst);
+    }
 
-    fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some((idx, in_this)) => match in_this {
-                true => match self.set_this.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-                false => match self.set_other.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-            },
-            None => None,
+    /// Moves to the previous value. From the first value, moves to the ghost position.
+    /// From the ghost position, moves to the last value.
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(&self.table.bst);
+    }
+
+    /// Returns the value after the cursor, without moving.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let table = self.table;
+        self.pos
+            .next_idx(&table.bst)
+            .map(|idx| table.bst.arena.key(idx))
+    }
+
+    /// Returns the value before the cursor, without moving.
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let table = self.table;
+        self.pos
+            .prev_idx(&table.bst)
+            .map(|idx| table.bst.arena.key(idx))
+    }
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> Clone for Cursor<'a, T, N, P> {
+    fn clone(&self) -> Self {
+        Cursor {
+            table: self.table,
+            pos: self.pos.clone(),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Union<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+impl<'a, T: Debug + Ord, const N: usize, P: BalancePolicy> Debug for Cursor<'a, T, N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.get()).finish()
+    }
+}
+
+/// A cursor over a [`SgSet`] with editing operations: insertion and removal at the cursor.
+///
+/// This `struct` is created by the [`lower_bound_mut`][crate::set::SgSet::lower_bound_mut] and
+/// [`upper_bound_mut`][crate::set::SgSet::upper_bound_mut] methods on [`SgSet`][crate::set::SgSet].
+/// See their documentation for more.
+pub struct CursorMut<'a, T: Ord, const N: usize, P = DefaultBalancePolicy> {
+    table: &'a mut SgSet<T, N, P>,
+    pos: CursorPos<N>,
+}
+
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> CursorMut<'a, T, N, P> {
+    /// Construct mutable cursor.
+    pub(crate) fn new(table: &'a mut SgSet<T, N, P>, pos: CursorPos<N>) -> Self {
+        CursorMut { table, pos }
+    }
+
+    /// Returns the value the cursor points at, or `None` at the ghost position.
+    pub fn get(&self) -> Option<&T> {
+        self.pos.curr().map(|idx| self.table.bst.arena.key(idx))
+    }
+
+    /// Moves to the next value. From the last value, moves to the ghost position.
+    /// From the ghost position, moves to the first value.
+    pub fn move_next(&mut self) {
+        self.pos.move_next(&self.table.bst);
+    }
+
+    /// Moves to the previous value. From the first value, moves to the ghost position.
+    /// From the ghost position, moves to the last value.
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(&self.table.bst);
+    }
+
+    /// Returns the value after the cursor, without moving.
+    pub fn peek_next(&self) -> Option<&T> {
+        self.pos
+            .next_idx(&self.table.bst)
+            .map(|idx| self.table.bst.arena.key(idx))
+    }
+
+    /// Returns the value before the cursor, without moving.
+    pub fn peek_prev(&self) -> Option<&T> {
+        self.pos
+            .prev_idx(&self.table.bst)
+            .map(|idx| self.table.bst.arena.key(idx))
+    }
+
+    /// Returns a read-only cursor at the same position, borrowing this one.
+    pub fn as_cursor(&self) -> Cursor<'_, T, N, P> {
+        Cursor::new(self.table, self.pos.clone())
+    }
+
+    /// Inserts a value immediately after the cursor, without moving.
+    /// At the ghost position, the value is inserted as the first value.
+    ///
+    /// Returns [`SgError::UnsortedInput`][crate::SgError::UnsortedInput] if `value` doesn't belong between the
+    /// cursor and the next value, or [`SgError::StackCapacityExceeded`][crate::SgError::StackCapacityExceeded] if
+    /// the set is full. The set is unchanged on error.
+    pub fn insert_after(&mut self, value: T) -> Result<(), SgError> {
+        self.pos.insert_after(&mut self.table.bst, value, ())
+    }
+
+    /// Inserts a value immediately before the cursor, without moving.
+    /// At the ghost position, the value is inserted as the last value.
+    ///
+    /// Returns [`SgError::UnsortedInput`][crate::SgError::UnsortedInput] if `value` doesn't belong between the
+    /// previous value and the cursor, or [`SgError::StackCapacityExceeded`][crate::SgError::StackCapacityExceeded]
+    /// if the set is full. The set is unchanged on error.
+    pub fn insert_before(&mut self, value: T) -> Result<(), SgError> {
+        self.pos.insert_before(&mut self.table.bst, value, ())
+    }
+
+    /// Removes the value the cursor points at, moving to the next value (or the ghost position if it was last).
+    /// Returns `None`, without moving, at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.pos.remove_current(&mut self.table.bst).map(|(k, _)| k)
+    }
+
+    /// Removes the value the cursor points at, moving to the previous value (or the ghost position if it was
+    /// first). Returns `None`, without moving, at the ghost position.
+    pub fn remove_current_and_move_back(&mut self) -> Option<T> {
+        self.pos
+            .remove_current_and_move_back(&mut self.table.bst)
+            .map(|(k, _)| k)
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Union<'a, T, N> {}
+impl<'a, T: Debug + Ord, const N: usize, P: BalancePolicy> Debug for CursorMut<'a, T, N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.get()).finish()
+    }
+}
 
 // Range APIs ----------------------------------------------------------------------------------------------------------
 
@@ -420,27 +752,25 @@ impl<'a, T: Ord + Default, const N: usize> FusedIterator for Union<'a, T, N> {}
 /// See its documentation for more.
 ///
 /// [`range`]: SgSet::range
-pub struct Range<'a, T: Ord + Default, const N: usize> {
-    pub(crate) table: &'a SgSet<T, N>,
-    pub(crate) node_idx_iter: <ArrayVec<[usize; N]> as IntoIterator>::IntoIter,
+pub struct Range<'a, T: Ord, const N: usize, P = DefaultBalancePolicy> {
+    pub(crate) table: &'a SgSet<T, N, P>,
+    pub(crate) node_idx_iter: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Range<'a, T, N> {
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> Iterator for Range<'a, T, N, P> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         let node_idx = self.node_idx_iter.next()?;
-        let node = &self.table.bst.arena[node_idx];
-        Some(node.key())
+        Some(self.table.bst.arena.key(node_idx))
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> DoubleEndedIterator for Range<'a, T, N> {
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> DoubleEndedIterator for Range<'a, T, N, P> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let node_idx = self.node_idx_iter.next_back()?;
-        let node = &self.table.bst.arena[node_idx];
-        Some(node.key())
+        Some(self.table.bst.arena.key(node_idx))
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Range<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, P: BalancePolicy> FusedIterator for Range<'a, T, N, P> {}
@@ -0,0 +1,229 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::iter::Peekable;
+
+use std::collections::hash_map::DefaultHasher;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::vec::Vec;
+
+use crate::map::SgMap;
+use crate::map_types::Iter;
+use crate::tree::SgError;
+
+/// Concurrent ordered map, keys are hash-partitioned across `SHARDS` independent [`SgMap`][crate::map::SgMap]s.
+/// Requires feature `std`.
+///
+/// Each shard sits behind its own lock, so operations on keys in different shards don't contend.
+/// Every shard has capacity `N`, for a total capacity of `N * SHARDS`.
+/// Since keys are distributed by hash, a shard may fill before total capacity is reached.
+///
+/// Like [`RwLock`][std::sync::RwLock], a shard is poisoned if a thread panics while holding its write lock
+/// (e.g. in a user-provided `Ord` impl), as the shard may be inconsistent.
+/// Any later operation touching a poisoned shard panics.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgShardedMap;
+/// use std::thread;
+///
+/// let map = SgShardedMap::<u32, u32, 64, 4>::new();
+///
+/// thread::scope(|s| {
+///     for t in 0..4 {
+///         let map = &map;
+///         s.spawn(move || {
+///             for i in 0..10 {
+///                 map.insert(t * 10 + i, t);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(map.len(), 40);
+/// assert_eq!(map.get(&25), Some(2));
+/// assert!(map.read().iter().map(|(k, _)| *k).eq(0..40));
+/// ```
+pub struct SgShardedMap<K: Ord, V, const N: usize, const SHARDS: usize> {
+    shards: [RwLock<SgMap<K, V, N>>; SHARDS],
+}
+
+impl<K: Ord + Hash, V, const N: usize, const SHARDS: usize> SgShardedMap<K, V, N, SHARDS> {
+    /// Makes a new, empty `SgShardedMap`.
+    /// Panics if `SHARDS` is zero.
+    pub fn new() -> Self {
+        assert!(SHARDS > 0, "Shard count must be non-zero!");
+        SgShardedMap {
+            shards: core::array::from_fn(|_| RwLock::new(SgMap::new())),
+        }
+    }
+
+    /// Number of shards.
+    pub const fn shard_count(&self) -> usize {
+        SHARDS
+    }
+
+    /// Total capacity, e.g. maximum number of map pairs across all shards.
+    pub const fn capacity(&self) -> usize {
+        N * SHARDS
+    }
+
+    /// Insert a key-value pair into the map, returning the old value if the key was present.
+    /// Panics if the key's shard is full.
+    pub fn insert(&self, key: K, val: V) -> Option<V> {
+        self.write_shard(&key).insert(key, val)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if the key's shard is full, else the `Ok` contains the old value, if any.
+    pub fn try_insert(&self, key: K, val: V) -> Result<Option<V>, SgError> {
+        self.write_shard(&key).try_insert(key, val)
+    }
+
+    /// Returns a copy of the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + Hash + ?Sized,
+        V: Clone,
+    {
+        self.read_shard(key).get(key).cloned()
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + Hash + ?Sized,
+    {
+        self.read_shard(key).contains_key(key)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + Hash + ?Sized,
+    {
+        self.write_shard(key).remove(key)
+    }
+
+    /// Returns the number of elements in the map.
+    /// Shards are counted one at a time, so concurrent updates may or may not be reflected.
+    pub fn len(&self) -> usize {
+        (0..SHARDS).map(|i| self.read_idx(i).len()).sum()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        (0..SHARDS).all(|i| self.read_idx(i).is_empty())
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&self) {
+        (0..SHARDS).for_each(|i| self.write_idx(i).clear());
+    }
+
+    /// Lock all shards for reading, e.g. for a consistent, ordered view of the entire map.
+    pub fn read(&self) -> ShardedReadGuard<'_, K, V, N> {
+        ShardedReadGuard {
+            guards: (0..SHARDS).map(|i| self.read_idx(i)).collect(),
+        }
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    // Index of the shard responsible for `key`.
+    fn shard_idx<Q: Hash + ?Sized>(key: &Q) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() % (SHARDS as u64)) as usize
+    }
+
+    fn read_shard<Q: Hash + ?Sized>(&self, key: &Q) -> RwLockReadGuard<'_, SgMap<K, V, N>> {
+        self.read_idx(Self::shard_idx(key))
+    }
+
+    fn write_shard<Q: Hash + ?Sized>(&self, key: &Q) -> RwLockWriteGuard<'_, SgMap<K, V, N>> {
+        self.write_idx(Self::shard_idx(key))
+    }
+
+    // A panic while a shard was write-locked (e.g. in `K::cmp`) may have left it mid-rebalance, so propagate poison
+    fn read_idx(&self, idx: usize) -> RwLockReadGuard<'_, SgMap<K, V, N>> {
+        self.shards[idx].read().expect("Shard lock poisoned!")
+    }
+
+    fn write_idx(&self, idx: usize) -> RwLockWriteGuard<'_, SgMap<K, V, N>> {
+        self.shards[idx].write().expect("Shard lock poisoned!")
+    }
+}
+
+impl<K: Ord + Hash, V, const N: usize, const SHARDS: usize> Default
+    for SgShardedMap<K, V, N, SHARDS>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize, const SHARDS: usize> Debug for SgShardedMap<K, V, N, SHARDS>
+where
+    K: Ord + Hash + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.read().iter()).finish()
+    }
+}
+
+// Read Guard ----------------------------------------------------------------------------------------------------------
+
+/// Read lock over every shard of an [`SgShardedMap`], see [`SgShardedMap::read`].
+/// Writers are blocked until it's dropped.
+pub struct ShardedReadGuard<'a, K: Ord, V, const N: usize> {
+    guards: Vec<RwLockReadGuard<'a, SgMap<K, V, N>>>,
+}
+
+impl<'a, K: Ord, V, const N: usize> ShardedReadGuard<'a, K, V, N> {
+    /// Gets an iterator over the entries of all shards, sorted by key.
+    pub fn iter(&self) -> ShardedIter<'_, K, V, N> {
+        ShardedIter {
+            shard_iters: self.guards.iter().map(|g| g.iter().peekable()).collect(),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.guards.iter().map(|g| g.len()).sum()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.guards.iter().all(|g| g.is_empty())
+    }
+}
+
+/// Ordered iterator over the entries of an [`SgShardedMap`], merging all shards.
+/// Created by [`ShardedReadGuard::iter`].
+pub struct ShardedIter<'a, K: Ord, V, const N: usize> {
+    shard_iters: Vec<Peekable<Iter<'a, K, V, N>>>,
+}
+
+impl<'a, K: Ord, V, const N: usize> Iterator for ShardedIter<'a, K, V, N> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Keys are unique across shards, so the minimum peeked key is the next in order
+        let mut opt_min: Option<(usize, &K)> = None;
+        for (i, shard_iter) in self.shard_iters.iter_mut().enumerate() {
+            if let Some((k, _)) = shard_iter.peek() {
+                if opt_min.map_or(true, |(_, min_k)| *k < min_k) {
+                    opt_min = Some((i, *k));
+                }
+            }
+        }
+
+        opt_min.and_then(|(i, _)| self.shard_iters[i].next())
+    }
+}
@@ -1,11 +1,14 @@
-use core::ops::{Index, IndexMut};
-use core::slice::{Iter, IterMut};
+#[cfg(feature = "split_values")]
+use core::marker::PhantomData;
+use core::ops::Range;
 
-use super::node::{Node, NodeGetHelper, NodeSwapHistHelper};
+use super::node::{LinkIdx, Node, NodeGetHelper, NodeSwapHistHelper};
 use super::node_dispatch::SmallNode;
 
-use smallnum::SmallUnsigned;
+#[cfg(not(feature = "alloc"))]
 use tinyvec::ArrayVec;
+#[cfg(feature = "alloc")]
+use tinyvec::TinyVec;
 
 /*
 Note:
@@ -15,35 +18,75 @@ All members in subset are <= host pointer width in size.
 If caller obeys contract, `U` will be smallest unsigned capable of representing const `N` (e.g. static capacity).
 */
 
+/// Vector backing the arena and per-item scratch buffers, with inline capacity const `N`.
+/// Fixed-capacity and stack-only by default.
+#[cfg(not(feature = "alloc"))]
+pub type SgVec<A> = ArrayVec<A>;
+
+/// Vector backing the arena and per-item scratch buffers, with inline capacity const `N`.
+/// Spills to the heap beyond `N` items, for trees sized at runtime.
+#[cfg(feature = "alloc")]
+pub type SgVec<A> = TinyVec<A>;
+
+/// Node as stored in the arena.
+/// Holds its value inline, unless feature `split_values` is enabled.
+#[cfg(not(feature = "split_values"))]
+pub type ArenaNode<K, V, U> = Node<K, V, U>;
+
+/// Node as stored in the arena.
+/// Holds only its key, values are kept in a parallel array at the same index.
+#[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+pub type ArenaNode<K, V, U> = Node<K, PhantomData<V>, U>;
+
+/// Node as stored in the arena.
+/// Holds only child links and balance metadata, keys and values are kept in parallel arrays at the same index.
+#[cfg(feature = "soa_arena")]
+pub type ArenaNode<K, V, U> = Node<PhantomData<K>, PhantomData<V>, U>;
+
 /// An arena allocator, meta programmable for low memory footprint.
 #[derive(Clone, Debug)]
-pub struct Arena<K: Default, V: Default, U: Default, const N: usize> {
-    vec: ArrayVec<[Option<Node<K, V, U>>; N]>,
+pub struct Arena<K, V, U: Default + LinkIdx, const N: usize> {
+    vec: SgVec<[Option<ArenaNode<K, V, U>>; N]>,
+
+    // Key of node at the same index, `None` for free slots
+    #[cfg(feature = "soa_arena")]
+    keys: SgVec<[Option<K>; N]>,
+
+    // Value of node at the same index, `None` for free slots
+    #[cfg(feature = "split_values")]
+    vals: SgVec<[Option<V>; N]>,
 
     #[cfg(not(feature = "low_mem_insert"))]
-    free_list: ArrayVec<[U; N]>,
+    free_list: SgVec<[U; N]>,
+
+    // Runtime capacity, in place of const `N`
+    #[cfg(feature = "alloc")]
+    cap: usize,
 }
 
-impl<
-        K: Default,
-        V: Default,
-        U: Default + Copy + SmallUnsigned + Ord + PartialEq + PartialOrd,
-        const N: usize,
-    > Arena<K, V, U, N>
-{
+impl<K, V, U: Default + LinkIdx + Ord + PartialEq + PartialOrd, const N: usize> Arena<K, V, U, N> {
     // TODO: is this function necessary?
     /// Const associated constructor for index scratch vector.
-    pub fn new_idx_vec() -> ArrayVec<[U; N]> {
-        ArrayVec::<[U; N]>::default()
+    pub fn new_idx_vec() -> SgVec<[U; N]> {
+        SgVec::<[U; N]>::default()
     }
 
     /// Constructor.
     pub fn new() -> Self {
         let a = Arena {
-            vec: ArrayVec::<[Option<Node<K, V, U>>; N]>::new(),
+            vec: SgVec::<[Option<ArenaNode<K, V, U>>; N]>::new(),
+
+            #[cfg(feature = "soa_arena")]
+            keys: SgVec::<[Option<K>; N]>::new(),
+
+            #[cfg(feature = "split_values")]
+            vals: SgVec::<[Option<V>; N]>::new(),
 
             #[cfg(not(feature = "low_mem_insert"))]
-            free_list: ArrayVec::<[U; N]>::new(),
+            free_list: SgVec::<[U; N]>::new(),
+
+            #[cfg(feature = "alloc")]
+            cap: N,
         };
 
         #[cfg(not(feature = "low_mem_insert"))]
@@ -56,19 +99,64 @@ impl<
 
         a
     }
-    /// Returns an iterator over immutable arena elements.
-    pub fn iter(&self) -> Iter<'_, Option<Node<K, V, U>>> {
-        self.vec.iter()
+
+    /// Constructor for a capacity chosen at runtime.
+    /// Storage is allocated on the heap up front if `cap` exceeds const `N`.
+    #[cfg(feature = "alloc")]
+    pub fn with_capacity(cap: usize) -> Self {
+        Arena {
+            vec: SgVec::<[Option<ArenaNode<K, V, U>>; N]>::with_capacity(cap),
+
+            #[cfg(feature = "soa_arena")]
+            keys: SgVec::<[Option<K>; N]>::with_capacity(cap),
+
+            #[cfg(feature = "split_values")]
+            vals: SgVec::<[Option<V>; N]>::with_capacity(cap),
+
+            #[cfg(not(feature = "low_mem_insert"))]
+            free_list: SgVec::<[U; N]>::with_capacity(cap),
+
+            cap,
+        }
+    }
+
+    /// Returns an iterator over arena slots, `None` for free slots.
+    pub fn iter(&self) -> ArenaIter<'_, K, V, U> {
+        ArenaIter::new(self)
     }
 
-    /// Returns an iterator over arena elements that allows modifying each value.
-    pub fn iter_mut(&mut self) -> IterMut<'_, Option<Node<K, V, U>>> {
-        self.vec.iter_mut()
+    /// Returns an iterator over a span of arena slots that allows modifying each value, `None` for free slots.
+    pub fn iter_mut_span(&mut self, span: Range<usize>) -> ArenaIterMut<'_, K, V, U> {
+        ArenaIterMut::new(self, span)
     }
 
     /// Total capacity, e.g. maximum number of items.
     pub fn capacity(&self) -> usize {
-        N
+        #[cfg(not(feature = "alloc"))]
+        {
+            N
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            self.cap
+        }
+    }
+
+    /// Remove all nodes. Only slots used since the last clear are reset, not the full capacity.
+    /// Slots aren't visited at all if nodes have no drop glue (`ArrayVec::truncate` checks `needs_drop`),
+    /// stale nodes past the new length are unreachable and overwritten on reuse.
+    pub fn clear(&mut self) {
+        self.vec.clear();
+
+        #[cfg(feature = "soa_arena")]
+        self.keys.clear();
+
+        #[cfg(feature = "split_values")]
+        self.vals.clear();
+
+        #[cfg(not(feature = "low_mem_insert"))]
+        self.free_list.clear();
     }
 
     /// Add node to area, growing if necessary, and return addition index.
@@ -85,7 +173,15 @@ impl<
             .position(|x| x.is_none())
             .map(|i| U::checked_from(i));
 
+        #[cfg(not(feature = "split_values"))]
         let node = Node::new(key, val);
+
+        #[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+        let node = Node::new(key, PhantomData);
+
+        #[cfg(feature = "soa_arena")]
+        let node = Node::new(PhantomData, PhantomData);
+
         match opt_free_idx {
             Some(free_idx) => {
                 debug_assert!(
@@ -93,17 +189,35 @@ impl<
                     "Internal invariant failed: overwrite of allocated node!"
                 );
                 self.vec[free_idx.usize()] = Some(node);
+
+                #[cfg(feature = "soa_arena")]
+                {
+                    self.keys[free_idx.usize()] = Some(key);
+                }
+
+                #[cfg(feature = "split_values")]
+                {
+                    self.vals[free_idx.usize()] = Some(val);
+                }
+
                 free_idx.usize()
             }
             None => {
                 self.vec.push(Some(node));
+
+                #[cfg(feature = "soa_arena")]
+                self.keys.push(Some(key));
+
+                #[cfg(feature = "split_values")]
+                self.vals.push(Some(val));
+
                 self.vec.len() - 1
             }
         }
     }
 
-    /// Remove node at a given index from area, return it.
-    pub fn remove(&mut self, idx: usize) -> Option<Node<K, V, U>> {
+    /// Remove node at a given index from area, return its key and value.
+    pub fn remove(&mut self, idx: usize) -> Option<(K, V)> {
         debug_assert!(
             idx < self.vec.len(),
             "API misuse: requested removal past last index!"
@@ -111,35 +225,148 @@ impl<
 
         if self.is_occupied(idx) {
             // Extract node
-            let node = core::mem::replace(&mut self.vec[idx], None);
+            #[cfg(not(feature = "soa_arena"))]
+            let node = self.vec[idx].take()?;
+
+            #[cfg(feature = "soa_arena")]
+            self.vec[idx].take()?;
 
             // Append removed index to free list
             #[cfg(not(feature = "low_mem_insert"))]
             self.free_list.push(U::checked_from(idx));
 
-            return node;
+            #[cfg(not(feature = "split_values"))]
+            return Some(node.into_entry());
+
+            #[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+            {
+                let (key, _) = node.into_entry();
+                let val = self.vals[idx].take()?;
+                return Some((key, val));
+            }
+
+            #[cfg(feature = "soa_arena")]
+            {
+                let key = self.keys[idx].take()?;
+                let val = self.vals[idx].take()?;
+                return Some((key, val));
+            }
         }
 
         None
     }
 
-    /// Remove node at a known-good index (simpler callsite and error handling) from area.
+    /// Remove node at a known-good index (simpler callsite and error handling) from area, return its key and value.
     /// This function can panic. If the index might be invalid, use `remove` instead.
-    pub fn hard_remove(&mut self, idx: usize) -> Node<K, V, U> {
+    pub fn hard_remove(&mut self, idx: usize) -> (K, V) {
         match self.remove(idx) {
-            Some(node) => node,
+            Some(entry) => entry,
             None => {
                 panic!("Internal invariant failed: attempted removal of node from invalid index.")
             }
         }
     }
 
+    /// Get key and value of node at a known-good index.
+    pub fn get(&self, idx: usize) -> (&K, &V) {
+        (self.key(idx), self.val(idx))
+    }
+
+    /// Get key and mutable value of node at a known-good index.
+    pub fn get_mut(&mut self, idx: usize) -> (&K, &mut V) {
+        #[cfg(not(feature = "split_values"))]
+        {
+            self.node_mut(idx).get_mut()
+        }
+
+        #[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+        match (&self.vec[idx], &mut self.vals[idx]) {
+            (Some(node), Some(val)) => (node.key(), val),
+            _ => unreachable!(),
+        }
+
+        #[cfg(feature = "soa_arena")]
+        match (&self.keys[idx], &mut self.vals[idx]) {
+            (Some(key), Some(val)) => (key, val),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Get value of node at a known-good index.
+    pub fn val(&self, idx: usize) -> &V {
+        #[cfg(not(feature = "split_values"))]
+        {
+            self.node(idx).val()
+        }
+
+        #[cfg(feature = "split_values")]
+        {
+            match &self.vals[idx] {
+                Some(val) => val,
+                None => unreachable!(),
+            }
+        }
+    }
+
+    /// Replace value of node at a known-good index, returning the old value.
+    pub fn replace_val(&mut self, idx: usize, val: V) -> V {
+        core::mem::replace(self.get_mut(idx).1, val)
+    }
+
+    /// Get mutable values of nodes at several known-good, pairwise distinct indexes.
+    /// Each `None` index yields a `None` reference, in the same position.
+    pub fn get_many_vals_mut<const M: usize>(
+        &mut self,
+        idxs: [Option<usize>; M],
+    ) -> [Option<&mut V>; M] {
+        let mut vals: [Option<&mut V>; M] = [(); M].map(|_| None);
+
+        // Visit in ascending index order, splitting each borrowed slot off the front of the remaining slice
+        let mut order = [0; M];
+        order.iter_mut().enumerate().for_each(|(i, o)| *o = i);
+        order.sort_unstable_by_key(|&i| idxs[i]);
+
+        #[cfg(not(feature = "split_values"))]
+        let mut rest = &mut self.vec[..];
+
+        #[cfg(feature = "split_values")]
+        let mut rest = &mut self.vals[..];
+
+        let mut offset = 0;
+        for i in order {
+            if let Some(idx) = idxs[i] {
+                assert!(
+                    idx >= offset,
+                    "Internal invariant failed: overlapping indexes."
+                );
+                let (slot, tail) = core::mem::take(&mut rest)[(idx - offset)..]
+                    .split_first_mut()
+                    .expect("Internal invariant failed: index out of bounds.");
+
+                #[cfg(not(feature = "split_values"))]
+                {
+                    vals[i] = slot.as_mut().map(|node| node.get_mut().1);
+                }
+
+                #[cfg(feature = "split_values")]
+                {
+                    vals[i] = slot.as_mut();
+                }
+
+                rest = tail;
+                offset = idx + 1;
+            }
+        }
+
+        vals
+    }
+
     /// Sort the arena in caller-requested order and update all tree metadata accordingly
     /// `unwraps` will never panic if caller invariants upheld (checked via `debug_assert`)
     pub fn sort(
         &mut self,
         root_idx: usize,
-        sort_metadata: ArrayVec<[NodeGetHelper<usize>; N]>, // `usize` here avoids `U` in tree iter signatures
+        sort_metadata: SgVec<[NodeGetHelper<usize>; N]>, // `usize` here avoids `U` in tree iter signatures
     ) -> usize {
         debug_assert!(sort_metadata.iter().all(|ngh| ngh.node_idx().is_some()));
 
@@ -150,6 +377,13 @@ impl<
             let curr_idx = swap_history.curr_idx(ngh.node_idx().unwrap());
             if curr_idx != sorted_idx {
                 self.vec.swap(curr_idx, sorted_idx);
+
+                #[cfg(feature = "soa_arena")]
+                self.keys.swap(curr_idx, sorted_idx);
+
+                #[cfg(feature = "split_values")]
+                self.vals.swap(curr_idx, sorted_idx);
+
                 swap_history.add(curr_idx, sorted_idx);
 
                 // TODO: move this out of loop body, should do once at end of func with `swap_history`
@@ -171,11 +405,10 @@ impl<
             if let Some(parent_idx) = ngh.parent_idx() {
                 let curr_parent_idx = swap_history.curr_idx(parent_idx);
                 let curr_child_idx = swap_history.curr_idx(ngh.node_idx().unwrap());
-                let parent_node = &mut self[curr_parent_idx];
                 if ngh.is_right_child() {
-                    parent_node.set_right_idx(Some(curr_child_idx));
+                    self.set_right_idx(curr_parent_idx, Some(curr_child_idx));
                 } else {
-                    parent_node.set_left_idx(Some(curr_child_idx));
+                    self.set_left_idx(curr_parent_idx, Some(curr_child_idx));
                 }
             }
         }
@@ -189,114 +422,441 @@ impl<
         self.vec.len()
     }
 
+    /// Indexes of free slots awaiting reuse.
+    #[cfg(not(feature = "low_mem_insert"))]
+    pub fn free_idxs(&self) -> impl Iterator<Item = usize> + '_ {
+        self.free_list.iter().map(|idx| idx.usize())
+    }
+
     /// Returns true if the index is occupied, e.g. `Some(node)`.
     pub fn is_occupied(&self, idx: usize) -> bool {
         (idx < self.vec.len()) && (self.vec[idx].is_some())
     }
 
+    /// Returns true if every entry in the arena is occupied, e.g. there are no free slots.
+    /// Scans the whole arena, so only used to cross-check the tree's constant-time equivalent.
+    pub fn is_compact(&self) -> bool {
+        self.vec.iter().all(|n| n.is_some())
+    }
+
+    /// Drop trailing free slots, so that the arena's length is one past the last occupied index.
+    pub fn truncate_free_tail(&mut self) {
+        while let Some(None) = self.vec.last() {
+            self.vec.pop();
+
+            #[cfg(feature = "soa_arena")]
+            self.keys.pop();
+
+            #[cfg(feature = "split_values")]
+            self.vals.pop();
+        }
+
+        #[cfg(not(feature = "low_mem_insert"))]
+        {
+            let len = self.vec.len();
+            self.free_list.retain(|i| i.usize() < len);
+        }
+    }
+
     /// Get the size of an individual arena node, in bytes.
     pub fn node_size(&self) -> usize {
-        core::mem::size_of::<Node<K, V, U>>()
+        core::mem::size_of::<ArenaNode<K, V, U>>()
     }
-}
 
-// Convenience Traits --------------------------------------------------------------------------------------------------
-
-/// Immutable indexing.
-/// Indexed location MUST be occupied.
-impl<K: Default, V: Default, U: Default, const N: usize> Index<usize> for Arena<K, V, U, N> {
-    type Output = Node<K, V, U>;
+    // Node at a known-good index.
+    fn node(&self, idx: usize) -> &ArenaNode<K, V, U> {
+        match &self.vec[idx] {
+            Some(node) => node,
+            None => unreachable!(),
+        }
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        match &self.vec[index] {
+    // Mutable node at a known-good index.
+    fn node_mut(&mut self, idx: usize) -> &mut ArenaNode<K, V, U> {
+        match &mut self.vec[idx] {
             Some(node) => node,
             None => unreachable!(),
         }
     }
 }
 
-/// Mutable indexing
+// Layout Accessors ----------------------------------------------------------------------------------------------------
+
+/// Index-addressed node interface, encapsulates arena layout.
+/// Nodes are interleaved by default, or split into parallel key, value, and link arrays with feature `soa_arena`.
 /// Indexed location MUST be occupied.
-impl<K: Default, V: Default, U: Default, const N: usize> IndexMut<usize> for Arena<K, V, U, N> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match self.vec.index_mut(index) {
-            Some(node) => node,
+pub trait NodeArena<K> {
+    /// Get key.
+    fn key(&self, idx: usize) -> &K;
+
+    /// Set key.
+    fn set_key(&mut self, idx: usize, key: K);
+
+    /// Replace key, returning the previous one.
+    fn replace_key(&mut self, idx: usize, key: K) -> K;
+
+    /// Get left child index.
+    fn left_idx(&self, idx: usize) -> Option<usize>;
+
+    /// Set left child index.
+    fn set_left_idx(&mut self, idx: usize, opt_child_idx: Option<usize>);
+
+    /// Get right child index.
+    fn right_idx(&self, idx: usize) -> Option<usize>;
+
+    /// Set right child index.
+    fn set_right_idx(&mut self, idx: usize, opt_child_idx: Option<usize>);
+
+    /// Get subtree size.
+    #[cfg(feature = "fast_rebalance")]
+    fn subtree_size(&self, idx: usize) -> usize;
+
+    /// Set subtree size.
+    #[cfg(feature = "fast_rebalance")]
+    fn set_subtree_size(&mut self, idx: usize, size: usize);
+
+    /// Get subtree height.
+    #[cfg(feature = "avl")]
+    fn height(&self, idx: usize) -> usize;
+
+    /// Set subtree height.
+    #[cfg(feature = "avl")]
+    fn set_height(&mut self, idx: usize, height: usize);
+}
+
+impl<K, V, U: Default + LinkIdx + Ord + PartialEq + PartialOrd, const N: usize> NodeArena<K>
+    for Arena<K, V, U, N>
+{
+    fn key(&self, idx: usize) -> &K {
+        #[cfg(not(feature = "soa_arena"))]
+        {
+            self.node(idx).key()
+        }
+
+        #[cfg(feature = "soa_arena")]
+        match &self.keys[idx] {
+            Some(key) => key,
+            None => unreachable!(),
+        }
+    }
+
+    fn set_key(&mut self, idx: usize, key: K) {
+        #[cfg(not(feature = "soa_arena"))]
+        self.node_mut(idx).set_key(key);
+
+        #[cfg(feature = "soa_arena")]
+        {
+            self.keys[idx] = Some(key);
+        }
+    }
+
+    fn replace_key(&mut self, idx: usize, key: K) -> K {
+        #[cfg(not(feature = "soa_arena"))]
+        {
+            self.node_mut(idx).replace_key(key)
+        }
+
+        #[cfg(feature = "soa_arena")]
+        match &mut self.keys[idx] {
+            Some(old_key) => core::mem::replace(old_key, key),
             None => unreachable!(),
         }
     }
+
+    fn left_idx(&self, idx: usize) -> Option<usize> {
+        self.node(idx).left_idx()
+    }
+
+    fn set_left_idx(&mut self, idx: usize, opt_child_idx: Option<usize>) {
+        self.node_mut(idx).set_left_idx(opt_child_idx);
+    }
+
+    fn right_idx(&self, idx: usize) -> Option<usize> {
+        self.node(idx).right_idx()
+    }
+
+    fn set_right_idx(&mut self, idx: usize, opt_child_idx: Option<usize>) {
+        self.node_mut(idx).set_right_idx(opt_child_idx);
+    }
+
+    #[cfg(feature = "fast_rebalance")]
+    fn subtree_size(&self, idx: usize) -> usize {
+        self.node(idx).subtree_size()
+    }
+
+    #[cfg(feature = "fast_rebalance")]
+    fn set_subtree_size(&mut self, idx: usize, size: usize) {
+        self.node_mut(idx).set_subtree_size(size);
+    }
+
+    #[cfg(feature = "avl")]
+    fn height(&self, idx: usize) -> usize {
+        self.node(idx).height()
+    }
+
+    #[cfg(feature = "avl")]
+    fn set_height(&mut self, idx: usize, height: usize) {
+        self.node_mut(idx).set_height(height);
+    }
 }
 
-impl<
-        K: Ord + Default,
-        V: Default,
-        U: Default + Copy + SmallUnsigned + Ord + PartialEq + PartialOrd,
-        const N: usize,
-    > Default for Arena<K, V, U, N>
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+impl<K: Ord, V, U: Default + LinkIdx + Ord + PartialEq + PartialOrd, const N: usize> Default
+    for Arena<K, V, U, N>
 {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/*
-NOTE: This is draft code for upgrades when `feature(generic_const_exprs)` stabilizes.
-
 // Wrapper Iterators ---------------------------------------------------------------------------------------------------
 
-pub struct ArenaIter<'a, K: Default, V: Default, U, const N: usize> {
-    arena_iter: core::slice::Iter<'a, Option<Node<K, V, U>>>,
+/// Iterator over arena slots, in arena order. Yields `None` for free slots.
+pub struct ArenaIter<'a, K, V, U: LinkIdx> {
+    #[cfg(not(feature = "soa_arena"))]
+    arena_iter: core::slice::Iter<'a, Option<ArenaNode<K, V, U>>>,
+
+    #[cfg(feature = "soa_arena")]
+    keys_iter: core::slice::Iter<'a, Option<K>>,
+
+    #[cfg(feature = "split_values")]
+    vals_iter: core::slice::Iter<'a, Option<V>>,
+
+    #[cfg(feature = "soa_arena")]
+    _links: PhantomData<U>,
 }
 
-impl<'a, K: Default, V: Default, U, const N: usize> ArenaIter<'a, K, V, U, N> {
-    pub fn new(arena: &'a Arena<K, V, U, N>) -> Self {
+impl<'a, K, V, U: LinkIdx> ArenaIter<'a, K, V, U> {
+    fn new<const N: usize>(arena: &'a Arena<K, V, U, N>) -> Self
+    where
+        U: Default,
+    {
         ArenaIter {
+            #[cfg(not(feature = "soa_arena"))]
             arena_iter: arena.vec.iter(),
+
+            #[cfg(feature = "soa_arena")]
+            keys_iter: arena.keys.iter(),
+
+            #[cfg(feature = "split_values")]
+            vals_iter: arena.vals.iter(),
+
+            #[cfg(feature = "soa_arena")]
+            _links: PhantomData,
         }
     }
 }
 
-impl<'a, K: Default, V: Default, U: SmallUnsigned + Copy, const N: usize> Iterator for ArenaIter<'a, K, V, U, N> {
-    type Item = (&'a K, &'a V);
+impl<'a, K, V, U: LinkIdx> Iterator for ArenaIter<'a, K, V, U> {
+    type Item = Option<(&'a K, &'a V)>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.arena_iter.next() {
-            Some(Some(node)) => Some((node.key(), node.val())),
-            _ => None,
+        #[cfg(not(feature = "split_values"))]
+        {
+            self.arena_iter
+                .next()
+                .map(|slot| slot.as_ref().map(|node| (node.key(), node.val())))
+        }
+
+        #[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+        {
+            self.arena_iter
+                .next()
+                .zip(self.vals_iter.next())
+                .map(|(slot, val)| {
+                    slot.as_ref()
+                        .zip(val.as_ref())
+                        .map(|(node, val)| (node.key(), val))
+                })
+        }
+
+        #[cfg(feature = "soa_arena")]
+        {
+            self.keys_iter
+                .next()
+                .zip(self.vals_iter.next())
+                .map(|(key, val)| key.as_ref().zip(val.as_ref()))
         }
     }
 }
 
-pub struct ArenaIterMut<'a, K: Default, V: Default, U, const N: usize> {
-    arena_iter_mut: core::slice::IterMut<'a, Option<Node<K, V, U>>>,
+impl<'a, K, V, U: LinkIdx> ExactSizeIterator for ArenaIter<'a, K, V, U> {
+    fn len(&self) -> usize {
+        #[cfg(not(feature = "soa_arena"))]
+        {
+            self.arena_iter.len()
+        }
+
+        #[cfg(feature = "soa_arena")]
+        {
+            self.keys_iter.len()
+        }
+    }
 }
 
-impl<'a, K: Default, V: Default, U, const N: usize> ArenaIterMut<'a, K, V, U, N> {
-    pub fn new(arena: &'a mut Arena<K, V, U, N>) -> Self {
+/// Iterator over arena slots, in arena order, that allows modifying each value. Yields `None` for free slots.
+pub struct ArenaIterMut<'a, K, V, U: LinkIdx> {
+    #[cfg(not(feature = "soa_arena"))]
+    arena_iter_mut: core::slice::IterMut<'a, Option<ArenaNode<K, V, U>>>,
+
+    #[cfg(feature = "soa_arena")]
+    keys_iter: core::slice::Iter<'a, Option<K>>,
+
+    #[cfg(feature = "split_values")]
+    vals_iter_mut: core::slice::IterMut<'a, Option<V>>,
+
+    #[cfg(feature = "soa_arena")]
+    _links: PhantomData<U>,
+}
+
+impl<'a, K, V, U: LinkIdx> ArenaIterMut<'a, K, V, U> {
+    fn new<const N: usize>(arena: &'a mut Arena<K, V, U, N>, span: Range<usize>) -> Self
+    where
+        U: Default,
+    {
         ArenaIterMut {
-            arena_iter_mut: arena.vec.iter_mut(),
+            #[cfg(not(feature = "soa_arena"))]
+            arena_iter_mut: arena.vec[span.start..span.end].iter_mut(),
+
+            #[cfg(feature = "soa_arena")]
+            keys_iter: arena.keys[span.start..span.end].iter(),
+
+            #[cfg(feature = "split_values")]
+            vals_iter_mut: arena.vals[span.start..span.end].iter_mut(),
+
+            #[cfg(feature = "soa_arena")]
+            _links: PhantomData,
         }
     }
+
+    /// Split into iterators over the first `mid` remaining slots and the rest.
+    #[cfg(feature = "rayon")]
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        #[cfg(not(feature = "soa_arena"))]
+        let (nodes_left, nodes_right) = self.arena_iter_mut.into_slice().split_at_mut(mid);
+
+        #[cfg(feature = "soa_arena")]
+        let (keys_left, keys_right) = self.keys_iter.as_slice().split_at(mid);
+
+        #[cfg(feature = "split_values")]
+        let (vals_left, vals_right) = self.vals_iter_mut.into_slice().split_at_mut(mid);
+
+        (
+            ArenaIterMut {
+                #[cfg(not(feature = "soa_arena"))]
+                arena_iter_mut: nodes_left.iter_mut(),
+
+                #[cfg(feature = "soa_arena")]
+                keys_iter: keys_left.iter(),
+
+                #[cfg(feature = "split_values")]
+                vals_iter_mut: vals_left.iter_mut(),
+
+                #[cfg(feature = "soa_arena")]
+                _links: PhantomData,
+            },
+            ArenaIterMut {
+                #[cfg(not(feature = "soa_arena"))]
+                arena_iter_mut: nodes_right.iter_mut(),
+
+                #[cfg(feature = "soa_arena")]
+                keys_iter: keys_right.iter(),
+
+                #[cfg(feature = "split_values")]
+                vals_iter_mut: vals_right.iter_mut(),
+
+                #[cfg(feature = "soa_arena")]
+                _links: PhantomData,
+            },
+        )
+    }
 }
 
-impl<'a, K: Default, V: Default, U: SmallUnsigned + Copy, const N: usize> Iterator for ArenaIterMut<'a, K, V, U, N> {
-    type Item = (&'a K, &'a mut V);
+impl<'a, K, V, U: LinkIdx> Iterator for ArenaIterMut<'a, K, V, U> {
+    type Item = Option<(&'a K, &'a mut V)>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.arena_iter_mut.next() {
-            Some(Some(node)) => Some(node.get_mut()),
-            _ => None,
+        #[cfg(not(feature = "split_values"))]
+        {
+            self.arena_iter_mut
+                .next()
+                .map(|slot| slot.as_mut().map(|node| node.get_mut()))
+        }
+
+        #[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+        {
+            self.arena_iter_mut
+                .next()
+                .zip(self.vals_iter_mut.next())
+                .map(|(slot, val)| {
+                    slot.as_ref()
+                        .zip(val.as_mut())
+                        .map(|(node, val)| (node.key(), val))
+                })
+        }
+
+        #[cfg(feature = "soa_arena")]
+        {
+            self.keys_iter
+                .next()
+                .zip(self.vals_iter_mut.next())
+                .map(|(key, val)| key.as_ref().zip(val.as_mut()))
+        }
+    }
+}
+
+impl<'a, K, V, U: LinkIdx> DoubleEndedIterator for ArenaIterMut<'a, K, V, U> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        #[cfg(not(feature = "split_values"))]
+        {
+            self.arena_iter_mut
+                .next_back()
+                .map(|slot| slot.as_mut().map(|node| node.get_mut()))
+        }
+
+        #[cfg(all(feature = "split_values", not(feature = "soa_arena")))]
+        {
+            self.arena_iter_mut
+                .next_back()
+                .zip(self.vals_iter_mut.next_back())
+                .map(|(slot, val)| {
+                    slot.as_ref()
+                        .zip(val.as_mut())
+                        .map(|(node, val)| (node.key(), val))
+                })
+        }
+
+        #[cfg(feature = "soa_arena")]
+        {
+            self.keys_iter
+                .next_back()
+                .zip(self.vals_iter_mut.next_back())
+                .map(|(key, val)| key.as_ref().zip(val.as_mut()))
+        }
+    }
+}
+
+impl<'a, K, V, U: LinkIdx> ExactSizeIterator for ArenaIterMut<'a, K, V, U> {
+    fn len(&self) -> usize {
+        #[cfg(not(feature = "soa_arena"))]
+        {
+            self.arena_iter_mut.len()
+        }
+
+        #[cfg(feature = "soa_arena")]
+        {
+            self.keys_iter.len()
         }
     }
 }
-*/
 
 // Test ----------------------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
 mod tests {
-    use super::Arena;
+    use super::{Arena, NodeArena};
     use crate::tree::node::NodeGetHelper;
-    use crate::tree::node_dispatch::SmallNode;
     use core::mem::size_of_val;
     use smallnum::small_unsigned;
     use tinyvec::array_vec;
@@ -315,8 +875,8 @@ mod tests {
         assert_eq!(n_2_idx, 1);
         assert_eq!(n_3_idx, 2);
 
-        let n_2_removed = arena.remove(n_2_idx).unwrap();
-        assert_eq!(n_2_removed.key(), &2);
+        let (n_2_key, _) = arena.remove(n_2_idx).unwrap();
+        assert_eq!(n_2_key, 2);
         assert!(arena.vec[1].is_none());
 
         let n_4_idx = arena.add(4, "n/a");
@@ -330,18 +890,17 @@ mod tests {
     fn test_index_mut() {
         let mut arena: Arena<isize, &str, small_unsigned!(CAPACITY), CAPACITY> = Arena::new();
         let n_1_idx = arena.add(1, "n/a");
-        assert_eq!(arena[n_1_idx].val(), &"n/a");
-        let n_1_mut_ref = &mut arena[n_1_idx];
-        n_1_mut_ref.set_val("This is a value. There are many like it but this one is mine.");
-        assert_ne!(arena[n_1_idx].val(), &"n/a");
+        assert_eq!(arena.val(n_1_idx), &"n/a");
+        let (_, n_1_mut_ref) = arena.get_mut(n_1_idx);
+        *n_1_mut_ref = "This is a value. There are many like it but this one is mine.";
+        assert_ne!(arena.val(n_1_idx), &"n/a");
     }
 
     #[test]
     fn test_index_1() {
         let mut arena: Arena<u64, &str, small_unsigned!(CAPACITY), CAPACITY> = Arena::new();
         let n_1_idx = arena.add(0xD00DFEED_u64, "n/a");
-        let n_1_ref = &arena[n_1_idx];
-        assert_eq!(n_1_ref.key(), &0xD00DFEED_u64);
+        assert_eq!(arena.key(n_1_idx), &0xD00DFEED_u64);
     }
 
     #[test]
@@ -349,7 +908,7 @@ mod tests {
     fn test_index_2() {
         let mut arena: Arena<u64, &str, small_unsigned!(CAPACITY), CAPACITY> = Arena::new();
         arena.add(0xD00DFEED_u64, "n/a");
-        let _ = &arena[1]; // OOB
+        let _ = arena.key(1); // OOB
     }
 
     #[test]
@@ -377,14 +936,13 @@ mod tests {
         let n_2_idx = arena.add(2, "n/a");
         arena.add(1, "n/a");
 
-        let n_2 = &mut arena[n_2_idx];
-        n_2.set_left_idx(Some(2));
-        n_2.set_right_idx(Some(0));
+        arena.set_left_idx(n_2_idx, Some(2));
+        arena.set_right_idx(n_2_idx, Some(0));
 
         // Unsorted (insertion/"physical" order)
-        assert_eq!(arena.vec[0].as_ref().unwrap().key(), &3);
-        assert_eq!(arena.vec[1].as_ref().unwrap().key(), &2);
-        assert_eq!(arena.vec[2].as_ref().unwrap().key(), &1);
+        assert_eq!(arena.key(0), &3);
+        assert_eq!(arena.key(1), &2);
+        assert_eq!(arena.key(2), &1);
 
         // Would be supplied for the above tree
         let sort_metadata = array_vec! { [NodeGetHelper<usize>; CAPACITY] =>
@@ -393,12 +951,12 @@ mod tests {
             NodeGetHelper::new(Some(0), Some(1), false),
         };
 
-        arena.sort(1, sort_metadata);
+        arena.sort(1, sort_metadata.into_iter().collect());
 
         // Sorted ("logical" order)
-        assert_eq!(arena.vec[0].as_ref().unwrap().key(), &1);
-        assert_eq!(arena.vec[1].as_ref().unwrap().key(), &2);
-        assert_eq!(arena.vec[2].as_ref().unwrap().key(), &3);
+        assert_eq!(arena.key(0), &1);
+        assert_eq!(arena.key(1), &2);
+        assert_eq!(arena.key(2), &3);
     }
 
     #[test]
@@ -432,6 +990,74 @@ mod tests {
         */
     }
 
+    #[cfg(feature = "split_values")]
+    #[test]
+    fn test_split_values() {
+        use super::ArenaNode;
+        use core::mem::size_of;
+
+        // Value doesn't contribute to node size
+        assert_eq!(
+            size_of::<ArenaNode<u64, [u8; 256], small_unsigned!(CAPACITY)>>(),
+            size_of::<ArenaNode<u64, (), small_unsigned!(CAPACITY)>>()
+        );
+
+        // Values follow their nodes through removal, reuse, and sort
+        let mut arena: Arena<usize, &str, small_unsigned!(CAPACITY), CAPACITY> = Arena::new();
+        let n_2_idx = arena.add(2, "two");
+        let n_1_idx = arena.add(1, "one");
+        assert_eq!(arena.remove(n_2_idx), Some((2, "two")));
+        assert_eq!(arena.add(3, "three"), n_2_idx);
+        assert_eq!(arena.get(n_1_idx), (&1, &"one"));
+
+        arena.set_right_idx(n_1_idx, Some(n_2_idx));
+        let sort_metadata = array_vec! { [NodeGetHelper<usize>; CAPACITY] =>
+            NodeGetHelper::new(Some(n_1_idx), None, false),
+            NodeGetHelper::new(Some(n_2_idx), Some(n_1_idx), true),
+        };
+        arena.sort(n_1_idx, sort_metadata.into_iter().collect());
+        assert_eq!(arena.get(0), (&1, &"one"));
+        assert_eq!(arena.get(1), (&3, &"three"));
+    }
+
+    #[cfg(feature = "soa_arena")]
+    #[test]
+    fn test_soa_arena() {
+        use super::ArenaNode;
+        use core::mem::size_of;
+
+        // Neither key nor value contributes to node size, only links remain
+        assert_eq!(
+            size_of::<ArenaNode<[u8; 256], [u8; 256], small_unsigned!(CAPACITY)>>(),
+            size_of::<ArenaNode<(), (), small_unsigned!(CAPACITY)>>()
+        );
+
+        // Keys follow their links through removal, reuse, and sort
+        let mut arena: Arena<usize, &str, small_unsigned!(CAPACITY), CAPACITY> = Arena::new();
+        let n_3_idx = arena.add(3, "three");
+        let n_1_idx = arena.add(1, "one");
+        let n_2_idx = arena.add(2, "two");
+        assert_eq!(arena.replace_key(n_1_idx, 0), 1);
+        assert_eq!(arena.remove(n_1_idx), Some((0, "one")));
+        assert_eq!(arena.add(1, "one"), n_1_idx);
+
+        arena.set_left_idx(n_2_idx, Some(n_1_idx));
+        arena.set_right_idx(n_2_idx, Some(n_3_idx));
+        let sort_metadata = array_vec! { [NodeGetHelper<usize>; CAPACITY] =>
+            NodeGetHelper::new(Some(n_1_idx), Some(n_2_idx), false),
+            NodeGetHelper::new(Some(n_2_idx), None, false),
+            NodeGetHelper::new(Some(n_3_idx), Some(n_2_idx), true),
+        };
+        let root_idx = arena.sort(n_2_idx, sort_metadata.into_iter().collect());
+        assert_eq!(root_idx, 1);
+        assert_eq!(arena.left_idx(root_idx), Some(0));
+        assert_eq!(arena.right_idx(root_idx), Some(2));
+        assert_eq!(
+            arena.iter().flatten().collect::<Vec<_>>(),
+            vec![(&1, &"one"), (&2, &"two"), (&3, &"three")]
+        );
+    }
+
     #[test]
     fn test_arena_next_back() {
         let mut arena: Arena<usize, usize, small_unsigned!(CAPACITY), CAPACITY> = Arena::new();
@@ -440,14 +1066,15 @@ mod tests {
         assert_eq!(1, arena.add(1, 1));
         assert_eq!(2, arena.add(2, 2));
 
-        assert_eq!(1, *arena.remove(1).unwrap().key());
+        assert_eq!((1, 1), arena.remove(1).unwrap());
         assert_eq!(1, arena.add(3, 3));
 
-        let mut iter_mut = arena.iter_mut();
+        let len = arena.len();
+        let mut iter_mut = arena.iter_mut_span(0..len);
         assert_eq!(iter_mut.len(), 3);
-        assert_eq!(&2, iter_mut.next_back().unwrap().as_ref().unwrap().key());
-        assert_eq!(&3, iter_mut.next_back().unwrap().as_ref().unwrap().key());
-        assert_eq!(&0, iter_mut.next_back().unwrap().as_ref().unwrap().key());
+        assert_eq!(&2, iter_mut.next_back().unwrap().unwrap().0);
+        assert_eq!(&3, iter_mut.next_back().unwrap().unwrap().0);
+        assert_eq!(&0, iter_mut.next_back().unwrap().unwrap().0);
         assert!(iter_mut.next_back().is_none());
     }
 }
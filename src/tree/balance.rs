@@ -0,0 +1,250 @@
+#[allow(unused_imports)] // micromath only used if `no_std`
+use micromath::F32Ext;
+
+/// Rebalance parameter alpha, as passed to a [`BalancePolicy`].
+/// See [`SgMap::set_rebal_param`][crate::map::SgMap::set_rebal_param].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalanceParams {
+    pub(crate) alpha_num: f32,
+    pub(crate) alpha_denom: f32,
+}
+
+impl BalanceParams {
+    /// Alpha numerator.
+    pub const fn alpha_num(&self) -> f32 {
+        self.alpha_num
+    }
+
+    /// Alpha denominator.
+    pub const fn alpha_denom(&self) -> f32 {
+        self.alpha_denom
+    }
+
+    /// Maximum depth of an alpha-height-balanced tree with `size` nodes, e.g. `floor(log_(1/alpha)(size))`.
+    pub fn height_bound(&self, size: usize) -> usize {
+        // log base (1/alpha), hence (denom/num)
+        (size as f32).log(self.alpha_denom / self.alpha_num).floor() as usize
+    }
+}
+
+/// Rebalancing decisions, see [`SgMap::with_balance_policy`][crate::map::SgMap::with_balance_policy].
+///
+/// After each insertion, [`needs_rebalance`][BalancePolicy::needs_rebalance] decides if a scapegoat search is due.
+/// If so, ancestors of the new node are visited bottom-up until [`is_scapegoat`][BalancePolicy::is_scapegoat]
+/// holds (or the root is reached), and that ancestor's subtree is rebuilt.
+/// After each removal, [`needs_rebuild_after_remove`][BalancePolicy::needs_rebuild_after_remove] decides if
+/// the entire tree is rebuilt.
+///
+/// Ready-made policies: [`WeightBalancePolicy`] (default), [`HeightBalancePolicy`], [`MaxDepthPolicy`],
+/// [`DeletionCountPolicy`], and [`NeverRebalancePolicy`].
+///
+/// A policy is a type parameter of the map or set, so calls are statically dispatched
+/// and the default (a zero-sized type) adds nothing to the map's footprint.
+///
+/// Policies only affect performance: every tree operation stays correct for any policy.
+/// Policies are unused if feature `weight_balanced` or `avl` is enabled, the tree is then rebalanced by rotations.
+pub trait BalancePolicy: Copy {
+    /// Returns `true` if an insertion at `depth` should trigger a scapegoat search,
+    /// given the tree's maximum size since the last full rebuild.
+    /// Defaults to the alpha-height bound.
+    fn needs_rebalance(&self, depth: usize, max_size: usize, params: BalanceParams) -> bool {
+        depth > params.height_bound(max_size)
+    }
+
+    /// Returns `true` if a parent, `height` levels above the inserted node, should be rebuilt.
+    /// `child_size` is the size of the subtree containing the inserted node, `parent_size` the size of the parent's.
+    fn is_scapegoat(
+        &self,
+        child_size: usize,
+        parent_size: usize,
+        height: usize,
+        params: BalanceParams,
+    ) -> bool;
+
+    /// Returns `true` if the entire tree should be rebuilt after a removal.
+    /// Defaults to rebuilding once size falls below half the maximum size since the last full rebuild.
+    fn needs_rebuild_after_remove(
+        &self,
+        size: usize,
+        max_size: usize,
+        params: BalanceParams,
+    ) -> bool {
+        let _ = params;
+        max_size > (2 * size)
+    }
+
+    /// Returns `true` if the policy keeps the tree loosely alpha-height-balanced,
+    /// e.g. no node deeper than `log_(1/alpha)(max_size) + 1`, as checked by [`SgMap::validate`][crate::map::SgMap::validate].
+    /// Defaults to `false`, e.g. no guarantee.
+    fn bounds_height(&self) -> bool {
+        false
+    }
+}
+
+/// Scapegoat is the first ancestor that isn't alpha-weight-balanced.
+/// The algorithm proposed in the [original paper](https://people.csail.mit.edu/rivest/pubs/GR93.pdf)
+/// (Galperin and Rivest, 1993). Default, unless feature `alt_impl` is enabled.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WeightBalancePolicy;
+
+impl BalancePolicy for WeightBalancePolicy {
+    fn is_scapegoat(
+        &self,
+        child_size: usize,
+        parent_size: usize,
+        _height: usize,
+        params: BalanceParams,
+    ) -> bool {
+        (params.alpha_denom * child_size as f32) > (params.alpha_num * parent_size as f32)
+    }
+
+    fn bounds_height(&self) -> bool {
+        true
+    }
+}
+
+/// Scapegoat is the first ancestor whose height exceeds the alpha-height bound of its child's subtree.
+/// An alternate algorithm proposed in Galperin's PhD thesis (1996). Default if feature `alt_impl` is enabled.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeightBalancePolicy;
+
+impl BalancePolicy for HeightBalancePolicy {
+    fn is_scapegoat(
+        &self,
+        child_size: usize,
+        _parent_size: usize,
+        height: usize,
+        params: BalanceParams,
+    ) -> bool {
+        height > params.height_bound(child_size)
+    }
+
+    fn bounds_height(&self) -> bool {
+        true
+    }
+}
+
+/// Rebalance only once an insertion lands deeper than a fixed depth, e.g. to bound worst-case lookup cost directly.
+/// Scapegoat is the first ancestor whose subtree, once rebuilt, shortens the insertion path.
+/// Removals rebuild as per the default.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxDepthPolicy {
+    max_depth: usize,
+}
+
+impl MaxDepthPolicy {
+    /// Policy allowing insertions up to `max_depth` levels below the root.
+    pub const fn new(max_depth: usize) -> Self {
+        MaxDepthPolicy { max_depth }
+    }
+
+    /// Maximum depth before a rebalance is triggered.
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+impl BalancePolicy for MaxDepthPolicy {
+    fn needs_rebalance(&self, depth: usize, _max_size: usize, _params: BalanceParams) -> bool {
+        depth > self.max_depth
+    }
+
+    fn is_scapegoat(
+        &self,
+        _child_size: usize,
+        parent_size: usize,
+        height: usize,
+        _params: BalanceParams,
+    ) -> bool {
+        // Depth of a perfectly balanced subtree with `parent_size` nodes is `floor(log2(parent_size))`
+        let balanced_depth = (usize::BITS - 1 - parent_size.leading_zeros()) as usize;
+        (height + 1) > balanced_depth
+    }
+}
+
+/// Rebuild the entire tree after a fixed number of removals, counted from the maximum size since the last rebuild.
+/// Insertions rebalance as per [`WeightBalancePolicy`].
+#[derive(Debug, Clone, Copy)]
+pub struct DeletionCountPolicy {
+    deletions: usize,
+}
+
+impl DeletionCountPolicy {
+    /// Policy rebuilding once `deletions` elements were removed. A `deletions` of `0` is treated as `1`.
+    pub const fn new(deletions: usize) -> Self {
+        DeletionCountPolicy {
+            deletions: if deletions == 0 { 1 } else { deletions },
+        }
+    }
+
+    /// Number of removals triggering a rebuild.
+    pub const fn deletions(&self) -> usize {
+        self.deletions
+    }
+}
+
+impl BalancePolicy for DeletionCountPolicy {
+    fn is_scapegoat(
+        &self,
+        child_size: usize,
+        parent_size: usize,
+        height: usize,
+        params: BalanceParams,
+    ) -> bool {
+        WeightBalancePolicy.is_scapegoat(child_size, parent_size, height, params)
+    }
+
+    fn needs_rebuild_after_remove(
+        &self,
+        size: usize,
+        max_size: usize,
+        _params: BalanceParams,
+    ) -> bool {
+        max_size.saturating_sub(size) >= self.deletions
+    }
+
+    fn bounds_height(&self) -> bool {
+        true
+    }
+}
+
+/// Never rebalance, e.g. for keys known to arrive in random order, or to compare against a plain BST.
+/// Sorted insertion degrades the tree to a linked list.
+/// [`SgMap::rebalance`][crate::map::SgMap::rebalance] still rebuilds on request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NeverRebalancePolicy;
+
+impl BalancePolicy for NeverRebalancePolicy {
+    fn needs_rebalance(&self, _depth: usize, _max_size: usize, _params: BalanceParams) -> bool {
+        false
+    }
+
+    fn is_scapegoat(
+        &self,
+        _child_size: usize,
+        _parent_size: usize,
+        _height: usize,
+        _params: BalanceParams,
+    ) -> bool {
+        false
+    }
+
+    fn needs_rebuild_after_remove(
+        &self,
+        _size: usize,
+        _max_size: usize,
+        _params: BalanceParams,
+    ) -> bool {
+        false
+    }
+}
+
+/// Policy used unless another is chosen, see [`BalancePolicy`].
+/// [`WeightBalancePolicy`], or [`HeightBalancePolicy`] if feature `alt_impl` is enabled.
+#[cfg(not(feature = "alt_impl"))]
+pub type DefaultBalancePolicy = WeightBalancePolicy;
+
+/// Policy used unless another is chosen, see [`BalancePolicy`].
+/// [`WeightBalancePolicy`], or [`HeightBalancePolicy`] if feature `alt_impl` is enabled.
+#[cfg(feature = "alt_impl")]
+pub type DefaultBalancePolicy = HeightBalancePolicy;
@@ -0,0 +1,336 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::ops::Bound;
+
+use smallnum::SmallUnsigned;
+
+use super::arena::{NodeArena, SgVec};
+use super::balance::BalancePolicy;
+use super::error::SgError;
+use super::tree::{Idx, SgTree};
+
+// Cursor Position -----------------------------------------------------------------------------------------------------
+
+/// Position of a cursor: the root-to-element path of arena indexes, so stepping needs no search from the root.
+/// An empty path is the "ghost" position, logically past both ends of the tree.
+///
+/// Stepping is amortized `O(1)`, like in-order iteration.
+/// Arena indexes of surviving nodes are stable across insertion and removal, so after structural changes (which
+/// may rotate or rebuild) the path is re-derived with a single `O(log n)` search.
+#[derive(Clone)]
+pub(crate) struct CursorPos<const N: usize> {
+    path: SgVec<[Idx; N]>,
+}
+
+impl<const N: usize> CursorPos<N> {
+    /// Position at the first element above `bound`, or the ghost if there is none.
+    pub(crate) fn lower_bound<K, V, Q, P: BalancePolicy>(
+        bst: &SgTree<K, V, N, P>,
+        bound: Bound<&Q>,
+    ) -> Self
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        Self::descend(
+            bst,
+            |key| match bound {
+                Bound::Included(b) => key.borrow() >= b,
+                Bound::Excluded(b) => key.borrow() > b,
+                Bound::Unbounded => true,
+            },
+            true,
+        )
+    }
+
+    /// Position at the last element below `bound`, or the ghost if there is none.
+    pub(crate) fn upper_bound<K, V, Q, P: BalancePolicy>(
+        bst: &SgTree<K, V, N, P>,
+        bound: Bound<&Q>,
+    ) -> Self
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        Self::descend(
+            bst,
+            |key| match bound {
+                Bound::Included(b) => key.borrow() <= b,
+                Bound::Excluded(b) => key.borrow() < b,
+                Bound::Unbounded => true,
+            },
+            false,
+        )
+    }
+
+    /// Arena index of the current element, `None` at the ghost.
+    pub(crate) fn curr(&self) -> Option<usize> {
+        self.path.last().map(|idx| idx.usize())
+    }
+
+    /// Arena index of the element after the current one, without moving.
+    /// At the ghost, this is the first element.
+    pub(crate) fn next_idx<K: Ord, V, P: BalancePolicy>(
+        &self,
+        bst: &SgTree<K, V, N, P>,
+    ) -> Option<usize> {
+        let idx = match self.curr() {
+            Some(idx) => idx,
+            None if bst.is_empty() => return None,
+            None => return Some(bst.min_idx),
+        };
+
+        match bst.arena.right_idx(idx) {
+            Some(right_idx) => Some(Self::leftmost(bst, right_idx)),
+            None => self
+                .path
+                .windows(2)
+                .rev()
+                .find(|w| bst.arena.left_idx(w[0].usize()) == Some(w[1].usize()))
+                .map(|w| w[0].usize()),
+        }
+    }
+
+    /// Arena index of the element before the current one, without moving.
+    /// At the ghost, this is the last element.
+    pub(crate) fn prev_idx<K: Ord, V, P: BalancePolicy>(
+        &self,
+        bst: &SgTree<K, V, N, P>,
+    ) -> Option<usize> {
+        let idx = match self.curr() {
+            Some(idx) => idx,
+            None if bst.is_empty() => return None,
+            None => return Some(bst.max_idx),
+        };
+
+        match bst.arena.left_idx(idx) {
+            Some(left_idx) => Some(Self::rightmost(bst, left_idx)),
+            None => self
+                .path
+                .windows(2)
+                .rev()
+                .find(|w| bst.arena.right_idx(w[0].usize()) == Some(w[1].usize()))
+                .map(|w| w[0].usize()),
+        }
+    }
+
+    /// Step to the next element. From the last element, steps to the ghost; from the ghost, to the first element.
+    pub(crate) fn move_next<K: Ord, V, P: BalancePolicy>(&mut self, bst: &SgTree<K, V, N, P>) {
+        let opt_right_idx = match self.curr() {
+            Some(idx) => bst.arena.right_idx(idx),
+            None => bst.opt_root_idx,
+        };
+
+        match opt_right_idx {
+            Some(right_idx) => {
+                let mut opt_idx = Some(right_idx);
+                while let Some(idx) = opt_idx {
+                    self.path.push(Idx::checked_from(idx));
+                    opt_idx = bst.arena.left_idx(idx);
+                }
+            }
+            None => {
+                // Climb until we leave a left subtree
+                while let Some(child_idx) = self.path.pop() {
+                    match self.path.last() {
+                        Some(parent_idx)
+                            if bst.arena.left_idx(parent_idx.usize())
+                                == Some(child_idx.usize()) =>
+                        {
+                            break
+                        }
+                        _ => continue,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Step to the previous element. From the first element, steps to the ghost; from the ghost, to the last element.
+    pub(crate) fn move_prev<K: Ord, V, P: BalancePolicy>(&mut self, bst: &SgTree<K, V, N, P>) {
+        let opt_left_idx = match self.curr() {
+            Some(idx) => bst.arena.left_idx(idx),
+            None => bst.opt_root_idx,
+        };
+
+        match opt_left_idx {
+            Some(left_idx) => {
+                let mut opt_idx = Some(left_idx);
+                while let Some(idx) = opt_idx {
+                    self.path.push(Idx::checked_from(idx));
+                    opt_idx = bst.arena.right_idx(idx);
+                }
+            }
+            None => {
+                // Climb until we leave a right subtree
+                while let Some(child_idx) = self.path.pop() {
+                    match self.path.last() {
+                        Some(parent_idx)
+                            if bst.arena.right_idx(parent_idx.usize())
+                                == Some(child_idx.usize()) =>
+                        {
+                            break
+                        }
+                        _ => continue,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Insert an entry immediately after the current element, without moving.
+    /// At the ghost, the entry becomes the first element.
+    pub(crate) fn insert_after<K: Ord, V, P: BalancePolicy>(
+        &mut self,
+        bst: &mut SgTree<K, V, N, P>,
+        key: K,
+        val: V,
+    ) -> Result<(), SgError> {
+        let is_ordered = Self::is_between(bst, self.curr(), &key, self.next_idx(bst));
+        self.priv_insert(bst, key, val, is_ordered)
+    }
+
+    /// Insert an entry immediately before the current element, without moving.
+    /// At the ghost, the entry becomes the last element.
+    pub(crate) fn insert_before<K: Ord, V, P: BalancePolicy>(
+        &mut self,
+        bst: &mut SgTree<K, V, N, P>,
+        key: K,
+        val: V,
+    ) -> Result<(), SgError> {
+        let is_ordered = Self::is_between(bst, self.prev_idx(bst), &key, self.curr());
+        self.priv_insert(bst, key, val, is_ordered)
+    }
+
+    /// Remove the current element, moving to the next one. No-op at the ghost.
+    pub(crate) fn remove_current<K: Ord, V, P: BalancePolicy>(
+        &mut self,
+        bst: &mut SgTree<K, V, N, P>,
+    ) -> Option<(K, V)> {
+        let idx = self.curr()?;
+        let opt_next_idx = self.next_idx(bst);
+        let entry = bst.priv_remove_by_idx(idx);
+        self.seek(bst, opt_next_idx);
+        entry
+    }
+
+    /// Remove the current element, moving to the previous one. No-op at the ghost.
+    pub(crate) fn remove_current_and_move_back<K: Ord, V, P: BalancePolicy>(
+        &mut self,
+        bst: &mut SgTree<K, V, N, P>,
+    ) -> Option<(K, V)> {
+        let idx = self.curr()?;
+        let opt_prev_idx = self.prev_idx(bst);
+        let entry = bst.priv_remove_by_idx(idx);
+        self.seek(bst, opt_prev_idx);
+        entry
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    // Descend from the root, tracking the deepest node accepted by `pred`.
+    // Lower bounds continue left of accepted nodes (seeking the smallest), upper bounds continue right.
+    fn descend<K, V, F, P: BalancePolicy>(bst: &SgTree<K, V, N, P>, pred: F, is_lower: bool) -> Self
+    where
+        K: Ord,
+        F: Fn(&K) -> bool,
+    {
+        let mut path = SgVec::<[Idx; N]>::new();
+        let mut found_len = 0;
+        let mut opt_idx = bst.opt_root_idx;
+
+        while let Some(idx) = opt_idx {
+            path.push(Idx::checked_from(idx));
+
+            let accepted = pred(bst.arena.key(idx));
+            if accepted {
+                found_len = path.len();
+            }
+
+            opt_idx = match accepted == is_lower {
+                true => bst.arena.left_idx(idx),
+                false => bst.arena.right_idx(idx),
+            };
+        }
+
+        // Accepted node is an ancestor of (or is) the last visited node
+        path.truncate(found_len);
+        CursorPos { path }
+    }
+
+    // Re-derive the path to the node at `opt_idx`, or move to the ghost if `None`.
+    fn seek<K: Ord, V, P: BalancePolicy>(
+        &mut self,
+        bst: &SgTree<K, V, N, P>,
+        opt_idx: Option<usize>,
+    ) {
+        self.path.clear();
+        if let Some(idx) = opt_idx {
+            let key = bst.arena.key(idx);
+            let mut opt_curr_idx = bst.opt_root_idx;
+            while let Some(curr_idx) = opt_curr_idx {
+                self.path.push(Idx::checked_from(curr_idx));
+                opt_curr_idx = match key.cmp(bst.arena.key(curr_idx)) {
+                    Ordering::Less => bst.arena.left_idx(curr_idx),
+                    Ordering::Greater => bst.arena.right_idx(curr_idx),
+                    Ordering::Equal => break,
+                };
+            }
+
+            debug_assert_eq!(self.curr(), Some(idx));
+        }
+    }
+
+    // Checked insert, then restore the path (the insert may have rotated or rebuilt).
+    fn priv_insert<K: Ord, V, P: BalancePolicy>(
+        &mut self,
+        bst: &mut SgTree<K, V, N, P>,
+        key: K,
+        val: V,
+        is_ordered: bool,
+    ) -> Result<(), SgError> {
+        if !is_ordered {
+            return Err(SgError::UnsortedInput);
+        }
+
+        let opt_curr_idx = self.curr();
+        let replaced = bst.try_insert(key, val)?; // Fails if full, key is known absent
+        debug_assert!(replaced.is_none());
+        self.seek(bst, opt_curr_idx);
+        Ok(())
+    }
+
+    // Whether `key` falls strictly between the keys at `opt_lo_idx` and `opt_hi_idx`, each unbounded if `None`.
+    fn is_between<K: Ord, V, P: BalancePolicy>(
+        bst: &SgTree<K, V, N, P>,
+        opt_lo_idx: Option<usize>,
+        key: &K,
+        opt_hi_idx: Option<usize>,
+    ) -> bool {
+        let above_lo = match opt_lo_idx {
+            Some(lo_idx) => bst.arena.key(lo_idx) < key,
+            None => true,
+        };
+
+        let below_hi = match opt_hi_idx {
+            Some(hi_idx) => key < bst.arena.key(hi_idx),
+            None => true,
+        };
+
+        above_lo && below_hi
+    }
+
+    fn leftmost<K: Ord, V, P: BalancePolicy>(bst: &SgTree<K, V, N, P>, mut idx: usize) -> usize {
+        while let Some(left_idx) = bst.arena.left_idx(idx) {
+            idx = left_idx;
+        }
+        idx
+    }
+
+    fn rightmost<K: Ord, V, P: BalancePolicy>(bst: &SgTree<K, V, N, P>, mut idx: usize) -> usize {
+        while let Some(right_idx) = bst.arena.right_idx(idx) {
+            idx = right_idx;
+        }
+        idx
+    }
+}
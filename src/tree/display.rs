@@ -0,0 +1,82 @@
+use core::fmt::{self, Debug, Display};
+use core::iter::{self, FromIterator};
+
+use super::arena::{Arena, NodeArena, SgVec};
+use super::balance::BalancePolicy;
+use super::tree::{Idx, SgTree};
+
+use smallnum::SmallUnsigned;
+
+/// Sideways ASCII rendering of a tree's structure, see [`SgMap::display_tree`][crate::map::SgMap::display_tree].
+/// Requires feature `stats`.
+pub struct TreeDisplay<'a, K: Ord, V, const N: usize> {
+    arena: &'a Arena<K, V, Idx, N>,
+    opt_root_idx: Option<usize>,
+}
+
+impl<K: Ord + Debug, V, const N: usize> Display for TreeDisplay<'_, K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arena = self.arena;
+
+        // Per ancestor below the root: `true` if it's its parent's last child, e.g. no further siblings to connect
+        let mut lasts = SgVec::<[bool; N]>::new();
+
+        if let Some(root_idx) = self.opt_root_idx {
+            // Pre-order, left before right: node index, depth, is right child, is last child
+            let mut worklist = SgVec::<[(Idx, Idx, bool, bool); N]>::from_iter(iter::once((
+                Idx::checked_from(root_idx),
+                0,
+                false,
+                true,
+            )));
+
+            while let Some((idx, depth, is_right, is_last)) = worklist.pop() {
+                let (idx, depth) = (idx.usize(), depth.usize());
+
+                if depth > 0 {
+                    lasts.truncate(depth - 1);
+                    for ancestor_is_last in lasts.iter() {
+                        f.write_str(if *ancestor_is_last { "    " } else { "|   " })?;
+                    }
+
+                    f.write_str(if is_last { "`-- " } else { "|-- " })?;
+                    f.write_str(if is_right { "R: " } else { "L: " })?;
+                    lasts.push(is_last);
+                }
+
+                writeln!(f, "{:?}", arena.key(idx))?;
+
+                let (opt_left_idx, opt_right_idx) = (arena.left_idx(idx), arena.right_idx(idx));
+                if let Some(right_idx) = opt_right_idx {
+                    worklist.push((
+                        Idx::checked_from(right_idx),
+                        Idx::checked_from(depth + 1),
+                        true,
+                        true,
+                    ));
+                }
+
+                if let Some(left_idx) = opt_left_idx {
+                    worklist.push((
+                        Idx::checked_from(left_idx),
+                        Idx::checked_from(depth + 1),
+                        false,
+                        opt_right_idx.is_none(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: Ord + Debug, V, const N: usize, P: BalancePolicy> SgTree<K, V, N, P> {
+    /// Render the tree's structure sideways as indented ASCII, one key (`Debug` formatted) per line.
+    pub fn display_tree(&self) -> TreeDisplay<'_, K, V, N> {
+        TreeDisplay {
+            arena: &self.arena,
+            opt_root_idx: self.opt_root_idx,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use core::fmt::{self, Debug, Write};
+use core::iter::{self, FromIterator};
+
+use super::arena::{NodeArena, SgVec};
+use super::balance::BalancePolicy;
+use super::tree::{Idx, SgTree};
+
+use smallnum::SmallUnsigned;
+
+// Escapes output for use inside a double-quoted DOT string.
+struct DotEscape<'a, W: Write>(&'a mut W);
+
+impl<W: Write> Write for DotEscape<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                _ => self.0.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: Ord + Debug, V, const N: usize, P: BalancePolicy> SgTree<K, V, N, P> {
+    /// Write the tree's structure as a Graphviz DOT digraph.
+    /// Each node is labeled with its key (`Debug` formatted) and arena index, each edge with `L` or `R`.
+    pub fn to_dot<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str("digraph {\n    node [shape=box];\n")?;
+
+        if let Some(root_idx) = self.opt_root_idx {
+            // Pre-order, left before right
+            let mut worklist =
+                SgVec::<[Idx; N]>::from_iter(iter::once(Idx::checked_from(root_idx)));
+            while let Some(idx) = worklist.pop() {
+                let idx = idx.usize();
+
+                write!(writer, "    n{} [label=\"", idx)?;
+                write!(DotEscape(writer), "{:?}", self.arena.key(idx))?;
+                writeln!(writer, "\\n#{}\"];", idx)?;
+
+                let children = [
+                    ("L", self.arena.left_idx(idx)),
+                    ("R", self.arena.right_idx(idx)),
+                ];
+                for (side, child_idx) in children
+                    .iter()
+                    .filter_map(|(side, opt_idx)| opt_idx.map(|i| (side, i)))
+                {
+                    writeln!(
+                        writer,
+                        "    n{} -> n{} [label=\"{}\"];",
+                        idx, child_idx, side
+                    )?;
+                }
+
+                for child_idx in children.iter().rev().filter_map(|(_, opt_idx)| *opt_idx) {
+                    worklist.push(Idx::checked_from(child_idx));
+                }
+            }
+        }
+
+        writer.write_str("}\n")
+    }
+}
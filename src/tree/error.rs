@@ -1,3 +1,5 @@
+use core::fmt;
+
 /// Errors for fallible operations.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[non_exhaustive]
@@ -12,17 +14,14 @@ pub enum SgError {
     /// Requested operation cannot complete, heap storage is full.
     HeapCapacityExceeded,
     */
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved3,
+    /// Input isn't in strictly ascending order, as required by sorted bulk construction or cursor insertion.
+    UnsortedInput,
 
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved4,
+    /// Rebalance tuning isn't supported, feature `weight_balanced` or `avl` rebalances by rotations instead.
+    RotationBalanced,
 
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved5,
+    /// Requested operation needs distinct keys, but two or more are equal.
+    DuplicateKeys,
 
     /// Reserved for future use
     #[doc(hidden)]
@@ -36,6 +35,34 @@ pub enum SgError {
     RebalanceFactorOutOfRange,
 }
 
+impl fmt::Display for SgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SgError::MaximumCapacityExceeded => write!(f, "maximum supported capacity exceeded"),
+            SgError::StackCapacityExceeded => write!(f, "stack storage capacity exceeded"),
+            SgError::UnsortedInput => write!(f, "input not in strictly ascending order"),
+            SgError::RebalanceFactorOutOfRange => write!(f, "rebalance factor out of range"),
+            SgError::RotationBalanced => {
+                write!(
+                    f,
+                    "rebalance tuning unsupported by rotation-based balancing"
+                )
+            }
+            SgError::DuplicateKeys => write!(f, "duplicate keys"),
+            SgError::Reserved6 | SgError::Reserved7 => {
+                write!(f, "reserved error")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SgError {}
+
+// Required by `rkyv` error sources
+#[cfg(all(feature = "rkyv", not(feature = "std")))]
+impl core::error::Error for SgError {}
+
 /*
 
 Requires nightly feature:
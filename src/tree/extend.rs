@@ -0,0 +1,27 @@
+/// Outcome of [`SgMap::try_extend`][crate::map::SgMap::try_extend] and
+/// [`SgSet::try_extend`][crate::set::SgSet::try_extend].
+///
+/// Items are consumed in iteration order, so a batch that stopped due to capacity can be resumed
+/// at offset [`consumed`][ExtendReport::consumed] once room is made.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct ExtendReport {
+    pub(crate) inserted: usize,
+    pub(crate) updated: usize,
+}
+
+impl ExtendReport {
+    /// Number of items added under a new key.
+    pub const fn inserted(&self) -> usize {
+        self.inserted
+    }
+
+    /// Number of items that replaced an existing key.
+    pub const fn updated(&self) -> usize {
+        self.updated
+    }
+
+    /// Total number of items stored, e.g. the offset of the first item not stored.
+    pub const fn consumed(&self) -> usize {
+        self.inserted + self.updated
+    }
+}
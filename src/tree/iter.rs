@@ -1,185 +1,222 @@
 use core::iter::FusedIterator;
+use core::ops::Range;
 
-use tinyvec::ArrayVec;
+use smallnum::SmallUnsigned;
 
-use super::node::Node;
-use super::node_dispatch::SmallNode;
+use super::arena::{Arena, ArenaIterMut, NodeArena, SgVec};
+use super::balance::BalancePolicy;
 use super::tree::{Idx, SgTree};
 
 // Immutable Reference Iterator ----------------------------------------------------------------------------------------
 
-/// Uses iterative in-order tree traversal algorithm.
-/// Maintains a small stack of arena indexes (won't contain all indexes simultaneously for a balanced tree).
-pub struct Iter<'a, K: Default, V: Default, const N: usize> {
-    bst: &'a SgTree<K, V, N>,
-    idx_stack: ArrayVec<[usize; N]>,
+/// Uses iterative in-order tree traversal algorithm, from both ends.
+/// Maintains two small stacks of arena indexes (won't contain all indexes simultaneously for a balanced tree),
+/// one per traversal frontier. Frontiers stop once the combined count of yielded items reaches the tree's length.
+pub struct Iter<'a, K, V, const N: usize> {
+    arena: &'a Arena<K, V, Idx, N>,
+    idx_stack: SgVec<[Idx; N]>,
+    rev_idx_stack: SgVec<[Idx; N]>,
     total_cnt: usize,
     spent_cnt: usize,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iter<'a, K, V, N> {
-    pub fn new(bst: &'a SgTree<K, V, N>) -> Self {
+impl<'a, K: Ord, V, const N: usize> Iter<'a, K, V, N> {
+    pub fn new<P: BalancePolicy>(bst: &'a SgTree<K, V, N, P>) -> Self {
         let mut ordered_iter = Iter {
-            bst,
-            idx_stack: ArrayVec::<[usize; N]>::new(),
+            arena: &bst.arena,
+            idx_stack: SgVec::<[Idx; N]>::new(),
+            rev_idx_stack: SgVec::<[Idx; N]>::new(),
             total_cnt: bst.len(),
             spent_cnt: 0,
         };
 
-        if let Some(root_idx) = ordered_iter.bst.opt_root_idx {
-            let mut curr_idx = root_idx;
-            loop {
-                let node = &ordered_iter.bst.arena[curr_idx];
-                match node.left_idx() {
-                    Some(lt_idx) => {
-                        ordered_iter.idx_stack.push(curr_idx);
-                        curr_idx = lt_idx;
-                    }
-                    None => {
-                        ordered_iter.idx_stack.push(curr_idx);
-                        break;
-                    }
-                }
-            }
+        ordered_iter.push_left_spine(bst.opt_root_idx);
+        ordered_iter.push_right_spine(bst.opt_root_idx);
+        ordered_iter
+    }
+
+    // Push a subtree's root and all its leftmost descendants, smallest on top.
+    fn push_left_spine(&mut self, mut opt_idx: Option<usize>) {
+        while let Some(idx) = opt_idx {
+            self.idx_stack.push(Idx::checked_from(idx));
+            opt_idx = self.arena.left_idx(idx);
         }
+    }
 
-        ordered_iter
+    // Push a subtree's root and all its rightmost descendants, largest on top.
+    fn push_right_spine(&mut self, mut opt_idx: Option<usize>) {
+        while let Some(idx) = opt_idx {
+            self.rev_idx_stack.push(Idx::checked_from(idx));
+            opt_idx = self.arena.right_idx(idx);
+        }
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for Iter<'a, K, V, N> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.idx_stack.pop() {
-            Some(pop_idx) => {
-                let node = &self.bst.arena[pop_idx];
-                if let Some(gt_idx) = node.right_idx() {
-                    let mut curr_idx = gt_idx;
-                    loop {
-                        let node = &self.bst.arena[curr_idx];
-                        match node.left_idx() {
-                            Some(lt_idx) => {
-                                self.idx_stack.push(curr_idx);
-                                curr_idx = lt_idx;
-                            }
-                            None => {
-                                self.idx_stack.push(curr_idx);
-                                break;
-                            }
-                        }
-                    }
-                }
-
-                let node = &self.bst.arena[pop_idx];
-                self.spent_cnt += 1;
-                Some((node.key(), node.val()))
-            }
-            None => None,
+        if self.spent_cnt == self.total_cnt {
+            return None;
+        }
+
+        let pop_idx = self.idx_stack.pop()?.usize();
+        self.push_left_spine(self.arena.right_idx(pop_idx));
+        self.spent_cnt += 1;
+        Some(self.arena.get(pop_idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.total_cnt - self.spent_cnt;
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for Iter<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.spent_cnt == self.total_cnt {
+            return None;
         }
+
+        let pop_idx = self.rev_idx_stack.pop()?.usize();
+        self.push_right_spine(self.arena.left_idx(pop_idx));
+        self.spent_cnt += 1;
+        Some(self.arena.get(pop_idx))
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for Iter<'a, K, V, N> {
     fn len(&self) -> usize {
         debug_assert!(self.spent_cnt <= self.total_cnt);
         self.total_cnt - self.spent_cnt
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Iter<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> FusedIterator for Iter<'a, K, V, N> {}
 
 // Mutable Reference Iterator ------------------------------------------------------------------------------------------
 
 pub struct IterMut<'a, K, V, const N: usize> {
-    arena_iter_mut: core::slice::IterMut<'a, Option<Node<K, V, Idx>>>,
+    arena_iter_mut: ArenaIterMut<'a, K, V, Idx>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> IterMut<'a, K, V, N> {
-    pub fn new(bst: &'a mut SgTree<K, V, N>) -> Self {
+impl<'a, K: Ord, V, const N: usize> IterMut<'a, K, V, N> {
+    pub fn new<P: BalancePolicy>(bst: &'a mut SgTree<K, V, N, P>) -> Self {
+        // Sorted nodes occupy the leading slots, free slots may follow
         bst.sort_arena();
+        let len = bst.len();
+        IterMut::new_span(bst, 0..len)
+    }
+
+    // Iterate over a span of arena slots, assumed sorted and fully occupied.
+    pub(super) fn new_span<P>(bst: &'a mut SgTree<K, V, N, P>, span: Range<usize>) -> Self {
         IterMut {
-            arena_iter_mut: bst.arena.iter_mut(),
+            arena_iter_mut: bst.arena.iter_mut_span(span),
         }
     }
+
+    /// Split into iterators over the first `mid` remaining items and the rest.
+    #[cfg(feature = "rayon")]
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let (left, right) = self.arena_iter_mut.split_at(mid);
+        (
+            IterMut {
+                arena_iter_mut: left,
+            },
+            IterMut {
+                arena_iter_mut: right,
+            },
+        )
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> Iterator for IterMut<'a, K, V, N> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.arena_iter_mut.next() {
-            Some(Some(node)) => Some(node.get_mut()),
+            Some(Some(entry)) => Some(entry),
             _ => None,
         }
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> DoubleEndedIterator
-    for IterMut<'a, K, V, N>
-{
+impl<'a, K: Ord, V, const N: usize> DoubleEndedIterator for IterMut<'a, K, V, N> {
     fn next_back(&mut self) -> Option<Self::Item> {
         match self.arena_iter_mut.next_back() {
-            Some(Some(node)) => Some(node.get_mut()),
+            Some(Some(entry)) => Some(entry),
             _ => None,
         }
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize> ExactSizeIterator for IterMut<'a, K, V, N> {
     fn len(&self) -> usize {
         self.arena_iter_mut.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for IterMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize> FusedIterator for IterMut<'a, K, V, N> {}
 
 // Consuming Iterator --------------------------------------------------------------------------------------------------
 
 /// Cheats a little by using internal flattening logic to sort, instead of re-implementing proper traversal.
-/// Maintains a shrinking list of arena indexes, initialized with all of them.
-pub struct IntoIter<K: Default, V: Default, const N: usize> {
-    bst: SgTree<K, V, N>,
-    sorted_idxs: ArrayVec<[usize; N]>,
+/// Maintains a shrinking list of arena indexes, initialized with all of them, consumed from either end.
+/// Nodes are taken straight out of the arena, links are left dangling since the tree is gone.
+pub struct IntoIter<K, V, const N: usize> {
+    arena: Arena<K, V, Idx, N>,
+    sorted_idxs: <SgVec<[usize; N]> as IntoIterator>::IntoIter,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> IntoIter<K, V, N> {
-    pub fn new(bst: SgTree<K, V, N>) -> Self {
-        let mut ordered_iter = IntoIter {
-            bst,
-            sorted_idxs: ArrayVec::<[usize; N]>::new(),
+impl<K: Ord, V, const N: usize> IntoIter<K, V, N> {
+    pub fn new<P: BalancePolicy>(bst: SgTree<K, V, N, P>) -> Self {
+        let sorted_idxs = match bst.opt_root_idx {
+            Some(root_idx) => bst.flatten_subtree_to_sorted_idxs(root_idx),
+            None => SgVec::<[usize; N]>::new(),
         };
 
-        if let Some(root_idx) = ordered_iter.bst.opt_root_idx {
-            ordered_iter.sorted_idxs = ordered_iter.bst.flatten_subtree_to_sorted_idxs(root_idx);
-            ordered_iter.sorted_idxs.reverse();
+        IntoIter {
+            arena: bst.arena,
+            sorted_idxs: sorted_idxs.into_iter(),
         }
+    }
 
-        ordered_iter
+    // Remove a node by arena index, indexes of other nodes are unaffected.
+    fn take(&mut self, opt_idx: Option<usize>) -> Option<(K, V)> {
+        match self.arena.remove(opt_idx?) {
+            Some((key, val)) => Some((key, val)),
+            None => {
+                debug_assert!(false, "Use of invalid index in consuming iterator!");
+                None
+            }
+        }
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize> Iterator for IntoIter<K, V, N> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.sorted_idxs.pop() {
-            Some(idx) => match self.bst.priv_remove_by_idx(idx) {
-                Some((key, val)) => Some((key, val)),
-                None => {
-                    debug_assert!(false, "Use of invalid index in consuming iterator!");
-                    None
-                }
-            },
-            None => None,
-        }
+        let opt_idx = self.sorted_idxs.next();
+        self.take(opt_idx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sorted_idxs.size_hint()
+    }
+}
+
+impl<K: Ord, V, const N: usize> DoubleEndedIterator for IntoIter<K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let opt_idx = self.sorted_idxs.next_back();
+        self.take(opt_idx)
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {
     fn len(&self) -> usize {
         self.sorted_idxs.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoIter<K, V, N> {}
+impl<K: Ord, V, const N: usize> FusedIterator for IntoIter<K, V, N> {}
@@ -0,0 +1,132 @@
+/// Per-call work limit for [`SgMap::maintenance`][crate::map::SgMap::maintenance] and
+/// [`SgSet::maintenance`][crate::set::SgSet::maintenance], measured in tree nodes visited.
+///
+/// Each maintenance task is all-or-nothing: it's only started if its full cost fits in the remaining budget.
+/// Costs are nominal, a rebuild or compaction is charged one node per element, so the budget bounds
+/// how much work is started but is **not** a worst case execution time bound:
+/// compaction sorts the arena (`O(n log n)`) after looking up each node (`O(n * h)` for tree height `h`).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct MaintenanceBudget {
+    nodes: usize,
+}
+
+impl MaintenanceBudget {
+    /// Budget allowing up to `nodes` tree nodes to be visited.
+    pub const fn nodes(nodes: usize) -> Self {
+        MaintenanceBudget { nodes }
+    }
+
+    /// Budget without limit, all pending work is performed.
+    pub const fn unlimited() -> Self {
+        MaintenanceBudget { nodes: usize::MAX }
+    }
+
+    /// Remaining number of nodes that may be visited.
+    pub const fn remaining(&self) -> usize {
+        self.nodes
+    }
+
+    /// Deduct `cost` nodes from the budget, if affordable.
+    /// Returns `false`, leaving the budget unchanged, if it isn't.
+    pub(crate) fn try_spend(&mut self, cost: usize) -> bool {
+        match self.nodes.checked_sub(cost) {
+            Some(remaining) => {
+                self.nodes = remaining;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Arena slot usage, see [`SgMap::fragmentation`][crate::map::SgMap::fragmentation].
+///
+/// Removals leave free slots behind, reused by later insertions.
+/// [`SgMap::maintenance`][crate::map::SgMap::maintenance] compacts the arena, sorting live nodes by key.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct ArenaFragmentation {
+    pub(crate) live: usize,
+    pub(crate) free_slots: usize,
+    pub(crate) longest_live_run: usize,
+    pub(crate) live_spread: usize,
+}
+
+impl ArenaFragmentation {
+    /// Number of live nodes, e.g. elements.
+    pub const fn live(&self) -> usize {
+        self.live
+    }
+
+    /// Number of free slots interleaved with live ones, e.g. holes below the highest live slot.
+    pub const fn free_slots(&self) -> usize {
+        self.free_slots
+    }
+
+    /// Length of the longest run of contiguous live slots.
+    pub const fn longest_live_run(&self) -> usize {
+        self.longest_live_run
+    }
+
+    /// Number of slots from the lowest to the highest live slot, inclusive. Equals [`live`][Self::live] if compact.
+    pub const fn live_spread(&self) -> usize {
+        self.live_spread
+    }
+
+    /// Returns `true` if there are no free slots between live ones.
+    pub const fn is_compact(&self) -> bool {
+        self.free_slots == 0
+    }
+}
+
+/// Placement of live nodes within the arena, see [`SgMap::set_arena_layout`][crate::map::SgMap::set_arena_layout].
+///
+/// Applied by [`SgMap::maintenance`][crate::map::SgMap::maintenance], which rebuilds the whole tree first if
+/// the layout isn't [`InOrder`][ArenaLayout::InOrder].
+/// Later insertions fill free slots (or append), so the layout degrades until the next maintenance pass.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ArenaLayout {
+    /// Sorted by key, e.g. iteration walks the arena front to back. The default.
+    InOrder,
+    /// Level by level from the root, so the top levels of every search path share a few cache lines.
+    BreadthFirst,
+    /// Recursive van Emde Boas order, cache-oblivious: every subtree of height `h` spans `O(2^h)` contiguous slots.
+    /// Cuts cache misses per lookup in large, read-heavy trees, regardless of cache line size.
+    VanEmdeBoas,
+}
+
+/// Cooperative yield point for long internal operations, see [`SgMap::set_yield_hook`][crate::map::SgMap::set_yield_hook].
+///
+/// Full rebuilds, bulk builds, and arena compaction call `callback` once every `period` nodes processed,
+/// e.g. to pet a watchdog or to yield to an executor, instead of stalling for the operation's full duration.
+#[cfg(feature = "yield_hook")]
+#[derive(Debug, Copy, Clone)]
+pub struct YieldHook {
+    callback: fn(),
+    period: usize,
+}
+
+#[cfg(feature = "yield_hook")]
+impl YieldHook {
+    /// Hook calling `callback` once every `period` nodes processed.
+    /// A `period` of `0` is treated as `1`.
+    pub const fn new(callback: fn(), period: usize) -> Self {
+        YieldHook {
+            callback,
+            period: if period == 0 { 1 } else { period },
+        }
+    }
+
+    /// Number of nodes processed between calls.
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Count one processed node, calling the callback if a period just elapsed.
+    pub(crate) fn tick(&self, work: &mut usize) {
+        *work += 1;
+        if *work >= self.period {
+            *work = 0;
+            (self.callback)();
+        }
+    }
+}
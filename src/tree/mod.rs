@@ -1,5 +1,4 @@
 mod node_dispatch;
-pub use node_dispatch::SmallNode;
 
 #[cfg(test)]
 mod test;
@@ -7,6 +6,7 @@ mod test;
 mod arena;
 #[cfg(fuzzing)]
 pub use arena::Arena;
+pub use arena::{NodeArena, SgVec};
 
 pub(super) mod node;
 #[cfg(fuzzing)]
@@ -15,9 +15,53 @@ pub use node::{Node, NodeGetHelper, NodeRebuildHelper};
 mod iter;
 pub use iter::{IntoIter, Iter, IterMut};
 
+mod cursor;
+pub(crate) use cursor::CursorPos;
+
 mod error;
 pub use error::SgError;
 
+mod balance;
+pub use balance::{
+    BalanceParams, BalancePolicy, DefaultBalancePolicy, DeletionCountPolicy, HeightBalancePolicy,
+    MaxDepthPolicy, NeverRebalancePolicy, WeightBalancePolicy,
+};
+
+mod extend;
+pub use extend::ExtendReport;
+
+mod maintenance;
+#[cfg(feature = "yield_hook")]
+pub use maintenance::YieldHook;
+pub use maintenance::{ArenaFragmentation, ArenaLayout, MaintenanceBudget};
+
+#[cfg(feature = "telemetry")]
+mod telemetry;
+#[cfg(feature = "telemetry")]
+pub use telemetry::TreeStats;
+
+mod raw;
+pub use raw::{RawNode, RawPartsError};
+
+#[cfg(feature = "stats")]
+mod display;
+#[cfg(feature = "stats")]
+pub use display::TreeDisplay;
+
+#[cfg(feature = "stats")]
+mod dot;
+
+#[cfg(feature = "stats")]
+mod shape;
+#[cfg(feature = "stats")]
+pub use shape::TreeShape;
+
+mod validate;
+pub use validate::InvariantError;
+
+#[cfg(any(feature = "weight_balanced", feature = "avl"))]
+mod rotate;
+
 #[allow(clippy::module_inception)]
 mod tree;
 pub use tree::{Idx, SgTree};
@@ -1,9 +1,11 @@
+use core::fmt::Debug;
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
 use core::ops::Sub;
 
+use super::arena::SgVec;
 use super::node_dispatch::SmallNode;
 
 use smallnum::SmallUnsigned;
-use tinyvec::ArrayVec;
 
 /*
 Note:
@@ -14,38 +16,100 @@ If caller obeys contract, `U` will be smallest unsigned capable of representing
 const `N` (e.g. static capacity).
 */
 
+// Child Link ----------------------------------------------------------------------------------------------------------
+
+/// Index type with a same-width, niche-optimized encoding for optional child links.
+/// Links hold `idx + 1`, with `MAX` for `None`, so `U::MAX - 1` is the largest linkable index.
+/// Zero stays invalid, so `Option<Node<K, V, U>>` (a free or occupied arena slot) costs no discriminant either.
+pub trait LinkIdx: SmallUnsigned + Copy {
+    /// Non-zero counterpart of the index type.
+    type Link: Copy + Debug;
+
+    /// Encode an optional index.
+    fn to_link(opt_idx: Option<usize>) -> Self::Link;
+
+    /// Decode an optional index.
+    fn from_link(link: Self::Link) -> Option<usize>;
+}
+
+macro_rules! impl_link_idx {
+    ( $( $uint:ty => $non_zero:ty ),* $(,)? ) => {
+        $(
+            impl LinkIdx for $uint {
+                type Link = $non_zero;
+
+                fn to_link(opt_idx: Option<usize>) -> Self::Link {
+                    let raw = match opt_idx {
+                        Some(idx) => <$uint>::checked_from(idx + 1),
+                        None => <$uint>::MAX,
+                    };
+
+                    debug_assert!(opt_idx.is_none() || raw != <$uint>::MAX, "Index too wide for link!");
+                    <$non_zero>::new(raw).expect("Link encoding is non-zero")
+                }
+
+                fn from_link(link: Self::Link) -> Option<usize> {
+                    match link.get() {
+                        <$uint>::MAX => None,
+                        raw => Some(raw.usize() - 1),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_link_idx!(
+    u8 => NonZeroU8,
+    u16 => NonZeroU16,
+    u32 => NonZeroU32,
+    u64 => NonZeroU64,
+    usize => NonZeroUsize,
+);
+
 // Tree Node -----------------------------------------------------------------------------------------------------------
 
 /// Binary tree node, meta programmable for low memory footprint.
 /// Users of it's APIs only need to declare `U` type or trait bounds at construction.
 /// All APIs take/return `usize` and normalize to `U` internally.
-#[derive(Clone, Debug, Default)]
-pub struct Node<K, V, U> {
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "cacheline_nodes", repr(align(64)))]
+pub struct Node<K, V, U: LinkIdx> {
+    #[cfg_attr(feature = "soa_arena", allow(dead_code))]
+    // Zero-sized, arena stores keys out-of-line
     key: K,
+    #[cfg_attr(feature = "split_values", allow(dead_code))]
+    // Zero-sized, arena stores values out-of-line
     val: V,
-    left_idx: Option<U>,
-    right_idx: Option<U>,
+    left_idx: U::Link,
+    right_idx: U::Link,
 
     #[cfg(feature = "fast_rebalance")]
     subtree_size: U,
+
+    #[cfg(feature = "avl")]
+    height: u8,
 }
 
-impl<K, V, U: SmallUnsigned> Node<K, V, U> {
+impl<K, V, U: LinkIdx> Node<K, V, U> {
     /// Constructor.
     pub fn new(key: K, val: V) -> Self {
         Node {
             key,
             val,
-            left_idx: None,
-            right_idx: None,
+            left_idx: U::to_link(None),
+            right_idx: U::to_link(None),
 
             #[cfg(feature = "fast_rebalance")]
             subtree_size: U::checked_from(1),
+
+            #[cfg(feature = "avl")]
+            height: 1,
         }
     }
 }
 
-impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K, V, U> {
+impl<K, V, U: LinkIdx> SmallNode<K, V> for Node<K, V, U> {
     fn key(&self) -> &K {
         &self.key
     }
@@ -54,46 +118,38 @@ impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K
         self.key = key;
     }
 
-    fn take_key(&mut self) -> K {
-        core::mem::take(&mut self.key)
+    fn replace_key(&mut self, key: K) -> K {
+        core::mem::replace(&mut self.key, key)
     }
 
+    #[cfg(not(feature = "split_values"))]
     fn val(&self) -> &V {
         &self.val
     }
 
+    #[cfg(not(feature = "split_values"))]
     fn get_mut(&mut self) -> (&K, &mut V) {
         (&self.key, &mut self.val)
     }
 
-    fn take_val(&mut self) -> V {
-        core::mem::take(&mut self.val)
-    }
-
-    fn set_val(&mut self, val: V) {
-        self.val = val;
+    fn into_entry(self) -> (K, V) {
+        (self.key, self.val)
     }
 
     fn left_idx(&self) -> Option<usize> {
-        self.left_idx.map(|i| i.usize())
+        U::from_link(self.left_idx)
     }
 
     fn set_left_idx(&mut self, opt_idx: Option<usize>) {
-        match opt_idx {
-            Some(idx) => self.left_idx = Some(U::checked_from(idx)),
-            None => self.left_idx = None,
-        }
+        self.left_idx = U::to_link(opt_idx);
     }
 
     fn right_idx(&self) -> Option<usize> {
-        self.right_idx.map(|i| i.usize())
+        U::from_link(self.right_idx)
     }
 
     fn set_right_idx(&mut self, opt_idx: Option<usize>) {
-        match opt_idx {
-            Some(idx) => self.right_idx = Some(U::checked_from(idx)),
-            None => self.right_idx = None,
-        }
+        self.right_idx = U::to_link(opt_idx);
     }
 
     #[cfg(feature = "fast_rebalance")]
@@ -105,6 +161,17 @@ impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K
     fn set_subtree_size(&mut self, size: usize) {
         self.subtree_size = U::checked_from(size);
     }
+
+    #[cfg(feature = "avl")]
+    fn height(&self) -> usize {
+        self.height.into()
+    }
+
+    #[cfg(feature = "avl")]
+    fn set_height(&mut self, height: usize) {
+        debug_assert!(height <= u8::MAX.into());
+        self.height = height as u8;
+    }
 }
 
 // Retrieval Helper ----------------------------------------------------------------------------------------------------
@@ -185,14 +252,14 @@ impl<U: SmallUnsigned + Ord + Sub + Copy> NodeRebuildHelper<U> {
 #[derive(Debug, Default)]
 pub struct NodeSwapHistHelper<U: Default, const N: usize> {
     /// Map `original_idx` -> `current_idx`
-    history: ArrayVec<[(U, U); N]>,
+    history: SgVec<[(U, U); N]>,
 }
 
 impl<U: Ord + Default + Copy + SmallUnsigned, const N: usize> NodeSwapHistHelper<U, N> {
     /// Constructor.
     pub fn new() -> Self {
         NodeSwapHistHelper {
-            history: ArrayVec::<[(U, U); N]>::default(),
+            history: SgVec::<[(U, U); N]>::default(),
         }
     }
 
@@ -267,6 +334,22 @@ mod tests {
         // No features
         #[cfg(target_pointer_width = "64")]
         #[cfg(not(feature = "fast_rebalance"))]
+        #[cfg(not(feature = "avl"))]
+        #[cfg(not(feature = "cacheline_nodes"))]
+        {
+            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 12);
+            assert_eq!(
+                size_of::<Option<Node<u32, u32, small_unsigned!(1024)>>>(),
+                12
+            );
+            assert_eq!(size_of::<Node<u8, u8, small_unsigned!(128)>>(), 4);
+        }
+
+        // avl only
+        #[cfg(target_pointer_width = "64")]
+        #[cfg(not(feature = "fast_rebalance"))]
+        #[cfg(feature = "avl")]
+        #[cfg(not(feature = "cacheline_nodes"))]
         {
             assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 16);
         }
@@ -274,8 +357,23 @@ mod tests {
         // fast_rebalance only
         #[cfg(target_pointer_width = "64")]
         #[cfg(feature = "fast_rebalance")]
+        #[cfg(not(feature = "cacheline_nodes"))]
+        {
+            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 16);
+        }
+
+        // cacheline_nodes, with any other features
+        #[cfg(feature = "cacheline_nodes")]
         {
-            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 20);
+            assert_eq!(
+                std::mem::align_of::<Node<u32, u32, small_unsigned!(1024)>>(),
+                64
+            );
+            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 64);
+            assert_eq!(
+                size_of::<Option<Node<u32, u32, small_unsigned!(1024)>>>(),
+                64
+            );
         }
     }
 }
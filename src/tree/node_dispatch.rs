@@ -1,27 +1,30 @@
 // Size-optimized Node Trait -------------------------------------------------------------------------------------------
 
 /// Interface encapsulates `U`.
-pub trait SmallNode<K, V: Default> {
+pub trait SmallNode<K, V> {
     /// Get key.
+    #[cfg_attr(feature = "soa_arena", allow(dead_code))]
     fn key(&self) -> &K;
 
     /// Set key.
+    #[cfg_attr(feature = "soa_arena", allow(dead_code))]
     fn set_key(&mut self, key: K);
 
-    // Take key, replacing current with `K::Default()`.
-    fn take_key(&mut self) -> K;
+    /// Replace key, returning the previous one.
+    #[cfg_attr(feature = "soa_arena", allow(dead_code))]
+    fn replace_key(&mut self, key: K) -> K;
 
     /// Get value.
+    #[cfg(not(feature = "split_values"))]
     fn val(&self) -> &V;
 
     /// Get key and mutable value.
+    #[cfg(not(feature = "split_values"))]
     fn get_mut(&mut self) -> (&K, &mut V);
 
-    /// Set value.
-    fn set_val(&mut self, val: V);
-
-    // Take value, replacing current with `V::Default()`.
-    fn take_val(&mut self) -> V;
+    /// Consume node, returning key and value.
+    #[cfg_attr(feature = "soa_arena", allow(dead_code))]
+    fn into_entry(self) -> (K, V);
 
     /// Get left index as `usize`.
     fn left_idx(&self) -> Option<usize>;
@@ -42,18 +45,30 @@ pub trait SmallNode<K, V: Default> {
     /// Set subtree size.
     #[cfg(feature = "fast_rebalance")]
     fn set_subtree_size(&mut self, size: usize);
+
+    /// Get subtree height.
+    #[cfg(feature = "avl")]
+    fn height(&self) -> usize;
+
+    /// Set subtree height.
+    #[cfg(feature = "avl")]
+    fn set_height(&mut self, height: usize);
 }
 
 /*
 NOTE: This is draft code for upgrades when `feature(generic_const_exprs)` stabilizes.
 
+Until then, only the single fixed-width `Idx` path is compiled - there's no per-accessor dispatch or multi-width
+monomorphization to pay for in code size. If this draft lands, gate it so a `min_code_size` feature can retain the
+single-width path (trades a few bytes of RAM for flash).
+
 use super::node::Node;
 use smallnum::SmallUnsignedLabel;
 
 // Enum Dispatch -------------------------------------------------------------------------------------------------------
 
 #[derive(Clone)]
-pub enum SmallNodeDispatch<K: Default, V: Default> {
+pub enum SmallNodeDispatch<K, V> {
     NodeUSIZE(Node<K, V, usize>),
     NodeU8(Node<K, V, u8>),
 
@@ -79,7 +94,7 @@ pub enum SmallNodeDispatch<K: Default, V: Default> {
     NodeU128(Node<K, V, u128>),
 }
 
-impl<K: Default, V: Default> SmallNodeDispatch<K, V> {
+impl<K, V> SmallNodeDispatch<K, V> {
     pub const fn new(key: K, val: V, uint: SmallUnsignedLabel) -> Self {
         match uint {
             SmallUnsignedLabel::USIZE => SmallNodeDispatch::NodeUSIZE(Node::<K, V, usize>::new(key, val)),
@@ -141,7 +156,7 @@ macro_rules! dispatch {
     };
 }
 
-impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
+impl<K, V> SmallNode<K, V> for SmallNodeDispatch<K, V> {
     fn key(&self) -> &K {
         dispatch!(self, key)
     }
@@ -150,8 +165,8 @@ impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
         dispatch!(self, set_key, key);
     }
 
-    fn take_key(&mut self) -> K {
-        dispatch!(self, take_key)
+    fn replace_key(&mut self, key: K) -> K {
+        dispatch!(self, replace_key, key)
     }
 
     fn val(&self) -> &V {
@@ -162,12 +177,8 @@ impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
         dispatch!(self, get_mut)
     }
 
-    fn set_val(&mut self, val: V) {
-        dispatch!(self, set_val, val);
-    }
-
-    fn take_val(&mut self) -> V {
-        dispatch!(self, take_val)
+    fn into_entry(self) -> (K, V) {
+        dispatch!(self, into_entry)
     }
 
     fn left_idx(&self) -> Option<usize> {
@@ -195,5 +206,15 @@ impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
     fn set_subtree_size(&mut self, size: usize) {
         dispatch!(self, set_subtree_size, size);
     }
+
+    #[cfg(feature = "avl")]
+    fn height(&self) -> usize {
+        dispatch!(self, height)
+    }
+
+    #[cfg(feature = "avl")]
+    fn set_height(&mut self, height: usize) {
+        dispatch!(self, set_height, height);
+    }
 }
 */
@@ -0,0 +1,148 @@
+use core::fmt;
+
+use super::arena::NodeArena;
+use super::tree::{Idx, SgTree};
+
+use tinyvec::ArrayVec;
+
+/// Externally built tree node, e.g. from codegen or an FFI loader.
+/// Children are slot indexes into the node sequence passed to
+/// [`SgMap::from_raw_parts`][crate::map::SgMap::from_raw_parts].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RawNode<K, V> {
+    /// Node key.
+    pub key: K,
+    /// Node value.
+    pub val: V,
+    /// Slot index of the left child, if any.
+    pub left: Option<Idx>,
+    /// Slot index of the right child, if any.
+    pub right: Option<Idx>,
+}
+
+/// Reasons a pre-built node arena is rejected by [`SgMap::from_raw_parts`][crate::map::SgMap::from_raw_parts].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum RawPartsError {
+    /// More slots than the map's capacity.
+    CapacityExceeded,
+
+    /// A root or child index refers to a slot that's out of bounds or empty.
+    InvalidIndex {
+        /// Referenced slot index.
+        idx: usize,
+    },
+
+    /// A node is reachable more than once, e.g. a cycle or shared child.
+    DuplicateLink {
+        /// Slot index of the node.
+        idx: usize,
+    },
+
+    /// A node isn't reachable from the root.
+    Unreachable {
+        /// Slot index of the node.
+        idx: usize,
+    },
+
+    /// Keys aren't in strictly ascending order (e.g. not a binary search tree, or duplicate keys).
+    Unordered {
+        /// Slot index of the first out-of-order node.
+        idx: usize,
+    },
+}
+
+impl fmt::Display for RawPartsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RawPartsError::CapacityExceeded => write!(f, "more slots than capacity"),
+            RawPartsError::InvalidIndex { idx } => write!(f, "invalid node index {}", idx),
+            RawPartsError::DuplicateLink { idx } => {
+                write!(f, "node at index {} linked more than once", idx)
+            }
+            RawPartsError::Unreachable { idx } => {
+                write!(f, "node at index {} unreachable from root", idx)
+            }
+            RawPartsError::Unordered { idx } => write!(f, "node at index {} out of order", idx),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RawPartsError {}
+
+impl<K: Ord, V, const N: usize> SgTree<K, V, N> {
+    /// Bulk load a pre-built binary search tree, given its node slots and root slot index.
+    /// Structure is validated, then nodes are relinked into a balanced tree (the given shape isn't kept).
+    pub fn from_raw_parts<I>(nodes: I, opt_root: Option<Idx>) -> Result<Self, RawPartsError>
+    where
+        I: IntoIterator<Item = Option<RawNode<K, V>>>,
+    {
+        let mut tree = Self::new();
+
+        // Per slot: arena index, left child slot, right child slot
+        let mut slots = ArrayVec::<[Option<(usize, Option<Idx>, Option<Idx>)>; N]>::new();
+        for opt_node in nodes {
+            if slots.len() == N {
+                return Err(RawPartsError::CapacityExceeded);
+            }
+
+            slots.push(opt_node.map(|node| {
+                let arena_idx = tree.arena.add(node.key, node.val);
+                (arena_idx, node.left, node.right)
+            }));
+        }
+
+        // Iterative in-order traversal, validating links and key order
+        let mut visited = ArrayVec::<[bool; N]>::new();
+        visited.resize(slots.len(), false);
+        let mut stack = ArrayVec::<[usize; N]>::new();
+        let mut sorted_idxs = ArrayVec::<[usize; N]>::new();
+        let mut opt_curr = opt_root.map(usize::from);
+
+        loop {
+            while let Some(pos) = opt_curr {
+                let (_, left, _) = slots
+                    .get(pos)
+                    .copied()
+                    .flatten()
+                    .ok_or(RawPartsError::InvalidIndex { idx: pos })?;
+
+                if visited[pos] {
+                    return Err(RawPartsError::DuplicateLink { idx: pos });
+                }
+
+                visited[pos] = true;
+                stack.push(pos);
+                opt_curr = left.map(usize::from);
+            }
+
+            match stack.pop() {
+                Some(pos) => {
+                    if let Some((arena_idx, _, right)) = slots[pos] {
+                        if let Some(&prev_idx) = sorted_idxs.last() {
+                            if tree.arena.key(prev_idx) >= tree.arena.key(arena_idx) {
+                                return Err(RawPartsError::Unordered { idx: pos });
+                            }
+                        }
+
+                        sorted_idxs.push(arena_idx);
+                        opt_curr = right.map(usize::from);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        if let Some(pos) = slots
+            .iter()
+            .zip(visited.iter())
+            .position(|(slot, visited)| slot.is_some() && !visited)
+        {
+            return Err(RawPartsError::Unreachable { idx: pos });
+        }
+
+        tree.rebuild_from_sorted_idxs(&sorted_idxs);
+        Ok(tree)
+    }
+}
@@ -0,0 +1,172 @@
+use super::arena::NodeArena;
+use super::balance::BalancePolicy;
+use super::tree::SgTree;
+
+use smallnum::SmallUnsigned;
+
+// Weight-balance parameters (delta, gamma), with node weight defined as subtree size + 1.
+// Hirai and Yamamoto (2011) prove (3, 2) is the only integer pair that restores balance with a single or double
+// rotation after any one insertion or removal.
+#[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+pub(super) const DELTA: usize = 3;
+#[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+const GAMMA: usize = 2;
+
+// Rotation-based rebalancing, enabled by feature `weight_balanced` or `avl` (which takes precedence if both are).
+// Weight-balance relies on the subtree sizes maintained by feature `fast_rebalance`, AVL on per-node heights.
+impl<K: Ord, V, const N: usize, P: BalancePolicy> SgTree<K, V, N, P> {
+    // Restore weight-balance bottom-up along `path` (root first, subtree sizes already updated).
+    // Rotations only re-link nodes, arena indexes (and thus min/max) are unchanged.
+    pub(super) fn rebalance_path<U: SmallUnsigned + Copy>(&mut self, path: &[U]) {
+        for (depth, idx) in path.iter().enumerate().rev() {
+            let idx = idx.usize();
+            let new_idx = self.balance_node(idx);
+            if new_idx != idx {
+                match depth.checked_sub(1) {
+                    Some(parent_depth) => {
+                        let parent_idx = path[parent_depth].usize();
+                        if self.arena.left_idx(parent_idx) == Some(idx) {
+                            self.arena.set_left_idx(parent_idx, Some(new_idx));
+                        } else {
+                            self.arena.set_right_idx(parent_idx, Some(new_idx));
+                        }
+                    }
+                    None => self.opt_root_idx = Some(new_idx),
+                }
+            }
+        }
+    }
+
+    // Rotate the subtree rooted at `idx` if it's out of weight-balance, returning the (possibly new) subtree root.
+    #[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+    fn balance_node(&mut self, idx: usize) -> usize {
+        let (opt_left_idx, opt_right_idx) = (self.arena.left_idx(idx), self.arena.right_idx(idx));
+        let (left_weight, right_weight) = (self.weight(opt_left_idx), self.weight(opt_right_idx));
+
+        if DELTA * left_weight < right_weight {
+            let right_idx = opt_right_idx.expect("Heavy subtree must be non-empty");
+            if self.weight(self.arena.left_idx(right_idx))
+                >= GAMMA * self.weight(self.arena.right_idx(right_idx))
+            {
+                let new_right_idx = self.rotate_right(right_idx);
+                self.arena.set_right_idx(idx, Some(new_right_idx));
+            }
+            self.rotate_left(idx)
+        } else if DELTA * right_weight < left_weight {
+            let left_idx = opt_left_idx.expect("Heavy subtree must be non-empty");
+            if self.weight(self.arena.right_idx(left_idx))
+                >= GAMMA * self.weight(self.arena.left_idx(left_idx))
+            {
+                let new_left_idx = self.rotate_left(left_idx);
+                self.arena.set_left_idx(idx, Some(new_left_idx));
+            }
+            self.rotate_right(idx)
+        } else {
+            idx
+        }
+    }
+
+    // Rotate the subtree rooted at `idx` if it's out of height-balance, returning the (possibly new) subtree root.
+    // Children are already balanced, so `idx`'s height is refreshed first.
+    #[cfg(feature = "avl")]
+    fn balance_node(&mut self, idx: usize) -> usize {
+        self.update_height(idx);
+
+        let (opt_left_idx, opt_right_idx) = (self.arena.left_idx(idx), self.arena.right_idx(idx));
+        let (left_height, right_height) = (
+            self.subtree_height(opt_left_idx),
+            self.subtree_height(opt_right_idx),
+        );
+
+        if right_height > left_height + 1 {
+            let right_idx = opt_right_idx.expect("Taller subtree must be non-empty");
+            if self.subtree_height(self.arena.left_idx(right_idx))
+                > self.subtree_height(self.arena.right_idx(right_idx))
+            {
+                let new_right_idx = self.rotate_right(right_idx);
+                self.arena.set_right_idx(idx, Some(new_right_idx));
+            }
+            self.rotate_left(idx)
+        } else if left_height > right_height + 1 {
+            let left_idx = opt_left_idx.expect("Taller subtree must be non-empty");
+            if self.subtree_height(self.arena.right_idx(left_idx))
+                > self.subtree_height(self.arena.left_idx(left_idx))
+            {
+                let new_left_idx = self.rotate_left(left_idx);
+                self.arena.set_left_idx(idx, Some(new_left_idx));
+            }
+            self.rotate_right(idx)
+        } else {
+            idx
+        }
+    }
+
+    // Promote the right child of `idx`, returning its index.
+    fn rotate_left(&mut self, idx: usize) -> usize {
+        let pivot_idx = self
+            .arena
+            .right_idx(idx)
+            .expect("Left rotation requires a right child");
+
+        let inner_idx = self.arena.left_idx(pivot_idx);
+        self.arena.set_right_idx(idx, inner_idx);
+        self.arena.set_left_idx(pivot_idx, Some(idx));
+        self.update_rotated(idx, pivot_idx);
+        pivot_idx
+    }
+
+    // Promote the left child of `idx`, returning its index.
+    fn rotate_right(&mut self, idx: usize) -> usize {
+        let pivot_idx = self
+            .arena
+            .left_idx(idx)
+            .expect("Right rotation requires a left child");
+
+        let inner_idx = self.arena.right_idx(pivot_idx);
+        self.arena.set_left_idx(idx, inner_idx);
+        self.arena.set_right_idx(pivot_idx, Some(idx));
+        self.update_rotated(idx, pivot_idx);
+        pivot_idx
+    }
+
+    // Following a rotation: refresh cached metadata of old subtree root, now a child, then of new subtree root.
+    fn update_rotated(&mut self, old_root_idx: usize, new_root_idx: usize) {
+        #[cfg(feature = "fast_rebalance")]
+        {
+            let subtree_size = self.arena.subtree_size(old_root_idx);
+            self.arena.set_subtree_size(new_root_idx, subtree_size);
+
+            let old_root_size = self.weight(self.arena.left_idx(old_root_idx))
+                + self.weight(self.arena.right_idx(old_root_idx))
+                - 1;
+            self.arena.set_subtree_size(old_root_idx, old_root_size);
+        }
+
+        #[cfg(feature = "avl")]
+        {
+            self.update_height(old_root_idx);
+            self.update_height(new_root_idx);
+        }
+    }
+
+    // Subtree size + 1, so empty subtrees have non-zero weight.
+    #[cfg(feature = "fast_rebalance")]
+    fn weight(&self, opt_idx: Option<usize>) -> usize {
+        opt_idx.map_or(0, |idx| self.arena.subtree_size(idx)) + 1
+    }
+
+    // Recompute height from children's.
+    #[cfg(feature = "avl")]
+    fn update_height(&mut self, idx: usize) {
+        let height = 1 + self
+            .subtree_height(self.arena.left_idx(idx))
+            .max(self.subtree_height(self.arena.right_idx(idx)));
+        self.arena.set_height(idx, height);
+    }
+
+    // Subtree height, zero if empty.
+    #[cfg(feature = "avl")]
+    fn subtree_height(&self, opt_idx: Option<usize>) -> usize {
+        opt_idx.map_or(0, |idx| self.arena.height(idx))
+    }
+}
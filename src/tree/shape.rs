@@ -0,0 +1,121 @@
+use core::iter::{self, FromIterator};
+
+use super::arena::{NodeArena, SgVec};
+use super::balance::BalancePolicy;
+use super::tree::{Idx, SgTree};
+
+use smallnum::SmallUnsigned;
+
+/// Structural metrics for a tree, see [`SgMap::shape`][crate::map::SgMap::shape].
+/// Requires feature `stats`.
+///
+/// Lookup cost is one key comparison per level visited, so [`height`][Self::height] bounds worst-case latency
+/// and [`internal_path_length`][Self::internal_path_length] gives the average.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct TreeShape {
+    pub(crate) len: usize,
+    pub(crate) height: usize,
+    pub(crate) internal_path_length: usize,
+    pub(crate) full_levels: usize,
+    pub(crate) alpha_height: usize,
+}
+
+impl TreeShape {
+    /// Number of elements.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tree is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels, e.g. key comparisons for a worst-case lookup. Zero if empty.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sum of every element's depth (root is depth `0`).
+    /// A successful lookup averages `internal_path_length / len + 1` key comparisons.
+    pub const fn internal_path_length(&self) -> usize {
+        self.internal_path_length
+    }
+
+    /// Number of levels, from the root down, with every position occupied.
+    pub const fn full_levels(&self) -> usize {
+        self.full_levels
+    }
+
+    /// Minimum possible height for this many elements, e.g. that of a perfectly balanced tree.
+    pub const fn optimal_height(&self) -> usize {
+        (usize::BITS - self.len.leading_zeros()) as usize
+    }
+
+    /// Maximum height of an alpha-height-balanced tree with this many elements, for the tree's rebalance parameter.
+    pub const fn alpha_height(&self) -> usize {
+        self.alpha_height
+    }
+
+    /// Levels to spare before exceeding [`alpha_height`][Self::alpha_height].
+    /// Negative if the tree isn't alpha-height-balanced (e.g. rebuilds are deferred, or a custom policy is in use).
+    pub const fn balance_slack(&self) -> isize {
+        (self.alpha_height as isize) - (self.height as isize)
+    }
+}
+
+impl<K: Ord, V, const N: usize, P: BalancePolicy> SgTree<K, V, N, P> {
+    /// Get the number of levels in the tree, zero if empty.
+    pub fn height(&self) -> usize {
+        self.shape().height
+    }
+
+    /// Get the sum of every node's depth (root is depth `0`).
+    pub fn internal_path_length(&self) -> usize {
+        self.shape().internal_path_length
+    }
+
+    /// Report structural metrics, in a single traversal. See [`TreeShape`] for details.
+    pub fn shape(&self) -> TreeShape {
+        let mut shape = TreeShape {
+            len: self.len(),
+            alpha_height: match self.is_empty() {
+                true => 0,
+                false => self.balance_params().height_bound(self.len()) + 1,
+            },
+            ..TreeShape::default()
+        };
+
+        // Node count per depth
+        let mut level_cnts = SgVec::<[Idx; N]>::new();
+        if let Some(root_idx) = self.opt_root_idx {
+            let mut worklist =
+                SgVec::<[(Idx, Idx); N]>::from_iter(iter::once((Idx::checked_from(root_idx), 0)));
+            while let Some((idx, depth)) = worklist.pop() {
+                let (idx, depth) = (idx.usize(), depth.usize());
+
+                shape.internal_path_length += depth;
+                if depth == level_cnts.len() {
+                    level_cnts.push(0);
+                }
+                level_cnts[depth] += 1;
+
+                for child_idx in [self.arena.left_idx(idx), self.arena.right_idx(idx)]
+                    .iter()
+                    .flatten()
+                {
+                    worklist.push((Idx::checked_from(*child_idx), Idx::checked_from(depth + 1)));
+                }
+            }
+        }
+
+        shape.height = level_cnts.len();
+        shape.full_levels = level_cnts
+            .iter()
+            .enumerate()
+            .take_while(|(depth, cnt)| (*depth < 16) && (cnt.usize() == (1 << depth)))
+            .count();
+
+        shape
+    }
+}
@@ -0,0 +1,126 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Snapshot of tree instrumentation counters, see [`SgMap::stats`][crate::map::SgMap::stats].
+/// Requires feature `telemetry`.
+///
+/// Counters accumulate from construction or the last [`reset_stats`][crate::map::SgMap::reset_stats],
+/// wrapping if `usize::MAX` is exceeded.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct TreeStats {
+    pub(crate) rebuilds: usize,
+    pub(crate) rebuild_nodes: usize,
+    pub(crate) max_rebuild_nodes: usize,
+    pub(crate) max_depth: usize,
+    pub(crate) lookups: usize,
+    pub(crate) lookup_cmps: usize,
+}
+
+impl TreeStats {
+    /// Number of subtree rebuilds, full or partial.
+    pub const fn rebuilds(&self) -> usize {
+        self.rebuilds
+    }
+
+    /// Total nodes re-linked across all rebuilds.
+    pub const fn rebuild_nodes(&self) -> usize {
+        self.rebuild_nodes
+    }
+
+    /// Nodes re-linked by the largest single rebuild.
+    pub const fn max_rebuild_nodes(&self) -> usize {
+        self.max_rebuild_nodes
+    }
+
+    /// Deepest insertion observed (root is depth `0`), before any rebuild it triggered.
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Number of key lookups, e.g. calls to `get`, `get_mut`, or `contains_key`.
+    pub const fn lookups(&self) -> usize {
+        self.lookups
+    }
+
+    /// Total key comparisons across all lookups.
+    /// Divide by [`lookups`][Self::lookups] for comparisons per lookup.
+    pub const fn lookup_cmps(&self) -> usize {
+        self.lookup_cmps
+    }
+}
+
+// Live counters, updated through `&self` for lookups.
+// Relaxed load-then-store rather than read-modify-write: counts are advisory, and targets without atomic RMW
+// (e.g. `thumbv6m`) are supported.
+#[derive(Debug, Default)]
+pub(crate) struct StatCounters {
+    rebuilds: AtomicUsize,
+    rebuild_nodes: AtomicUsize,
+    max_rebuild_nodes: AtomicUsize,
+    max_depth: AtomicUsize,
+    lookups: AtomicUsize,
+    lookup_cmps: AtomicUsize,
+}
+
+impl StatCounters {
+    /// Count a rebuild re-linking `nodes` nodes.
+    pub(crate) fn record_rebuild(&self, nodes: usize) {
+        add(&self.rebuilds, 1);
+        add(&self.rebuild_nodes, nodes);
+        raise(&self.max_rebuild_nodes, nodes);
+    }
+
+    /// Count an insertion at `depth`.
+    pub(crate) fn record_depth(&self, depth: usize) {
+        raise(&self.max_depth, depth);
+    }
+
+    /// Count a lookup taking `cmps` key comparisons.
+    pub(crate) fn record_lookup(&self, cmps: usize) {
+        add(&self.lookups, 1);
+        add(&self.lookup_cmps, cmps);
+    }
+
+    /// Current counter values.
+    pub(crate) fn snapshot(&self) -> TreeStats {
+        TreeStats {
+            rebuilds: self.rebuilds.load(Ordering::Relaxed),
+            rebuild_nodes: self.rebuild_nodes.load(Ordering::Relaxed),
+            max_rebuild_nodes: self.max_rebuild_nodes.load(Ordering::Relaxed),
+            max_depth: self.max_depth.load(Ordering::Relaxed),
+            lookups: self.lookups.load(Ordering::Relaxed),
+            lookup_cmps: self.lookup_cmps.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Zero all counters.
+    pub(crate) fn reset(&mut self) {
+        *self = StatCounters::default();
+    }
+}
+
+impl Clone for StatCounters {
+    fn clone(&self) -> Self {
+        let stats = self.snapshot();
+        StatCounters {
+            rebuilds: AtomicUsize::new(stats.rebuilds),
+            rebuild_nodes: AtomicUsize::new(stats.rebuild_nodes),
+            max_rebuild_nodes: AtomicUsize::new(stats.max_rebuild_nodes),
+            max_depth: AtomicUsize::new(stats.max_depth),
+            lookups: AtomicUsize::new(stats.lookups),
+            lookup_cmps: AtomicUsize::new(stats.lookup_cmps),
+        }
+    }
+}
+
+fn add(counter: &AtomicUsize, n: usize) {
+    counter.store(
+        counter.load(Ordering::Relaxed).wrapping_add(n),
+        Ordering::Relaxed,
+    );
+}
+
+fn raise(counter: &AtomicUsize, n: usize) {
+    if n > counter.load(Ordering::Relaxed) {
+        counter.store(n, Ordering::Relaxed);
+    }
+}
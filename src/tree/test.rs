@@ -2,9 +2,16 @@ use core::fmt::Debug;
 use core::iter::FromIterator;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 
-use super::node_dispatch::SmallNode;
+use super::arena::NodeArena;
 use super::tree::{Idx, SgTree};
-use super::SgError;
+#[cfg(any(feature = "weight_balanced", feature = "avl"))]
+use super::DefaultBalancePolicy;
+use super::{
+    ArenaLayout, BalancePolicy, ExtendReport, InvariantError, MaintenanceBudget, RawNode,
+    RawPartsError, SgError,
+};
+#[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+use super::{DeletionCountPolicy, MaxDepthPolicy, NeverRebalancePolicy};
 
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
@@ -40,30 +47,32 @@ pub fn get_test_tree_and_keys() -> (SgTree<usize, &'static str, CAPACITY>, Vec<u
 // 1. A right child node's key is always greater than it's parent's key.
 // 2. A left child node's key is always less than it's parent's key.
 // 3. Every node has at most 1 parent.
-fn assert_logical_invariants<K: Ord + Default, V: Default, const N: usize>(sgt: &SgTree<K, V, N>) {
+fn assert_logical_invariants<K: Ord, V, const N: usize, P: BalancePolicy>(
+    sgt: &SgTree<K, V, N, P>,
+) {
+    sgt.assert_invariants();
+
     if let Some(root_idx) = sgt.opt_root_idx {
         let mut child_idxs = vec![root_idx]; // Count as "child" to make sure there's no other ref to this index
-        let mut subtree_worklist = vec![&sgt.arena[root_idx]];
+        let mut subtree_worklist = vec![root_idx];
 
-        while let Some(node) = subtree_worklist.pop() {
-            if let Some(left_idx) = node.left_idx() {
-                let left_child_node = &sgt.arena[left_idx];
+        while let Some(idx) = subtree_worklist.pop() {
+            if let Some(left_idx) = sgt.arena.left_idx(idx) {
                 assert!(
-                    left_child_node.key() < node.key(),
+                    sgt.arena.key(left_idx) < sgt.arena.key(idx),
                     "Internal invariant failed: left child >= parent!"
                 );
                 child_idxs.push(left_idx);
-                subtree_worklist.push(&left_child_node);
+                subtree_worklist.push(left_idx);
             }
 
-            if let Some(right_idx) = node.right_idx() {
-                let right_child_node = &sgt.arena[right_idx];
+            if let Some(right_idx) = sgt.arena.right_idx(idx) {
                 assert!(
-                    right_child_node.key() > node.key(),
+                    sgt.arena.key(right_idx) > sgt.arena.key(idx),
                     "Internal invariant failed: right child <= parent!"
                 );
                 child_idxs.push(right_idx);
-                subtree_worklist.push(&right_child_node);
+                subtree_worklist.push(right_idx);
             }
         }
 
@@ -88,12 +97,11 @@ fn logical_fuzz<const N: usize>(
     let mut slow_rng = rand::thread_rng();
 
     for i in 0..iter_cnt {
-        let rand_key: usize;
-        if check_invars {
-            rand_key = slow_rng.gen();
+        let rand_key: usize = if check_invars {
+            slow_rng.gen()
         } else {
-            rand_key = fast_rng.gen();
-        }
+            fast_rng.gen()
+        };
 
         // Rand value insert
         shadow_keys.insert(rand_key);
@@ -101,7 +109,7 @@ fn logical_fuzz<const N: usize>(
 
         // Verify internal state post-insert
         if check_invars {
-            assert_logical_invariants(&sgt);
+            assert_logical_invariants(sgt);
             assert_eq!(
                 sgt.len(),
                 shadow_keys.len(),
@@ -121,7 +129,7 @@ fn logical_fuzz<const N: usize>(
 
             // Verify internal state post-remove
             if check_invars {
-                assert_logical_invariants(&sgt);
+                assert_logical_invariants(sgt);
                 assert_eq!(
                     sgt.len(),
                     shadow_keys.len(),
@@ -160,8 +168,8 @@ fn logical_fuzz<const N: usize>(
 #[allow(dead_code)]
 fn id_perm_fill<K, V, const N: usize>(sgt: &mut SgTree<K, V, N>)
 where
-    K: From<usize> + Eq + Debug + Ord + Default,
-    V: From<usize> + Eq + Debug + Default,
+    K: From<usize> + Eq + Debug + Ord,
+    V: From<usize> + Eq + Debug,
 {
     sgt.clear();
     for i in 0..sgt.capacity() {
@@ -225,34 +233,113 @@ fn test_tree_sizing() {
 
     // No features
     #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
     #[cfg(not(feature = "low_mem_insert"))]
     #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 18_504);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 14_424);
     }
 
     // All features
     #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
     #[cfg(feature = "low_mem_insert")]
     #[cfg(feature = "fast_rebalance")]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 20_552);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 16_464);
     }
 
     // low_mem_insert only
     #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
     #[cfg(feature = "low_mem_insert")]
     #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 16_456);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 12_368);
     }
 
     // fast_rebalance only
     #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
     #[cfg(not(feature = "low_mem_insert"))]
     #[cfg(feature = "fast_rebalance")]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
+    {
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 18_520);
+    }
+
+    // split_values only, the parallel value array adds its own length field and a per-slot occupancy tag
+    #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
+    #[cfg(not(feature = "low_mem_insert"))]
+    #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(feature = "split_values")]
+    #[cfg(not(feature = "soa_arena"))]
+    #[cfg(not(feature = "yield_hook"))]
+    {
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 18_520);
+    }
+
+    // soa_arena only, keys move to a third parallel array with its own length field and occupancy tags
+    #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
+    #[cfg(not(feature = "low_mem_insert"))]
+    #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(feature = "soa_arena")]
+    #[cfg(not(feature = "yield_hook"))]
+    {
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 22_616);
+    }
+
+    // yield_hook only, the optional hook adds a function pointer and a period
+    #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(not(feature = "alloc"))]
+    #[cfg(not(feature = "low_mem_insert"))]
+    #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(feature = "yield_hook")]
+    {
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 14_440);
+    }
+
+    // alloc only, the runtime capacity and inline/heap storage tags add a little
+    #[cfg(target_pointer_width = "64")]
+    #[cfg(not(feature = "telemetry"))]
+    #[cfg(feature = "alloc")]
+    #[cfg(not(feature = "low_mem_insert"))]
+    #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(feature = "avl"))]
+    #[cfg(not(feature = "cacheline_nodes"))]
+    #[cfg(not(feature = "split_values"))]
+    #[cfg(not(feature = "yield_hook"))]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 22_600);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 14_440);
     }
 }
 
@@ -288,12 +375,9 @@ fn test_iter() {
 
 #[test]
 fn test_from_iter() {
-    let mut key_val_tuples = Vec::new();
-    key_val_tuples.push((1, "1"));
-    key_val_tuples.push((2, "2"));
-    key_val_tuples.push((3, "3"));
+    let key_val_tuples = vec![(1, "1"), (2, "2"), (3, "3")];
 
-    let sgt = SgTree::<_, _, CAPACITY>::from_iter(key_val_tuples.into_iter());
+    let sgt = SgTree::<_, _, CAPACITY>::from_iter(key_val_tuples);
 
     assert!(sgt.len() == 3);
     assert_eq!(
@@ -333,6 +417,51 @@ fn test_append() {
     );
 }
 
+#[test]
+fn test_append_merge() {
+    const SMALL_CAPACITY: usize = 64;
+
+    let mut a = SgTree::<usize, usize, SMALL_CAPACITY>::new();
+    let mut b = SgTree::<usize, usize, SMALL_CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    // Interleaved and overlapping, with freed slots in `a`'s arena
+    for k in 0..40 {
+        a.insert(k, 0);
+        bt_map.insert(k, 0);
+    }
+    a.retain(|k, _| k % 4 != 1);
+    bt_map.retain(|k, _| k % 4 != 1);
+    for k in (0..60).step_by(3) {
+        b.insert(k, 1);
+        bt_map.insert(k, 1);
+    }
+
+    let rebal_cnt = a.rebal_cnt();
+    assert!(a.try_append(&mut b).is_ok());
+    assert_eq!(a.rebal_cnt(), rebal_cnt + 1);
+    assert!(b.is_empty());
+    assert_logical_invariants(&a);
+    assert_logical_invariants(&b);
+    assert!(a.iter().eq(bt_map.iter()));
+
+    // Overflow leaves both trees untouched
+    for k in 100..(100 + SMALL_CAPACITY - a.len() + 1) {
+        b.insert(k, 2);
+    }
+    b.insert(0, 2);
+    assert_eq!(a.try_append(&mut b), Err(SgError::StackCapacityExceeded));
+    assert!(a.iter().eq(bt_map.iter()));
+    assert_eq!(b.len(), SMALL_CAPACITY - a.len() + 2);
+
+    // Fits exactly once a duplicate is dropped from the count
+    b.pop_last();
+    assert!(a.try_append(&mut b).is_ok());
+    assert!(a.is_full());
+    assert_eq!(a.get(&0), Some(&2));
+    assert_logical_invariants(&a);
+}
+
 #[test]
 fn test_flatten() {
     let keys = vec![2, 1, 3];
@@ -345,14 +474,14 @@ fn test_flatten() {
     let root_idx = sgt.opt_root_idx.unwrap();
     let sorted_idxs = sgt.flatten_subtree_to_sorted_idxs::<u16>(root_idx);
 
-    assert_eq!(sorted_idxs, array_vec![[u16; CAPACITY] => 1, 0, 2]);
+    assert_eq!(sorted_idxs[..], [1, 0, 2]);
 
     sgt.remove(&2);
 
     let root_idx = sgt.opt_root_idx.unwrap();
     let sorted_idxs = sgt.flatten_subtree_to_sorted_idxs::<u16>(root_idx);
 
-    assert_eq!(sorted_idxs, array_vec![[u16; CAPACITY] => 1, 2]);
+    assert_eq!(sorted_idxs[..], [1, 2]);
 }
 
 #[test]
@@ -494,6 +623,7 @@ fn test_first_last() {
     assert!(sgt.last_key().is_none());
 }
 
+#[cfg(not(any(feature = "weight_balanced", feature = "avl")))] // Rotations instead of subtree rebuilds
 #[test]
 fn test_subtree_rebalance() {
     let mut sgt: SgTree<usize, &str, CAPACITY> = SgTree::new();
@@ -568,6 +698,109 @@ fn test_retain() {
     assert!(sg_map.iter().eq(bt_map.iter()));
 }
 
+#[test]
+fn test_retain_in_place() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    for i in 0..CAPACITY {
+        sgt.insert(i, i);
+    }
+
+    // Nothing removed, no rebuild
+    let rebal_cnt = sgt.rebal_cnt();
+    sgt.retain(|_, _| true);
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+    assert_eq!(sgt.len(), CAPACITY);
+
+    // Single rebuild, values mutable in ascending key order
+    let mut last = None;
+    sgt.retain(|&k, v| {
+        assert!(last < Some(k));
+        last = Some(k);
+        *v *= 2;
+        k % 3 == 0
+    });
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+    assert_logical_invariants(&sgt);
+    assert!(sgt
+        .iter()
+        .map(|(&k, &v)| (k, v))
+        .eq((0..CAPACITY).step_by(3).map(|k| (k, k * 2))));
+
+    // Freed slots are reusable
+    for i in CAPACITY..(CAPACITY + (CAPACITY - sgt.len())) {
+        assert!(sgt.try_insert(i, i).is_ok());
+    }
+    assert_eq!(sgt.len(), CAPACITY);
+    assert_logical_invariants(&sgt);
+
+    // Remove everything
+    sgt.retain(|_, _| false);
+    assert!(sgt.is_empty());
+    assert_eq!(sgt.first_key_value(), None);
+    sgt.insert(1, 1);
+    assert_logical_invariants(&sgt);
+}
+
+#[test]
+fn test_retain_panic() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    for i in 0..CAPACITY {
+        sgt.insert(i, i);
+    }
+
+    // Predicate panics midway, after rejecting some elements
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        sgt.retain(|&k, _| {
+            assert!(k < CAPACITY / 2, "Predicate panic");
+            k % 2 == 0
+        });
+    }));
+    assert!(result.is_err());
+
+    // Nothing freed, links intact
+    assert_eq!(sgt.len(), CAPACITY);
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().map(|(&k, _)| k).eq(0..CAPACITY));
+
+    sgt.retain(|&k, _| k % 2 == 0);
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().map(|(&k, _)| k).eq((0..CAPACITY).step_by(2)));
+}
+
+#[test]
+fn test_split_off() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    for i in (0..CAPACITY).map(|i| i * 2) {
+        sgt.insert(i, i);
+    }
+
+    // Absent key splits at its would-be position, single rebuild
+    let rebal_cnt = sgt.rebal_cnt();
+    let upper = sgt.split_off(&(CAPACITY + 1));
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+    assert_logical_invariants(&sgt);
+    assert_logical_invariants(&upper);
+    assert!(sgt.iter().map(|(k, _)| *k).eq((0..=CAPACITY).step_by(2)));
+    assert!(upper
+        .iter()
+        .map(|(k, _)| *k)
+        .eq(((CAPACITY + 2)..(CAPACITY * 2)).step_by(2)));
+
+    // Freed slots are reusable
+    for i in 0..upper.len() {
+        assert!(sgt.try_insert(i * 2 + 1, i).is_ok());
+    }
+    assert_eq!(sgt.len(), CAPACITY);
+
+    // Degenerate splits
+    assert!(sgt.split_off(&usize::MAX).is_empty());
+    assert_eq!(sgt.len(), CAPACITY);
+    let all = sgt.split_off(&0);
+    assert!(sgt.is_empty());
+    assert_eq!(all.len(), CAPACITY);
+    assert_logical_invariants(&all);
+}
+
 #[test]
 fn test_extend() {
     let mut sgt_1 = SgTree::<_, _, CAPACITY>::new();
@@ -655,7 +888,7 @@ fn test_extend_panic() {
 
     // Attempt to extend already full tree
     assert_eq!(sgt.len(), sgt.capacity());
-    sgt.extend(sgt_2.into_iter()); // Should panic
+    sgt.extend(sgt_2); // Should panic
 }
 
 #[test]
@@ -682,6 +915,7 @@ fn test_debug() {
 }
 
 #[test]
+#[cfg_attr(feature = "telemetry", allow(clippy::mutable_key_type))] // Counters aren't hashed
 fn test_hash() {
     let sgt_1 = SgTree::from([(3, 4), (1, 2), (5, 6)]);
     let sgt_2: SgTree<_, _, 3> = [(1, 2), (3, 4), (5, 6)].into();
@@ -701,22 +935,25 @@ fn test_clone() {
     assert_eq!(sgt_1, sgt_2);
 }
 
-#[cfg(not(feature = "alt_impl"))] // This affects rebalance count and is experimental.
+#[cfg(not(any(feature = "alt_impl", feature = "weight_balanced", feature = "avl")))] // This affects rebalance count and is experimental.
 #[test]
 fn test_set_rebal_param() {
-    assert!(CAPACITY >= 100);
+    #[allow(clippy::assertions_on_constants)]
+    {
+        assert!(CAPACITY >= 100);
+    }
     let data: Vec<(usize, usize)> = (0..100).map(|x| (x, x)).collect();
-    let sgt_1 = SgTree::<_, _, CAPACITY>::from_iter(data.clone().into_iter());
+    let sgt_1 = SgTree::<_, _, CAPACITY>::from_iter(data.clone());
 
     // Lax rebalancing
     let mut sgt_2 = SgTree::<_, _, CAPACITY>::new();
     assert!(sgt_2.set_rebal_param(0.9, 1.0).is_ok());
-    sgt_2.extend(data.clone().into_iter());
+    sgt_2.extend(data.clone());
 
     // Strict rebalancing
     let mut sgt_3 = SgTree::<_, _, CAPACITY>::new();
     assert!(sgt_3.set_rebal_param(1.0, 2.0).is_ok());
-    sgt_3.extend(data.into_iter());
+    sgt_3.extend(data);
 
     // Invalid rebalance factor
     assert_eq!(
@@ -724,6 +961,21 @@ fn test_set_rebal_param() {
         Err(SgError::RebalanceFactorOutOfRange)
     );
 
+    // Fixed-point equivalent
+    let mut sgt_4 = SgTree::<_, _, CAPACITY>::new();
+    assert!(sgt_4.set_rebalance_factor(9, 10).is_ok());
+    assert_eq!(sgt_4.rebal_param(), (9.0, 10.0));
+    sgt_4.extend((0..100).map(|x| (x, x)));
+    assert_eq!(sgt_4.rebal_cnt(), sgt_2.rebal_cnt());
+    assert!(sgt_4.set_rebalance_factor(1, 2).is_ok());
+    for (num, denom) in [(2, 1), (1, 1), (49, 100), (0, 0), (u16::MAX, u16::MAX)] {
+        assert_eq!(
+            sgt_4.set_rebalance_factor(num, denom),
+            Err(SgError::RebalanceFactorOutOfRange)
+        );
+    }
+    assert_eq!(sgt_4.rebal_param(), (1.0, 2.0));
+
     // Alpha tuning OK
     assert!(sgt_3.rebal_cnt() > sgt_2.rebal_cnt());
     assert!(sgt_1.rebal_cnt() > sgt_2.rebal_cnt());
@@ -752,11 +1004,23 @@ fn test_intersect_cnt() {
     assert_eq!(sgt_1.intersect_cnt(&sgt_2), 0);
 }
 
-#[should_panic(expected = "Max stack item capacity (0xffff) exceeded!")]
+#[should_panic(expected = "Max stack item capacity (0xfffe) exceeded!")]
 #[test]
 fn test_capacity_exceed() {
-    const OVER_CAP: usize = (Idx::MAX as usize) + 1;
-    let _ = SgTree::<u8, u8, OVER_CAP>::new();
+    const OVER_CAP: usize = Idx::MAX as usize;
+
+    // Over-capacity tree may exceed the default 2MB test thread stack (e.g. with feature `cacheline_nodes`)
+    let result = std::thread::Builder::new()
+        .stack_size(4 * core::mem::size_of::<SgTree<u8, u8, OVER_CAP>>())
+        .spawn(|| {
+            let _ = SgTree::<u8, u8, OVER_CAP>::new();
+        })
+        .unwrap()
+        .join();
+
+    if let Err(panic) = result {
+        std::panic::resume_unwind(panic);
+    }
 }
 
 #[test]
@@ -774,3 +1038,1031 @@ fn test_double_ended_iter_mut() {
     assert_eq!(None, iter.next());
     assert_eq!(None, iter.next_back());
 }
+
+#[test]
+fn test_take_smallest_largest() {
+    let (mut sgt, mut keys) = get_test_tree_and_keys();
+    keys.sort_unstable();
+
+    let smallest = sgt.take_smallest(3);
+    assert_logical_invariants(&smallest);
+    assert_logical_invariants(&sgt);
+    assert!(smallest
+        .iter()
+        .map(|(k, _)| *k)
+        .eq(keys[..3].iter().cloned()));
+    assert!(sgt.iter().map(|(k, _)| *k).eq(keys[3..].iter().cloned()));
+
+    let largest = sgt.take_largest(4);
+    assert_logical_invariants(&largest);
+    assert_logical_invariants(&sgt);
+    assert!(largest
+        .iter()
+        .map(|(k, _)| *k)
+        .eq(keys[(keys.len() - 4)..].iter().cloned()));
+    assert!(sgt
+        .iter()
+        .map(|(k, _)| *k)
+        .eq(keys[3..(keys.len() - 4)].iter().cloned()));
+    assert_eq!(sgt.first_key(), Some(&keys[3]));
+    assert_eq!(sgt.last_key(), Some(&keys[keys.len() - 5]));
+
+    // Tree remains usable post-take
+    sgt.insert(0, "n/a");
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.first_key(), Some(&0));
+
+    // Over-sized take drains everything
+    let len = sgt.len();
+    let rest = sgt.take_largest(len + 1);
+    assert_eq!(rest.len(), len);
+    assert!(sgt.is_empty());
+    assert!(sgt.take_smallest(1).is_empty());
+}
+
+#[test]
+fn test_split_at_rank() {
+    let (mut sgt, mut keys) = get_test_tree_and_keys();
+    keys.sort_unstable();
+    let mid = keys.len() / 2;
+
+    let rest = sgt.split_at_rank(mid);
+    assert_logical_invariants(&rest);
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().map(|(k, _)| *k).eq(keys[..mid].iter().cloned()));
+    assert!(rest.iter().map(|(k, _)| *k).eq(keys[mid..].iter().cloned()));
+
+    // Out-of-range ranks
+    assert!(sgt.split_at_rank(mid + 1).is_empty());
+    assert_eq!(sgt.len(), mid);
+    assert_eq!(sgt.split_at_rank(0).len(), mid);
+    assert!(sgt.is_empty());
+}
+
+#[test]
+fn test_from_sorted_iter() {
+    fn height(sgt: &SgTree<usize, usize, CAPACITY>, opt_idx: Option<usize>) -> usize {
+        opt_idx.map_or(0, |idx| {
+            1 + height(sgt, sgt.arena.left_idx(idx)).max(height(sgt, sgt.arena.right_idx(idx)))
+        })
+    }
+
+    // Perfectly balanced, no rebalance
+    let sgt =
+        SgTree::<usize, usize, CAPACITY>::from_sorted_iter((0..CAPACITY).map(|k| (k, k))).unwrap();
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert_eq!(height(&sgt, sgt.opt_root_idx), 11); // ceil(log2(1024 + 1))
+    assert!(sgt.iter().map(|(k, _)| *k).eq(0..CAPACITY));
+
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::from_sorted_iter(core::iter::empty()).unwrap();
+    assert!(sgt.is_empty());
+    sgt.insert(1, 1);
+    assert_logical_invariants(&sgt);
+
+    // Duplicates and descending order rejected
+    assert_eq!(
+        SgTree::<usize, usize, CAPACITY>::from_sorted_iter([(1, 1), (1, 1)]).err(),
+        Some(SgError::UnsortedInput)
+    );
+    assert_eq!(
+        SgTree::<usize, usize, CAPACITY>::from_sorted_iter([(2, 2), (1, 1)]).err(),
+        Some(SgError::UnsortedInput)
+    );
+    assert_eq!(
+        SgTree::<usize, usize, CAPACITY>::from_sorted_iter((0..=CAPACITY).map(|k| (k, k))).err(),
+        Some(SgError::StackCapacityExceeded)
+    );
+}
+
+#[test]
+fn test_rank_select() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0);
+
+    assert_eq!(sgt.rank(&0), 0);
+    assert_eq!(sgt.select(0), None);
+
+    // Exercise subtree size upkeep across inserts, removals, and rebuilds
+    for _ in 0..(CAPACITY * 4) {
+        let key = rng.gen_range(0, CAPACITY * 2);
+        match rng.gen_bool(0.6) && !sgt.is_full() {
+            true => {
+                sgt.insert(key, key);
+                bt_map.insert(key, key);
+            }
+            false => {
+                sgt.remove(&key);
+                bt_map.remove(&key);
+            }
+        }
+    }
+
+    for (rank, (k, v)) in bt_map.iter().enumerate() {
+        assert_eq!(sgt.select(rank), Some((k, v)));
+        assert_eq!(sgt.rank(k), rank);
+        assert_eq!(sgt.rank(&(k + 1)), bt_map.range(..(k + 1)).count());
+    }
+    assert_eq!(sgt.select(sgt.len()), None);
+    assert_eq!(sgt.rank(&usize::MAX), sgt.len());
+}
+
+#[test]
+fn test_from_raw_parts() {
+    fn node(key: usize, left: Option<Idx>, right: Option<Idx>) -> Option<RawNode<usize, ()>> {
+        Some(RawNode {
+            key,
+            val: (),
+            left,
+            right,
+        })
+    }
+
+    // Degenerate right-leaning chain, rebalanced on adoption
+    let chain = (0..100).map(|i| node(i, None, if i < 99 { Some((i + 1) as Idx) } else { None }));
+    let sgt = SgTree::<usize, (), 100>::from_raw_parts(chain, Some(0)).unwrap();
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().map(|(k, _)| *k).eq(0..100));
+
+    // Holes are fine
+    let sgt =
+        SgTree::<usize, (), 4>::from_raw_parts([None, node(1, None, None), None], Some(1)).unwrap();
+    assert_eq!(sgt.len(), 1);
+    let sgt = SgTree::<usize, (), 4>::from_raw_parts([None, None], None).unwrap();
+    assert!(sgt.is_empty());
+
+    assert_eq!(
+        SgTree::<usize, (), 1>::from_raw_parts([node(1, None, None), None], Some(0)).unwrap_err(),
+        RawPartsError::CapacityExceeded
+    );
+    assert_eq!(
+        SgTree::<usize, (), 4>::from_raw_parts([node(1, Some(1), None), None], Some(0))
+            .unwrap_err(),
+        RawPartsError::InvalidIndex { idx: 1 }
+    );
+    assert_eq!(
+        SgTree::<usize, (), 4>::from_raw_parts([node(1, None, None)], Some(3)).unwrap_err(),
+        RawPartsError::InvalidIndex { idx: 3 }
+    );
+    assert_eq!(
+        SgTree::<usize, (), 4>::from_raw_parts(
+            [node(1, None, Some(1)), node(2, Some(0), None)],
+            Some(0)
+        )
+        .unwrap_err(),
+        RawPartsError::DuplicateLink { idx: 0 }
+    );
+    assert_eq!(
+        SgTree::<usize, (), 4>::from_raw_parts([node(1, None, None), node(2, None, None)], Some(0))
+            .unwrap_err(),
+        RawPartsError::Unreachable { idx: 1 }
+    );
+    assert_eq!(
+        SgTree::<usize, (), 4>::from_raw_parts(
+            [node(1, None, Some(1)), node(1, None, None)],
+            Some(0)
+        )
+        .unwrap_err(),
+        RawPartsError::Unordered { idx: 1 }
+    );
+}
+
+#[test]
+fn test_validate() {
+    let (sgt, _) = get_test_tree_and_keys();
+    assert_eq!(sgt.validate(), Ok(()));
+    assert_eq!(SgTree::<usize, usize, CAPACITY>::new().validate(), Ok(()));
+
+    let root_idx = sgt.opt_root_idx.unwrap();
+    let min_idx = sgt.min_idx;
+
+    // Cycle
+    let mut bad = sgt.clone();
+    bad.arena.set_left_idx(min_idx, Some(root_idx));
+    assert_eq!(
+        bad.validate(),
+        Err(InvariantError::DuplicateLink { idx: root_idx })
+    );
+
+    // Dangling link
+    let mut bad = sgt.clone();
+    bad.arena.set_left_idx(min_idx, Some(sgt.len()));
+    assert_eq!(
+        bad.validate(),
+        Err(InvariantError::InvalidIndex { idx: sgt.len() })
+    );
+
+    // Detached subtree
+    let mut bad = sgt.clone();
+    bad.arena.set_left_idx(root_idx, None);
+    assert!(matches!(
+        bad.validate(),
+        Err(InvariantError::LengthMismatch { len, reachable }) if (len == sgt.len()) && (reachable < len)
+    ));
+
+    // Not a binary search tree
+    let mut bad = sgt.clone();
+    bad.arena.replace_key(min_idx, usize::MAX);
+    assert!(matches!(
+        bad.validate(),
+        Err(InvariantError::Unordered { .. })
+    ));
+
+    // Stale cache
+    let mut bad = sgt.clone();
+    bad.min_idx = bad.max_idx;
+    assert_eq!(bad.validate(), Err(InvariantError::StaleMinMax));
+
+    // Scapegoat guarantee only holds while automatic rebuilds are on
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    {
+        let mut bad = SgTree::<usize, usize, CAPACITY>::new();
+        bad.set_auto_rebalance(false);
+        bad.extend((0..100).map(|k| (k, k)));
+        assert_eq!(bad.validate(), Ok(()));
+
+        bad.set_auto_rebalance(true);
+        assert_eq!(
+            bad.validate(),
+            Err(InvariantError::Unbalanced { idx: bad.max_idx })
+        );
+
+        bad.rebalance();
+        assert_eq!(bad.validate(), Ok(()));
+    }
+
+    assert_eq!(
+        InvariantError::DuplicateLink { idx: 3 }.to_string(),
+        "node at index 3 linked more than once"
+    );
+}
+
+#[test]
+#[should_panic(expected = "Internal invariant failed: stale cached min or max index")]
+fn test_assert_invariants_panic() {
+    let (mut sgt, _) = get_test_tree_and_keys();
+    sgt.min_idx = sgt.max_idx;
+    sgt.assert_invariants();
+}
+
+#[test]
+fn test_maintenance() {
+    let (mut sgt, keys) = get_test_tree_and_keys();
+    let mut budget = MaintenanceBudget::nodes(0);
+
+    // Freshly built, only compaction could be pending
+    assert!(sgt.maintenance(MaintenanceBudget::unlimited()));
+    assert!(sgt.maintenance(budget));
+
+    // Remove a few keys, leaving holes in the arena (but not enough to trigger a rebuild)
+    for k in keys.iter().step_by(4) {
+        assert!(sgt.remove(k).is_some());
+    }
+    assert_logical_invariants(&sgt);
+    let remaining: Vec<usize> = sgt.iter().map(|(k, _)| *k).collect();
+
+    // Work is all-or-nothing, an insufficient budget leaves the tree untouched
+    let rebal_cnt = sgt.rebal_cnt();
+    budget = MaintenanceBudget::nodes(sgt.len() - 1);
+    assert!(!sgt.maintenance(budget));
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+    assert_eq!(budget.remaining(), sgt.len() - 1);
+
+    // Budget for rebuild only
+    assert!(!sgt.maintenance(MaintenanceBudget::nodes(sgt.len())));
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+    assert_logical_invariants(&sgt);
+
+    // Budget for compaction
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(sgt.len())));
+    assert_eq!(sgt.arena.len(), sgt.len());
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().map(|(k, _)| *k).eq(remaining.iter().cloned()));
+    assert!(sgt
+        .arena
+        .iter()
+        .map(|slot| *slot.unwrap().0)
+        .eq(remaining.iter().cloned()));
+
+    // Still usable, no holes introduced by insertion
+    sgt.insert(0, "n/a");
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.first_key(), Some(&0));
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(0)));
+
+    // Empty tree with a dirty arena
+    while sgt.pop_last().is_some() {}
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(0)));
+    assert_eq!(sgt.arena.len(), 0);
+    sgt.insert(1, "n/a");
+    assert_logical_invariants(&sgt);
+}
+
+#[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+#[test]
+fn test_balance_policies() {
+    // Never: sorted insertion degrades to a list, removals don't rebuild
+    let mut sgt = SgTree::<usize, usize, CAPACITY, _>::with_balance_policy(NeverRebalancePolicy);
+    sgt.extend((0..CAPACITY).map(|k| (k, k)));
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert!(!sgt.is_balanced());
+    for k in 0..(CAPACITY - 1) {
+        assert_eq!(sgt.remove(&k), Some(k));
+    }
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert_eq!(sgt.len(), 1);
+
+    // Max depth: sorted insertion never lands deeper than the limit
+    let max_depth = MaxDepthPolicy::new(16);
+    let mut sgt = SgTree::<usize, usize, CAPACITY, _>::with_balance_policy(max_depth);
+    assert_eq!(max_depth.max_depth(), 16);
+    for k in 0..CAPACITY {
+        sgt.insert(k, k);
+        assert!(sgt.max_depth() <= max_depth.max_depth());
+    }
+    assert_logical_invariants(&sgt);
+    assert!(sgt.rebal_cnt() > 0);
+
+    // Policy swap keeps the type, only the depth limit changes
+    assert!(sgt.set_balance_policy(MaxDepthPolicy::new(12)).is_ok());
+    sgt.clear();
+    for k in 0..CAPACITY {
+        sgt.insert(k, k);
+        assert!(sgt.max_depth() <= 12);
+    }
+    assert_logical_invariants(&sgt);
+
+    // Deletion count: full rebuild after every 100 removals
+    let deletions = DeletionCountPolicy::new(100);
+    let mut sgt = SgTree::<usize, usize, CAPACITY, _>::with_balance_policy(deletions);
+    assert_eq!(deletions.deletions(), 100);
+    assert_eq!(DeletionCountPolicy::new(0).deletions(), 1);
+    sgt.extend((0..CAPACITY).map(|k| (k, k)));
+    assert!(sgt.is_balanced());
+
+    let rebal_cnt = sgt.rebal_cnt();
+    for k in 0..99 {
+        assert_eq!(sgt.remove(&k), Some(k));
+    }
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+    assert_eq!(sgt.remove(&99), Some(99));
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+    assert_logical_invariants(&sgt);
+}
+
+#[cfg(any(feature = "weight_balanced", feature = "avl"))]
+#[test]
+fn test_rotation_rejects_tuning() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let default_param = sgt.rebal_param();
+    assert_eq!(
+        sgt.set_rebal_param(3.0, 4.0),
+        Err(SgError::RotationBalanced)
+    );
+    assert_eq!(
+        sgt.set_rebalance_factor(3, 4),
+        Err(SgError::RotationBalanced)
+    );
+    assert_eq!(
+        sgt.set_balance_policy(DefaultBalancePolicy::default()),
+        Err(SgError::RotationBalanced)
+    );
+    assert_eq!(sgt.rebal_param(), default_param);
+
+    // Still balanced by rotations
+    sgt.extend((0..CAPACITY).map(|k| (k, k)));
+    assert!(sgt.is_balanced());
+    assert_logical_invariants(&sgt);
+}
+
+#[test]
+fn test_deferred_rebalance() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert!(sgt.auto_rebalance());
+    assert!(sgt.is_balanced());
+
+    // Sorted insertion, worst case without rebuilds
+    sgt.set_auto_rebalance(false);
+    for k in 0..CAPACITY {
+        sgt.insert(k, k);
+    }
+    assert_logical_invariants(&sgt);
+
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    {
+        assert_eq!(sgt.rebal_cnt(), 0);
+        assert!(!sgt.is_balanced());
+    }
+
+    // Removals don't rebuild either
+    let rebal_cnt = sgt.rebal_cnt();
+    for k in (0..CAPACITY).step_by(2) {
+        assert_eq!(sgt.remove(&k), Some(k));
+    }
+    assert_logical_invariants(&sgt);
+
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+
+    // Single explicit rebuild
+    sgt.rebalance();
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+    assert_logical_invariants(&sgt);
+    assert!(sgt.is_balanced());
+    assert!(sgt.iter().map(|(k, _)| *k).eq((1..CAPACITY).step_by(2)));
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(sgt.len())));
+
+    // Back to automatic
+    sgt.set_auto_rebalance(true);
+    for k in (0..CAPACITY).step_by(2) {
+        sgt.insert(k, k);
+    }
+    assert!(sgt.is_balanced());
+
+    // Empty tree
+    sgt.clear();
+    sgt.rebalance();
+    assert!(sgt.is_balanced());
+}
+
+#[test]
+fn test_arena_layout() {
+    let mut sgt: SgTree<usize, usize, CAPACITY> = (1..=15).map(|k| (k, k)).collect();
+    let arena_keys = |sgt: &SgTree<usize, usize, CAPACITY>| -> Vec<usize> {
+        sgt.arena.iter().map(|slot| *slot.unwrap().0).collect()
+    };
+    assert_eq!(sgt.arena_layout(), ArenaLayout::InOrder);
+
+    // Perfect tree of height 4, level by level
+    sgt.set_arena_layout(ArenaLayout::BreadthFirst);
+    assert!(!sgt.maintenance(MaintenanceBudget::nodes(sgt.len())));
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(2 * sgt.len())));
+    assert_logical_invariants(&sgt);
+    assert_eq!(
+        arena_keys(&sgt),
+        vec![8, 4, 12, 2, 6, 10, 14, 1, 3, 5, 7, 9, 11, 13, 15]
+    );
+
+    // Top half, then each bottom subtree left to right
+    sgt.set_arena_layout(ArenaLayout::VanEmdeBoas);
+    assert!(sgt.maintenance(MaintenanceBudget::unlimited()));
+    assert_logical_invariants(&sgt);
+    assert_eq!(
+        arena_keys(&sgt),
+        vec![8, 4, 12, 2, 1, 3, 6, 5, 7, 10, 9, 11, 14, 13, 15]
+    );
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(0)));
+
+    // Removals re-apply the layout
+    assert_eq!(sgt.remove(&15), Some(15));
+    assert!(!sgt.maintenance(MaintenanceBudget::nodes(sgt.len())));
+    assert!(sgt.maintenance(MaintenanceBudget::unlimited()));
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.arena.len(), sgt.len());
+    assert!(sgt.iter().map(|(k, _)| *k).eq(1..15));
+
+    // Back to sorted, without a rebuild
+    sgt.set_arena_layout(ArenaLayout::InOrder);
+    assert!(sgt.maintenance(MaintenanceBudget::nodes(sgt.len())));
+    assert_eq!(arena_keys(&sgt), (1..15).collect::<Vec<_>>());
+
+    // Larger random trees, checked against a reference
+    let mut rng = SmallRng::from_entropy();
+    for layout in [ArenaLayout::BreadthFirst, ArenaLayout::VanEmdeBoas] {
+        let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+        let mut map = BTreeMap::new();
+        for _ in 0..CAPACITY {
+            let k = rng.gen_range(0, 4 * CAPACITY);
+            sgt.insert(k, k);
+            map.insert(k, k);
+        }
+        for _ in 0..(CAPACITY / 4) {
+            let k = rng.gen_range(0, 4 * CAPACITY);
+            assert_eq!(sgt.remove(&k), map.remove(&k));
+        }
+
+        sgt.set_arena_layout(layout);
+        assert!(sgt.maintenance(MaintenanceBudget::unlimited()));
+        assert_logical_invariants(&sgt);
+        assert_eq!(sgt.arena.len(), sgt.len());
+        assert!(sgt.iter().eq(map.iter()));
+        for k in map.keys() {
+            assert_eq!(sgt.get(k), Some(k));
+        }
+    }
+}
+
+#[cfg(feature = "telemetry")]
+#[test]
+fn test_telemetry() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(sgt.stats(), Default::default());
+
+    // Sorted insertion, rebuilds touch at most every node
+    for k in 0..CAPACITY {
+        sgt.insert(k, k);
+    }
+    let stats = sgt.stats();
+    assert!(stats.max_depth() > 0);
+    assert_eq!(stats.lookups(), 0);
+
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    {
+        assert_eq!(stats.rebuilds(), sgt.rebal_cnt());
+        assert!(stats.max_rebuild_nodes() <= CAPACITY);
+        assert!(stats.rebuild_nodes() >= stats.max_rebuild_nodes());
+    }
+
+    // Full rebuild touches every node
+    sgt.rebalance();
+    assert_eq!(sgt.stats().max_rebuild_nodes(), CAPACITY);
+
+    // Lookups, hits and misses, bounded by tree height
+    sgt.reset_stats();
+    assert_eq!(sgt.stats(), Default::default());
+    for k in 0..(2 * CAPACITY) {
+        assert_eq!(sgt.contains_key(&k), k < CAPACITY);
+    }
+    let stats = sgt.stats();
+    assert_eq!(stats.lookups(), 2 * CAPACITY);
+    assert!(stats.lookup_cmps() >= stats.lookups());
+    assert!(stats.lookup_cmps() <= stats.lookups() * 11);
+
+    // Clones carry counters over
+    let clone = sgt.clone();
+    assert_eq!(clone.stats(), stats);
+}
+
+#[cfg(feature = "yield_hook")]
+#[test]
+fn test_yield_hook() {
+    use super::YieldHook;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static YIELDS: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_yield() {
+        YIELDS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let (mut sgt, keys) = get_test_tree_and_keys();
+    assert!(sgt.yield_hook().is_none());
+
+    // No hook, no calls
+    for k in keys.iter().step_by(4) {
+        sgt.remove(k);
+    }
+    sgt.maintenance(MaintenanceBudget::unlimited());
+    assert_eq!(YIELDS.load(Ordering::Relaxed), 0);
+
+    // Full rebuild and compaction each visit every node
+    sgt.set_yield_hook(Some(YieldHook::new(on_yield, 1)));
+    for k in keys.iter().step_by(3) {
+        sgt.remove(k);
+    }
+    assert!(sgt.maintenance(MaintenanceBudget::unlimited()));
+    assert!(YIELDS.load(Ordering::Relaxed) >= 2 * sgt.len());
+    assert_logical_invariants(&sgt);
+
+    // Period bounds work between calls
+    sgt.set_yield_hook(Some(YieldHook::new(on_yield, usize::MAX)));
+    let before = YIELDS.load(Ordering::Relaxed);
+    let taken = sgt.take_smallest(3);
+    assert_eq!(YIELDS.load(Ordering::Relaxed), before);
+    assert_eq!(taken.yield_hook().map(|h| h.period()), Some(usize::MAX));
+
+    // Zero period treated as one
+    assert_eq!(YieldHook::new(on_yield, 0).period(), 1);
+
+    sgt.set_yield_hook(None);
+    assert!(sgt.yield_hook().is_none());
+}
+
+// Padding of the tree to cacheline-aligned nodes would obscure per-slot cost
+#[cfg(not(feature = "cacheline_nodes"))]
+#[test]
+fn test_fixed_idx_width() {
+    use super::arena::ArenaNode;
+    use core::mem::size_of;
+
+    const SMALL_CAPACITY: usize = 16;
+    const LARGE_CAPACITY: usize = 1024;
+
+    // Per-slot arena cost doesn't depend on capacity
+    let slot_size = size_of::<Option<ArenaNode<u32, u32, Idx>>>()
+        + if cfg!(feature = "split_values") {
+            size_of::<Option<u32>>()
+        } else {
+            0
+        }
+        + if cfg!(feature = "soa_arena") {
+            size_of::<Option<u32>>()
+        } else {
+            0
+        }
+        + if cfg!(feature = "low_mem_insert") {
+            0
+        } else {
+            size_of::<Idx>()
+        };
+
+    assert_eq!(
+        size_of::<SgTree<u32, u32, LARGE_CAPACITY>>()
+            - size_of::<SgTree<u32, u32, SMALL_CAPACITY>>(),
+        (LARGE_CAPACITY - SMALL_CAPACITY) * slot_size
+    );
+}
+
+#[test]
+fn test_max_len() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(sgt.max_len(), CAPACITY);
+
+    // Invalid limits
+    assert_eq!(
+        sgt.set_max_len(CAPACITY + 1),
+        Err(SgError::MaximumCapacityExceeded)
+    );
+    sgt.insert(0, 0);
+    sgt.insert(1, 1);
+    assert_eq!(sgt.set_max_len(1), Err(SgError::StackCapacityExceeded));
+
+    // Soft limit
+    assert!(sgt.set_max_len(3).is_ok());
+    assert!(sgt.try_insert(2, 2).is_ok());
+    assert!(sgt.is_full());
+    assert_eq!(sgt.try_insert(3, 3), Err(SgError::StackCapacityExceeded));
+    assert_eq!(sgt.try_insert(2, 20), Ok(Some(2)));
+    assert_eq!(
+        sgt.try_extend(IntoIterator::into_iter([(2, 2), (4, 4)]))
+            .unwrap_err(),
+        (
+            ExtendReport {
+                inserted: 0,
+                updated: 1
+            },
+            (4, 4)
+        )
+    );
+
+    // Limit doesn't transfer on append
+    let mut other = SgTree::<usize, usize, CAPACITY>::new();
+    assert!(other.try_append(&mut sgt).is_ok());
+    assert_eq!(other.max_len(), CAPACITY);
+    assert_eq!(sgt.max_len(), 3);
+
+    let mut small = SgTree::<usize, usize, CAPACITY>::new();
+    assert!(small.set_max_len(2).is_ok());
+    assert_eq!(
+        small.try_append(&mut other),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert!(small.is_empty());
+    assert_eq!(other.len(), 3);
+
+    // Limit survives clear
+    sgt.insert(0, 0);
+    sgt.clear();
+    assert_eq!(sgt.max_len(), 3);
+    assert_logical_invariants(&sgt);
+}
+
+#[should_panic(expected = "Stack-storage capacity exceeded!")]
+#[test]
+fn test_max_len_insert_panic() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert!(sgt.set_max_len(1).is_ok());
+    sgt.insert(0, 0);
+    sgt.insert(0, 1);
+    sgt.insert(1, 1);
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_display_tree() {
+    let empty = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(format!("{}", empty.display_tree()), "");
+
+    // Perfect tree, then a lone left child
+    let mut sgt: SgTree<usize, (), CAPACITY> = (1..=7).map(|k| (k, ())).collect();
+    sgt.rebalance();
+    sgt.insert(0, ());
+    let expected = [
+        "4",
+        "|-- L: 2",
+        "|   |-- L: 1",
+        "|   |   `-- L: 0",
+        "|   `-- R: 3",
+        "`-- R: 6",
+        "    |-- L: 5",
+        "    `-- R: 7",
+    ];
+    assert_eq!(
+        format!("{}", sgt.display_tree()),
+        expected
+            .iter()
+            .map(|l| format!("{}\n", l))
+            .collect::<String>()
+    );
+
+    // One line per element, in pre-order
+    let (sgt, keys) = get_test_tree_and_keys();
+    let rendered = format!("{}", sgt.display_tree());
+    assert_eq!(rendered.lines().count(), keys.len());
+    assert_eq!(
+        rendered.lines().next().unwrap(),
+        sgt.arena.key(sgt.opt_root_idx.unwrap()).to_string()
+    );
+    let mut rendered_keys: Vec<usize> = rendered
+        .lines()
+        .map(|l| l.rsplit(' ').next().unwrap().parse().unwrap())
+        .collect();
+    rendered_keys.sort_unstable();
+    assert_eq!(
+        rendered_keys,
+        sgt.iter().map(|(k, _)| *k).collect::<Vec<_>>()
+    );
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_to_dot() {
+    let mut dot = String::new();
+    SgTree::<usize, usize, CAPACITY>::new()
+        .to_dot(&mut dot)
+        .unwrap();
+    assert_eq!(dot, "digraph {\n    node [shape=box];\n}\n");
+
+    // One node line per element, one edge line per non-root element
+    let (sgt, keys) = get_test_tree_and_keys();
+    let mut dot = String::new();
+    sgt.to_dot(&mut dot).unwrap();
+    assert_eq!(
+        dot.matches("[label=\"L\"]").count() + dot.matches("[label=\"R\"]").count(),
+        keys.len() - 1
+    );
+    for (idx, k) in sgt
+        .iter()
+        .map(|(k, _)| (sgt.internal_get::<_, Idx>(None, k).node_idx().unwrap(), k))
+    {
+        assert!(dot.contains(&format!("    n{} [label=\"{}\\n#{}\"];\n", idx, k, idx)));
+    }
+
+    // Root first
+    let root_idx = sgt.opt_root_idx.unwrap();
+    assert!(dot
+        .lines()
+        .nth(2)
+        .unwrap()
+        .starts_with(&format!("    n{} ", root_idx)));
+
+    // Escaped labels
+    let mut sgt = SgTree::<&str, (), CAPACITY>::new();
+    sgt.insert("a \"quoted\" \\ key", ());
+    let mut dot = String::new();
+    sgt.to_dot(&mut dot).unwrap();
+    assert!(dot.contains(r#"n0 [label="\"a \\\"quoted\\\" \\\\ key\"\n#0"];"#));
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_shape() {
+    let empty = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(empty.shape(), Default::default());
+    assert!(empty.shape().is_empty());
+    assert_eq!(empty.height(), 0);
+
+    // Consistent with the histogram
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    for _ in 0..CAPACITY {
+        let k = rng.gen_range(0, 4 * CAPACITY);
+        sgt.insert(k, k);
+    }
+
+    let mut hist = [0; CAPACITY];
+    let height = sgt.depth_histogram(&mut hist);
+    let shape = sgt.shape();
+    assert_eq!(shape.len(), sgt.len());
+    assert_eq!(shape.height(), height);
+    assert_eq!(sgt.height(), height);
+    assert_eq!(
+        shape.internal_path_length(),
+        hist.iter()
+            .enumerate()
+            .map(|(depth, cnt)| depth * (*cnt as usize))
+            .sum::<usize>()
+    );
+    assert_eq!(sgt.internal_path_length(), shape.internal_path_length());
+    assert_eq!(
+        shape.full_levels(),
+        hist.iter()
+            .enumerate()
+            .take_while(|(depth, cnt)| **cnt == (1 << depth))
+            .count()
+    );
+    assert!(shape.height() >= shape.optimal_height());
+    assert_eq!(shape.balance_slack() >= 0, sgt.is_balanced());
+
+    // Perfect tree
+    let mut sgt: SgTree<usize, usize, CAPACITY> = (0..1023).map(|k| (k, k)).collect();
+    sgt.rebalance();
+    let shape = sgt.shape();
+    assert_eq!(shape.height(), 10);
+    assert_eq!(shape.optimal_height(), 10);
+    assert_eq!(shape.full_levels(), 10);
+    assert_eq!(
+        shape.internal_path_length(),
+        (0..10).map(|depth| depth << depth).sum::<usize>()
+    );
+
+    // Degenerate tree
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    {
+        let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+        sgt.set_auto_rebalance(false);
+        sgt.extend((0..100).map(|k| (k, k)));
+        let shape = sgt.shape();
+        assert_eq!(shape.height(), 100);
+        assert_eq!(shape.full_levels(), 1);
+        assert_eq!(shape.internal_path_length(), (0..100).sum::<usize>());
+        assert!(shape.balance_slack() < 0);
+    }
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_depth_histogram() {
+    let mut hist = [0; 32];
+    let empty = SgTree::<usize, &str, CAPACITY>::new();
+    assert_eq!(empty.depth_histogram(&mut hist), 0);
+    assert!(hist.iter().all(|cnt| *cnt == 0));
+
+    let (mut sgt, keys) = get_test_tree_and_keys();
+    let height = sgt.depth_histogram(&mut hist);
+    assert_eq!(hist.iter().sum::<u32>() as usize, keys.len());
+    assert_eq!(hist[0], 1);
+    assert!(hist[height - 1] > 0);
+    assert!(hist[height..].iter().all(|cnt| *cnt == 0));
+    for (depth, cnt) in hist.iter().enumerate().take(height) {
+        assert!(*cnt <= (1 << depth));
+    }
+
+    // Truncated buffer still reports full height
+    let mut short_hist = [0; 2];
+    assert_eq!(sgt.depth_histogram(&mut short_hist), height);
+    assert_eq!(short_hist, [1, hist[1]]);
+
+    // Perfectly balanced after a full rebuild, only the last level may be partial
+    sgt.remove(&keys[0]);
+    sgt.maintenance(MaintenanceBudget::unlimited());
+    let height = sgt.depth_histogram(&mut hist);
+    assert_eq!(height, (usize::BITS - sgt.len().leading_zeros()) as usize);
+    for (depth, cnt) in hist.iter().enumerate().take(height - 1) {
+        assert_eq!(*cnt, 1 << depth);
+    }
+}
+
+// Verify cached subtree sizes (and, with feature `weight_balanced`, node weight-balance) by recount.
+#[cfg(feature = "fast_rebalance")]
+fn assert_subtree_sizes<K: Ord, V, const N: usize>(
+    sgt: &SgTree<K, V, N>,
+    opt_idx: Option<usize>,
+) -> usize {
+    match opt_idx {
+        Some(idx) => {
+            let left_size = assert_subtree_sizes(sgt, sgt.arena.left_idx(idx));
+            let right_size = assert_subtree_sizes(sgt, sgt.arena.right_idx(idx));
+            assert_eq!(sgt.arena.subtree_size(idx), left_size + right_size + 1);
+
+            #[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+            {
+                assert!(3 * (left_size + 1) > right_size);
+                assert!(3 * (right_size + 1) > left_size);
+            }
+
+            sgt.arena.subtree_size(idx)
+        }
+        None => 0,
+    }
+}
+
+#[cfg(feature = "fast_rebalance")]
+#[test]
+fn test_subtree_size_cache() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut rng = SmallRng::seed_from_u64(0x5eed);
+
+    for i in 0..CAPACITY {
+        sgt.insert(rng.gen_range(0, 2 * CAPACITY), i); // Includes overwrites
+        assert_subtree_sizes(&sgt, sgt.opt_root_idx);
+    }
+
+    while !sgt.is_empty() {
+        let key = rng.gen_range(0, 2 * CAPACITY);
+        sgt.remove(&key); // Includes two-child removals
+        assert_subtree_sizes(&sgt, sgt.opt_root_idx);
+        if key % 7 == 0 {
+            sgt.pop_first();
+            assert_subtree_sizes(&sgt, sgt.opt_root_idx);
+        }
+    }
+}
+
+#[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+#[test]
+fn test_weight_balanced_rotation() {
+    fn height(sgt: &SgTree<usize, usize, CAPACITY>, opt_idx: Option<usize>) -> usize {
+        opt_idx.map_or(0, |idx| {
+            1 + height(sgt, sgt.arena.left_idx(idx)).max(height(sgt, sgt.arena.right_idx(idx)))
+        })
+    }
+
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+
+    // Sorted insertion, worst case for an unbalanced tree
+    for i in 0..CAPACITY {
+        sgt.insert(i, i);
+    }
+
+    assert_subtree_sizes(&sgt, sgt.opt_root_idx);
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert!(
+        height(&sgt, sgt.opt_root_idx) <= 2 * (usize::BITS - CAPACITY.leading_zeros()) as usize
+    );
+
+    // Remove from one side only
+    for i in 0..(CAPACITY - 10) {
+        assert_eq!(sgt.remove(&i), Some(i));
+    }
+
+    assert_subtree_sizes(&sgt, sgt.opt_root_idx);
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert!(sgt.iter().map(|(k, _)| *k).eq((CAPACITY - 10)..CAPACITY));
+    assert_eq!(
+        sgt.first_key_value(),
+        Some((&(CAPACITY - 10), &(CAPACITY - 10)))
+    );
+    assert_eq!(
+        sgt.last_key_value(),
+        Some((&(CAPACITY - 1), &(CAPACITY - 1)))
+    );
+}
+
+// Verify cached heights and AVL balance by recount, returning subtree height.
+#[cfg(feature = "avl")]
+fn assert_avl_heights<K: Ord, V, const N: usize>(
+    sgt: &SgTree<K, V, N>,
+    opt_idx: Option<usize>,
+) -> usize {
+    match opt_idx {
+        Some(idx) => {
+            let left_height = assert_avl_heights(sgt, sgt.arena.left_idx(idx));
+            let right_height = assert_avl_heights(sgt, sgt.arena.right_idx(idx));
+            assert_eq!(sgt.arena.height(idx), left_height.max(right_height) + 1);
+            assert!(left_height.abs_diff(right_height) <= 1);
+            sgt.arena.height(idx)
+        }
+        None => 0,
+    }
+}
+
+#[cfg(feature = "avl")]
+#[test]
+fn test_avl_rotation() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut rng = SmallRng::seed_from_u64(0xa51);
+
+    // Sorted insertion, worst case for an unbalanced tree
+    for i in 0..CAPACITY {
+        sgt.insert(i, i);
+        assert_avl_heights(&sgt, sgt.opt_root_idx);
+    }
+
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert_eq!(sgt.arena.height(sgt.opt_root_idx.unwrap()), 11);
+
+    // Random removals, including two-child cases, and overwrites
+    while sgt.len() > 10 {
+        let key = rng.gen_range(0, CAPACITY);
+        if sgt.remove(&key).is_some() {
+            sgt.insert(key + CAPACITY, key);
+            sgt.pop_last();
+        }
+        assert_avl_heights(&sgt, sgt.opt_root_idx);
+    }
+
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.rebal_cnt(), 0);
+
+    // Bulk rebuild leaves valid heights
+    sgt.retain(|k, _| k % 2 == 0);
+    assert_avl_heights(&sgt, sgt.opt_root_idx);
+    sgt.insert(1, 1);
+    assert_avl_heights(&sgt, sgt.opt_root_idx);
+}
@@ -2,25 +2,33 @@ use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
-use core::iter::FromIterator;
+use core::iter::{self, FromIterator};
 use core::mem;
 use core::ops::{
-    Bound::{Excluded, Included},
-    Index, RangeBounds, Sub,
+    Bound::{Excluded, Included, Unbounded},
+    Index, Range, RangeBounds, Sub,
 };
 
-use super::arena::Arena;
+use super::arena::{Arena, NodeArena, SgVec};
+use super::balance::BalanceParams;
+use super::balance::{BalancePolicy, DefaultBalancePolicy};
 use super::error::SgError;
+use super::extend::ExtendReport;
 use super::iter::{IntoIter, Iter, IterMut};
-use super::node::{NodeGetHelper, NodeRebuildHelper};
-use super::node_dispatch::SmallNode;
+#[cfg(feature = "yield_hook")]
+use super::maintenance::YieldHook;
+use super::maintenance::{ArenaFragmentation, ArenaLayout, MaintenanceBudget};
+use super::node::{LinkIdx, NodeGetHelper, NodeRebuildHelper};
+#[cfg(feature = "telemetry")]
+use super::telemetry::{StatCounters, TreeStats};
 
-#[allow(unused_imports)] // micromath only used if `no_std`
-use micromath::F32Ext;
 use smallnum::SmallUnsigned;
-use tinyvec::{array_vec, ArrayVec};
 
-// The `u16::MAX` limit is documented in our main `README.md`.
+// The `u16::MAX - 1` limit is documented in our main `README.md`.
+/// Arena index type, fixed width regardless of capacity `N`.
+///
+/// Every [`SgMap`][crate::SgMap] and [`SgSet`][crate::SgSet] stores internal node links as `Idx`,
+/// so node layout is identical across instances of different capacities.
 pub type Idx = u16;
 
 // See: https://github.com/tnballo/scapegoat/blob/master/CONFIG.md
@@ -29,7 +37,7 @@ const DEFAULT_ALPHA_DENOM: f32 = 3.0;
 
 /// A memory-efficient, self-balancing binary search tree.
 #[derive(Clone)]
-pub struct SgTree<K: Default, V: Default, const N: usize> {
+pub struct SgTree<K, V, const N: usize, P = DefaultBalancePolicy> {
     // Storage
     pub(crate) arena: Arena<K, V, Idx, N>,
     pub(crate) opt_root_idx: Option<usize>,
@@ -39,20 +47,100 @@ pub struct SgTree<K: Default, V: Default, const N: usize> {
     pub(crate) min_idx: usize,
     curr_size: usize,
 
+    // Runtime limit
+    max_len: usize,
+
     // Balance control
     alpha_num: f32,
     alpha_denom: f32,
     max_size: usize,
     rebal_cnt: usize,
+    policy: P,
+    auto_rebalance: bool,
+
+    // Arena placement
+    layout: ArenaLayout,
+    layout_pending: bool,
+
+    // Cooperative scheduling
+    #[cfg(feature = "yield_hook")]
+    opt_yield_hook: Option<YieldHook>,
+
+    // Instrumentation
+    #[cfg(feature = "telemetry")]
+    stats: StatCounters,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
+impl<K: Ord, V, const N: usize> SgTree<K, V, N> {
     // Public API ------------------------------------------------------------------------------------------------------
 
     /// Makes a new, empty `SgTree`.
     pub fn new() -> Self {
-        if N > SgTree::<K, V, N>::max_capacity() {
-            panic!("Max stack item capacity (0x{:x}) exceeded!", Idx::MAX);
+        Self::with_balance_policy(DefaultBalancePolicy::default())
+    }
+
+    /// Makes a new, empty `SgTree` with capacity chosen at runtime, in place of const `N`.
+    /// Storage beyond `N` items is heap-allocated up front.
+    #[cfg(feature = "alloc")]
+    pub fn with_capacity(cap: usize) -> Self {
+        if cap > SgTree::<K, V, N>::max_capacity() {
+            panic!(
+                "Max item capacity (0x{:x}) exceeded!",
+                SgTree::<K, V, N>::max_capacity()
+            );
+        }
+
+        SgTree {
+            arena: Arena::<K, V, Idx, N>::with_capacity(cap),
+            max_len: cap,
+            ..Self::new()
+        }
+    }
+
+    // Attempt conversion from an iterator.
+    /// Will fail if iterator length exceeds `u16::MAX - 1`.
+    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Result<Self, SgError> {
+        match iter.len() <= SgTree::<K, V, N>::max_capacity() {
+            true => Ok(SgTree::from_iter(iter)),
+            false => Err(SgError::MaximumCapacityExceeded),
+        }
+    }
+
+    /// Build a perfectly balanced tree from entries in strictly ascending key order, in `O(n)`.
+    /// Fails if input isn't strictly ascending or exceeds capacity.
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Result<Self, SgError> {
+        let mut sgt = Self::new();
+        let mut sorted_idxs = SgVec::<[usize; N]>::new();
+
+        for (key, val) in iter {
+            if let Some(&prev_idx) = sorted_idxs.last() {
+                if sgt.arena.key(prev_idx) >= &key {
+                    return Err(SgError::UnsortedInput);
+                }
+            }
+
+            if sorted_idxs.len() >= sgt.max_len() {
+                return Err(SgError::StackCapacityExceeded);
+            }
+
+            sorted_idxs.push(sgt.arena.add(key, val));
+        }
+
+        sgt.rebuild_from_sorted_idxs(&sorted_idxs);
+        Ok(sgt)
+    }
+}
+
+impl<K: Ord, V, const N: usize, P: BalancePolicy> SgTree<K, V, N, P> {
+    /// Makes a new, empty `SgTree` rebalanced per `policy`.
+    pub fn with_balance_policy(policy: P) -> Self {
+        if N > Self::max_capacity() {
+            panic!(
+                "Max stack item capacity (0x{:x}) exceeded!",
+                Self::max_capacity()
+            );
         }
 
         SgTree {
@@ -61,10 +149,19 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             max_idx: 0,
             min_idx: 0,
             curr_size: 0,
+            max_len: N,
             alpha_num: DEFAULT_ALPHA_NUM,
             alpha_denom: DEFAULT_ALPHA_DENOM,
             max_size: 0,
             rebal_cnt: 0,
+            policy,
+            auto_rebalance: true,
+            layout: ArenaLayout::InOrder,
+            layout_pending: false,
+            #[cfg(feature = "yield_hook")]
+            opt_yield_hook: None,
+            #[cfg(feature = "telemetry")]
+            stats: StatCounters::default(),
         }
     }
 
@@ -78,8 +175,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
     ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
     ///
-    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range),
+    /// or if feature `weight_balanced` or `avl` is enabled (rotations ignore `a`).
     pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
+        if cfg!(any(feature = "weight_balanced", feature = "avl")) {
+            return Err(SgError::RotationBalanced);
+        }
+
         let a = alpha_num / alpha_denom;
         match (0.5..1.0).contains(&a) {
             true => {
@@ -91,74 +193,229 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
+    /// Fixed-point equivalent of [`set_rebal_param`][SgTree::set_rebal_param], for targets without an FPU.
+    /// Alpha is `alpha_num / alpha_denom`, validated with integer arithmetic only.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range),
+    /// or if feature `weight_balanced` or `avl` is enabled (rotations ignore `a`).
+    pub fn set_rebalance_factor(
+        &mut self,
+        alpha_num: u16,
+        alpha_denom: u16,
+    ) -> Result<(), SgError> {
+        if cfg!(any(feature = "weight_balanced", feature = "avl")) {
+            return Err(SgError::RotationBalanced);
+        }
+
+        let (num, denom) = (u32::from(alpha_num), u32::from(alpha_denom));
+        match (2 * num >= denom) && (num < denom) {
+            true => {
+                // Lossless, every `u16` is exactly representable
+                self.alpha_num = f32::from(alpha_num);
+                self.alpha_denom = f32::from(alpha_denom);
+                Ok(())
+            }
+            false => Err(SgError::RebalanceFactorOutOfRange),
+        }
+    }
+
     /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
     /// See [the corresponding setter method][SgTree::set_rebal_param] for more details.
     pub fn rebal_param(&self) -> (f32, f32) {
         (self.alpha_num, self.alpha_denom)
     }
 
+    /// Replace the policy deciding when and where to rebalance, e.g. to retune a parameterized policy.
+    /// See [`BalancePolicy`] for details.
+    ///
+    /// Returns `Err` if feature `weight_balanced` or `avl` is enabled (rotations ignore policies).
+    pub fn set_balance_policy(&mut self, policy: P) -> Result<(), SgError> {
+        if cfg!(any(feature = "weight_balanced", feature = "avl")) {
+            return Err(SgError::RotationBalanced);
+        }
+
+        self.policy = policy;
+        Ok(())
+    }
+
+    /// Enable or disable automatic scapegoat rebuilds on insertion and removal, e.g. to defer them during bulk ingest.
+    /// While disabled, the tree may become arbitrarily unbalanced until [`rebalance`][Self::rebalance] is called.
+    /// Has no effect if feature `weight_balanced` or `avl` is enabled, rotations keep the tree balanced.
+    pub fn set_auto_rebalance(&mut self, enabled: bool) {
+        self.auto_rebalance = enabled;
+    }
+
+    /// Returns `true` if automatic scapegoat rebuilds are enabled (the default).
+    pub fn auto_rebalance(&self) -> bool {
+        self.auto_rebalance
+    }
+
+    /// Rebuild the entire tree into a perfectly balanced one, e.g. after a bulk ingest with automatic rebuilds disabled.
+    pub fn rebalance(&mut self) {
+        match self.opt_root_idx {
+            Some(root_idx) => {
+                self.rebuild::<Idx>(root_idx);
+                self.max_size = self.curr_size;
+            }
+            None => self.max_size = 0,
+        }
+    }
+
+    /// Returns `true` if the tree is alpha-height-balanced, e.g. no node is deeper than `log_(1/alpha)(len)`.
+    /// Runs in `O(n)` time.
+    pub fn is_balanced(&self) -> bool {
+        self.max_depth() <= self.balance_params().height_bound(self.curr_size)
+    }
+
+    /// Set the placement of live nodes within the arena, applied by the next [`maintenance`][Self::maintenance].
+    /// See [`ArenaLayout`] for details.
+    pub fn set_arena_layout(&mut self, layout: ArenaLayout) {
+        self.layout = layout;
+        self.layout_pending = true;
+    }
+
+    /// Get the placement of live nodes within the arena.
+    pub fn arena_layout(&self) -> ArenaLayout {
+        self.layout
+    }
+
+    /// Set (or clear, if `None`) the hook periodically called during long internal operations.
+    /// See [`YieldHook`] for details.
+    #[cfg(feature = "yield_hook")]
+    pub fn set_yield_hook(&mut self, opt_hook: Option<YieldHook>) {
+        self.opt_yield_hook = opt_hook;
+    }
+
+    /// Get the hook periodically called during long internal operations, if any.
+    #[cfg(feature = "yield_hook")]
+    pub fn yield_hook(&self) -> Option<YieldHook> {
+        self.opt_yield_hook
+    }
+
     /// Total capacity, e.g. maximum number of tree pairs.
     pub fn capacity(&self) -> usize {
         self.arena.capacity()
     }
 
+    /// Set a runtime limit on the number of tree pairs, at or below capacity.
+    /// Once reached, insertion of a new key behaves as though stack capacity were exhausted.
+    ///
+    /// Returns `Err` if `limit` exceeds the const capacity or is less than the current length.
+    pub fn set_max_len(&mut self, limit: usize) -> Result<(), SgError> {
+        if limit > self.capacity() {
+            return Err(SgError::MaximumCapacityExceeded);
+        }
+
+        if limit < self.len() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        self.max_len = limit;
+        Ok(())
+    }
+
+    /// Get the runtime limit on the number of tree pairs.
+    /// Equal to [`capacity`][SgTree::capacity] unless set via [`set_max_len`][SgTree::set_max_len].
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
     /// Get the size of an individual node in this tree, in bytes.
     pub fn node_size(&self) -> usize {
         self.arena.node_size()
     }
 
     /// Moves all elements from `other` into `self`, leaving `other` empty.
-    pub fn append(&mut self, other: &mut SgTree<K, V, N>)
+    /// Panics if the combined length exceeds capacity, without having mutated either tree.
+    pub fn append(&mut self, other: &mut SgTree<K, V, N, P>)
     where
         K: Ord,
     {
-        // Nothing to append!
-        if other.is_empty() {
-            return;
-        }
-
-        // Nothing to append to!
-        if self.is_empty() {
-            mem::swap(self, other);
-            return;
-        }
-
-        // Rip elements directly out of other's arena and clear it
-        for arena_idx in 0..other.arena.len() {
-            if let Some(mut node) = other.arena.remove(arena_idx) {
-                self.insert(node.take_key(), node.take_val());
-            }
+        if self.try_append(other).is_err() {
+            panic!("Stack-storage capacity exceeded!");
         }
-        other.clear();
     }
 
     /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
-    pub fn try_append(&mut self, other: &mut SgTree<K, V, N>) -> Result<(), SgError> {
+    /// Duplicate keys take `other`'s entry. Runs in `O(n + m)`, with a single rebuild.
+    pub fn try_append(&mut self, other: &mut SgTree<K, V, N, P>) -> Result<(), SgError> {
         // Nothing to append!
         if other.is_empty() {
             return Ok(());
         }
 
-        // Nothing to append to!
-        if self.is_empty() {
-            mem::swap(self, other);
+        // Nothing to append to! Swap storage only, settings stay with each tree
+        if self.is_empty()
+            && (other.len() <= self.max_len())
+            && (self.capacity() == other.capacity())
+        {
+            mem::swap(&mut self.arena, &mut other.arena);
+            mem::swap(&mut self.opt_root_idx, &mut other.opt_root_idx);
+            mem::swap(&mut self.min_idx, &mut other.min_idx);
+            mem::swap(&mut self.max_idx, &mut other.max_idx);
+            mem::swap(&mut self.curr_size, &mut other.curr_size);
+            mem::swap(&mut self.max_size, &mut other.max_size);
+            mem::swap(&mut self.layout_pending, &mut other.layout_pending);
+
+            // Arena placement follows the other tree's layout
+            if self.layout != other.layout {
+                self.layout_pending = true;
+                other.layout_pending = true;
+            }
+
             return Ok(());
         }
 
-        // Rip elements directly out of other's arena and clear it
-        if (self.len() + other.len() - self.intersect_cnt(other)) <= self.capacity() {
-            for arena_idx in 0..other.arena.len() {
-                if let Some(mut node) = other.arena.remove(arena_idx) {
-                    self.try_insert(node.take_key(), node.take_val())?;
+        let self_idxs = self.sorted_idxs();
+        let other_idxs = other.sorted_idxs();
+
+        // Preemptive - we haven't mutated `self` or `other`!
+        // Caller can assume unchanged state.
+        let merged_len =
+            self.len() + other.len() - self.sorted_intersect_cnt(&self_idxs, other, &other_idxs);
+        if merged_len > self.max_len() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        // Merge sorted index lists, ripping elements directly out of other's arena
+        let mut merged_idxs = SgVec::<[usize; N]>::new();
+        let mut self_iter = self_idxs.into_iter().peekable();
+        let mut other_iter = other_idxs.into_iter().peekable();
+        let mut work = 0;
+
+        loop {
+            self.yield_point(&mut work);
+            let ord = match (self_iter.peek(), other_iter.peek()) {
+                (Some(&self_idx), Some(&other_idx)) => {
+                    self.arena.key(self_idx).cmp(other.arena.key(other_idx))
                 }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+
+            if ord == Ordering::Less {
+                merged_idxs.extend(self_iter.next());
+                continue;
+            }
+
+            // Replaced entry's slot is freed before the incoming entry claims one
+            if ord == Ordering::Equal {
+                if let Some(self_idx) = self_iter.next() {
+                    self.arena.hard_remove(self_idx);
+                }
+            }
+
+            if let Some(other_idx) = other_iter.next() {
+                let (key, val) = other.arena.hard_remove(other_idx);
+                merged_idxs.push(self.arena.add(key, val));
             }
-            other.clear();
-        } else {
-            // Preemptive - we haven't mutated `self` or `other`!
-            // Caller can assume unchanged state.
-            return Err(SgError::StackCapacityExceeded);
         }
 
+        other.clear();
+        self.rebuild_from_sorted_idxs(&merged_idxs);
+        self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
+
         Ok(())
     }
 
@@ -170,6 +427,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     where
         K: Ord,
     {
+        if (self.len() >= self.max_len()) && !self.contains_key(&key) {
+            panic!("Stack-storage capacity exceeded!");
+        }
+
         self.internal_balancing_insert::<Idx>(key, val).0
     }
 
@@ -177,42 +438,38 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     /// Returns `Err` if tree's stack capacity is full, else the `Ok` contains:
     /// * `None` if the tree did not have this key present.
     /// * The old value if the tree did have this key present (both the value and key are updated,
-    /// this accommodates types that can be `==` without being identical).
+    ///   this accommodates types that can be `==` without being identical).
     pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError>
     where
         K: Ord,
     {
         // Replace current slot or safely fill a new one
-        match self.contains_key(&key) || (self.capacity() > self.len()) {
+        match self.contains_key(&key) || (self.max_len() > self.len()) {
             true => Ok(self.internal_balancing_insert::<Idx>(key, val).0),
             false => Err(SgError::StackCapacityExceeded),
         }
     }
 
-    // Attempt to extend a collection with the contents of an iterator.
-    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+    /// Attempt to extend a collection with the contents of an iterator.
+    /// Stops at the first item that doesn't fit, returning `Err` with counts of the items stored before it
+    /// and the rejected item itself.
+    pub fn try_extend<I: IntoIterator<Item = (K, V)>>(
         &mut self,
         iter: I,
-    ) -> Result<(), SgError> {
-        if iter.len() <= (self.capacity() - self.len()) {
-            iter.into_iter().for_each(move |(k, v)| {
-                assert!(self.try_insert(k, v).is_ok());
-            });
-            Ok(())
-        } else {
-            Err(SgError::StackCapacityExceeded)
-        }
-    }
+    ) -> Result<ExtendReport, (ExtendReport, (K, V))> {
+        let mut report = ExtendReport::default();
+        for (k, v) in iter {
+            if !self.contains_key(&k) && (self.max_len() <= self.len()) {
+                return Err((report, (k, v)));
+            }
 
-    // Attempt conversion from an iterator.
-    /// Will fail if iterator length exceeds `u16::MAX`.
-    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
-        iter: I,
-    ) -> Result<Self, SgError> {
-        match iter.len() <= SgTree::<K, V, N>::max_capacity() {
-            true => Ok(SgTree::from_iter(iter)),
-            false => Err(SgError::MaximumCapacityExceeded),
+            match self.internal_balancing_insert::<Idx>(k, v).0 {
+                None => report.inserted += 1,
+                Some(_) => report.updated += 1,
+            }
         }
+
+        Ok(report)
     }
 
     /// Gets an iterator over the entries of the tree, sorted by key.
@@ -234,18 +491,23 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         K: Borrow<Q> + Ord,
         Q: Ord + ?Sized,
     {
-        match self.priv_remove_by_key(key) {
-            Some((key, val)) => {
-                if self.max_size > (2 * self.curr_size) {
-                    if let Some(root_idx) = self.opt_root_idx {
-                        self.rebuild::<Idx>(root_idx);
-                        self.max_size = self.curr_size;
-                    }
-                }
-                Some((key, val))
+        let entry = self.priv_remove_by_key(key)?;
+
+        #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+        if self.auto_rebalance
+            && self.policy.needs_rebuild_after_remove(
+                self.curr_size,
+                self.max_size,
+                self.balance_params(),
+            )
+        {
+            if let Some(root_idx) = self.opt_root_idx {
+                self.rebuild::<Idx>(root_idx);
+                self.max_size = self.curr_size;
             }
-            None => None,
         }
+
+        Some(entry)
     }
 
     /// Removes a key from the tree, returning the value at the key if the key was previously in the tree.
@@ -261,12 +523,42 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     }
 
     /// Retains only the elements specified by the predicate.
+    /// Failing nodes are freed in place, then survivors are re-linked with a single rebuild.
+    /// If the predicate panics, the tree is left intact: nothing is freed until every element has been visited.
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&K, &mut V) -> bool,
         K: Ord,
     {
-        self.priv_drain_filter(|k, v| !f(k, v));
+        let root_idx = match self.opt_root_idx {
+            Some(root_idx) => root_idx,
+            None => return,
+        };
+
+        // Stable in-place partition: survivors keep their sorted order at the front, failing nodes follow
+        let mut sorted_idxs = self.flatten_subtree_to_sorted_idxs::<usize>(root_idx);
+        let mut kept_cnt = 0;
+
+        for i in 0..sorted_idxs.len() {
+            let (k, v) = self.arena.get_mut(sorted_idxs[i]);
+            if f(k, v) {
+                sorted_idxs.swap(kept_cnt, i);
+                kept_cnt += 1;
+            }
+        }
+
+        // Nothing removed, links still valid
+        if kept_cnt == self.len() {
+            return;
+        }
+
+        let (kept_idxs, removed_idxs) = sorted_idxs.split_at(kept_cnt);
+        for &idx in removed_idxs {
+            self.arena.hard_remove(idx);
+        }
+
+        self.rebuild_from_sorted_idxs(kept_idxs);
+        self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
     }
 
     /// Splits the collection into two at the given key. Returns everything after the given key, including the key.
@@ -275,7 +567,104 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         K: Borrow<Q> + Ord,
         Q: Ord + ?Sized,
     {
-        self.priv_drain_filter(|k, _| k >= key)
+        let sorted_idxs = match self.opt_root_idx {
+            Some(root_idx) => self.flatten_subtree_to_sorted_idxs::<usize>(root_idx),
+            None => SgVec::new(),
+        };
+
+        let start = sorted_idxs.partition_point(|&idx| self.arena.key(idx).borrow() < key);
+        let end = sorted_idxs.len();
+        self.priv_take_span_of_sorted_idxs(sorted_idxs, start..end)
+    }
+
+    /// Removes the `k` smallest elements from the tree, returning them as a new tree.
+    /// If `k` exceeds the tree's length, all elements are removed.
+    pub fn take_smallest(&mut self, k: usize) -> Self {
+        let k = k.min(self.len());
+        self.priv_take_sorted_span(0, k)
+    }
+
+    /// Removes the `k` largest elements from the tree, returning them as a new tree.
+    /// If `k` exceeds the tree's length, all elements are removed.
+    pub fn take_largest(&mut self, k: usize) -> Self {
+        let len = self.len();
+        let k = k.min(len);
+        self.priv_take_sorted_span(len - k, len)
+    }
+
+    /// Splits the tree at the given in-order index. Returns everything from that index onward.
+    /// If `rank` exceeds the tree's length, nothing is removed.
+    pub fn split_at_rank(&mut self, rank: usize) -> Self {
+        let len = self.len();
+        self.priv_take_sorted_span(rank.min(len), len)
+    }
+
+    /// Returns the number of keys less than `key`, whether or not `key` is present.
+    /// Runs in `O(log n)` with feature `fast_rebalance` (using subtree sizes), `O(n)` otherwise.
+    #[cfg(feature = "fast_rebalance")]
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        let mut rank = 0;
+        let mut opt_idx = self.opt_root_idx;
+
+        while let Some(idx) = opt_idx {
+            let left_size = self.left_subtree_size(idx);
+            match key.cmp(self.arena.key(idx).borrow()) {
+                Ordering::Less => opt_idx = self.arena.left_idx(idx),
+                Ordering::Equal => return rank + left_size,
+                Ordering::Greater => {
+                    rank += left_size + 1;
+                    opt_idx = self.arena.right_idx(idx);
+                }
+            }
+        }
+
+        rank
+    }
+
+    /// Returns the number of keys less than `key`, whether or not `key` is present.
+    /// Runs in `O(log n)` with feature `fast_rebalance` (using subtree sizes), `O(n)` otherwise.
+    #[cfg(not(feature = "fast_rebalance"))]
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.iter().take_while(|(k, _)| (*k).borrow() < key).count()
+    }
+
+    /// Returns the key-value pair at the given in-order index (rank), e.g. `select(0)` is the minimum.
+    /// Runs in `O(log n)` with feature `fast_rebalance` (using subtree sizes), `O(n)` otherwise.
+    #[cfg(feature = "fast_rebalance")]
+    pub fn select(&self, mut rank: usize) -> Option<(&K, &V)> {
+        if rank >= self.len() {
+            return None;
+        }
+
+        let mut opt_idx = self.opt_root_idx;
+        while let Some(idx) = opt_idx {
+            let left_size = self.left_subtree_size(idx);
+            match rank.cmp(&left_size) {
+                Ordering::Less => opt_idx = self.arena.left_idx(idx),
+                Ordering::Equal => return Some(self.arena.get(idx)),
+                Ordering::Greater => {
+                    rank -= left_size + 1;
+                    opt_idx = self.arena.right_idx(idx);
+                }
+            }
+        }
+
+        unreachable!("Internal invariant failed: subtree sizes don't match length!")
+    }
+
+    /// Returns the key-value pair at the given in-order index (rank), e.g. `select(0)` is the minimum.
+    /// Runs in `O(log n)` with feature `fast_rebalance` (using subtree sizes), `O(n)` otherwise.
+    #[cfg(not(feature = "fast_rebalance"))]
+    pub fn select(&self, rank: usize) -> Option<(&K, &V)> {
+        self.iter().nth(rank)
     }
 
     /// Returns the key-value pair corresponding to the given key.
@@ -287,14 +676,8 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         K: Borrow<Q> + Ord,
         Q: Ord + ?Sized,
     {
-        let ngh: NodeGetHelper<Idx> = self.internal_get(None, key);
-        match ngh.node_idx() {
-            Some(idx) => {
-                let node = &self.arena[idx];
-                Some((node.key(), node.val()))
-            }
-            None => None,
-        }
+        let ngh: NodeGetHelper<Idx> = self.lookup(key);
+        ngh.node_idx().map(|idx| self.arena.get(idx))
     }
 
     /// Returns a reference to the value corresponding to the given key.
@@ -318,22 +701,68 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         K: Borrow<Q> + Ord,
         Q: Ord + ?Sized,
     {
-        let ngh: NodeGetHelper<Idx> = self.internal_get(None, key);
+        let ngh: NodeGetHelper<Idx> = self.lookup(key);
         match ngh.node_idx() {
             Some(idx) => {
-                let (_, val) = self.arena[idx].get_mut();
+                let (_, val) = self.arena.get_mut(idx);
                 Some(val)
             }
             None => None,
         }
     }
 
+    /// Get mutable references to the values of several distinct keys at once.
+    /// Each absent key yields `None`, in the same position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any two keys are equal.
+    pub fn get_many_mut<Q, const M: usize>(&mut self, keys: [&Q; M]) -> [Option<&mut V>; M]
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        match self.try_get_many_mut(keys) {
+            Ok(vals) => vals,
+            Err(_) => panic!("Duplicate keys passed to `get_many_mut`."),
+        }
+    }
+
+    /// Get mutable references to the values of several distinct keys at once.
+    /// Each absent key yields `None`, in the same position.
+    /// Keys are compared pairwise to rule out duplicates, e.g. `O(M^2)` comparisons on top of `M` lookups.
+    ///
+    /// Returns `Err` if any two keys are equal.
+    pub fn try_get_many_mut<Q, const M: usize>(
+        &mut self,
+        keys: [&Q; M],
+    ) -> Result<[Option<&mut V>; M], SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        for (i, key) in keys.iter().enumerate() {
+            if keys[(i + 1)..].iter().any(|other| other == key) {
+                return Err(SgError::DuplicateKeys);
+            }
+        }
+
+        let idxs = keys.map(|key| self.internal_get::<Q, Idx>(None, key).node_idx());
+        Ok(self.arena.get_many_vals_mut(idxs))
+    }
+
     /// Clears the tree, removing all elements.
     pub fn clear(&mut self) {
         if !self.is_empty() {
-            let rebal_cnt = self.rebal_cnt;
-            *self = SgTree::new();
-            self.rebal_cnt = rebal_cnt;
+            // Resets only previously used arena slots, e.g. no re-initialization of full capacity
+            self.arena.clear();
+            self.opt_root_idx = None;
+            self.max_idx = 0;
+            self.min_idx = 0;
+            self.curr_size = 0;
+            self.alpha_num = DEFAULT_ALPHA_NUM;
+            self.alpha_denom = DEFAULT_ALPHA_DENOM;
+            self.max_size = 0;
         }
     }
 
@@ -354,10 +783,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         self.opt_root_idx.is_none()
     }
 
-    /// Returns `true` if the tree's capacity is filled.
+    /// Returns `true` if the tree's capacity, or runtime limit, is filled.
     pub fn is_full(&self) -> bool {
-        debug_assert!(self.len() <= self.capacity());
-        self.len() == self.capacity()
+        debug_assert!(self.len() <= self.max_len());
+        self.len() == self.max_len()
     }
 
     /// Returns a reference to the first key-value pair in the tree.
@@ -367,8 +796,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         K: Ord,
     {
         if !self.is_empty() {
-            let node = &self.arena[self.min_idx];
-            Some((node.key(), node.val()))
+            Some(self.arena.get(self.min_idx))
         } else {
             None
         }
@@ -398,8 +826,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         K: Ord,
     {
         if !self.is_empty() {
-            let node = &self.arena[self.max_idx];
-            Some((node.key(), node.val()))
+            Some(self.arena.get(self.max_idx))
         } else {
             None
         }
@@ -427,21 +854,171 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         self.curr_size
     }
 
+    /// Perform pending structural work, as long as it fits within `budget`.
+    /// Returns `true` if no work remains pending.
+    ///
+    /// Pending work consists of:
+    /// * A full rebuild, if elements were removed since the last one (pre-empts the rebuild a later removal would trigger).
+    /// * Compaction of the arena, so that nodes are contiguous and placed per [`arena_layout`][Self::arena_layout].
+    ///
+    /// Layouts other than [`ArenaLayout::InOrder`] are computed over a freshly rebuilt tree,
+    /// so compaction or a layout change then costs a full rebuild plus placement.
+    pub fn maintenance(&mut self, mut budget: MaintenanceBudget) -> bool {
+        if self.layout != ArenaLayout::InOrder {
+            let pending =
+                self.layout_pending || (self.max_size > self.curr_size) || !self.arena_is_compact();
+
+            if pending && budget.try_spend(2 * self.curr_size) {
+                match self.opt_root_idx {
+                    Some(root_idx) => {
+                        self.rebuild::<Idx>(root_idx);
+                        self.max_size = self.curr_size;
+                        self.layout_arena();
+                        self.arena.truncate_free_tail();
+                    }
+                    None => self.max_size = 0,
+                }
+                self.layout_pending = false;
+            }
+
+            return (self.max_size == self.curr_size)
+                && self.arena_is_compact()
+                && !self.layout_pending;
+        }
+
+        // Deferred rebuild
+        if self.max_size > self.curr_size {
+            match self.opt_root_idx {
+                Some(root_idx) => {
+                    if budget.try_spend(self.curr_size) {
+                        self.rebuild::<Idx>(root_idx);
+                        self.max_size = self.curr_size;
+                    }
+                }
+                None => self.max_size = 0,
+            }
+        }
+
+        // Compaction
+        if (!self.arena_is_compact() || self.layout_pending) && budget.try_spend(self.curr_size) {
+            self.sort_arena();
+            self.arena.truncate_free_tail();
+            self.layout_pending = false;
+        }
+
+        (self.max_size == self.curr_size) && self.arena_is_compact() && !self.layout_pending
+    }
+
+    /// Returns true if the arena has no free slots, in constant time (every live node occupies one slot).
+    fn arena_is_compact(&self) -> bool {
+        debug_assert_eq!(self.arena.len() == self.curr_size, self.arena.is_compact());
+        self.arena.len() == self.curr_size
+    }
+
+    /// Report arena slot usage, e.g. to decide when [`maintenance`][Self::maintenance] is worth its cost.
+    pub fn fragmentation(&self) -> ArenaFragmentation {
+        let mut report = ArenaFragmentation {
+            live: self.curr_size,
+            ..ArenaFragmentation::default()
+        };
+
+        let mut opt_first_live = None;
+        let mut last_live = 0;
+        let mut curr_run = 0;
+        for (idx, slot) in self.arena.iter().enumerate() {
+            match slot {
+                Some(_) => {
+                    opt_first_live.get_or_insert(idx);
+                    last_live = idx;
+                    curr_run += 1;
+                    report.longest_live_run = report.longest_live_run.max(curr_run);
+                }
+                None => curr_run = 0,
+            }
+        }
+
+        if let Some(first_live) = opt_first_live {
+            report.live_spread = last_live - first_live + 1;
+            report.free_slots = (last_live + 1) - self.curr_size;
+        }
+
+        report
+    }
+
     /// Get the number of times this tree rebalanced itself (for testing and/or performance engineering).
     /// This count will wrap if `usize::MAX` is exceeded.
     pub fn rebal_cnt(&self) -> usize {
         self.rebal_cnt
     }
 
+    /// Fill `buf` with node counts per depth (root is depth `0`), returning the tree's height.
+    /// Depths at or beyond `buf.len()` aren't recorded, in which case the returned height exceeds `buf.len()`.
+    #[cfg(feature = "stats")]
+    pub fn depth_histogram(&self, buf: &mut [u32]) -> usize {
+        buf.iter_mut().for_each(|cnt| *cnt = 0);
+
+        let mut height = 0;
+        if let Some(root_idx) = self.opt_root_idx {
+            let mut worklist =
+                SgVec::<[(Idx, Idx); N]>::from_iter(iter::once((Idx::checked_from(root_idx), 0)));
+            while let Some((idx, depth)) = worklist.pop() {
+                let (idx, depth) = (idx.usize(), depth.usize());
+
+                height = height.max(depth + 1);
+                if let Some(cnt) = buf.get_mut(depth) {
+                    *cnt += 1;
+                }
+
+                for child_idx in [self.arena.left_idx(idx), self.arena.right_idx(idx)]
+                    .iter()
+                    .flatten()
+                {
+                    worklist.push((Idx::checked_from(*child_idx), Idx::checked_from(depth + 1)));
+                }
+            }
+        }
+
+        height
+    }
+
+    /// Snapshot of instrumentation counters. See [`TreeStats`] for details.
+    #[cfg(feature = "telemetry")]
+    pub fn stats(&self) -> TreeStats {
+        self.stats.snapshot()
+    }
+
+    /// Zero all instrumentation counters.
+    #[cfg(feature = "telemetry")]
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+    }
+
     // Crate-internal API ----------------------------------------------------------------------------------------------
 
+    // Restore every setting to its default, as for a new tree of the same capacity. Contents are unaffected.
+    pub(crate) fn reset_config(&mut self)
+    where
+        P: Default,
+    {
+        self.max_len = self.capacity();
+        self.alpha_num = DEFAULT_ALPHA_NUM;
+        self.alpha_denom = DEFAULT_ALPHA_DENOM;
+        self.policy = P::default();
+        self.auto_rebalance = true;
+        self.layout = ArenaLayout::InOrder;
+
+        #[cfg(feature = "yield_hook")]
+        {
+            self.opt_yield_hook = None;
+        }
+    }
+
     // Remove a node by index.
     // A wrapper for by-key removal, traversal is still required to determine node parent.
     #[cfg(not(feature = "fast_rebalance"))]
     pub(crate) fn priv_remove_by_idx(&mut self, idx: usize) -> Option<(K, V)> {
         if self.arena.is_occupied(idx) {
-            let node = &self.arena[idx];
-            let ngh: NodeGetHelper<Idx> = self.internal_get(None, node.key());
+            let ngh: NodeGetHelper<Idx> = self.internal_get(None, self.arena.key(idx));
             debug_assert!(
                 ngh.node_idx().unwrap() == idx,
                 "By-key retrieval index doesn't match arena storage index!"
@@ -457,9 +1034,8 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     #[cfg(feature = "fast_rebalance")]
     pub(crate) fn priv_remove_by_idx(&mut self, idx: usize) -> Option<(K, V)> {
         if self.arena.is_occupied(idx) {
-            let node = &self.arena[idx];
             let mut path = Arena::<K, V, Idx, N>::new_idx_vec();
-            let ngh = self.internal_get(Some(&mut path), node.key());
+            let ngh = self.internal_get(Some(&mut path), self.arena.key(idx));
             debug_assert!(
                 ngh.node_idx().unwrap() == idx,
                 "By-key retrieval index doesn't match arena storage index!"
@@ -470,24 +1046,66 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
+    // Maximum node depth guaranteed by the balance policy, if any.
+    // Loosely alpha-height-balanced, per the original paper: `log_(1/alpha)(max_size) + 1`.
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    pub(super) fn depth_guarantee(&self) -> Option<usize> {
+        match self.auto_rebalance && self.policy.bounds_height() {
+            true => Some(self.balance_params().height_bound(self.max_size) + 1),
+            false => None,
+        }
+    }
+
+    // Depth of the deepest node, root at depth `0`. Zero if empty.
+    pub(crate) fn max_depth(&self) -> usize {
+        let mut max_depth = 0;
+        if let Some(root_idx) = self.opt_root_idx {
+            let mut worklist =
+                SgVec::<[(Idx, Idx); N]>::from_iter(iter::once((Idx::checked_from(root_idx), 0)));
+            while let Some((idx, depth)) = worklist.pop() {
+                let (idx, depth) = (idx.usize(), depth.usize());
+                max_depth = max_depth.max(depth);
+
+                for child_idx in [self.arena.left_idx(idx), self.arena.right_idx(idx)]
+                    .iter()
+                    .flatten()
+                {
+                    worklist.push((Idx::checked_from(*child_idx), Idx::checked_from(depth + 1)));
+                }
+            }
+        }
+
+        max_depth
+    }
+
+    // Size of a node's left subtree, from cached subtree sizes
+    #[cfg(feature = "fast_rebalance")]
+    fn left_subtree_size(&self, idx: usize) -> usize {
+        self.arena
+            .left_idx(idx)
+            .map_or(0, |left_idx| self.arena.subtree_size(left_idx))
+    }
+
     // Flatten subtree into array of node indexes sorted by node key
     pub(crate) fn flatten_subtree_to_sorted_idxs<U: SmallUnsigned + Default + Copy>(
         &self,
         idx: usize,
-    ) -> ArrayVec<[U; N]> {
-        let mut subtree_worklist = array_vec![[U; N] => U::checked_from(idx)];
-        let mut subtree_flattened = array_vec![[U; N] => U::checked_from(idx)];
+    ) -> SgVec<[U; N]> {
+        let mut subtree_worklist = SgVec::<[U; N]>::from_iter(iter::once(U::checked_from(idx)));
+        let mut subtree_flattened = SgVec::<[U; N]>::from_iter(iter::once(U::checked_from(idx)));
+        let mut work = 0;
 
         while let Some(idx) = subtree_worklist.pop() {
-            let node = &self.arena[idx.usize()];
+            self.yield_point(&mut work);
+            let idx = idx.usize();
 
-            if let Some(left_idx) = node.left_idx() {
+            if let Some(left_idx) = self.arena.left_idx(idx) {
                 let left = U::checked_from(left_idx);
                 subtree_worklist.push(left);
                 subtree_flattened.push(left);
             }
 
-            if let Some(right_idx) = node.right_idx() {
+            if let Some(right_idx) = self.arena.right_idx(idx) {
                 let right = U::checked_from(right_idx);
                 subtree_worklist.push(right);
                 subtree_flattened.push(right);
@@ -497,7 +1115,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         // Sort by key
         // Faster than sort_by() but may not preserve order of equal elements - OK b/c tree won't have equal nodes
         subtree_flattened
-            .sort_unstable_by(|a, b| self.arena[a.usize()].key().cmp(self.arena[b.usize()].key()));
+            .sort_unstable_by(|a, b| self.arena.key(a.usize()).cmp(self.arena.key(b.usize())));
 
         subtree_flattened
     }
@@ -505,15 +1123,18 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     /// Sort the internal arena such that logically contiguous nodes are in-order (by key).
     pub(crate) fn sort_arena(&mut self) {
         if let Some(root_idx) = self.opt_root_idx {
+            let mut work = 0;
             let mut sort_metadata = self
                 .arena
                 .iter()
-                .filter(|n| n.is_some())
-                .map(|n| n.as_ref().unwrap())
-                .map(|n| self.internal_get(None, n.key()))
-                .collect::<ArrayVec<[NodeGetHelper<usize>; N]>>();
-
-            sort_metadata.sort_unstable_by_key(|ngh| self.arena[ngh.node_idx().unwrap()].key());
+                .flatten()
+                .map(|(key, _)| {
+                    self.yield_point(&mut work);
+                    self.internal_get(None, key)
+                })
+                .collect::<SgVec<[NodeGetHelper<usize>; N]>>();
+
+            sort_metadata.sort_unstable_by_key(|ngh| self.arena.key(ngh.node_idx().unwrap()));
             let sorted_root_idx = self.arena.sort(root_idx, sort_metadata);
 
             self.opt_root_idx = Some(sorted_root_idx);
@@ -522,41 +1143,187 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
+    /// Sort the internal arena such that nodes are placed per the current layout.
+    /// For layouts other than in-order, the tree is assumed freshly rebuilt (e.g. height at most `log2(len) + 1`).
+    fn layout_arena(&mut self) {
+        let root_idx = match (self.layout, self.opt_root_idx) {
+            (ArenaLayout::InOrder, _) | (_, None) => return self.sort_arena(),
+            (_, Some(root_idx)) => root_idx,
+        };
+
+        // Breadth-first walk, tagging each node with its 1-based heap index (children of `i` are `2i` and `2i + 1`)
+        let mut work = 0;
+        let mut placement = SgVec::<[(NodeGetHelper<usize>, usize); N]>::new();
+        placement.push((NodeGetHelper::new(Some(root_idx), None, false), 1));
+
+        let mut next = 0;
+        while let Some((idx, heap_idx)) = placement
+            .get(next)
+            .map(|(ngh, heap_idx)| (ngh.node_idx().unwrap(), *heap_idx))
+        {
+            self.yield_point(&mut work);
+
+            if let Some(left_idx) = self.arena.left_idx(idx) {
+                let helper = NodeGetHelper::new(Some(left_idx), Some(idx), false);
+                placement.push((helper, 2 * heap_idx));
+            }
+
+            if let Some(right_idx) = self.arena.right_idx(idx) {
+                let helper = NodeGetHelper::new(Some(right_idx), Some(idx), true);
+                placement.push((helper, 2 * heap_idx + 1));
+            }
+
+            next += 1;
+        }
+
+        // Breadth-first is walk order, van Emde Boas needs a re-sort
+        if self.layout == ArenaLayout::VanEmdeBoas {
+            let height = placement
+                .last()
+                .map(|(_, heap_idx)| heap_depth(*heap_idx) + 1)
+                .unwrap_or(0);
+
+            placement.sort_unstable_by(|(_, a), (_, b)| veb_cmp(*a, *b, height));
+        }
+
+        let sort_metadata = placement.into_iter().map(|(ngh, _)| ngh).collect();
+        let sorted_root_idx = self.arena.sort(root_idx, sort_metadata);
+
+        self.opt_root_idx = Some(sorted_root_idx);
+        self.update_max_idx();
+        self.update_min_idx();
+    }
+
+    /// Contents of every arena slot, in arena order, e.g. for layout-stable persistence.
+    /// Yields exactly `N` items, `None` for unoccupied slots.
+    pub(crate) fn arena_slots(&self) -> impl Iterator<Item = Option<(&K, &V)>> {
+        self.arena
+            .iter()
+            .chain(core::iter::repeat_with(|| None))
+            .take(N)
+    }
+
     /// Total common elements between two trees
-    pub(crate) fn intersect_cnt(&self, other: &SgTree<K, V, N>) -> usize {
-        self.iter().filter(|(k, _)| other.contains_key(k)).count()
+    #[cfg(test)]
+    pub(crate) fn intersect_cnt(&self, other: &SgTree<K, V, N, P>) -> usize {
+        self.sorted_intersect_cnt(&self.sorted_idxs(), other, &other.sorted_idxs())
+    }
+
+    // Count keys common to both trees, given each tree's arena indexes sorted by key, in `O(n + m)`.
+    fn sorted_intersect_cnt(
+        &self,
+        self_idxs: &[usize],
+        other: &SgTree<K, V, N, P>,
+        other_idxs: &[usize],
+    ) -> usize {
+        let mut cnt = 0;
+        let mut self_iter = self_idxs.iter().peekable();
+        let mut other_iter = other_idxs.iter().peekable();
+
+        while let (Some(&&self_idx), Some(&&other_idx)) = (self_iter.peek(), other_iter.peek()) {
+            match self.arena.key(self_idx).cmp(other.arena.key(other_idx)) {
+                Ordering::Less => {
+                    self_iter.next();
+                }
+                Ordering::Greater => {
+                    other_iter.next();
+                }
+                Ordering::Equal => {
+                    cnt += 1;
+                    self_iter.next();
+                    other_iter.next();
+                }
+            }
+        }
+
+        cnt
+    }
+
+    // Arena indexes of every live node, sorted by key.
+    fn sorted_idxs(&self) -> SgVec<[usize; N]> {
+        match self.opt_root_idx {
+            Some(root_idx) => self.flatten_subtree_to_sorted_idxs(root_idx),
+            None => SgVec::new(),
+        }
     }
 
     // Maximum tree capacity (const N value).
+    // One less than `Idx::MAX`, the all-ones link encoding is reserved for "no child".
     pub(crate) fn max_capacity() -> usize {
-        Idx::MAX as usize
+        (Idx::MAX as usize) - 1
     }
 
-    /// Find arena indexes for a given range
-    pub(crate) fn range_search<T, R>(&self, range: &R) -> ArrayVec<[usize; N]>
+    /// Find arena indexes for a given range, sorted by key.
+    /// Descends to the lower bound, then traverses in-order until past the upper bound, e.g. `O(log n + k)` for `k` results.
+    pub(crate) fn range_search<T, R>(&self, range: &R) -> SgVec<[usize; N]>
     where
         T: Ord + ?Sized,
         R: RangeBounds<T>,
         K: Borrow<T> + Ord,
     {
-        let mut node_idxs = ArrayVec::<[usize; N]>::new();
+        let mut node_idxs = SgVec::<[usize; N]>::new();
+        let mut idx_stack = SgVec::<[usize; N]>::new();
+        let mut opt_curr_idx = self.opt_root_idx;
+
+        loop {
+            // Descend leftward, skipping subtrees below the lower bound
+            while let Some(curr_idx) = opt_curr_idx {
+                let below_start = match range.start_bound() {
+                    Included(start) => self.arena.key(curr_idx).borrow() < start,
+                    Excluded(start) => self.arena.key(curr_idx).borrow() <= start,
+                    Unbounded => false,
+                };
 
-        for (idx, node) in self
-            .arena
-            .iter()
-            .enumerate()
-            .filter_map(|(i, node)| Some((i, node.as_ref()?)))
-        {
-            if range.contains(node.key().borrow()) {
-                node_idxs.push(idx);
+                if below_start {
+                    opt_curr_idx = self.arena.right_idx(curr_idx);
+                } else {
+                    idx_stack.push(curr_idx);
+                    opt_curr_idx = self.arena.left_idx(curr_idx);
+                }
             }
-        }
 
-        node_idxs.sort_unstable_by_key(|idx| self.arena[*idx].key());
+            // Visit next in-order node, stopping past the upper bound
+            match idx_stack.pop() {
+                Some(idx) => {
+                    let above_end = match range.end_bound() {
+                        Included(end) => self.arena.key(idx).borrow() > end,
+                        Excluded(end) => self.arena.key(idx).borrow() >= end,
+                        Unbounded => false,
+                    };
+
+                    if above_end {
+                        break;
+                    }
+
+                    node_idxs.push(idx);
+                    opt_curr_idx = self.arena.right_idx(idx);
+                }
+                None => break,
+            }
+        }
 
         node_idxs
     }
 
+    /// Get a mutable iterator over the entries in a given range.
+    /// Sorts the arena, so in-range nodes occupy a contiguous span of slots.
+    pub(crate) fn range_iter_mut<T, R>(&mut self, range: &R) -> IterMut<'_, K, V, N>
+    where
+        T: Ord + ?Sized,
+        R: RangeBounds<T>,
+        K: Borrow<T> + Ord,
+    {
+        self.sort_arena();
+        let node_idxs = self.range_search(range);
+        let span = match (node_idxs.first(), node_idxs.last()) {
+            (Some(first), Some(last)) => *first..(*last + 1),
+            _ => 0..0,
+        };
+
+        debug_assert_eq!(span.len(), node_idxs.len());
+        IterMut::new_span(self, span)
+    }
+
     /// Validate range
     pub(crate) fn assert_valid_range<T, R>(range: &R)
     where
@@ -567,15 +1334,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         match (range.start_bound(), range.end_bound()) {
             (Included(start), Included(end))
             | (Included(start), Excluded(end))
-            | (Excluded(start), Included(end)) => {
-                if start > end {
-                    panic!("range start is greater than range end");
-                }
+            | (Excluded(start), Included(end))
+                if start > end =>
+            {
+                panic!("range start is greater than range end");
             }
-            (Excluded(start), Excluded(end)) => {
-                if start == end {
-                    panic!("range start and end are equal and excluded");
-                }
+            (Excluded(start), Excluded(end)) if start == end => {
+                panic!("range start and end are equal and excluded");
             }
             _ => {}
         }
@@ -585,9 +1350,36 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     // `opt_path` is only populated if `Some` and key is found.
     pub(crate) fn internal_get<Q, U: SmallUnsigned + Default + Copy>(
         &self,
-        mut opt_path: Option<&mut ArrayVec<[U; N]>>,
+        opt_path: Option<&mut SgVec<[U; N]>>,
         key: &Q,
     ) -> NodeGetHelper<U>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.search(opt_path, key).0
+    }
+
+    // Search on behalf of a public lookup, counted by telemetry.
+    fn lookup<Q>(&self, key: &Q) -> NodeGetHelper<Idx>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        let (ngh, _cmps) = self.search(None, key);
+
+        #[cfg(feature = "telemetry")]
+        self.stats.record_lookup(_cmps);
+
+        ngh
+    }
+
+    // Iterative search, as per `internal_get`, also returning the number of key comparisons made.
+    fn search<Q, U: SmallUnsigned + Default + Copy>(
+        &self,
+        mut opt_path: Option<&mut SgVec<[U; N]>>,
+        key: &Q,
+    ) -> (NodeGetHelper<U>, usize)
     where
         K: Borrow<Q> + Ord,
         Q: Ord + ?Sized,
@@ -597,15 +1389,15 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 let mut opt_parent_idx = None;
                 let mut curr_idx = root_idx;
                 let mut is_right_child = false;
+                let mut cmps = 0;
                 loop {
-                    let node = &self.arena[curr_idx];
-
                     if let Some(ref mut path) = opt_path {
                         path.push(U::checked_from(curr_idx));
                     }
 
-                    match key.cmp(node.key().borrow()) {
-                        Ordering::Less => match node.left_idx() {
+                    cmps += 1;
+                    match key.cmp(self.arena.key(curr_idx).borrow()) {
+                        Ordering::Less => match self.arena.left_idx(curr_idx) {
                             Some(lt_idx) => {
                                 opt_parent_idx = Some(curr_idx);
                                 curr_idx = lt_idx;
@@ -616,7 +1408,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                     path.clear(); // Find failed, clear path
                                 }
 
-                                return NodeGetHelper::new(None, None, false);
+                                return (NodeGetHelper::new(None, None, false), cmps);
                             }
                         },
                         Ordering::Equal => {
@@ -624,13 +1416,12 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                 path.pop(); // Only parents in path
                             }
 
-                            return NodeGetHelper::new(
-                                Some(curr_idx),
-                                opt_parent_idx,
-                                is_right_child,
+                            return (
+                                NodeGetHelper::new(Some(curr_idx), opt_parent_idx, is_right_child),
+                                cmps,
                             );
                         }
-                        Ordering::Greater => match node.right_idx() {
+                        Ordering::Greater => match self.arena.right_idx(curr_idx) {
                             Some(gt_idx) => {
                                 opt_parent_idx = Some(curr_idx);
                                 curr_idx = gt_idx;
@@ -641,50 +1432,165 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                     path.clear(); // Find failed, clear path
                                 }
 
-                                return NodeGetHelper::new(None, None, false);
+                                return (NodeGetHelper::new(None, None, false), cmps);
                             }
                         },
                     }
                 }
             }
-            None => NodeGetHelper::new(None, None, false),
+            None => (NodeGetHelper::new(None, None, false), 0),
         }
     }
 
     // Sorted insert of node into the tree (outer).
     // Re-balances the tree if necessary.
     //
-    // Returns the old value, if any, and the index of the new node in the arena.
-    pub(crate) fn internal_balancing_insert<U: Default + Copy + Ord + Sub + SmallUnsigned>(
+    // Returns the old value, if any, the index of the new node in the arena, and the search path depth of the
+    // subtree rebuilt by re-balancing, if any.
+    pub(crate) fn internal_balancing_insert<U: Default + LinkIdx + Ord + Sub>(
         &mut self,
         key: K,
         val: V,
-    ) -> (Option<V>, usize) {
-        let mut path: ArrayVec<[U; N]> = Arena::<K, V, U, N>::new_idx_vec();
+    ) -> (Option<V>, usize, Option<usize>) {
+        let mut path: SgVec<[U; N]> = Arena::<K, V, U, N>::new_idx_vec();
         let (opt_val, ngh) = self.priv_insert(&mut path, key, val);
+        let opt_rebuild_depth = self.priv_rebalance_after_insert(&path);
+
+        debug_assert!(ngh.node_idx().is_some());
+        let new_node_idx = ngh.node_idx().expect("Inserted node index must be `Some`");
+        (opt_val, new_node_idx, opt_rebuild_depth)
+    }
+
+    // Iterative search recording the full search path, for a later insert without a second traversal.
+    // If key found, returns node idx, parent idx, and a bool indicating if node is right child.
+    // If not found, returns the would-be parent idx and side: `path` then holds every ancestor of the would-be node.
+    pub(crate) fn internal_search<Q>(
+        &self,
+        path: &mut SgVec<[Idx; N]>,
+        key: &Q,
+    ) -> NodeGetHelper<Idx>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut opt_curr_idx = self.opt_root_idx;
+        let mut opt_parent_idx = None;
+        let mut is_right_child = false;
+
+        while let Some(curr_idx) = opt_curr_idx {
+            match key.cmp(self.arena.key(curr_idx).borrow()) {
+                Ordering::Equal => {
+                    return NodeGetHelper::new(Some(curr_idx), opt_parent_idx, is_right_child)
+                }
+                Ordering::Less => {
+                    opt_curr_idx = self.arena.left_idx(curr_idx);
+                    is_right_child = false;
+                }
+                Ordering::Greater => {
+                    opt_curr_idx = self.arena.right_idx(curr_idx);
+                    is_right_child = true;
+                }
+            }
+
+            path.push(Idx::checked_from(curr_idx));
+            opt_parent_idx = Some(curr_idx);
+        }
+
+        NodeGetHelper::new(None, opt_parent_idx, is_right_child)
+    }
+
+    // Insert a key known to be absent at the position found by `internal_search`, re-balancing if necessary.
+    // The tree must not have been modified since the search. Returns the index of the new node in the arena.
+    pub(crate) fn internal_insert_vacant(
+        &mut self,
+        path: &SgVec<[Idx; N]>,
+        ngh: NodeGetHelper<Idx>,
+        key: K,
+        val: V,
+    ) -> usize {
+        debug_assert!(ngh.node_idx().is_none());
+        debug_assert!(!self.is_full());
+
+        let new_node_idx = match ngh.parent_idx() {
+            Some(parent_idx) => {
+                let new_min_found = &key < self.arena.key(self.min_idx);
+                let new_max_found = &key > self.arena.key(self.max_idx);
+                let new_node_idx = self.arena.add(key, val);
+
+                if new_min_found {
+                    self.min_idx = new_node_idx;
+                }
+                if new_max_found {
+                    self.max_idx = new_node_idx;
+                }
+
+                if ngh.is_right_child() {
+                    self.arena.set_right_idx(parent_idx, Some(new_node_idx));
+                } else {
+                    self.arena.set_left_idx(parent_idx, Some(new_node_idx));
+                }
+
+                new_node_idx
+            }
 
+            // Empty tree
+            None => {
+                debug_assert_eq!(self.curr_size, 0);
+                let root_idx = self.arena.add(key, val);
+                self.opt_root_idx = Some(root_idx);
+                self.max_idx = root_idx;
+                self.min_idx = root_idx;
+                root_idx
+            }
+        };
+
+        self.curr_size += 1;
+        self.max_size += 1;
+        self.priv_rebalance_after_insert(path);
+
+        new_node_idx
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    // Update subtree metadata along the path of a new node, then re-balance if necessary.
+    // Returns the path depth of the subtree rebuilt, if any. Rotations don't count as rebuilds.
+    fn priv_rebalance_after_insert<U: Default + Copy + Ord + Sub + SmallUnsigned>(
+        &mut self,
+        path: &SgVec<[U; N]>,
+    ) -> Option<usize> {
         #[cfg(feature = "fast_rebalance")]
         {
             // Update subtree sizes
-            for parent_idx in &path {
-                let parent_node = &mut self.arena[(*parent_idx).usize()];
-                parent_node.set_subtree_size(parent_node.subtree_size() + 1);
+            for parent_idx in path {
+                let parent_idx = (*parent_idx).usize();
+                self.arena
+                    .set_subtree_size(parent_idx, self.arena.subtree_size(parent_idx) + 1);
             }
         }
 
+        #[cfg(feature = "telemetry")]
+        self.stats.record_depth(path.len());
+
         // Potential rebalance
-        if path.len() > self.alpha_balance_depth(self.max_size) {
-            if let Some(scapegoat_idx) = self.find_scapegoat(&path) {
-                self.rebuild::<U>(scapegoat_idx);
+        #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+        if self.auto_rebalance
+            && self
+                .policy
+                .needs_rebalance(path.len(), self.max_size, self.balance_params())
+        {
+            if let Some(scapegoat_depth) = self.find_scapegoat(path) {
+                self.rebuild::<U>(path[scapegoat_depth].usize());
+                return Some(scapegoat_depth);
             }
         }
 
-        debug_assert!(ngh.node_idx().is_some());
-        let new_node_idx = ngh.node_idx().expect("Inserted node index must be `Some`");
-        (opt_val, new_node_idx)
-    }
+        // Rotations along insertion path
+        #[cfg(any(feature = "weight_balanced", feature = "avl"))]
+        self.rebalance_path(path);
 
-    // Private API -----------------------------------------------------------------------------------------------------
+        None
+    }
 
     // Sorted insert of node into the tree (inner).
     // Maintains a traversal path to avoid nodes needing to maintain a parent index.
@@ -694,7 +1600,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     // returns the old value.
     fn priv_insert<U: SmallUnsigned + Default + Copy>(
         &mut self,
-        path: &mut ArrayVec<[U; N]>,
+        path: &mut SgVec<[U; N]>,
         key: K,
         val: V,
     ) -> (Option<V>, NodeGetHelper<U>) {
@@ -706,18 +1612,16 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 let mut opt_val = None;
                 let ngh: NodeGetHelper<U>;
                 loop {
-                    let curr_node = &mut self.arena[curr_idx];
                     path.push(U::checked_from(curr_idx));
 
-                    match key.cmp(curr_node.key()) {
+                    match key.cmp(self.arena.key(curr_idx)) {
                         Ordering::Less => {
-                            match curr_node.left_idx() {
+                            match self.arena.left_idx(curr_idx) {
                                 Some(left_idx) => curr_idx = left_idx,
                                 None => {
                                     // New min check
                                     let mut new_min_found = false;
-                                    let min_node = &self.arena[self.min_idx];
-                                    if &key < min_node.key() {
+                                    if &key < self.arena.key(self.min_idx) {
                                         new_min_found = true;
                                     }
 
@@ -740,24 +1644,24 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                         }
                         Ordering::Equal => {
                             // Replacing key necessary b/c custom Eq impl may not consider all K's fields
-                            curr_node.set_key(key);
+                            self.arena.set_key(curr_idx, key);
 
                             // Replacing val necessary b/c it may be different
-                            opt_val = Some(curr_node.take_val());
-                            curr_node.set_val(val);
+                            opt_val = Some(self.arena.replace_val(curr_idx, val));
 
-                            // Key/val updated "in-place": no need to update `curr_node`'s parent or children
+                            // Key/val updated "in-place": no need to update `curr_idx`'s parent or children
+                            // Nor any ancestors, so path is cleared
+                            path.clear();
                             ngh = NodeGetHelper::new(Some(curr_idx), None, false);
                             break;
                         }
                         Ordering::Greater => {
-                            match curr_node.right_idx() {
+                            match self.arena.right_idx(curr_idx) {
                                 Some(right_idx) => curr_idx = right_idx,
                                 None => {
                                     // New max check
                                     let mut new_max_found = false;
-                                    let max_node = &self.arena[self.max_idx];
-                                    if &key > max_node.key() {
+                                    if &key > self.arena.key(self.max_idx) {
                                         new_max_found = true;
                                     }
 
@@ -786,11 +1690,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     self.curr_size += 1;
                     self.max_size += 1;
 
-                    let parent_node = &mut self.arena[parent_idx];
                     if ngh.is_right_child() {
-                        parent_node.set_right_idx(ngh.node_idx());
+                        self.arena.set_right_idx(parent_idx, ngh.node_idx());
                     } else {
-                        parent_node.set_left_idx(ngh.node_idx());
+                        self.arena.set_left_idx(parent_idx, ngh.node_idx());
                     }
                 }
 
@@ -842,16 +1745,18 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     #[allow(unused_variables)] // `opt_path` only used when feature `fast_rebalance` is enabled
     fn priv_remove<U: SmallUnsigned + Default + Copy>(
         &mut self,
-        opt_path: Option<&ArrayVec<[U; N]>>,
+        opt_path: Option<&SgVec<[U; N]>>,
         ngh: NodeGetHelper<U>,
     ) -> Option<(K, V)> {
         match ngh.node_idx() {
             Some(node_idx) => {
-                let node_to_remove = &self.arena[node_idx];
-
                 // Copy out child indexes to reduce scope of above immutable borrow
-                let node_to_remove_left_idx = node_to_remove.left_idx();
-                let mut node_to_remove_right_idx = node_to_remove.right_idx();
+                let node_to_remove_left_idx = self.arena.left_idx(node_idx);
+                let mut node_to_remove_right_idx = self.arena.right_idx(node_idx);
+
+                // Deepest node whose subtree shrunk, unless node to remove has two children
+                #[cfg(any(feature = "weight_balanced", feature = "avl"))]
+                let mut opt_rebal_idx = ngh.parent_idx();
 
                 let new_child = match (node_to_remove_left_idx, node_to_remove_right_idx) {
                     // No children
@@ -869,32 +1774,33 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                         let mut min_parent_idx = node_idx;
 
                         #[cfg(feature = "fast_rebalance")]
-                        let min_node_subtree_size = node_to_remove.subtree_size() - 1;
+                        let min_node_subtree_size = self.arena.subtree_size(node_idx) - 1;
 
                         loop {
-                            let min_node = &self.arena[min_idx];
-                            match min_node.left_idx() {
+                            match self.arena.left_idx(min_idx) {
                                 // Continue search for min node
                                 Some(lt_idx) => {
+                                    // Every node on the left spine above the min node loses it
+                                    #[cfg(feature = "fast_rebalance")]
+                                    {
+                                        self.arena.set_subtree_size(
+                                            min_idx,
+                                            self.arena.subtree_size(min_idx) - 1,
+                                        );
+                                    }
+
                                     min_parent_idx = min_idx;
                                     min_idx = lt_idx;
                                 }
                                 // Min node found, unlink it
-                                None => match min_node.right_idx() {
+                                None => match self.arena.right_idx(min_idx) {
                                     Some(_) => {
-                                        let unlink_new_child = min_node.right_idx();
+                                        let unlink_new_child = self.arena.right_idx(min_idx);
                                         if min_parent_idx == node_idx {
                                             node_to_remove_right_idx = unlink_new_child;
                                         } else {
-                                            let min_parent_node = &mut self.arena[min_parent_idx];
-                                            min_parent_node.set_left_idx(unlink_new_child);
-
-                                            #[cfg(feature = "fast_rebalance")]
-                                            {
-                                                min_parent_node.set_subtree_size(
-                                                    min_parent_node.subtree_size() - 1,
-                                                );
-                                            }
+                                            self.arena
+                                                .set_left_idx(min_parent_idx, unlink_new_child);
                                         }
                                         break;
                                     }
@@ -902,15 +1808,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                         if min_parent_idx == node_idx {
                                             node_to_remove_right_idx = None;
                                         } else {
-                                            let min_parent_node = &mut self.arena[min_parent_idx];
-                                            min_parent_node.set_left_idx(None);
-
-                                            #[cfg(feature = "fast_rebalance")]
-                                            {
-                                                min_parent_node.set_subtree_size(
-                                                    min_parent_node.subtree_size() - 1,
-                                                );
-                                            }
+                                            self.arena.set_left_idx(min_parent_idx, None);
                                         }
                                         break;
                                     }
@@ -918,14 +1816,22 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                             }
                         }
 
+                        // Deepest node whose subtree shrunk
+                        #[cfg(any(feature = "weight_balanced", feature = "avl"))]
+                        {
+                            opt_rebal_idx = match min_parent_idx == node_idx {
+                                true => Some(min_idx),
+                                false => Some(min_parent_idx),
+                            };
+                        }
+
                         // Re-link min node to removed node's children
-                        let min_node = &mut self.arena[min_idx];
-                        min_node.set_right_idx(node_to_remove_right_idx);
-                        min_node.set_left_idx(node_to_remove_left_idx);
+                        self.arena.set_right_idx(min_idx, node_to_remove_right_idx);
+                        self.arena.set_left_idx(min_idx, node_to_remove_left_idx);
 
                         #[cfg(feature = "fast_rebalance")]
                         {
-                            min_node.set_subtree_size(min_node_subtree_size);
+                            self.arena.set_subtree_size(min_idx, min_node_subtree_size);
                         }
 
                         // Return as new child
@@ -936,11 +1842,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 // Update parent or root
                 match ngh.parent_idx() {
                     Some(parent_idx) => {
-                        let parent_node = &mut self.arena[parent_idx];
                         if ngh.is_right_child() {
-                            parent_node.set_right_idx(new_child);
+                            self.arena.set_right_idx(parent_idx, new_child);
                         } else {
-                            parent_node.set_left_idx(new_child);
+                            self.arena.set_left_idx(parent_idx, new_child);
                         }
                     }
                     None => {
@@ -949,7 +1854,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 }
 
                 // Perform removal
-                let mut removed_node = self.arena.hard_remove(node_idx);
+                let removed_entry = self.arena.hard_remove(node_idx);
                 self.curr_size -= 1;
 
                 // Update min/max
@@ -965,68 +1870,115 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     debug_assert!(opt_path.is_some());
                     if let Some(path) = opt_path {
                         for parent_idx in path {
-                            let parent_node = &mut self.arena[(*parent_idx).usize()];
-                            debug_assert!(parent_node.subtree_size() > 1);
-                            parent_node.set_subtree_size(parent_node.subtree_size() - 1);
+                            let parent_idx = (*parent_idx).usize();
+                            debug_assert!(self.arena.subtree_size(parent_idx) > 1);
+                            self.arena.set_subtree_size(
+                                parent_idx,
+                                self.arena.subtree_size(parent_idx) - 1,
+                            );
                         }
                     }
                 }
 
-                Some((removed_node.take_key(), removed_node.take_val()))
+                // Rotations along path to deepest shrunk subtree
+                #[cfg(any(feature = "weight_balanced", feature = "avl"))]
+                if let Some(rebal_idx) = opt_rebal_idx {
+                    let mut rebal_path = Arena::<K, V, Idx, N>::new_idx_vec();
+                    self.internal_get(Some(&mut rebal_path), self.arena.key(rebal_idx));
+                    rebal_path.push(Idx::checked_from(rebal_idx));
+                    self.rebalance_path(&rebal_path);
+                }
+
+                Some(removed_entry)
             }
             None => None,
         }
     }
 
-    /// Temporary internal drain_filter() implementation. To be replaced/supplemented with a public implementation.
-    fn priv_drain_filter<Q, F>(&mut self, mut pred: F) -> Self
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-        F: FnMut(&Q, &mut V) -> bool,
-    {
-        /*
-        // TODO: make public version with this signature
-        pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F>
-        where
-            K: Ord,
-            F: FnMut(&K, &mut V) -> bool,
-        {
-        */
+    // Move the in-order span `[start, end)` of elements into a new tree.
+    // Both trees are rebuilt exactly once, regardless of span length.
+    fn priv_take_sorted_span(&mut self, start: usize, end: usize) -> Self {
+        debug_assert!(start <= end && end <= self.len());
 
-        // TODO: this implementation is rather inefficient!
+        let sorted_idxs = match self.opt_root_idx {
+            Some(root_idx) if start < end => self.flatten_subtree_to_sorted_idxs::<usize>(root_idx),
+            _ => SgVec::new(),
+        };
 
-        let mut key_idxs = Arena::<K, V, Idx, N>::new_idx_vec();
-        let mut remove_idxs = Arena::<K, V, Idx, N>::new_idx_vec();
+        self.priv_take_span_of_sorted_idxs(sorted_idxs, start..end)
+    }
 
-        // Below iter_mut() will want to sort, require want consistent indexes, so do work up front
-        self.sort_arena();
+    // Move elements at positions `span` of `sorted_idxs` (every live node's index, sorted by key) into a new tree.
+    fn priv_take_span_of_sorted_idxs(
+        &mut self,
+        sorted_idxs: SgVec<[usize; N]>,
+        span: Range<usize>,
+    ) -> Self {
+        let mut taken = Self::with_balance_policy(self.policy);
 
-        // Safely treat mutable ref as immutable, init list of node's arena indexes
-        for (k, _) in &(*self) {
-            let ngh: NodeGetHelper<Idx> = self.internal_get(None, k.borrow());
-            debug_assert!(ngh.node_idx().is_some());
-            key_idxs.push(Idx::checked_from(ngh.node_idx().unwrap()));
+        #[cfg(feature = "alloc")]
+        {
+            taken.arena = Arena::<K, V, Idx, N>::with_capacity(self.capacity());
+            taken.max_len = self.capacity();
         }
 
-        // Filter arena index list to those not matching predicate
-        for (i, (k, v)) in self.iter_mut().enumerate() {
-            if pred(k.borrow(), v) {
-                remove_idxs.push(key_idxs[i]);
-            }
+        #[cfg(feature = "yield_hook")]
+        taken.set_yield_hook(self.opt_yield_hook);
+
+        if span.is_empty() {
+            return taken;
         }
 
-        // Drain non-matches
-        let mut drained_sgt = Self::new();
-        for i in remove_idxs {
-            if let Some((k, v)) = self.priv_remove_by_idx(i.usize()) {
-                drained_sgt
-                    .try_insert(k, v)
-                    .expect("Stack-storage capacity exceeded!");
+        let mut taken_idxs = SgVec::<[usize; N]>::new();
+        let mut kept_idxs = SgVec::<[usize; N]>::new();
+
+        for (pos, idx) in sorted_idxs.into_iter().enumerate() {
+            if span.contains(&pos) {
+                let (key, val) = self.arena.hard_remove(idx);
+                taken_idxs.push(taken.arena.add(key, val));
+            } else {
+                kept_idxs.push(idx);
             }
         }
 
-        drained_sgt
+        taken.rebuild_from_sorted_idxs(&taken_idxs);
+        self.rebuild_from_sorted_idxs(&kept_idxs);
+        self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
+
+        taken
+    }
+
+    // Re-link every live node into a balanced tree.
+    // `sorted_idxs` must contain the arena index of each live node, sorted by key.
+    pub(super) fn rebuild_from_sorted_idxs(&mut self, sorted_idxs: &[usize]) {
+        self.curr_size = sorted_idxs.len();
+        self.max_size = sorted_idxs.len();
+
+        match (sorted_idxs.first(), sorted_idxs.last()) {
+            (Some(&min_idx), Some(&max_idx)) => {
+                self.opt_root_idx = Some(min_idx);
+                if sorted_idxs.len() == 1 {
+                    self.arena.set_left_idx(min_idx, None);
+                    self.arena.set_right_idx(min_idx, None);
+
+                    #[cfg(feature = "fast_rebalance")]
+                    self.arena.set_subtree_size(min_idx, 1);
+
+                    #[cfg(feature = "avl")]
+                    self.arena.set_height(min_idx, 1);
+                } else {
+                    self.rebalance_subtree_from_sorted_idxs::<Idx>(min_idx, sorted_idxs);
+                }
+
+                self.min_idx = min_idx;
+                self.max_idx = max_idx;
+            }
+            _ => {
+                self.opt_root_idx = None;
+                self.min_idx = 0;
+                self.max_idx = 0;
+            }
+        }
     }
 
     /// Minimum update without recursion
@@ -1035,8 +1987,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             Some(root_idx) => {
                 let mut curr_idx = root_idx;
                 loop {
-                    let node = &self.arena[curr_idx];
-                    match node.left_idx() {
+                    match self.arena.left_idx(curr_idx) {
                         Some(lt_idx) => curr_idx = lt_idx,
                         None => {
                             self.min_idx = curr_idx;
@@ -1055,8 +2006,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             Some(root_idx) => {
                 let mut curr_idx = root_idx;
                 loop {
-                    let node = &self.arena[curr_idx];
-                    match node.right_idx() {
+                    match self.arena.right_idx(curr_idx) {
                         Some(gt_idx) => curr_idx = gt_idx,
                         None => {
                             self.max_idx = curr_idx;
@@ -1069,24 +2019,28 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
-    // Traverse upward, using path information, to find first unbalanced parent.
-    // Uses the algorithm proposed in the original paper (Galperin and Rivest, 1993).
-    #[cfg(not(feature = "alt_impl"))]
+    // Traverse upward, using path information, to find the first parent the balance policy considers a scapegoat.
+    // Returns the scapegoat's depth in the path.
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
     fn find_scapegoat<U: SmallUnsigned + Default>(&self, path: &[U]) -> Option<usize> {
         if path.len() <= 1 {
             return None;
         }
 
+        let params = self.balance_params();
+        let mut height = 0;
         let mut node_subtree_size = 1; // Newly inserted
         let mut parent_path_idx = path.len() - 1; // Parent of newly inserted
         let mut parent_subtree_size = self.get_subtree_size::<U>(path[parent_path_idx].usize());
 
         while (parent_path_idx > 0)
-            && (self.alpha_denom * node_subtree_size as f32)
-                <= (self.alpha_num * parent_subtree_size as f32)
+            && !self
+                .policy
+                .is_scapegoat(node_subtree_size, parent_subtree_size, height, params)
         {
             node_subtree_size = parent_subtree_size;
             parent_path_idx -= 1;
+            height += 1;
             parent_subtree_size = self.get_subtree_size_differential::<U>(
                 path[parent_path_idx].usize(),     // Parent index
                 path[parent_path_idx + 1].usize(), // Child index
@@ -1096,53 +2050,24 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             debug_assert!(parent_subtree_size > node_subtree_size);
         }
 
-        Some(path[parent_path_idx].usize())
-    }
-
-    // Traverse upward, using path information, to find first unbalanced parent.
-    // Uses an alternate algorithm proposed in Galperin's PhD thesis (1996).
-    #[cfg(feature = "alt_impl")]
-    fn find_scapegoat<U: SmallUnsigned + Default>(&self, path: &[U]) -> Option<usize> {
-        if path.len() <= 1 {
-            return None;
-        }
-
-        let mut i = 0;
-        let mut node_subtree_size = 1; // Newly inserted
-        let mut parent_path_idx = path.len() - 1; // Parent of newly inserted
-        let mut parent_subtree_size = self.get_subtree_size::<U>(path[parent_path_idx].usize());
-
-        while (parent_path_idx > 0) && (i <= self.alpha_balance_depth(node_subtree_size)) {
-            node_subtree_size = parent_subtree_size;
-            parent_path_idx -= 1;
-            i += 1;
-            parent_subtree_size = self.get_subtree_size_differential::<U>(
-                path[parent_path_idx].usize(),     // Parent index
-                path[parent_path_idx + 1].usize(), // Child index
-                node_subtree_size,                 // Child subtree size
-            );
-
-            debug_assert!(parent_subtree_size > node_subtree_size);
-        }
-
-        Some(path[parent_path_idx].usize())
+        Some(parent_path_idx)
     }
 
     // Iterative subtree size computation
     #[cfg(not(feature = "fast_rebalance"))]
     fn get_subtree_size<U: SmallUnsigned + Default>(&self, idx: usize) -> usize {
-        let mut subtree_worklist = array_vec![[U; N] => U::checked_from(idx)];
+        let mut subtree_worklist = SgVec::<[U; N]>::from_iter(iter::once(U::checked_from(idx)));
         let mut subtree_size = 0;
 
         while let Some(idx) = subtree_worklist.pop() {
-            let node = &self.arena[idx.usize()];
+            let idx = idx.usize();
             subtree_size += 1;
 
-            if let Some(left_idx) = node.left_idx() {
+            if let Some(left_idx) = self.arena.left_idx(idx) {
                 subtree_worklist.push(U::checked_from(left_idx));
             }
 
-            if let Some(right_idx) = node.right_idx() {
+            if let Some(right_idx) = self.arena.right_idx(idx) {
                 subtree_worklist.push(U::checked_from(right_idx));
             }
         }
@@ -1152,38 +2077,38 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
 
     // Retrieve cached subtree size
     #[cfg(feature = "fast_rebalance")]
+    #[allow(clippy::extra_unused_type_parameters)] // Signature parity with non-cached variant
     fn get_subtree_size<U: SmallUnsigned>(&self, idx: usize) -> usize {
-        self.arena[idx].subtree_size()
+        self.arena.subtree_size(idx)
     }
 
     // Differential subtree size helper
-    #[cfg(not(feature = "fast_rebalance"))]
+    #[cfg(not(any(feature = "fast_rebalance", feature = "avl")))]
     fn get_subtree_size_differential<U: SmallUnsigned + Default>(
         &self,
         parent_idx: usize,
         child_idx: usize,
         child_subtree_size: usize,
     ) -> usize {
-        let parent = &self.arena[parent_idx];
-
         debug_assert!(
-            (parent.right_idx() == Some(child_idx)) || (parent.left_idx() == Some(child_idx))
+            (self.arena.right_idx(parent_idx) == Some(child_idx))
+                || (self.arena.left_idx(parent_idx) == Some(child_idx))
         );
 
         let mut is_right_child = false;
-        if let Some(right_child_idx) = parent.right_idx() {
+        if let Some(right_child_idx) = self.arena.right_idx(parent_idx) {
             if right_child_idx == child_idx {
                 is_right_child = true;
             }
         }
 
         let other_child_subtree_size = if is_right_child {
-            match parent.left_idx() {
+            match self.arena.left_idx(parent_idx) {
                 Some(idx) => self.get_subtree_size::<U>(idx),
                 None => 0,
             }
         } else {
-            match parent.right_idx() {
+            match self.arena.right_idx(parent_idx) {
                 Some(idx) => self.get_subtree_size::<U>(idx),
                 None => 0,
             }
@@ -1201,7 +2126,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
 
     // Subtree size helper
     // Size already cached if `fast_rebalance` is enabled, no need for differential logic
-    #[cfg(feature = "fast_rebalance")]
+    #[cfg(all(
+        feature = "fast_rebalance",
+        not(any(feature = "weight_balanced", feature = "avl"))
+    ))]
     fn get_subtree_size_differential<U: SmallUnsigned>(
         &self,
         parent_idx: usize,
@@ -1229,6 +2157,9 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             return;
         }
 
+        #[cfg(feature = "telemetry")]
+        self.stats.record_rebuild(sorted_arena_idxs.len());
+
         debug_assert!(
             self.opt_root_idx.is_some(),
             "Internal invariant failed: rebalance of multi-node tree without root!"
@@ -1237,7 +2168,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         let sorted_last_idx = sorted_arena_idxs.len() - 1;
         let subtree_root_sorted_idx = sorted_last_idx / 2;
         let subtree_root_arena_idx = sorted_arena_idxs[subtree_root_sorted_idx];
-        let mut subtree_worklist = ArrayVec::<[(U, NodeRebuildHelper<U>); N]>::default();
+        let mut subtree_worklist = SgVec::<[(U, NodeRebuildHelper<U>); N]>::default();
 
         // Init worklist with middle node (balanced subtree root)
         subtree_worklist.push((
@@ -1250,29 +2181,32 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             if sorted_arena_idxs.contains(&root_idx) {
                 self.opt_root_idx = Some(subtree_root_arena_idx);
             } else {
-                let old_subtree_root = &self.arena[old_subtree_root_idx];
-                let ngh: NodeGetHelper<U> = self.internal_get(None, old_subtree_root.key());
+                let ngh: NodeGetHelper<U> =
+                    self.internal_get(None, self.arena.key(old_subtree_root_idx));
                 debug_assert!(
                     ngh.parent_idx().is_some(),
                     "Internal invariant failed: rebalance of non-root parent-less node!"
                 );
                 if let Some(parent_idx) = ngh.parent_idx() {
-                    let parent_node = &mut self.arena[parent_idx];
                     if ngh.is_right_child() {
-                        parent_node.set_right_idx(Some(subtree_root_arena_idx));
+                        self.arena
+                            .set_right_idx(parent_idx, Some(subtree_root_arena_idx));
                     } else {
-                        parent_node.set_left_idx(Some(subtree_root_arena_idx));
+                        self.arena
+                            .set_left_idx(parent_idx, Some(subtree_root_arena_idx));
                     }
                 }
             }
         }
 
         // Iteratively re-assign all children
+        let mut work = 0;
         while let Some((sorted_idx, parent_nrh)) = subtree_worklist.pop() {
-            let parent_node = &mut self.arena[sorted_arena_idxs[sorted_idx.usize()]];
+            self.yield_point(&mut work);
+            let parent_idx = sorted_arena_idxs[sorted_idx.usize()];
 
-            parent_node.set_left_idx(None);
-            parent_node.set_right_idx(None);
+            self.arena.set_left_idx(parent_idx, None);
+            self.arena.set_right_idx(parent_idx, None);
 
             // Set left child
             if parent_nrh.low_idx < parent_nrh.mid_idx {
@@ -1280,7 +2214,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     parent_nrh.low_idx.usize(),
                     parent_nrh.mid_idx.usize() - 1,
                 );
-                parent_node.set_left_idx(Some(sorted_arena_idxs[child_nrh.mid_idx.usize()]));
+                self.arena.set_left_idx(
+                    parent_idx,
+                    Some(sorted_arena_idxs[child_nrh.mid_idx.usize()]),
+                );
                 subtree_worklist.push((child_nrh.mid_idx, child_nrh));
             }
 
@@ -1290,16 +2227,31 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     parent_nrh.mid_idx.usize() + 1,
                     parent_nrh.high_idx.usize(),
                 );
-                parent_node.set_right_idx(Some(sorted_arena_idxs[child_nrh.mid_idx.usize()]));
+                self.arena.set_right_idx(
+                    parent_idx,
+                    Some(sorted_arena_idxs[child_nrh.mid_idx.usize()]),
+                );
                 subtree_worklist.push((child_nrh.mid_idx, child_nrh));
             }
 
             // Set subtree size
             #[cfg(feature = "fast_rebalance")]
             {
-                parent_node
-                    .set_subtree_size(parent_nrh.high_idx.usize() - parent_nrh.low_idx.usize() + 1);
-                debug_assert!(parent_node.subtree_size() >= 1);
+                self.arena.set_subtree_size(
+                    parent_idx,
+                    parent_nrh.high_idx.usize() - parent_nrh.low_idx.usize() + 1,
+                );
+                debug_assert!(self.arena.subtree_size(parent_idx) >= 1);
+            }
+
+            // Set subtree height, perfectly balanced so determined by subtree size
+            #[cfg(feature = "avl")]
+            {
+                let subtree_size = parent_nrh.high_idx.usize() - parent_nrh.low_idx.usize() + 1;
+                self.arena.set_height(
+                    parent_idx,
+                    (usize::BITS - subtree_size.leading_zeros()) as usize,
+                );
             }
         }
 
@@ -1311,20 +2263,74 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         );
     }
 
-    // Alpha weight balance computation helper.
-    fn alpha_balance_depth(&self, val: usize) -> usize {
-        // log base (1/alpha), hence (denom/num)
-        (val as f32).log(self.alpha_denom / self.alpha_num).floor() as usize
+    // Count one node processed by a long operation, calling the yield hook if one is set and due.
+    #[cfg(feature = "yield_hook")]
+    fn yield_point(&self, work: &mut usize) {
+        if let Some(hook) = &self.opt_yield_hook {
+            hook.tick(work);
+        }
+    }
+
+    #[cfg(not(feature = "yield_hook"))]
+    #[inline(always)]
+    fn yield_point(&self, _work: &mut usize) {}
+
+    // Current rebalance parameters, for the balance policy.
+    pub(super) fn balance_params(&self) -> BalanceParams {
+        BalanceParams {
+            alpha_num: self.alpha_num,
+            alpha_denom: self.alpha_denom,
+        }
+    }
+}
+
+// Layout Helpers ------------------------------------------------------------------------------------------------------
+
+// Depth of a 1-based heap index, root at depth `0`.
+fn heap_depth(heap_idx: usize) -> u32 {
+    (mem::size_of::<usize>() as u32 * 8) - 1 - heap_idx.leading_zeros()
+}
+
+// Relative order of two heap indexes within a van Emde Boas layout of a tree with the given height.
+// The tree is split into a top half and bottom subtrees, top first, then bottom subtrees left to right, recursively.
+fn veb_cmp(mut a: usize, mut b: usize, mut height: u32) -> Ordering {
+    loop {
+        if a == b {
+            return Ordering::Equal;
+        }
+
+        let (depth_a, depth_b) = (heap_depth(a), heap_depth(b));
+        let top_height = height / 2;
+
+        match (depth_a < top_height, depth_b < top_height) {
+            (true, true) => height = top_height,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => {
+                // Roots of the bottom subtrees containing each index
+                let (rel_a, rel_b) = (depth_a - top_height, depth_b - top_height);
+                let (root_a, root_b) = (a >> rel_a, b >> rel_b);
+                if root_a != root_b {
+                    return root_a.cmp(&root_b);
+                }
+
+                // Same bottom subtree, re-index relative to its root
+                a = (a & ((1 << rel_a) - 1)) | (1 << rel_a);
+                b = (b & ((1 << rel_b) - 1)) | (1 << rel_b);
+                height -= top_height;
+            }
+        }
     }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 // Debug
-impl<K, V, const N: usize> Debug for SgTree<K, V, N>
+impl<K, V, const N: usize, P> Debug for SgTree<K, V, N, P>
 where
-    K: Ord + Debug + Default,
-    V: Debug + Default,
+    K: Ord + Debug,
+    V: Debug,
+    P: BalancePolicy,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
@@ -1332,21 +2338,20 @@ where
 }
 
 // Default
-impl<K, V, const N: usize> Default for SgTree<K, V, N>
+impl<K, V, const N: usize, P> Default for SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    P: BalancePolicy + Default,
 {
     fn default() -> Self {
-        Self::new()
+        Self::with_balance_policy(P::default())
     }
 }
 
 // From array
 impl<K, V, const N: usize> From<[(K, V); N]> for SgTree<K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn from(arr: [(K, V); N]) -> Self {
         IntoIterator::into_iter(arr).collect()
@@ -1368,8 +2373,7 @@ See issue from 2018: https://github.com/rust-lang/rust/issues/50133#issuecomment
 // TryFrom array
 impl<K, V, const N: usize> TryFrom<[(K, V); N]> for SgTree<K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Error = SgError;
 
@@ -1383,11 +2387,11 @@ where
 */
 
 // Indexing
-impl<K, V, Q, const N: usize> Index<&Q> for SgTree<K, V, N>
+impl<K, V, Q, const N: usize, P> Index<&Q> for SgTree<K, V, N, P>
 where
-    K: Borrow<Q> + Ord + Default,
+    K: Borrow<Q> + Ord,
     Q: Ord + ?Sized,
-    V: Default,
+    P: BalancePolicy,
 {
     type Output = V;
 
@@ -1402,10 +2406,10 @@ where
 }
 
 // Extension from iterator.
-impl<K, V, const N: usize> Extend<(K, V)> for SgTree<K, V, N>
+impl<K, V, const N: usize, P> Extend<(K, V)> for SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    P: BalancePolicy,
 {
     fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |(k, v)| {
@@ -1416,10 +2420,11 @@ where
 }
 
 // Extension from reference iterator.
-impl<'a, K, V, const N: usize> Extend<(&'a K, &'a V)> for SgTree<K, V, N>
+impl<'a, K, V, const N: usize, P> Extend<(&'a K, &'a V)> for SgTree<K, V, N, P>
 where
-    K: Ord + Copy + Default,
-    V: Copy + Default,
+    K: Ord + Copy,
+    V: Copy,
+    P: BalancePolicy,
 {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
@@ -1427,51 +2432,56 @@ where
 }
 
 // PartialEq
-impl<K, V, const N: usize> PartialEq for SgTree<K, V, N>
+impl<K, V, const N: usize, P> PartialEq for SgTree<K, V, N, P>
 where
-    K: Ord + PartialEq + Default,
-    V: PartialEq + Default,
+    K: Ord + PartialEq,
+    V: PartialEq,
+    P: BalancePolicy,
 {
-    fn eq(&self, other: &SgTree<K, V, N>) -> bool {
+    fn eq(&self, other: &SgTree<K, V, N, P>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 // Eq
-impl<K, V, const N: usize> Eq for SgTree<K, V, N>
+impl<K, V, const N: usize, P> Eq for SgTree<K, V, N, P>
 where
-    K: Ord + Eq + Default,
-    V: Eq + Default,
+    K: Ord + Eq,
+    V: Eq,
+    P: BalancePolicy,
 {
 }
 
 // PartialOrd
-impl<K, V, const N: usize> PartialOrd for SgTree<K, V, N>
+impl<K, V, const N: usize, P> PartialOrd for SgTree<K, V, N, P>
 where
-    K: Ord + PartialOrd + Default,
-    V: PartialOrd + Default,
+    K: Ord + PartialOrd,
+    V: PartialOrd,
+    P: BalancePolicy,
 {
-    fn partial_cmp(&self, other: &SgTree<K, V, N>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &SgTree<K, V, N, P>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 // Ord
-impl<K, V, const N: usize> Ord for SgTree<K, V, N>
+impl<K, V, const N: usize, P> Ord for SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Ord + Default,
+    K: Ord,
+    V: Ord,
+    P: BalancePolicy,
 {
-    fn cmp(&self, other: &SgTree<K, V, N>) -> Ordering {
+    fn cmp(&self, other: &SgTree<K, V, N, P>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 // Hash
-impl<K, V, const N: usize> Hash for SgTree<K, V, N>
+impl<K, V, const N: usize, P> Hash for SgTree<K, V, N, P>
 where
-    K: Ord + Hash + Default,
-    V: Hash + Default,
+    K: Ord + Hash,
+    V: Hash,
+    P: BalancePolicy,
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for i in self {
@@ -1483,13 +2493,13 @@ where
 // Iterators -----------------------------------------------------------------------------------------------------------
 
 // Construct from iterator.
-impl<K, V, const N: usize> FromIterator<(K, V)> for SgTree<K, V, N>
+impl<K, V, const N: usize, P> FromIterator<(K, V)> for SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    P: BalancePolicy + Default,
 {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
-        let mut sgt = SgTree::new();
+        let mut sgt = SgTree::default();
 
         for (k, v) in iter {
             sgt.try_insert(k, v)
@@ -1501,10 +2511,10 @@ where
 }
 
 // Reference iterator, mutable
-impl<'a, K, V, const N: usize> IntoIterator for &'a mut SgTree<K, V, N>
+impl<'a, K, V, const N: usize, P> IntoIterator for &'a mut SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    P: BalancePolicy,
 {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V, N>;
@@ -1515,10 +2525,10 @@ where
 }
 
 // Reference iterator, immutable
-impl<'a, K, V, const N: usize> IntoIterator for &'a SgTree<K, V, N>
+impl<'a, K, V, const N: usize, P> IntoIterator for &'a SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    P: BalancePolicy,
 {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V, N>;
@@ -1529,10 +2539,10 @@ where
 }
 
 // Consuming iterator
-impl<K, V, const N: usize> IntoIterator for SgTree<K, V, N>
+impl<K, V, const N: usize, P> IntoIterator for SgTree<K, V, N, P>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    P: BalancePolicy,
 {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V, N>;
@@ -0,0 +1,273 @@
+use core::fmt;
+
+use super::arena::{NodeArena, SgVec};
+use super::balance::BalancePolicy;
+#[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+use super::rotate::DELTA;
+use super::tree::{Idx, SgTree};
+
+use smallnum::SmallUnsigned;
+
+/// Internal invariant violations, reported by [`SgMap::validate`][crate::map::SgMap::validate].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum InvariantError {
+    /// A root or child link refers to a slot that's out of bounds or free, e.g. a dangling index.
+    InvalidIndex {
+        /// Referenced slot index.
+        idx: usize,
+    },
+
+    /// A node is reachable more than once, e.g. a cycle or shared child.
+    DuplicateLink {
+        /// Slot index of the node.
+        idx: usize,
+    },
+
+    /// An occupied slot isn't reachable from the root, e.g. a leaked node.
+    Unreachable {
+        /// Slot index of the node.
+        idx: usize,
+    },
+
+    /// Keys aren't in strictly ascending order (e.g. not a binary search tree, or duplicate keys).
+    Unordered {
+        /// Slot index of the first out-of-order node.
+        idx: usize,
+    },
+
+    /// A free list entry is out of bounds, occupied (e.g. reachable), or listed twice.
+    FreeListCorrupt {
+        /// Listed slot index.
+        idx: usize,
+    },
+
+    /// Number of reachable nodes doesn't match the tree's length.
+    LengthMismatch {
+        /// Tree length.
+        len: usize,
+        /// Reachable node count.
+        reachable: usize,
+    },
+
+    /// Cached minimum or maximum index doesn't refer to the smallest or largest key.
+    StaleMinMax,
+
+    /// Cached per-node metadata (subtree size or height) doesn't match the node's subtree.
+    StaleMetadata {
+        /// Slot index of the node.
+        idx: usize,
+    },
+
+    /// A node violates the balance invariant: too deep for a loosely alpha-height-balanced scapegoat tree,
+    /// or subtrees out of balance if feature `weight_balanced` or `avl` is enabled.
+    Unbalanced {
+        /// Slot index of the node.
+        idx: usize,
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::InvalidIndex { idx } => write!(f, "dangling link to index {}", idx),
+            InvariantError::DuplicateLink { idx } => {
+                write!(f, "node at index {} linked more than once", idx)
+            }
+            InvariantError::Unreachable { idx } => {
+                write!(f, "node at index {} unreachable from root", idx)
+            }
+            InvariantError::Unordered { idx } => write!(f, "node at index {} out of order", idx),
+            InvariantError::FreeListCorrupt { idx } => {
+                write!(
+                    f,
+                    "free list entry {} out of bounds, occupied, or repeated",
+                    idx
+                )
+            }
+            InvariantError::LengthMismatch { len, reachable } => {
+                write!(f, "length {} but {} reachable nodes", len, reachable)
+            }
+            InvariantError::StaleMinMax => write!(f, "stale cached min or max index"),
+            InvariantError::StaleMetadata { idx } => {
+                write!(f, "node at index {} has stale metadata", idx)
+            }
+            InvariantError::Unbalanced { idx } => write!(f, "node at index {} unbalanced", idx),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvariantError {}
+
+impl<K: Ord, V, const N: usize, P: BalancePolicy> SgTree<K, V, N, P> {
+    /// Check every internal invariant, returning the first violation found.
+    /// Runs in `O(n)` time, intended for fuzzing and debugging.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let slot_cnt = self.arena.iter().len();
+
+        // Per slot: reachable (or, for free slots, already listed), subtree size, subtree height
+        let mut seen = SgVec::<[bool; N]>::new();
+        seen.resize(slot_cnt, false);
+        let mut sizes = SgVec::<[Idx; N]>::new();
+        sizes.resize(slot_cnt, 0);
+        let mut heights = SgVec::<[Idx; N]>::new();
+        heights.resize(slot_cnt, 0);
+
+        // Pre-order traversal, validating links before following them
+        let mut preorder = SgVec::<[Idx; N]>::new();
+        #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+        let mut deepest = (0, 0); // Index and depth
+        if let Some(root_idx) = self.opt_root_idx {
+            if !self.arena.is_occupied(root_idx) {
+                return Err(InvariantError::InvalidIndex { idx: root_idx });
+            }
+
+            seen[root_idx] = true;
+            let mut worklist = SgVec::<[(Idx, Idx); N]>::new();
+            worklist.push((Idx::checked_from(root_idx), 0));
+
+            while let Some((idx, depth)) = worklist.pop() {
+                let (idx, depth) = (idx.usize(), depth.usize());
+                preorder.push(Idx::checked_from(idx));
+                #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+                if depth > deepest.1 {
+                    deepest = (idx, depth);
+                }
+
+                for child_idx in [self.arena.left_idx(idx), self.arena.right_idx(idx)]
+                    .iter()
+                    .flatten()
+                {
+                    let child_idx = *child_idx;
+                    if !self.arena.is_occupied(child_idx) {
+                        return Err(InvariantError::InvalidIndex { idx: child_idx });
+                    }
+
+                    if seen[child_idx] {
+                        return Err(InvariantError::DuplicateLink { idx: child_idx });
+                    }
+
+                    seen[child_idx] = true;
+                    worklist.push((Idx::checked_from(child_idx), Idx::checked_from(depth + 1)));
+                }
+            }
+        }
+
+        if preorder.len() != self.len() {
+            return Err(InvariantError::LengthMismatch {
+                len: self.len(),
+                reachable: preorder.len(),
+            });
+        }
+
+        if let Some(idx) = (0..slot_cnt).find(|&idx| self.arena.is_occupied(idx) && !seen[idx]) {
+            return Err(InvariantError::Unreachable { idx });
+        }
+
+        // Free slots are never reachable, so `seen` now flags repeats
+        #[cfg(not(feature = "low_mem_insert"))]
+        for idx in self.arena.free_idxs() {
+            if (idx >= slot_cnt) || self.arena.is_occupied(idx) || seen[idx] {
+                return Err(InvariantError::FreeListCorrupt { idx });
+            }
+
+            seen[idx] = true;
+        }
+
+        self.validate_order()?;
+
+        // Children before parents
+        for idx in preorder.iter().rev() {
+            let idx = idx.usize();
+            let (opt_left_idx, opt_right_idx) =
+                (self.arena.left_idx(idx), self.arena.right_idx(idx));
+            let size = |opt_idx: Option<usize>| opt_idx.map_or(0, |i| sizes[i].usize());
+            let height = |opt_idx: Option<usize>| opt_idx.map_or(0, |i| heights[i].usize());
+            let (left_size, right_size) = (size(opt_left_idx), size(opt_right_idx));
+            let (left_height, right_height) = (height(opt_left_idx), height(opt_right_idx));
+
+            sizes[idx] = Idx::checked_from(1 + left_size + right_size);
+            heights[idx] = Idx::checked_from(1 + left_height.max(right_height));
+
+            #[cfg(feature = "fast_rebalance")]
+            if self.arena.subtree_size(idx) != sizes[idx].usize() {
+                return Err(InvariantError::StaleMetadata { idx });
+            }
+
+            #[cfg(feature = "avl")]
+            {
+                if self.arena.height(idx) != heights[idx].usize() {
+                    return Err(InvariantError::StaleMetadata { idx });
+                }
+
+                if left_height.max(right_height) - left_height.min(right_height) > 1 {
+                    return Err(InvariantError::Unbalanced { idx });
+                }
+            }
+
+            // Weight is subtree size + 1
+            #[cfg(all(feature = "weight_balanced", not(feature = "avl")))]
+            if (DELTA * (left_size + 1) < (right_size + 1))
+                || (DELTA * (right_size + 1) < (left_size + 1))
+            {
+                return Err(InvariantError::Unbalanced { idx });
+            }
+        }
+
+        #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+        if let Some(max_depth) = self.depth_guarantee() {
+            if deepest.1 > max_depth {
+                return Err(InvariantError::Unbalanced { idx: deepest.0 });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Panics with a description of the first violated internal invariant, if any.
+    /// See [`validate`][SgTree::validate].
+    pub fn assert_invariants(&self) {
+        if let Err(err) = self.validate() {
+            panic!("Internal invariant failed: {}", err);
+        }
+    }
+
+    // In-order traversal (links already validated), checking key order and cached min/max.
+    fn validate_order(&self) -> Result<(), InvariantError> {
+        let mut worklist = SgVec::<[Idx; N]>::new();
+        let mut opt_curr = self.opt_root_idx;
+        let mut opt_prev: Option<usize> = None;
+        let mut opt_first = None;
+
+        loop {
+            while let Some(idx) = opt_curr {
+                worklist.push(Idx::checked_from(idx));
+                opt_curr = self.arena.left_idx(idx);
+            }
+
+            match worklist.pop() {
+                Some(idx) => {
+                    let idx = idx.usize();
+                    if let Some(prev_idx) = opt_prev {
+                        if self.arena.key(prev_idx) >= self.arena.key(idx) {
+                            return Err(InvariantError::Unordered { idx });
+                        }
+                    }
+
+                    opt_first = opt_first.or(Some(idx));
+                    opt_prev = Some(idx);
+                    opt_curr = self.arena.right_idx(idx);
+                }
+                None => break,
+            }
+        }
+
+        match (opt_first, opt_prev) {
+            (Some(first), Some(last)) if (first != self.min_idx) || (last != self.max_idx) => {
+                Err(InvariantError::StaleMinMax)
+            }
+            _ => Ok(()),
+        }
+    }
+}
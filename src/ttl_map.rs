@@ -0,0 +1,180 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Ordered map with expiring entries, up to `N` key-value pairs (live or expired).
+///
+/// Each entry records the tick at which it expires, from a caller-supplied `u64` clock, and is live while `now` is
+/// before that tick. Lookups and iteration skip expired entries lazily, [`purge_expired`][SgTtlMap::purge_expired]
+/// reclaims their slots.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgTtlMap;
+///
+/// // Neighbor address -> link quality, expiring 100 ticks after last heard
+/// let mut neighbors = SgTtlMap::<u16, u8, 8>::new();
+/// neighbors.insert(0x0a, 90, 100);
+/// neighbors.insert(0x0b, 40, 150);
+///
+/// assert_eq!(neighbors.get(&0x0a, 50), Some(&90));
+/// assert_eq!(neighbors.get(&0x0a, 100), None); // Expired
+/// assert!(neighbors.iter(120).eq([(&0x0b, &40)]));
+///
+/// assert_eq!(neighbors.purge_expired(120), 1);
+/// assert_eq!(neighbors.len(), 1);
+/// ```
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SgTtlMap<K: Ord, V, const N: usize> {
+    // Key -> (expiry tick, value)
+    map: SgMap<K, (u64, V), N>,
+}
+
+impl<K: Ord, V, const N: usize> SgTtlMap<K, V, N> {
+    /// Makes a new, empty `SgTtlMap`.
+    pub fn new() -> Self {
+        SgTtlMap { map: SgMap::new() }
+    }
+
+    /// Number of stored entries, including expired ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Number of entries live at tick `now`.
+    pub fn live_len(&self, now: u64) -> usize {
+        self.iter(now).count()
+    }
+
+    /// Returns `true` if the map stores no entries, live or expired.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of entries, live or expired, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Insert a key-value pair expiring at tick `expires_at`, returning the key's previous value (even if expired).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is new and the map is full.
+    /// Use [`try_insert`][SgTtlMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V, expires_at: u64) -> Option<V> {
+        self.map
+            .insert(key, (expires_at, val))
+            .map(|(_, old_val)| old_val)
+    }
+
+    /// Insert a key-value pair expiring at tick `expires_at`, returning the key's previous value (even if expired).
+    /// Returns `Err`, leaving the map unchanged, if the key is new and the map is full.
+    /// Purging expired entries may free space.
+    pub fn try_insert(&mut self, key: K, val: V, expires_at: u64) -> Result<Option<V>, SgError> {
+        self.map
+            .try_insert(key, (expires_at, val))
+            .map(|opt_old| opt_old.map(|(_, old_val)| old_val))
+    }
+
+    /// Get a key's value, if live at tick `now`.
+    pub fn get<Q>(&self, key: &Q, now: u64) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map
+            .get(key)
+            .filter(|(expires_at, _)| now < *expires_at)
+            .map(|(_, val)| val)
+    }
+
+    /// Get a mutable reference to a key's value, if live at tick `now`.
+    pub fn get_mut<Q>(&mut self, key: &Q, now: u64) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map
+            .get_mut(key)
+            .filter(|(expires_at, _)| now < *expires_at)
+            .map(|(_, val)| val)
+    }
+
+    /// Returns `true` if the key is live at tick `now`.
+    pub fn contains_key<Q>(&self, key: &Q, now: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key, now).is_some()
+    }
+
+    /// Get the tick at which a key expires (or expired), if stored.
+    pub fn expires_at<Q>(&self, key: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key).map(|(expires_at, _)| *expires_at)
+    }
+
+    /// Change the tick at which a stored key expires, e.g. to refresh it.
+    /// Returns `false` if the key isn't stored.
+    pub fn set_expires_at<Q>(&mut self, key: &Q, expires_at: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.map.get_mut(key) {
+            Some((old_expires_at, _)) => {
+                *old_expires_at = expires_at;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a key, returning its value (even if expired) if stored.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(key).map(|(_, val)| val)
+    }
+
+    /// Remove every entry expired at tick `now`, returning the number removed.
+    pub fn purge_expired(&mut self, now: u64) -> usize {
+        let len = self.map.len();
+        self.map.retain(|_, (expires_at, _)| now < *expires_at);
+        len - self.map.len()
+    }
+
+    /// Gets an iterator over the entries live at tick `now`, in ascending key order.
+    pub fn iter(&self, now: u64) -> impl DoubleEndedIterator<Item = (&K, &V)> + '_ {
+        self.map
+            .iter()
+            .filter(move |(_, (expires_at, _))| now < *expires_at)
+            .map(|(key, (_, val))| (key, val))
+    }
+}
+
+impl<K, V, const N: usize> Debug for SgTtlMap<K, V, N>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    // Includes expired entries, with their expiry ticks
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
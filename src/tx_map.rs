@@ -0,0 +1,241 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Ordered map with all-or-nothing transactions, up to `N` key-value pairs.
+///
+/// Between [`begin`][SgTxMap::begin] and [`commit`][SgTxMap::commit], the first change to each key journals its
+/// prior state, so [`rollback`][SgTxMap::rollback] can restore the map as it was at `begin`.
+/// The journal shares the map's capacity `N`: a mutation that would overflow it returns `Err`, leaving the map
+/// unchanged, and the transaction can still be rolled back.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{SgError, SgTxMap};
+///
+/// let mut routes = SgTxMap::<u8, u16, 2>::new();
+/// routes.insert(1, 80);
+///
+/// // Multi-step update aborted by a capacity error
+/// assert!(routes.begin());
+/// routes.insert(1, 8080);
+/// routes.insert(2, 443);
+/// assert_eq!(routes.try_insert(3, 22), Err(SgError::StackCapacityExceeded));
+/// assert!(routes.rollback());
+///
+/// assert!(routes.iter().eq([(&1, &80)]));
+/// ```
+#[derive(Default, Clone)]
+pub struct SgTxMap<K: Ord, V, const N: usize> {
+    map: SgMap<K, V, N>,
+
+    // Key -> state at `begin`, `None` if absent
+    journal: SgMap<K, Option<V>, N>,
+    in_tx: bool,
+}
+
+impl<K: Ord + Clone, V: Clone, const N: usize> SgTxMap<K, V, N> {
+    /// Makes a new, empty `SgTxMap`.
+    pub fn new() -> Self {
+        SgTxMap {
+            map: SgMap::new(),
+            journal: SgMap::new(),
+            in_tx: false,
+        }
+    }
+
+    /// Number of key-value pairs.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of key-value pairs, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Number of journal records held for the open transaction.
+    pub fn journal_len(&self) -> usize {
+        self.journal.len()
+    }
+
+    /// Returns `true` if a transaction is open.
+    pub fn in_transaction(&self) -> bool {
+        self.in_tx
+    }
+
+    /// Open a transaction.
+    /// Returns `false`, leaving the open transaction as is, if one is already open.
+    pub fn begin(&mut self) -> bool {
+        match self.in_tx {
+            true => false,
+            false => {
+                self.in_tx = true;
+                true
+            }
+        }
+    }
+
+    /// Keep the open transaction's changes, discarding its journal.
+    /// Returns `false` if no transaction is open.
+    pub fn commit(&mut self) -> bool {
+        self.journal.clear();
+        core::mem::replace(&mut self.in_tx, false)
+    }
+
+    /// Undo the open transaction's changes, restoring the map as it was at [`begin`][SgTxMap::begin].
+    /// Returns `false` if no transaction is open.
+    pub fn rollback(&mut self) -> bool {
+        // Removing new keys first ensures restored ones fit
+        self.remove_new_keys();
+        while let Some((key, prior)) = self.journal.pop_first() {
+            if let Some(val) = prior {
+                self.map.insert(key, val);
+            }
+        }
+
+        core::mem::replace(&mut self.in_tx, false)
+    }
+
+    /// Remove all elements.
+    /// Within a transaction, can be rolled back.
+    pub fn clear(&mut self) {
+        if !self.in_tx {
+            self.map.clear();
+            return;
+        }
+
+        // Only keys present at `begin` remain journaled, at most `N`
+        self.remove_new_keys();
+        self.journal.retain(|_, prior| prior.is_some());
+        while let Some((key, val)) = self.map.pop_first() {
+            if !self.journal.contains_key(&key) {
+                self.journal.insert(key, Some(val));
+            }
+        }
+    }
+
+    /// Insert a key-value pair, returning the old value if the key was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is new and the map is full, or the transaction journal is full.
+    /// Use [`try_insert`][SgTxMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.try_insert(key, val) {
+            Ok(opt_old) => opt_old,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Insert a key-value pair, returning the old value if the key was present.
+    /// Returns `Err`, leaving the map unchanged, if the key is new and the map is full,
+    /// or the transaction journal is full.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        let opt_old = self.map.get(&key);
+        if opt_old.is_none() && (self.map.len() >= self.map.max_len()) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        if self.in_tx && !self.journal.contains_key(&key) {
+            let prior = opt_old.cloned();
+            self.journal.try_insert(key.clone(), prior)?;
+        }
+
+        Ok(self.map.insert(key, val))
+    }
+
+    /// Remove a key, returning its value if present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transaction journal is full.
+    /// Use [`try_remove`][SgTxMap::try_remove] for a fallible alternative.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.try_remove(key) {
+            Ok(opt_val) => opt_val,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Remove a key, returning its value if present.
+    /// Returns `Err`, leaving the map unchanged, if the transaction journal is full.
+    pub fn try_remove<Q>(&mut self, key: &Q) -> Result<Option<V>, SgError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.in_tx {
+            return Ok(self.map.remove(key));
+        }
+
+        match self.journal.get(key) {
+            // Key was absent at `begin`, so needs no record once removed
+            Some(None) => {
+                self.journal.remove(key);
+            }
+            Some(Some(_)) => {}
+            None => {
+                let (key, val) = match self.map.get_key_value(key) {
+                    Some(entry) => entry,
+                    None => return Ok(None),
+                };
+                self.journal.try_insert(key.clone(), Some(val.clone()))?;
+            }
+        }
+
+        Ok(self.map.remove(key))
+    }
+
+    /// Get a key's value.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns `true` if the map contains the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Gets an iterator over the entries, sorted by key.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator + '_ {
+        self.map.iter()
+    }
+
+    // Remove keys inserted since `begin`, leaving their (now redundant) records.
+    fn remove_new_keys(&mut self) {
+        for (key, _) in self.journal.iter().filter(|(_, prior)| prior.is_none()) {
+            self.map.remove(key);
+        }
+    }
+}
+
+impl<K, V, const N: usize> Debug for SgTxMap<K, V, N>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
@@ -0,0 +1,335 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter;
+
+use crate::map::SgMap;
+use crate::tree::SgError;
+
+/// Handle to a snapshot of an [`SgVersionedMap`], see [`SgVersionedMap::snapshot`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Version(u64);
+
+/// Ordered map with read-only snapshots of past versions: up to `N` key-value pairs and `J` journal records.
+///
+/// Snapshots share storage with the live map. The first change to a key after a snapshot journals the key's prior
+/// value, so a snapshot reads the earliest journal record at or after it, or else the live value.
+/// The journal is bounded: when full, the oldest snapshot's records are dropped and it can no longer be read.
+///
+/// Unlike a persistent (path-copying) tree, snapshots aren't separate trees. Updates stay in-place `O(log n)`,
+/// plus at most one journal record per key per snapshot, and storage is fixed at `N + J` entries.
+/// The cost moves to reads: a snapshot lookup also searches the journal, and snapshot iteration merges both maps.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgVersionedMap;
+///
+/// let mut config = SgVersionedMap::<&str, u32, 8, 4>::new();
+/// config.insert("baud", 9600);
+/// let v1 = config.snapshot();
+///
+/// config.insert("baud", 115200);
+/// config.insert("parity", 1);
+///
+/// let old = config.at(v1).unwrap();
+/// assert_eq!(old.get(&"baud"), Some(&9600));
+/// assert!(old.iter().eq([(&"baud", &9600)]));
+/// assert_eq!(config.get(&"baud"), Some(&115200));
+///
+/// // Done auditing, reclaim journal space
+/// let v2 = config.snapshot();
+/// config.release_before(v2);
+/// assert!(config.at(v1).is_none());
+/// ```
+#[derive(Default, Clone)]
+pub struct SgVersionedMap<K: Ord, V, const N: usize, const J: usize> {
+    map: SgMap<K, V, N>,
+
+    // (Key, version) -> key's value when that version was taken, `None` if absent
+    journal: SgMap<(K, u64), Option<V>, J>,
+
+    // Readable versions are `oldest_version..next_version`
+    oldest_version: u64,
+    next_version: u64,
+}
+
+impl<K: Ord + Clone, V: Clone, const N: usize, const J: usize> SgVersionedMap<K, V, N, J> {
+    /// Makes a new, empty `SgVersionedMap`.
+    pub fn new() -> Self {
+        SgVersionedMap {
+            map: SgMap::new(),
+            journal: SgMap::new(),
+            oldest_version: 0,
+            next_version: 0,
+        }
+    }
+
+    /// Number of live key-value pairs.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the live map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maximum number of live key-value pairs, e.g. `N`.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Number of journal records held for snapshots.
+    pub fn journal_len(&self) -> usize {
+        self.journal.len()
+    }
+
+    /// Remove all live elements and release every snapshot.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.journal.clear();
+        self.oldest_version = self.next_version;
+    }
+
+    /// Capture the live map's current contents, returning a handle for reading them later via [`at`][Self::at].
+    pub fn snapshot(&mut self) -> Version {
+        let version = Version(self.next_version);
+        self.next_version += 1;
+        version
+    }
+
+    /// Read-only view of a snapshot's contents.
+    /// Returns `None` if the snapshot was released, or dropped to make room in the journal.
+    pub fn at(&self, version: Version) -> Option<VersionView<'_, K, V, N, J>> {
+        match (self.oldest_version..self.next_version).contains(&version.0) {
+            true => Some(VersionView {
+                table: self,
+                version: version.0,
+            }),
+            false => None,
+        }
+    }
+
+    /// Release every snapshot older than `version`, reclaiming their journal records.
+    pub fn release_before(&mut self, version: Version) {
+        self.oldest_version = self.oldest_version.max(version.0.min(self.next_version));
+
+        let oldest_version = self.oldest_version;
+        self.journal
+            .retain(|(_, journaled_version), _| *journaled_version >= oldest_version);
+    }
+
+    /// Insert a key-value pair into the live map, returning the old value if the key was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is new and the live map is full.
+    /// Use [`try_insert`][SgVersionedMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.try_insert(key, val) {
+            Ok(opt_old) => opt_old,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Insert a key-value pair into the live map, returning the old value if the key was present.
+    /// Returns `Err`, leaving the map unchanged, if the key is new and the live map is full.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        if !self.map.contains_key(&key) && (self.map.len() >= self.map.max_len()) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        self.journal_prior(&key);
+        Ok(self.map.insert(key, val))
+    }
+
+    /// Remove a key from the live map, returning its value if present.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let key = self.map.get_key_value(key)?.0.clone();
+        self.journal_prior(&key);
+        self.map.remove::<K>(&key)
+    }
+
+    /// Get a key's live value.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Get a mutable reference to a key's live value.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let key = self.map.get_key_value(key)?.0.clone();
+        self.journal_prior(&key);
+        self.map.get_mut::<K>(&key)
+    }
+
+    /// Returns `true` if the live map contains the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Gets an iterator over the live entries, sorted by key.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator + '_ {
+        self.map.iter()
+    }
+
+    // Journal a key's live value for the latest snapshot, unless already journaled since it was taken.
+    // If the journal is full, the oldest snapshots are dropped first.
+    fn journal_prior(&mut self, key: &K) {
+        while self.oldest_version < self.next_version {
+            let record_key = (key.clone(), self.next_version - 1);
+            if self.journal.contains_key(&record_key) {
+                return;
+            }
+
+            if self.journal.len() < self.journal.max_len() {
+                let prior = self.map.get(key).cloned();
+                self.journal.insert(record_key, prior);
+                return;
+            }
+
+            self.drop_oldest();
+        }
+    }
+
+    // Drop the oldest journaled version's records, and every snapshot that relied on them.
+    fn drop_oldest(&mut self) {
+        let oldest_journaled = self
+            .journal
+            .keys()
+            .map(|(_, version)| *version)
+            .min()
+            .unwrap_or(self.next_version - 1);
+
+        self.release_before(Version(oldest_journaled + 1));
+    }
+}
+
+impl<K, V, const N: usize, const J: usize> Debug for SgVersionedMap<K, V, N, J>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
+
+/// Read-only view of an [`SgVersionedMap`] snapshot, see [`SgVersionedMap::at`].
+pub struct VersionView<'a, K: Ord, V, const N: usize, const J: usize> {
+    table: &'a SgVersionedMap<K, V, N, J>,
+    version: u64,
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, const J: usize> VersionView<'a, K, V, N, J> {
+    /// Get a key's value as of the snapshot.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    /// Journal records are keyed by owned `K`, found via the live map in `O(log n)`,
+    /// or, for a key removed since the snapshot, via a scan of the journal in `O(J)`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let table = self.table;
+        let owned_key = match table.map.get_key_value(key) {
+            Some((live_key, _)) => live_key,
+            None => table
+                .journal
+                .keys()
+                .map(|(journaled_key, _)| journaled_key)
+                .find(|journaled_key| (*journaled_key).borrow() == key)?,
+        };
+
+        let opt_record = table
+            .journal
+            .range((owned_key.clone(), self.version)..)
+            .next()
+            .filter(|((journaled_key, _), _)| journaled_key == owned_key);
+
+        match opt_record {
+            Some((_, prior)) => prior.as_ref(),
+            None => table.map.get(key),
+        }
+    }
+
+    /// Returns `true` if the key was present as of the snapshot.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Number of key-value pairs as of the snapshot.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns `true` if the snapshot contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Gets an iterator over the entries as of the snapshot, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        let version = self.version;
+        let mut live = self.table.map.iter().peekable();
+        let mut journal = self
+            .table
+            .journal
+            .iter()
+            .filter(move |((_, journaled_version), _)| *journaled_version >= version)
+            .peekable();
+
+        // Merge live entries with journaled ones, which take precedence
+        iter::from_fn(move || loop {
+            let opt_journal_key = journal.peek().map(|((key, _), _)| key);
+            match (live.peek(), opt_journal_key) {
+                (None, None) => return None,
+                (Some((live_key, _)), Some(journal_key)) if *live_key < journal_key => {
+                    return live.next()
+                }
+                (Some(_), None) => return live.next(),
+                (_, Some(_)) => {
+                    // A key's earliest qualifying record holds its value as of the snapshot
+                    let ((key, _), prior) = journal.next()?;
+                    while journal
+                        .peek()
+                        .is_some_and(|((next_key, _), _)| next_key == key)
+                    {
+                        journal.next();
+                    }
+                    if live.peek().is_some_and(|(live_key, _)| *live_key == key) {
+                        live.next();
+                    }
+
+                    if let Some(val) = prior {
+                        return Some((key, val));
+                    }
+                }
+            }
+        })
+    }
+}
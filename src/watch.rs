@@ -0,0 +1,243 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::ops::{Bound, RangeBounds};
+
+use crate::map::SgMap;
+use crate::map_types::{Iter, Range};
+use crate::tree::SgError;
+
+/// Change to an entry within a watched range, see [`SgWatchMap::watch`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum WatchEvent<'a, V> {
+    /// New entry inserted.
+    Inserted(&'a V),
+
+    /// Existing entry's value replaced.
+    Updated {
+        /// Previous value.
+        old: &'a V,
+        /// Current value.
+        new: &'a V,
+    },
+
+    /// Entry removed.
+    Removed(&'a V),
+}
+
+// Manual impls, events only hold references so don't require `V: Clone`
+impl<V> Clone for WatchEvent<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for WatchEvent<'_, V> {}
+
+/// Watch callback, invoked with the changed key and event.
+pub type WatchFn<K, V> = fn(&K, WatchEvent<'_, V>);
+
+/// Handle to a registered watch, for [`SgWatchMap::unwatch`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct WatchId(usize);
+
+// Registered key range and callback.
+struct Watch<K, V> {
+    range: (Bound<K>, Bound<K>),
+    callback: WatchFn<K, V>,
+}
+
+/// Ordered map with range-scoped change notifications: up to `W` watches, each a key range and callback.
+///
+/// Every insertion, update, and removal of an entry within a watched range invokes that watch's callback,
+/// after the change is applied.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use scapegoat::{SgWatchMap, WatchEvent};
+///
+/// static NET_CHANGES: AtomicU32 = AtomicU32::new(0);
+///
+/// let mut config = SgWatchMap::<&str, u32, 10, 2>::new();
+/// config.watch("net."..="net.~", |_key, event| {
+///     if let WatchEvent::Updated { .. } = event {
+///         NET_CHANGES.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }).unwrap();
+///
+/// config.insert("net.mtu", 1500);
+/// config.insert("net.mtu", 9000);
+/// config.insert("log.level", 3);
+/// config.insert("log.level", 4);
+///
+/// assert_eq!(NET_CHANGES.load(Ordering::Relaxed), 1);
+/// ```
+pub struct SgWatchMap<K: Ord, V, const N: usize, const W: usize> {
+    map: SgMap<K, V, N>,
+    watches: [Option<Watch<K, V>>; W],
+}
+
+impl<K: Ord + Clone, V, const N: usize, const W: usize> SgWatchMap<K, V, N, W> {
+    /// Makes a new, empty `SgWatchMap`.
+    pub fn new() -> Self {
+        SgWatchMap {
+            map: SgMap::new(),
+            watches: core::array::from_fn(|_| None),
+        }
+    }
+
+    /// Register `callback` for changes to entries within `range`.
+    /// Returns `Err` if all `W` watch slots are in use.
+    pub fn watch<R: RangeBounds<K>>(
+        &mut self,
+        range: R,
+        callback: WatchFn<K, V>,
+    ) -> Result<WatchId, SgError> {
+        let (idx, slot) = self
+            .watches
+            .iter_mut()
+            .enumerate()
+            .find(|(_, slot)| slot.is_none())
+            .ok_or(SgError::StackCapacityExceeded)?;
+
+        *slot = Some(Watch {
+            range: (range.start_bound().cloned(), range.end_bound().cloned()),
+            callback,
+        });
+
+        Ok(WatchId(idx))
+    }
+
+    /// Unregister a watch, returning `true` if it was registered.
+    pub fn unwatch(&mut self, id: WatchId) -> bool {
+        self.watches
+            .get_mut(id.0)
+            .and_then(|slot| slot.take())
+            .is_some()
+    }
+
+    /// Number of registered watches.
+    pub fn watch_count(&self) -> usize {
+        self.watches.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Total capacity, e.g. maximum number of map pairs.
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Insert a key-value pair into the map, returning the old value if the key was present.
+    /// Panics if capacity is exceeded.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let notify_key = key.clone();
+        let opt_old = self.map.insert(key, val);
+        self.notify_upsert(&notify_key, opt_old.as_ref());
+        opt_old
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if capacity is exceeded, else the `Ok` contains the old value, if any.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        let notify_key = key.clone();
+        let opt_old = self.map.try_insert(key, val)?;
+        self.notify_upsert(&notify_key, opt_old.as_ref());
+        Ok(opt_old)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (k, v) = self.map.remove_entry(key)?;
+        self.notify(&k, WatchEvent::Removed(&v));
+        Some(v)
+    }
+
+    /// Clears the map, removing all elements and notifying watches of each removal.
+    pub fn clear(&mut self) {
+        while let Some((k, v)) = self.map.pop_first() {
+            self.notify(&k, WatchEvent::Removed(&v));
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V, N> {
+        self.map.iter()
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the map.
+    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V, N>
+    where
+        T: Ord + ?Sized,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        self.map.range(range)
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    fn notify_upsert(&self, key: &K, opt_old: Option<&V>) {
+        if let Some(new) = self.map.get(key) {
+            match opt_old {
+                Some(old) => self.notify(key, WatchEvent::Updated { old, new }),
+                None => self.notify(key, WatchEvent::Inserted(new)),
+            }
+        }
+    }
+
+    fn notify(&self, key: &K, event: WatchEvent<'_, V>) {
+        self.watches
+            .iter()
+            .flatten()
+            .filter(|watch| watch.range.contains(key))
+            .for_each(|watch| (watch.callback)(key, event));
+    }
+}
+
+impl<K: Ord + Clone, V, const N: usize, const W: usize> Default for SgWatchMap<K, V, N, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize, const W: usize> Debug for SgWatchMap<K, V, N, W>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
@@ -1,8 +1,8 @@
 use std::collections::BTreeMap;
 use std::iter::FromIterator;
-use std::ops::Bound::{Excluded, Included};
+use std::ops::Bound::{Excluded, Included, Unbounded};
 
-use scapegoat::{sgmap, SgError, SgMap};
+use scapegoat::{sgmap, ArenaLayout, CapacityBound, MaintenanceBudget, SgError, SgMap};
 
 use rand::Rng;
 
@@ -111,7 +111,7 @@ fn test_basic_map_functionality() {
 #[test]
 fn test_map_from_iter() {
     let key_val_tuples = vec![(1, "1"), (2, "2"), (3, "3")];
-    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples.into_iter());
+    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples);
 
     assert!(sgm.len() == 3);
     assert_eq!(
@@ -130,7 +130,7 @@ fn test_map_from_iter_panic() {
 #[test]
 fn test_map_iter() {
     let key_val_tuples = vec![(1, "1"), (2, "2"), (3, "3")];
-    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples.into_iter());
+    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples);
     let mut sgm_iter = sgm.iter();
 
     assert_eq!(sgm_iter.next(), Some((&1, &"1")));
@@ -139,6 +139,74 @@ fn test_map_iter() {
     assert_eq!(sgm_iter.next(), None);
 }
 
+#[test]
+fn test_map_iter_ranked() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    assert_eq!(sgm.iter_ranked().next(), None);
+
+    for k in (0..DEFAULT_CAPACITY).rev() {
+        sgm.insert(k * 10, k);
+    }
+
+    let mut ranked = sgm.iter_ranked();
+    assert_eq!(ranked.len(), DEFAULT_CAPACITY);
+    for (rank, k, v) in ranked.by_ref().take(3) {
+        assert_eq!((*k, *v), (rank * 10, rank));
+    }
+
+    // Ranks from both ends stay consistent when interleaved
+    assert_eq!(ranked.next_back(), Some((9, &90, &9)));
+    assert_eq!(ranked.next(), Some((3, &30, &3)));
+    assert_eq!(ranked.len(), 5);
+    assert!(ranked
+        .rev()
+        .map(|(rank, _, _)| rank)
+        .eq((4..(DEFAULT_CAPACITY - 1)).rev()));
+
+    // Matches `enumerate`, in both directions
+    assert!(sgm
+        .iter_ranked()
+        .map(|(rank, k, v)| (rank, (k, v)))
+        .eq(sgm.iter().enumerate()));
+    assert!(sgm
+        .iter_ranked()
+        .rev()
+        .all(|(rank, k, _)| sgm.range(..k).count() == rank));
+}
+
+#[test]
+fn test_map_find_by_value() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    assert_eq!(sgm.find_by_value(|_| true), None);
+
+    for k in 0..DEFAULT_CAPACITY {
+        sgm.insert(k, k % 3);
+    }
+
+    // Stops at first match
+    let mut visited = 0;
+    assert_eq!(
+        sgm.find_by_value(|v| {
+            visited += 1;
+            *v == 2
+        }),
+        Some((&2, &2))
+    );
+    assert_eq!(visited, 3);
+
+    // Lazy, captures local state
+    let target = 1;
+    let mut matches = sgm.find_all_by_value(|v| *v == target);
+    assert_eq!(matches.next(), Some((&1, &1)));
+    assert_eq!(matches.next(), Some((&4, &1)));
+    assert_eq!(
+        sgm.find_all_by_value(|v| *v == 0)
+            .map(|(k, _)| *k)
+            .collect::<Vec<_>>(),
+        [0, 3, 6, 9]
+    );
+}
+
 #[test]
 fn test_map_iter_mut() {
     let key_val_tuples = vec![
@@ -152,7 +220,7 @@ fn test_map_iter_mut() {
         ("c", 3),
     ];
 
-    let mut sgm = SgMap::<_, _, 8>::from_iter(key_val_tuples.into_iter());
+    let mut sgm = SgMap::<_, _, 8>::from_iter(key_val_tuples);
     assert_eq!(sgm.len(), 8);
     assert_eq!(sgm.first_key_value(), Some((&"a", &1)));
     assert_eq!(sgm.last_key_value(), Some((&"h", &8)));
@@ -212,6 +280,117 @@ fn test_map_iter_mut_rand() {
     assert!(result_vec.iter().all(|(_, v)| *v == 25));
 }
 
+#[test]
+fn test_map_get_many_mut() {
+    let mut sgm: SgMap<usize, usize, DEFAULT_CAPACITY> =
+        (0..DEFAULT_CAPACITY).map(|k| (k, k)).collect();
+
+    // Removals and re-insertions scatter arena indexes relative to key order
+    sgm.remove(&3);
+    sgm.remove(&7);
+    sgm.insert(12, 12);
+    sgm.insert(3, 3);
+
+    let [a, b, c, d] = sgm.get_many_mut([&9, &7, &0, &3]);
+    assert_eq!(
+        (a.as_deref(), b, c.as_deref(), d.as_deref()),
+        (Some(&9), None, Some(&0), Some(&3))
+    );
+    *a.unwrap() += 100;
+    *c.unwrap() += 100;
+    *d.unwrap() += 100;
+
+    assert_eq!(sgm[&9], 109);
+    assert_eq!(sgm[&0], 100);
+    assert_eq!(sgm[&3], 103);
+    assert_eq!(sgm[&12], 12);
+
+    let [] = sgm.get_many_mut::<usize, 0>([]);
+    let mut empty = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    assert_eq!(empty.get_many_mut([&1, &2]), [None, None]);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate keys passed to `get_many_mut`.")]
+fn test_map_get_many_mut_dup_panic() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    sgm.insert(1, 1);
+    sgm.get_many_mut([&1, &2, &1]);
+}
+
+#[test]
+fn test_map_try_get_many_mut() {
+    let mut sgm: SgMap<usize, usize, DEFAULT_CAPACITY> = (0..10).map(|k| (k, k)).collect();
+
+    assert_eq!(
+        sgm.try_get_many_mut([&1, &2, &1]),
+        Err(SgError::DuplicateKeys)
+    );
+    assert_eq!(sgm.try_get_many_mut([&4, &4]), Err(SgError::DuplicateKeys));
+
+    let [a, b] = sgm.try_get_many_mut([&2, &20]).unwrap();
+    assert_eq!(b, None);
+    *a.unwrap() += 100;
+    assert_eq!(sgm[&2], 102);
+
+    let [] = sgm.try_get_many_mut::<usize, 0>([]).unwrap();
+}
+
+#[test]
+fn test_map_non_default_values() {
+    use std::num::NonZeroU32;
+
+    // No `Default` impl
+    #[derive(Debug, PartialEq)]
+    struct Handle(NonZeroU32);
+
+    let handle = |h| Handle(NonZeroU32::new(h).unwrap());
+    let mut sgm = SgMap::<u32, Handle, DEFAULT_CAPACITY>::new();
+    for k in 1..=DEFAULT_CAPACITY as u32 {
+        sgm.insert(k, handle(k));
+    }
+
+    assert_eq!(sgm.remove(&3), Some(handle(3)));
+    assert_eq!(sgm.insert(11, handle(11)), None);
+    assert_eq!(sgm.insert(11, handle(12)), Some(handle(11)));
+    sgm.retain(|k, _| k % 2 == 1);
+    assert!(sgm.into_values().map(|v| v.0.get()).eq([1, 5, 7, 9, 12]));
+}
+
+#[test]
+fn test_map_non_default_keys() {
+    use scapegoat::SgSet;
+    use std::num::NonZeroU32;
+
+    // No `Default` impls
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    struct SensorId(NonZeroU32);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    enum Zone {
+        North,
+        South,
+    }
+
+    let id = |i| SensorId(NonZeroU32::new(i).unwrap());
+    let mut sgm = SgMap::<SensorId, Zone, DEFAULT_CAPACITY>::new();
+    for i in 1..=DEFAULT_CAPACITY as u32 {
+        let zone = if i % 2 == 0 { Zone::North } else { Zone::South };
+        sgm.insert(id(i), zone);
+    }
+
+    assert_eq!(sgm.remove_entry(&id(4)), Some((id(4), Zone::North)));
+    assert_eq!(sgm.pop_first(), Some((id(1), Zone::South)));
+    assert_eq!(sgm.insert(id(11), Zone::North), None);
+    assert_eq!(sgm.first_key(), Some(&id(2)));
+    assert_eq!(sgm.last_key(), Some(&id(11)));
+
+    let mut sgs: SgSet<Zone, 2> = sgm.values().copied().collect();
+    assert_eq!(sgs.replace(Zone::South), Some(Zone::South));
+    assert_eq!(sgs.take(&Zone::North), Some(Zone::North));
+    assert!(sgs.into_iter().eq([Zone::South]));
+}
+
 #[test]
 fn test_map_append() {
     let mut a = SgMap::new();
@@ -250,6 +429,33 @@ fn test_map_insert_fallible() {
     assert_eq!(a.try_insert(4, "4"), Err(SgError::StackCapacityExceeded));
 }
 
+#[test]
+fn test_map_try_insert_std() {
+    let mut a = SgMap::<_, _, 2>::new();
+
+    *a.try_insert_std(1, String::from("one")).unwrap() += "!";
+    assert!(a.try_insert_std(2, String::from("two")).is_ok());
+
+    // Rejected value handed back, existing entry untouched
+    let err = a.try_insert_std(1, String::from("uno")).unwrap_err();
+    assert_eq!(err.value, "uno");
+    assert_eq!(err.entry.get(), "one!");
+    assert_eq!(
+        err.to_string(),
+        "failed to insert \"uno\", key 1 already exists with value \"one!\""
+    );
+    assert_eq!(a.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Stack-storage capacity exceeded!")]
+fn test_map_try_insert_std_panic() {
+    let mut a = SgMap::<_, _, 1>::new();
+    assert!(a.try_insert_std(1, 1).is_ok());
+    assert!(a.try_insert_std(1, 1).is_err());
+    let _ = a.try_insert_std(2, 2);
+}
+
 #[test]
 fn test_map_append_fallible() {
     let mut a = SgMap::<_, _, 6>::new();
@@ -295,6 +501,36 @@ fn test_map_append_fallible() {
     );
 }
 
+#[test]
+fn test_map_append_keeps_settings() {
+    let mut a = SgMap::<_, _, 10>::new();
+    a.set_auto_rebalance(false);
+    assert!(a.set_max_len(5).is_ok());
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    assert!(a.set_rebal_param(0.9, 1.0).is_ok());
+    let a_param = a.rebal_param();
+
+    let mut b = SgMap::<_, _, 10>::new();
+    b.set_arena_layout(ArenaLayout::BreadthFirst);
+    b.extend((0..4).map(|k| (k, k)));
+
+    // Empty `a` takes `b`'s entries wholesale, each map keeps its own settings
+    a.append(&mut b);
+    assert!(b.is_empty());
+    assert!(a.keys().copied().eq(0..4));
+    assert!(!a.auto_rebalance());
+    assert_eq!(a.max_len(), 5);
+    assert_eq!(a.rebal_param(), a_param);
+    assert_eq!(a.arena_layout(), ArenaLayout::InOrder);
+    assert!(b.auto_rebalance());
+    assert_eq!(b.max_len(), 10);
+    assert_eq!(b.arena_layout(), ArenaLayout::BreadthFirst);
+    assert!(a.validate().is_ok() && b.validate().is_ok());
+
+    assert!(a.maintenance(MaintenanceBudget::unlimited()));
+    assert!(a.validate().is_ok());
+}
+
 #[should_panic]
 #[test]
 fn test_map_insert_panic() {
@@ -343,6 +579,58 @@ fn test_map_range_mut() {
     assert_eq!(map["e"], 10);
 }
 
+#[test]
+fn test_map_range_bounds() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::ops::Bound;
+
+    let mut rng = SmallRng::seed_from_u64(0x7a9e);
+    let mut sgm = SgMap::<u32, u32, 256>::new();
+    let mut btm = BTreeMap::new();
+
+    for _ in 0..200 {
+        let k = rng.gen_range(0, 400);
+        sgm.insert(k, k);
+        btm.insert(k, k);
+    }
+
+    // Holes, so searches also land between keys
+    for k in (0..400).step_by(7) {
+        sgm.remove(&k);
+        btm.remove(&k);
+    }
+
+    let bound = |rng: &mut SmallRng, k: u32| match rng.gen_range(0, 3) {
+        0 => Bound::Included(k),
+        1 => Bound::Excluded(k),
+        _ => Bound::Unbounded,
+    };
+
+    for _ in 0..500 {
+        let (a, b) = (rng.gen_range(0, 420), rng.gen_range(0, 420));
+        let range = (bound(&mut rng, a.min(b)), bound(&mut rng, a.max(b)));
+        if let (Bound::Excluded(s), Bound::Excluded(e)) = range {
+            if s == e {
+                continue;
+            }
+        }
+
+        assert!(sgm.range(range).eq(btm.range(range)));
+        assert!(sgm.range(range).rev().eq(btm.range(range).rev()));
+
+        for (_, v) in sgm.range_mut(range) {
+            *v += 1;
+        }
+        for (_, v) in btm.range_mut(range) {
+            *v += 1;
+        }
+        assert!(sgm.range_mut(range).rev().eq(btm.range_mut(range).rev()));
+    }
+
+    assert!(sgm.iter().eq(btm.iter()));
+}
+
 #[should_panic(expected = "range start is greater than range end in BTreeMap")]
 #[test]
 fn test_btree_map_range_panic_1() {
@@ -466,3 +754,1734 @@ fn test_map_macro_panic() {
         "d" => 0x64, // Capacity exceeded!
     };
 }
+
+#[test]
+fn test_map_capacity_bound() {
+    fn fill<C: CapacityBound>(c: &mut C, mut insert: impl FnMut(&mut C, usize)) -> usize {
+        let mut i = 0;
+        while !c.is_full() {
+            insert(c, i);
+            i += 1;
+        }
+        i
+    }
+
+    let mut map = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    assert!(CapacityBound::is_empty(&map));
+    assert_eq!(
+        <SgMap<usize, usize, DEFAULT_CAPACITY>>::CAPACITY,
+        DEFAULT_CAPACITY
+    );
+
+    assert!(map.set_max_len(4).is_ok());
+    assert_eq!(
+        fill(&mut map, |m, i| {
+            m.insert(i, i);
+        }),
+        4
+    );
+    assert_eq!(CapacityBound::len(&map), 4);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_map_with_capacity() {
+    const RUNTIME_CAPACITY: usize = 5_000;
+
+    // Heap-only storage, inline capacity zero
+    let mut sgm = SgMap::<usize, usize, 0>::with_capacity(RUNTIME_CAPACITY);
+    let mut btm = BTreeMap::new();
+    assert_eq!(sgm.capacity(), RUNTIME_CAPACITY);
+    assert_eq!(sgm.max_len(), RUNTIME_CAPACITY);
+
+    let mut rng = rand::thread_rng();
+    while !sgm.is_full() {
+        let key = rng.gen_range(0, 4 * RUNTIME_CAPACITY);
+        assert_eq!(sgm.insert(key, key * 2), btm.insert(key, key * 2));
+    }
+
+    assert_eq!(sgm.len(), RUNTIME_CAPACITY);
+    assert!(sgm.iter().eq(btm.iter()));
+    assert_eq!(
+        sgm.try_insert(4 * RUNTIME_CAPACITY, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    for key in btm.keys().step_by(3).copied().collect::<Vec<_>>() {
+        assert_eq!(sgm.remove(&key), btm.remove(&key));
+    }
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Split half keeps the runtime capacity
+    let split_key = *btm.keys().nth(btm.len() / 2).unwrap();
+    let sgm_hi = sgm.split_off(&split_key);
+    let btm_hi = btm.split_off(&split_key);
+    assert_eq!(sgm_hi.capacity(), RUNTIME_CAPACITY);
+    assert!(sgm.iter().eq(btm.iter()));
+    assert!(sgm_hi.iter().eq(btm_hi.iter()));
+
+    // Inline storage spills over
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::with_capacity(2 * DEFAULT_CAPACITY);
+    (0..(2 * DEFAULT_CAPACITY)).for_each(|i| assert_eq!(sgm.try_insert(i, i), Ok(None)));
+    assert!(sgm.is_full());
+    assert!(sgm.keys().copied().eq(0..(2 * DEFAULT_CAPACITY)));
+}
+
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "Max item capacity (0xfffe) exceeded!")]
+#[test]
+fn test_map_with_capacity_panic() {
+    let _ = SgMap::<usize, usize, 0>::with_capacity(usize::from(u16::MAX));
+}
+
+#[test]
+fn test_map_try_from_results() {
+    #[derive(Debug, PartialEq)]
+    enum ParseErr {
+        Item(usize),
+        Capacity(SgError),
+    }
+
+    impl From<SgError> for ParseErr {
+        fn from(err: SgError) -> Self {
+            ParseErr::Capacity(err)
+        }
+    }
+
+    let parse = |n: usize| match n % 7 {
+        0 => Err(ParseErr::Item(n)),
+        _ => Ok((n, n * 2)),
+    };
+
+    // Duplicate keys don't consume capacity
+    let map = SgMap::<usize, usize, 3>::try_from_results([1, 2, 1, 3].iter().map(|&n| parse(n)));
+    assert_eq!(
+        map.unwrap().into_iter().collect::<Vec<_>>(),
+        vec![(1, 2), (2, 4), (3, 6)]
+    );
+
+    assert_eq!(
+        SgMap::<usize, usize, DEFAULT_CAPACITY>::try_from_results((1..20).map(parse)),
+        Err(ParseErr::Item(7))
+    );
+
+    assert_eq!(
+        SgMap::<usize, usize, 4>::try_from_results((1..7).map(parse)),
+        Err(ParseErr::Capacity(SgError::StackCapacityExceeded))
+    );
+}
+
+#[test]
+fn test_atomic_map() {
+    use scapegoat::SgAtomicMap;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    let mut map = SgAtomicMap::<usize, AtomicUsize, DEFAULT_CAPACITY>::new();
+    for k in 0..DEFAULT_CAPACITY {
+        assert_eq!(map.insert(k, k), None);
+    }
+    assert_eq!(
+        map.try_insert(DEFAULT_CAPACITY, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(map.try_insert(0, 100), Ok(Some(0)));
+
+    // Concurrent value updates through a shared reference
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for k in 0..DEFAULT_CAPACITY {
+                    map.get(&k).unwrap().fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.load(&0, Ordering::SeqCst), Some(104));
+    assert_eq!(map.swap(&1, 0, Ordering::SeqCst), Some(5));
+    assert!(!map.store(&DEFAULT_CAPACITY, 0, Ordering::SeqCst));
+    assert_eq!(map.remove(&2), Some(6));
+    assert_eq!(map.len(), DEFAULT_CAPACITY - 1);
+
+    let flags = SgAtomicMap::<&str, AtomicBool, 2>::default();
+    assert!(flags.is_empty());
+    assert_eq!(flags.load("ready", Ordering::SeqCst), None);
+}
+
+#[test]
+fn test_map_copied_iters() {
+    let mut sgm = SgMap::<u16, u32, DEFAULT_CAPACITY>::new();
+    let mut btm = BTreeMap::new();
+
+    for k in [5, 3, 9, 1, 7] {
+        sgm.insert(k, u32::from(k) * 10);
+        btm.insert(k, u32::from(k) * 10);
+    }
+
+    assert!(sgm.keys_copied().eq(btm.keys().copied()));
+    assert!(sgm.values_copied().eq(btm.values().copied()));
+    assert_eq!(sgm.values_copied().len(), btm.len());
+}
+
+#[test]
+fn test_map_persist_image() {
+    use scapegoat::persist::{PersistError, FORMAT_VERSION, HEADER_LEN};
+
+    let sgm = SgMap::<u16, bool, DEFAULT_CAPACITY>::from_iter([(3, true), (1, false), (2, true)]);
+    let mut buf = [0; 64];
+    let len = sgm.to_bytes(&mut buf).unwrap();
+    assert_eq!(len, HEADER_LEN + 3 * 3);
+
+    // Round trip
+    assert_eq!(
+        SgMap::<u16, bool, DEFAULT_CAPACITY>::from_bytes(&buf[..len]),
+        Ok(sgm.clone())
+    );
+
+    // Encode into a short buffer
+    assert_eq!(
+        sgm.to_bytes(&mut buf[..(len - 1)]),
+        Err(PersistError::BufferTooSmall {
+            needed: len,
+            available: len - 1
+        })
+    );
+
+    // Truncated image
+    assert!(matches!(
+        SgMap::<u16, bool, DEFAULT_CAPACITY>::from_bytes(&buf[..(len - 1)]),
+        Err(PersistError::BufferTooSmall { .. })
+    ));
+
+    // Capacity too small for image
+    assert_eq!(
+        SgMap::<u16, bool, 2>::from_bytes(&buf),
+        Err(PersistError::CapacityExceeded {
+            image_capacity: DEFAULT_CAPACITY,
+            image_len: 3,
+            capacity: 2
+        })
+    );
+
+    // Key and value types differ
+    assert_eq!(
+        SgMap::<u32, bool, DEFAULT_CAPACITY>::from_bytes(&buf),
+        Err(PersistError::ItemSizeMismatch {
+            found: (2, 1),
+            expected: (4, 1)
+        })
+    );
+
+    // Invalid value encoding
+    let mut corrupt = buf;
+    corrupt[HEADER_LEN + 2] = 2;
+    assert_eq!(
+        SgMap::<u16, bool, DEFAULT_CAPACITY>::from_bytes(&corrupt),
+        Err(PersistError::InvalidItem { idx: 0 })
+    );
+
+    // Out-of-order keys
+    let mut corrupt = buf;
+    corrupt[HEADER_LEN + 3] = 0;
+    assert_eq!(
+        SgMap::<u16, bool, DEFAULT_CAPACITY>::from_bytes(&corrupt),
+        Err(PersistError::InvalidItem { idx: 1 })
+    );
+
+    // Future format version
+    let mut future = buf;
+    future[4] = FORMAT_VERSION + 1;
+    assert_eq!(
+        SgMap::<u16, bool, DEFAULT_CAPACITY>::from_bytes(&future),
+        Err(PersistError::UnsupportedVersion {
+            found: FORMAT_VERSION + 1,
+            supported: FORMAT_VERSION
+        })
+    );
+
+    // Foreign data
+    assert_eq!(
+        SgMap::<u16, bool, DEFAULT_CAPACITY>::from_bytes(&[0; 64]),
+        Err(PersistError::BadMagic)
+    );
+}
+
+#[test]
+fn test_map_inline_str_keys() {
+    use scapegoat::InlineStr;
+    use std::convert::TryFrom;
+
+    let words = [
+        "delta", "alpha", "", "charlie", "bravo", "alphabet", "échelle",
+    ];
+    let mut sgm = SgMap::<InlineStr<8>, usize, DEFAULT_CAPACITY>::new();
+    let mut btm = BTreeMap::new();
+
+    for (i, w) in words.iter().enumerate() {
+        sgm.insert(InlineStr::try_from(*w).unwrap(), i);
+        btm.insert(w.to_string(), i);
+    }
+
+    assert!(sgm
+        .iter()
+        .map(|(k, v)| (k.as_str(), *v))
+        .eq(btm.iter().map(|(k, v)| (k.as_str(), *v))));
+    assert_eq!(sgm.get("charlie"), Some(&3));
+    assert_eq!(sgm.remove("alpha"), Some(1));
+    assert!(!sgm.contains_key("alpha"));
+    assert!(sgm.contains_key("alphabet"));
+
+    let mut s = InlineStr::<8>::try_from("alpha").unwrap();
+    assert_eq!(s.try_push_str("bet"), Ok(()));
+    assert_eq!(s, "alphabet");
+    assert_eq!(s.try_push_str("!"), Err(SgError::StackCapacityExceeded));
+    assert_eq!(s.len(), 8);
+    assert_eq!(
+        InlineStr::<8>::try_from("alphabets"),
+        Err(SgError::StackCapacityExceeded)
+    );
+}
+
+#[test]
+fn test_map_persist_wal() {
+    use scapegoat::persist::{PersistError, Wal, WAL_HEADER_LEN};
+
+    type Map = SgMap<u16, u16, DEFAULT_CAPACITY>;
+
+    let record_len = Wal::<u16, u16>::record_len();
+    let mut snapshot = [0; 128];
+    let mut journal = vec![0; WAL_HEADER_LEN + 4 * record_len];
+    let mut sgm = Map::new();
+
+    // Fill the log
+    let mut wal = Wal::new(&mut journal).unwrap();
+    assert_eq!(wal.slots(), 4);
+    for k in 0..4 {
+        assert_eq!(wal.insert(&mut sgm, k, k * 10), Ok(None));
+    }
+    assert_eq!(
+        wal.insert(&mut sgm, 4, 40),
+        Err(PersistError::LogFull { slots: 4 })
+    );
+    assert!(!sgm.contains_key(&4));
+
+    // Snapshot, checkpoint, and wrap around the ring
+    sgm.to_bytes(&mut snapshot).unwrap();
+    wal.checkpoint();
+    assert_eq!(wal.pending(), 0);
+    assert_eq!(wal.remove(&mut sgm, &0), Ok(Some(0)));
+    assert_eq!(wal.remove(&mut sgm, &9), Ok(None));
+    assert_eq!(wal.insert(&mut sgm, 1, 11), Ok(Some(10)));
+    assert_eq!(wal.pending(), 2);
+
+    let mut recovered = Map::from_bytes(&snapshot).unwrap();
+    assert_eq!(recovered.recover(&journal), Ok(2));
+    assert_eq!(recovered, sgm);
+
+    // Resume and log more
+    let mut wal = Wal::open(&mut journal).unwrap();
+    assert_eq!(wal.pending(), 2);
+    assert_eq!(wal.insert(&mut sgm, 5, 50), Ok(None));
+
+    // Torn final record is discarded
+    let last = WAL_HEADER_LEN + 3 * record_len;
+    journal[last + 5] ^= 0xff;
+    let mut recovered = Map::from_bytes(&snapshot).unwrap();
+    assert_eq!(recovered.recover(&journal), Ok(2));
+    assert!(!recovered.contains_key(&5));
+
+    // Incompatible log
+    let mut small = SgMap::<u16, u16, 2>::new();
+    assert_eq!(
+        SgMap::<u32, u16, DEFAULT_CAPACITY>::new().recover(&journal),
+        Err(PersistError::ItemSizeMismatch {
+            found: (2, 2),
+            expected: (4, 2)
+        })
+    );
+    assert_eq!(small.recover(&[0; 64]), Err(PersistError::BadMagic));
+
+    // Power loss after writing a snapshot, but before checkpointing
+    let mut small = SgMap::<u16, u16, 2>::new();
+    small.insert(1, 10);
+    let mut journal = vec![0; WAL_HEADER_LEN + 4 * record_len];
+    let mut wal = Wal::new(&mut journal).unwrap();
+    assert_eq!(wal.insert(&mut small, 9, 90), Ok(None));
+    assert_eq!(wal.remove(&mut small, &9), Ok(Some(90)));
+    assert_eq!(wal.insert(&mut small, 2, 20), Ok(None));
+    small.to_bytes(&mut snapshot).unwrap();
+
+    let mut recovered = SgMap::<u16, u16, 2>::from_bytes(&snapshot).unwrap();
+    assert_eq!(recovered.recover(&journal), Ok(3));
+    assert_eq!(recovered, small);
+}
+
+#[test]
+fn test_map_persist_pages() {
+    use scapegoat::persist::PersistError;
+
+    type Map = SgMap<u32, u32, DEFAULT_CAPACITY>;
+
+    // 9-byte slots, 2 per page: header page + 5 slot pages
+    let mut flash = [[0xff; 20]; 6];
+    assert_eq!(Map::paged_len::<20>(), Ok(6));
+
+    let mut sgm = Map::new();
+    assert_eq!(sgm.save_pages(&mut flash), Ok(6));
+    assert_eq!(sgm.save_pages(&mut flash), Ok(0));
+
+    for k in 0..DEFAULT_CAPACITY as u32 {
+        sgm.insert(k, k);
+    }
+    assert_eq!(sgm.save_pages(&mut flash), Ok(5));
+
+    // Update and removal only dirty their own page
+    sgm.insert(3, 33);
+    assert_eq!(sgm.save_pages(&mut flash), Ok(1));
+    sgm.remove(&8);
+    assert_eq!(sgm.save_pages(&mut flash), Ok(1));
+    assert_eq!(Map::load_pages(&mut flash), Ok(sgm.clone()));
+
+    // Too few items fit
+    assert_eq!(
+        SgMap::<u32, u32, 4>::load_pages(&mut flash),
+        Err(PersistError::CapacityExceeded {
+            image_capacity: DEFAULT_CAPACITY,
+            image_len: 5,
+            capacity: 4
+        })
+    );
+
+    // Incompatible geometry
+    let mut small_flash = [[0; 20]; 5];
+    assert_eq!(
+        sgm.save_pages(&mut small_flash),
+        Err(PersistError::BufferTooSmall {
+            needed: 120,
+            available: 100
+        })
+    );
+    let mut big_pages = [[0; 40]; 6];
+    assert_eq!(sgm.save_pages(&mut big_pages), Ok(4));
+    let mut big_pages_as_small = [[0; 20]; 6];
+    big_pages_as_small[0].copy_from_slice(&big_pages[0][..20]);
+    assert_eq!(
+        Map::load_pages(&mut big_pages_as_small),
+        Err(PersistError::PageSizeMismatch {
+            found: 40,
+            expected: 20
+        })
+    );
+
+    // Set round trip
+    let sgs = scapegoat::SgSet::<u32, DEFAULT_CAPACITY>::from_iter(sgm.keys().copied());
+    let mut set_flash = [[0; 20]; 4];
+    assert!(sgs.save_pages(&mut set_flash).is_ok());
+    assert_eq!(scapegoat::SgSet::load_pages(&mut set_flash), Ok(sgs));
+}
+
+#[test]
+fn test_direct_map() {
+    use scapegoat::SgDirectMap;
+
+    fn check<const N: usize>(is_direct: bool) {
+        let mut sgm = SgDirectMap::<i8, i32, N>::new();
+        let mut btm = BTreeMap::new();
+        assert_eq!(sgm.is_direct(), is_direct);
+
+        for k in [0, -128, 127, -1, 1, 5, -1] {
+            assert_eq!(
+                sgm.insert(k, i32::from(k) * 2),
+                btm.insert(k, i32::from(k) * 2)
+            );
+        }
+        assert_eq!(sgm.len(), btm.len());
+        assert!(sgm.iter().eq(btm.iter().map(|(k, v)| (*k, v))));
+
+        *sgm.get_mut(&5).unwrap() += 1;
+        assert_eq!(sgm.get(&5), Some(&11));
+        assert_eq!(sgm.remove(&-128), Some(-256));
+        assert_eq!(sgm.remove(&-128), None);
+        assert!(!sgm.contains_key(&-128));
+        assert_eq!(sgm.len(), 5);
+
+        sgm.clear();
+        assert!(sgm.is_empty());
+    }
+
+    check::<256>(true);
+    check::<DEFAULT_CAPACITY>(false);
+
+    let mut sgm = SgDirectMap::<bool, u8, 2>::new();
+    assert!(sgm.is_direct());
+    assert_eq!(sgm.try_insert(true, 1), Ok(None));
+    assert_eq!(sgm.try_insert(false, 0), Ok(None));
+    assert!(sgm.is_full());
+}
+
+#[test]
+fn test_map_fixed_point_keys() {
+    use scapegoat::{Q16_16, Q1_15, UQ16_16};
+
+    let vals = [3.5, -0.25, 0.0, -7.75, 1.0 / 65536.0, 100.125];
+    let mut sgm = SgMap::<Q16_16, usize, DEFAULT_CAPACITY>::new();
+    for (i, v) in vals.iter().enumerate() {
+        sgm.insert(Q16_16::from_f32(*v), i);
+    }
+
+    let mut sorted = vals;
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!(sgm.keys().map(|k| k.to_f32()).eq(sorted.iter().copied()));
+
+    assert_eq!(sgm.get(&1), Some(&4));
+    assert_eq!(sgm.get(&-0x4000), Some(&1));
+    assert_eq!(sgm.remove(&Q16_16::from_int(-8).raw()), None);
+    assert_eq!(Q16_16::from_f32(-7.75).to_int(), -8);
+    assert_eq!(Q16_16::from_f32(1e9), Q16_16::from_raw(i32::MAX));
+
+    assert_eq!(Q1_15::from_f32(-1.0).raw(), i16::MIN);
+    assert_eq!(UQ16_16::from_f32(-1.0).raw(), 0);
+    assert_eq!(f32::from(UQ16_16::from_int(3)), 3.0);
+}
+
+#[test]
+fn test_watch_map() {
+    use scapegoat::{SgWatchMap, WatchEvent};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LOW: [AtomicUsize; 3] = [
+        AtomicUsize::new(0),
+        AtomicUsize::new(0),
+        AtomicUsize::new(0),
+    ];
+    static HIGH: [AtomicUsize; 3] = [
+        AtomicUsize::new(0),
+        AtomicUsize::new(0),
+        AtomicUsize::new(0),
+    ];
+
+    fn tally(counts: &[AtomicUsize; 3], event: WatchEvent<'_, u32>) {
+        let idx = match event {
+            WatchEvent::Inserted(_) => 0,
+            WatchEvent::Updated { old, new } => {
+                assert_ne!(old, new);
+                1
+            }
+            WatchEvent::Removed(_) => 2,
+        };
+        counts[idx].fetch_add(1, Ordering::Relaxed);
+    }
+
+    let counts = |c: &[AtomicUsize; 3]| {
+        c.iter()
+            .map(|a| a.load(Ordering::Relaxed))
+            .collect::<Vec<_>>()
+    };
+
+    let mut sgm = SgWatchMap::<u32, u32, DEFAULT_CAPACITY, 2>::new();
+    let low = sgm.watch(..5, |_, e| tally(&LOW, e)).unwrap();
+    sgm.watch(3.., |_, e| tally(&HIGH, e)).unwrap();
+    assert_eq!(
+        sgm.watch(.., |_, _| {}),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    sgm.insert(1, 1);
+    sgm.insert(4, 4);
+    sgm.insert(1, 10);
+    assert_eq!(sgm.try_insert(9, 9), Ok(None));
+    assert_eq!(sgm.remove(&4), Some(4));
+    assert_eq!(sgm.remove(&4), None);
+    assert_eq!(counts(&LOW), [2, 1, 1]);
+    assert_eq!(counts(&HIGH), [2, 0, 1]);
+
+    assert!(sgm.unwatch(low));
+    assert!(!sgm.unwatch(low));
+    assert_eq!(sgm.watch_count(), 1);
+
+    sgm.clear();
+    assert!(sgm.is_empty());
+    assert_eq!(counts(&LOW), [2, 1, 1]);
+    assert_eq!(counts(&HIGH), [2, 0, 2]);
+}
+
+#[test]
+fn test_arena_pool() {
+    let mut pool = scapegoat::SgArenaPool::<usize, usize, DEFAULT_CAPACITY, 2>::new();
+    assert_eq!(pool.pool_size(), 2);
+    assert_eq!(pool.map_capacity(), DEFAULT_CAPACITY);
+
+    for round in 0..3 {
+        let [mut first, mut second] = pool.acquire();
+        assert!(first.is_empty() && second.is_empty());
+        assert_eq!(first.capacity(), DEFAULT_CAPACITY);
+
+        for k in 0..DEFAULT_CAPACITY {
+            first.insert(k, round);
+        }
+        assert_eq!(
+            first.try_insert(DEFAULT_CAPACITY, round),
+            Err(SgError::StackCapacityExceeded)
+        );
+
+        second.insert(round, round);
+        second.remove(&round);
+        assert!(second.is_empty());
+
+        assert_eq!(first.get(&0), Some(&round));
+    }
+
+    // Maps returned empty, with full capacity available
+    let [mut first, _] = pool.acquire();
+    assert!(first.is_empty());
+    assert!((0..DEFAULT_CAPACITY).all(|k| first.try_insert(k, k).is_ok()));
+    assert_eq!(first.len(), DEFAULT_CAPACITY);
+    drop(first);
+
+    // Settings changed through a handle don't leak into the next acquisition
+    let [mut first, _] = pool.acquire();
+    let default_param = first.rebal_param();
+    assert!(first.set_max_len(10).is_ok());
+    first.set_auto_rebalance(false);
+    first.set_arena_layout(ArenaLayout::BreadthFirst);
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    assert!(first.set_rebal_param(3.0, 4.0).is_ok());
+    drop(first);
+
+    let [first, _] = pool.acquire();
+    assert_eq!(first.max_len(), DEFAULT_CAPACITY);
+    assert!(first.auto_rebalance());
+    assert_eq!(first.arena_layout(), ArenaLayout::InOrder);
+    assert_eq!(first.rebal_param(), default_param);
+}
+
+#[test]
+fn test_map_clear_drop_glue() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[derive(Default)]
+    struct DropCounter(Option<Rc<Cell<usize>>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            if let Some(cnt) = &self.0 {
+                cnt.set(cnt.get() + 1);
+            }
+        }
+    }
+
+    // With drop glue: every value dropped exactly once, by `clear` or by drop
+    let drops = Rc::new(Cell::new(0));
+    let mut sgm = SgMap::<usize, DropCounter, DEFAULT_CAPACITY>::new();
+    for k in 0..DEFAULT_CAPACITY {
+        sgm.insert(k, DropCounter(Some(drops.clone())));
+    }
+    sgm.remove(&0);
+    assert_eq!(drops.get(), 1);
+
+    sgm.clear();
+    assert_eq!(drops.get(), DEFAULT_CAPACITY);
+
+    sgm.insert(0, DropCounter(Some(drops.clone())));
+    drop(sgm);
+    assert_eq!(drops.get(), DEFAULT_CAPACITY + 1);
+
+    // Without drop glue: stale slots aren't reset, but are never observed
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    sgm.extend((0..DEFAULT_CAPACITY).map(|k| (k, k)));
+    sgm.clear();
+    assert!(sgm.is_empty());
+    assert_eq!(sgm.iter().next(), None);
+
+    sgm.insert(5, 50);
+    assert!(sgm.iter().eq([(&5, &50)]));
+    assert_eq!(sgm.get(&0), None);
+    assert!((0..(DEFAULT_CAPACITY - 1)).all(|k| sgm.try_insert(k + 10, k).is_ok()));
+    assert_eq!(sgm.len(), DEFAULT_CAPACITY);
+}
+
+#[test]
+fn test_map_fragmentation() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    assert_eq!(sgm.fragmentation(), Default::default());
+    assert!(sgm.fragmentation().is_compact());
+
+    sgm.extend((0..DEFAULT_CAPACITY).map(|k| (k, k)));
+    let frag = sgm.fragmentation();
+    assert_eq!(frag.live(), DEFAULT_CAPACITY);
+    assert_eq!(frag.longest_live_run(), DEFAULT_CAPACITY);
+    assert_eq!(frag.live_spread(), DEFAULT_CAPACITY);
+    assert!(frag.is_compact());
+
+    // Holes at the front and middle, trailing free slots aren't counted
+    for k in [0, 1, 5, 9] {
+        sgm.remove(&k);
+    }
+    let frag = sgm.fragmentation();
+    assert_eq!(frag.live(), DEFAULT_CAPACITY - 4);
+    assert_eq!(frag.free_slots(), 3);
+    assert_eq!(frag.longest_live_run(), 3);
+    assert_eq!(frag.live_spread(), DEFAULT_CAPACITY - 3);
+    assert!(!frag.is_compact());
+
+    // Re-insertion reuses free slots
+    sgm.insert(100, 100);
+    sgm.insert(101, 101);
+    sgm.insert(102, 102);
+    sgm.insert(103, 103);
+    assert!(sgm.fragmentation().is_compact());
+
+    sgm.clear();
+    assert_eq!(sgm.fragmentation(), Default::default());
+}
+
+#[test]
+fn test_symbol_map() {
+    use scapegoat::{SgInterner, SgSymbolMap};
+
+    let mut interner = SgInterner::<16, 4>::new();
+    let mut first = SgSymbolMap::<usize, DEFAULT_CAPACITY>::new();
+    let mut second = SgSymbolMap::<usize, DEFAULT_CAPACITY>::new();
+    assert!(interner.is_empty());
+    assert_eq!(interner.get("beta"), None);
+
+    let beta = interner.intern("beta").unwrap();
+    let alpha = interner.intern("alpha").unwrap();
+    assert_eq!(interner.intern("beta"), Ok(beta));
+    assert_eq!(interner.get("alpha"), Some(alpha));
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.bytes_used(), 9);
+
+    // Keys ordered by interning order, not string contents
+    first.insert(alpha, 1);
+    first.insert(beta, 2);
+    second.insert(interner.intern("beta").unwrap(), 3);
+    assert!(first
+        .keys()
+        .map(|sym| interner.resolve(*sym))
+        .eq([Some("beta"), Some("alpha")]));
+    assert_eq!(second.get(&beta), Some(&3));
+
+    // Empty string is a valid key
+    let empty = interner.intern("").unwrap();
+    assert_eq!(interner.resolve(empty), Some(""));
+
+    // Byte capacity, then symbol capacity
+    assert_eq!(
+        interner.intern("too long"),
+        Err(SgError::StackCapacityExceeded)
+    );
+    let gamma = interner.intern("gamma").unwrap();
+    assert_eq!(interner.bytes_used(), 14);
+    assert_eq!(interner.intern("d"), Err(SgError::StackCapacityExceeded));
+    assert_eq!(interner.intern("gamma"), Ok(gamma));
+
+    interner.clear();
+    assert!(interner.is_empty());
+    assert_eq!(interner.resolve(beta), None);
+}
+
+#[test]
+fn test_map_insert_many() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let batch = (0..(DEFAULT_CAPACITY + 2))
+        .map(|k| (k, k))
+        .chain([(0, 100)]);
+
+    let mut inserted = 0;
+    let mut replaced = 0;
+    let mut failed = 0;
+    for result in sgm.insert_many(batch) {
+        match result {
+            Ok(None) => inserted += 1,
+            Ok(Some(old_val)) => {
+                assert_eq!(old_val, 0);
+                replaced += 1;
+            }
+            Err((err, (k, v))) => {
+                assert_eq!(err, SgError::StackCapacityExceeded);
+                assert!(k >= DEFAULT_CAPACITY && k == v);
+                failed += 1;
+            }
+        }
+    }
+
+    assert_eq!((inserted, replaced, failed), (DEFAULT_CAPACITY, 1, 2));
+    assert_eq!(sgm.len(), DEFAULT_CAPACITY);
+    assert_eq!(sgm.get(&0), Some(&100));
+
+    // Lazy: nothing inserted until consumed
+    sgm.clear();
+    let mut iter = sgm.insert_many([(1, 1), (2, 2)]);
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(Ok(None)));
+    drop(iter);
+    assert!(sgm.into_iter().eq([(1, 1)]));
+}
+
+#[test]
+fn test_range_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgRangeMap;
+
+    let mut ranges = SgRangeMap::<u32, u8, DEFAULT_CAPACITY>::new();
+    let mut points = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for _ in 0..2_000 {
+        let start = rng.gen_range(0, 64);
+        let end = rng.gen_range(0, 64);
+        let val = rng.gen_range(0, 3);
+        if rng.gen_bool(0.5) {
+            if ranges.try_insert(start..end, val).is_ok() {
+                points.extend((start..end).map(|p| (p, val)));
+            }
+        } else if ranges.try_remove(start..end).is_ok() {
+            (start..end).for_each(|p| {
+                points.remove(&p);
+            });
+        }
+
+        // Same points, as disjoint, ascending ranges, adjoining only if values differ
+        assert!((0..64).all(|p| ranges.get(&p) == points.get(&p)));
+        assert!(ranges.iter().all(|(r, _)| r.start < r.end));
+        assert!(ranges
+            .iter()
+            .zip(ranges.iter().skip(1))
+            .all(|((a, a_val), (b, b_val))| (a.end < b.start)
+                || (a.end == b.start && a_val != b_val)));
+    }
+
+    // Full: splitting insert and remove fail, overwrites and coalescing ones don't
+    ranges.clear();
+    (0..DEFAULT_CAPACITY as u32).for_each(|i| ranges.insert((i * 4)..(i * 4 + 3), i as u8));
+    assert_eq!(ranges.len(), ranges.capacity());
+    assert_eq!(
+        ranges.try_insert(100..101, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(
+        ranges.try_insert(5..6, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(ranges.try_remove(5..6), Err(SgError::StackCapacityExceeded));
+    assert!(ranges.try_insert(5..6, 1).is_ok());
+    assert!(ranges.try_insert(3..5, 0).is_ok());
+    assert_eq!(ranges.get_key_value(&4), Some((0..5, &0)));
+    assert_eq!(ranges.get_key_value(&5), Some((5..7, &1)));
+    assert!(ranges.try_insert(7..8, 2).is_ok());
+    assert_eq!(ranges.get_key_value(&7), Some((7..11, &2)));
+    assert!(ranges.try_insert(3..10, 9).is_ok());
+    assert!(ranges
+        .iter()
+        .take(3)
+        .eq([(0..3, &0), (3..10, &9), (10..11, &2)]));
+    assert_eq!(
+        format!("{:?}", ranges).get(..25),
+        Some("{0..3: 0, 3..10: 9, 10..1")
+    );
+}
+
+#[test]
+fn test_multi_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgMultiMap;
+
+    // Reference model: key -> values in insertion order
+    let mut multi = SgMultiMap::<u8, u32, DEFAULT_CAPACITY>::new();
+    let mut model: BTreeMap<u8, Vec<u32>> = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for i in 0..2_000 {
+        let key = rng.gen_range(0, 4);
+        match rng.gen_range(0, 4) {
+            0 | 1 => {
+                if multi.try_insert(key, i).is_ok() {
+                    model.entry(key).or_default().push(i);
+                } else {
+                    assert_eq!(multi.len(), multi.capacity());
+                }
+            }
+            2 => {
+                let expected = model
+                    .get_mut(&key)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.remove(0));
+                assert_eq!(multi.remove(&key), expected);
+            }
+            _ => {
+                let expected = model.remove(&key).map_or(0, |v| v.len());
+                assert_eq!(multi.remove_all(&key), expected);
+            }
+        }
+
+        assert_eq!(multi.len(), model.values().map(|v| v.len()).sum::<usize>());
+        for key in 0..4 {
+            let expected = model.get(&key).cloned().unwrap_or_default();
+            assert!(multi.get_all(&key).eq(expected.iter()));
+            assert!(multi.get_all(&key).rev().eq(expected.iter().rev()));
+            assert_eq!(multi.count(&key), expected.len());
+            assert_eq!(multi.contains_key(&key), !expected.is_empty());
+            assert_eq!(multi.get(&key), expected.first());
+        }
+        assert!(multi.iter().eq(model
+            .iter()
+            .flat_map(|(k, v)| v.iter().map(move |val| (k, val)))));
+    }
+
+    // Mutable access, equality ignores removal history
+    multi.clear();
+    multi.extend([(2, 20), (1, 10), (2, 21)]);
+    multi.get_all_mut(&2).for_each(|val| *val += 100);
+    let other: SgMultiMap<u8, u32, DEFAULT_CAPACITY> = [(0, 0), (1, 10), (2, 120), (2, 121)]
+        .iter()
+        .copied()
+        .collect();
+    assert_ne!(multi, other);
+    multi.insert(0, 0);
+    assert_eq!(multi, other);
+    assert!(multi.keys().eq([&0, &1, &2, &2]));
+    assert_eq!(format!("{:?}", multi), "{0: 0, 1: 10, 2: 120, 2: 121}");
+
+    // Full
+    (multi.len()..multi.capacity()).for_each(|_| multi.insert(3, 0));
+    assert_eq!(multi.try_insert(3, 0), Err(SgError::StackCapacityExceeded));
+}
+
+#[test]
+fn test_interval_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgIntervalMap;
+
+    // Enough intervals for rebuilds to reshape deep subtrees
+    const CAPACITY: usize = 64;
+    let mut intervals = SgIntervalMap::<u8, u32, CAPACITY>::new();
+    let mut model: BTreeMap<(u8, u8), u32> = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for i in 0..4_000 {
+        let start = rng.gen_range(0, 100);
+        let end = start + rng.gen_range(0, 20);
+        match rng.gen_range(0, 3) {
+            0 | 1 => match intervals.try_insert(start..end, i) {
+                Ok(opt_old) if start < end => assert_eq!(opt_old, model.insert((start, end), i)),
+                Ok(opt_old) => assert_eq!(opt_old, None),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert_eq!(intervals.len(), intervals.capacity());
+                }
+            },
+            _ => assert_eq!(intervals.remove(&(start..end)), model.remove(&(start, end))),
+        }
+
+        assert_eq!(intervals.len(), model.len());
+        assert!(intervals
+            .iter()
+            .eq(model.iter().map(|((s, e), val)| (*s..*e, val))));
+
+        let point = rng.gen_range(0, 120);
+        assert!(intervals.query_point(&point).eq(model
+            .iter()
+            .filter(|((s, e), _)| (*s <= point) && (point < *e))
+            .map(|((s, e), val)| (*s..*e, val))));
+
+        let query = start..end;
+        assert!(intervals.query_overlap(query.clone()).eq(model
+            .iter()
+            .filter(|((s, e), _)| !query.is_empty() && (*s < query.end) && (*e > query.start))
+            .map(|((s, e), val)| (*s..*e, val))));
+    }
+
+    // Exact lookups, mutation, formatting
+    intervals.clear();
+    intervals.extend([(5..9, 1), (0..10, 0), (5..6, 2)]);
+    if let Some(val) = intervals.get_mut(&(5..6)) {
+        *val += 10;
+    }
+    assert_eq!(intervals.get(&(5..6)), Some(&12));
+    assert!(intervals.contains(&(0..10)));
+    assert!(!intervals.contains(&(0..9)));
+    assert_eq!(format!("{:?}", intervals), "{0..10: 0, 5..6: 12, 5..9: 1}");
+    assert!(intervals.query_overlap(3..3).eq([]));
+
+    let other: SgIntervalMap<u8, u32, CAPACITY> = [(0..10, 0), (5..6, 12), (5..9, 1)]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(intervals, other);
+}
+
+#[test]
+fn test_bi_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgBiMap;
+
+    let mut bi_map = SgBiMap::<u8, u16, DEFAULT_CAPACITY>::new();
+    let mut model: BTreeMap<u8, u16> = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for _ in 0..2_000 {
+        let left = rng.gen_range(0, 16);
+        let right = rng.gen_range(0, 16);
+        match rng.gen_range(0, 4) {
+            0 | 1 => {
+                let by_left = model.get(&left).map(|r| (left, *r));
+                let by_right = model
+                    .iter()
+                    .find(|(_, r)| **r == right)
+                    .map(|(l, r)| (*l, *r))
+                    .filter(|pair| Some(*pair) != by_left);
+
+                match bi_map.try_insert(left, right) {
+                    Ok(displaced) => {
+                        assert_eq!(displaced, (by_left, by_right));
+                        model.retain(|l, r| (*l != left) && (*r != right));
+                        model.insert(left, right);
+                    }
+                    Err(err) => {
+                        assert_eq!(err, SgError::StackCapacityExceeded);
+                        assert!(by_left.is_none() && by_right.is_none());
+                        assert_eq!(bi_map.len(), bi_map.capacity());
+                    }
+                }
+            }
+            2 => assert_eq!(
+                bi_map.remove_by_left(&left),
+                model.remove(&left).map(|r| (left, r))
+            ),
+            _ => {
+                let expected = model
+                    .iter()
+                    .find(|(_, r)| **r == right)
+                    .map(|(l, r)| (*l, *r));
+                if let Some((l, _)) = expected {
+                    model.remove(&l);
+                }
+                assert_eq!(bi_map.remove_by_right(&right), expected);
+            }
+        }
+
+        assert_eq!(bi_map.len(), model.len());
+        assert!(bi_map.iter().eq(model.iter()));
+        for (l, r) in model.iter() {
+            assert_eq!(bi_map.get_by_left(l), Some(r));
+            assert_eq!(bi_map.get_by_right(r), Some(l));
+        }
+
+        let mut by_right: Vec<_> = model.iter().collect();
+        by_right.sort_by_key(|(_, r)| **r);
+        assert!(bi_map.iter_by_right().eq(by_right.into_iter()));
+    }
+
+    // Borrowed lookups, collection, and formatting
+    let names: SgBiMap<String, u8, DEFAULT_CAPACITY> = [("b", 1), ("a", 2), ("c", 1)]
+        .iter()
+        .map(|(s, n)| (s.to_string(), *n))
+        .collect();
+    assert_eq!(names.get_by_left("a"), Some(&2));
+    assert!(!names.contains_left("b"));
+    assert!(names.contains_right(&1));
+    assert_eq!(format!("{:?}", names), r#"{"a": 2, "c": 1}"#);
+}
+
+#[test]
+fn test_lru_cache() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgLruCache;
+
+    // Reference model: entries from most to least recently used
+    let mut cache = SgLruCache::<u8, u32, DEFAULT_CAPACITY>::new();
+    let mut model: Vec<(u8, u32)> = Vec::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for i in 0..4_000 {
+        let key = rng.gen_range(0, 16);
+        let pos = model.iter().position(|(k, _)| *k == key);
+        match rng.gen_range(0, 5) {
+            0 | 1 => {
+                let expected = match pos {
+                    Some(pos) => Some(model.remove(pos)),
+                    None if model.len() == DEFAULT_CAPACITY => model.pop(),
+                    None => None,
+                };
+                model.insert(0, (key, i));
+                assert_eq!(cache.insert(key, i), expected);
+            }
+            2 => {
+                let expected = pos.map(|pos| {
+                    let entry = model.remove(pos);
+                    model.insert(0, entry);
+                    entry.1
+                });
+                assert_eq!(cache.get(&key).copied(), expected);
+            }
+            3 => {
+                assert_eq!(cache.peek(&key), pos.map(|pos| &model[pos].1));
+                assert_eq!(cache.contains_key(&key), pos.is_some());
+            }
+            _ => match rng.gen_range(0, 2) {
+                0 => assert_eq!(cache.remove(&key), pos.map(|pos| model.remove(pos).1)),
+                _ => assert_eq!(cache.pop_lru(), model.pop()),
+            },
+        }
+
+        assert_eq!(cache.len(), model.len());
+        assert!(cache
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .eq(model.iter().copied()));
+        assert_eq!(
+            cache.peek_lru().map(|(k, v)| (*k, *v)),
+            model.last().copied()
+        );
+    }
+
+    // Mutation, borrowed lookups, and formatting
+    let mut names = SgLruCache::<String, u32, 2>::new();
+    names.insert("a".to_string(), 1);
+    names.insert("b".to_string(), 2);
+    if let Some(val) = names.get_mut("a") {
+        *val += 10;
+    }
+    assert_eq!(format!("{:?}", names), r#"{"a": 11, "b": 2}"#);
+    assert_eq!(names.insert("c".to_string(), 3), Some(("b".to_string(), 2)));
+
+    names.clear();
+    assert!(names.is_empty());
+    assert_eq!(names.peek_lru(), None);
+}
+
+#[test]
+fn test_ttl_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgTtlMap;
+
+    // Reference model: key -> (expiry tick, value), including expired entries
+    let mut ttl_map = SgTtlMap::<u8, u32, DEFAULT_CAPACITY>::new();
+    let mut model: BTreeMap<u8, (u64, u32)> = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+    let mut now = 0;
+
+    for i in 0..4_000 {
+        now += rng.gen_range(0, 3);
+        let key = rng.gen_range(0, 16);
+        match rng.gen_range(0, 6) {
+            0 | 1 => {
+                let expires_at = now + rng.gen_range(0, 20);
+                match ttl_map.try_insert(key, i, expires_at) {
+                    Ok(opt_old) => {
+                        assert_eq!(opt_old, model.insert(key, (expires_at, i)).map(|(_, v)| v))
+                    }
+                    Err(err) => {
+                        assert_eq!(err, SgError::StackCapacityExceeded);
+                        assert_eq!(ttl_map.len(), ttl_map.capacity());
+                    }
+                }
+            }
+            2 => assert_eq!(ttl_map.remove(&key), model.remove(&key).map(|(_, v)| v)),
+            3 => {
+                let expires_at = now + 10;
+                assert_eq!(
+                    ttl_map.set_expires_at(&key, expires_at),
+                    model.get_mut(&key).map(|e| e.0 = expires_at).is_some()
+                );
+            }
+            4 => {
+                let expected = model.values().filter(|(exp, _)| *exp <= now).count();
+                model.retain(|_, (exp, _)| now < *exp);
+                assert_eq!(ttl_map.purge_expired(now), expected);
+            }
+            _ => {
+                if let Some(val) = ttl_map.get_mut(&key, now) {
+                    *val += 1;
+                    model.get_mut(&key).unwrap().1 += 1;
+                }
+            }
+        }
+
+        let live = model.iter().filter(|(_, (exp, _))| now < *exp);
+        assert_eq!(ttl_map.len(), model.len());
+        assert_eq!(ttl_map.live_len(now), live.clone().count());
+        assert!(ttl_map.iter(now).eq(live.map(|(k, (_, v))| (k, v))));
+        for key in 0..16 {
+            let expected = model
+                .get(&key)
+                .filter(|(exp, _)| now < *exp)
+                .map(|(_, v)| v);
+            assert_eq!(ttl_map.get(&key, now), expected);
+            assert_eq!(ttl_map.contains_key(&key, now), expected.is_some());
+            assert_eq!(
+                ttl_map.expires_at(&key),
+                model.get(&key).map(|(exp, _)| *exp)
+            );
+        }
+    }
+
+    // Formatting shows expired entries too
+    ttl_map.clear();
+    ttl_map.insert(1, 10, 5);
+    ttl_map.insert(2, 20, 50);
+    assert_eq!(format!("{:?}", ttl_map), "{1: (5, 10), 2: (50, 20)}");
+    assert!(ttl_map.iter(10).rev().eq([(&2, &20)]));
+}
+
+#[test]
+fn test_priority_queue() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgPriorityQueue;
+
+    // Reference model: key -> priority, popped by minimum (priority, key)
+    let mut queue = SgPriorityQueue::<u8, u16, DEFAULT_CAPACITY>::new();
+    let mut model: BTreeMap<u8, u16> = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+    let model_min =
+        |m: &BTreeMap<u8, u16>| m.iter().map(|(k, p)| (*p, *k)).min().map(|(p, k)| (k, p));
+
+    for _ in 0..4_000 {
+        let key = rng.gen_range(0, 16);
+        let priority = rng.gen_range(0, 8);
+        match rng.gen_range(0, 5) {
+            0 | 1 => match queue.try_push(key, priority) {
+                Ok(opt_old) => assert_eq!(opt_old, model.insert(key, priority)),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(!model.contains_key(&key));
+                    assert_eq!(queue.len(), queue.capacity());
+                }
+            },
+            2 => {
+                let expected = model.get_mut(&key).map(|p| std::mem::replace(p, priority));
+                assert_eq!(queue.change_priority(&key, priority), expected);
+            }
+            3 => assert_eq!(queue.remove(&key), model.remove(&key)),
+            _ => {
+                let expected = model_min(&model);
+                if let Some((k, _)) = expected {
+                    model.remove(&k);
+                }
+                assert_eq!(queue.pop(), expected);
+            }
+        }
+
+        let mut by_priority: Vec<_> = model.iter().map(|(k, p)| (*p, *k)).collect();
+        by_priority.sort();
+        assert_eq!(queue.len(), model.len());
+        assert!(queue
+            .iter()
+            .map(|(k, p)| (*p, *k))
+            .eq(by_priority.into_iter()));
+        assert_eq!(queue.peek().map(|(k, p)| (*k, *p)), model_min(&model));
+        for key in 0..16 {
+            assert_eq!(queue.priority(&key), model.get(&key));
+            assert_eq!(queue.contains_key(&key), model.contains_key(&key));
+        }
+    }
+
+    // Collection, borrowed lookups, and formatting
+    let names: SgPriorityQueue<String, u8, DEFAULT_CAPACITY> = [("b", 2), ("a", 2), ("c", 1)]
+        .iter()
+        .map(|(s, p)| (s.to_string(), *p))
+        .collect();
+    assert_eq!(names.priority("a"), Some(&2));
+    assert_eq!(format!("{:?}", names), r#"{"c": 1, "a": 2, "b": 2}"#);
+}
+
+#[test]
+fn test_versioned_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgVersionedMap;
+
+    const JOURNAL_CAPACITY: usize = 6;
+
+    // Reference model: live map, plus a full copy per readable snapshot
+    let mut map = SgVersionedMap::<u8, u16, DEFAULT_CAPACITY, JOURNAL_CAPACITY>::new();
+    let mut model: BTreeMap<u8, u16> = BTreeMap::new();
+    let mut snapshots = Vec::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for _ in 0..4_000 {
+        let key = rng.gen_range(0, 16);
+        let val = rng.gen_range(0, 100);
+        match rng.gen_range(0, 10) {
+            0..=3 => match map.try_insert(key, val) {
+                Ok(opt_old) => assert_eq!(opt_old, model.insert(key, val)),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(!model.contains_key(&key));
+                    assert_eq!(map.len(), map.capacity());
+                }
+            },
+            4 | 5 => assert_eq!(map.remove(&key), model.remove(&key)),
+            6 => {
+                if let Some(v) = map.get_mut(&key) {
+                    *v += 1;
+                }
+                if let Some(v) = model.get_mut(&key) {
+                    *v += 1;
+                }
+            }
+            7 | 8 => snapshots.push((map.snapshot(), model.clone())),
+            _ => {
+                if !snapshots.is_empty() {
+                    let (version, _) = snapshots[rng.gen_range(0, snapshots.len())];
+                    map.release_before(version);
+                    assert!(map.at(version).is_some());
+                }
+            }
+        }
+
+        assert!(map.journal_len() <= JOURNAL_CAPACITY);
+        assert!(map.iter().eq(model.iter()));
+
+        // Dropped snapshots stay unreadable, the rest match their copies
+        snapshots.retain(|(version, _)| map.at(*version).is_some());
+        for (version, copy) in &snapshots {
+            let view = map.at(*version).unwrap();
+            assert_eq!(view.len(), copy.len());
+            assert!(view.iter().eq(copy.iter()));
+            for key in 0..16 {
+                assert_eq!(view.get(&key), copy.get(&key));
+            }
+        }
+    }
+
+    // Releasing everything reclaims the journal
+    let latest = map.snapshot();
+    map.insert(0, 0);
+    map.release_before(latest);
+    assert!(map.journal_len() <= 1);
+    map.clear();
+    assert_eq!(map.journal_len(), 0);
+    assert!(map.at(latest).is_none());
+    assert_eq!(format!("{:?}", map), "{}");
+
+    // Snapshot lookups by borrowed key form
+    let mut names = SgVersionedMap::<String, u8, DEFAULT_CAPACITY, JOURNAL_CAPACITY>::new();
+    names.insert("a".to_string(), 1);
+    names.insert("b".to_string(), 2);
+    let v1 = names.snapshot();
+    names.insert("a".to_string(), 10);
+    names.remove("b");
+
+    let view = names.at(v1).unwrap();
+    assert_eq!(view.get("a"), Some(&1));
+    assert!(view.contains_key("b"));
+    assert!(!view.contains_key("c"));
+}
+
+#[test]
+fn test_tx_map() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use scapegoat::SgTxMap;
+
+    // Reference model: live map, plus a copy taken at `begin`
+    let mut map = SgTxMap::<u8, u16, DEFAULT_CAPACITY>::new();
+    let mut model: BTreeMap<u8, u16> = BTreeMap::new();
+    let mut opt_saved: Option<BTreeMap<u8, u16>> = None;
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for _ in 0..4_000 {
+        let key = rng.gen_range(0, 24);
+        let val = rng.gen_range(0, 100);
+        match rng.gen_range(0, 24) {
+            0..=9 => match map.try_insert(key, val) {
+                Ok(opt_old) => assert_eq!(opt_old, model.insert(key, val)),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(
+                        (!model.contains_key(&key) && (map.len() == map.capacity()))
+                            || (map.journal_len() == map.capacity())
+                    );
+                }
+            },
+            10..=18 => match map.try_remove(&key) {
+                Ok(opt_val) => assert_eq!(opt_val, model.remove(&key)),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(model.contains_key(&key));
+                    assert_eq!(map.journal_len(), map.capacity());
+                }
+            },
+            19 => {
+                assert_eq!(map.begin(), opt_saved.is_none());
+                opt_saved.get_or_insert_with(|| model.clone());
+            }
+            20 => assert_eq!(map.commit(), opt_saved.take().is_some()),
+            21 | 22 => {
+                let opt_restored = opt_saved.take();
+                assert_eq!(map.rollback(), opt_restored.is_some());
+                if let Some(restored) = opt_restored {
+                    model = restored;
+                }
+            }
+            _ => {
+                map.clear();
+                model.clear();
+            }
+        }
+
+        assert_eq!(map.in_transaction(), opt_saved.is_some());
+        assert!(map.journal_len() <= map.capacity());
+        assert_eq!(map.len(), model.len());
+        assert!(map.iter().eq(model.iter()));
+        for key in 0..24 {
+            assert_eq!(map.get(&key), model.get(&key));
+        }
+    }
+
+    // Churning keys present at `begin` can fill the journal
+    let mut map = SgTxMap::<u8, u8, 2>::new();
+    map.insert(1, 1);
+    map.insert(2, 2);
+    assert!(map.begin());
+    assert_eq!(map.remove(&1), Some(1));
+    map.insert(3, 3);
+    assert_eq!(map.try_remove(&2), Err(SgError::StackCapacityExceeded));
+    assert!(map.rollback());
+    assert!(map.iter().eq([(&1, &1), (&2, &2)]));
+
+    // Outside a transaction, changes aren't journaled
+    let mut map = SgTxMap::<u8, u8, 1>::new();
+    map.insert(1, 1);
+    map.remove(&1);
+    map.insert(2, 2);
+    assert_eq!(map.journal_len(), 0);
+    assert!(!map.rollback());
+    assert_eq!(format!("{:?}", map), "{2: 2}");
+}
+
+#[test]
+fn test_map_pop_while() {
+    let mut sgm: SgMap<u32, u32, DEFAULT_CAPACITY> = SgMap::from_iter((0..10).map(|k| (k, k * 10)));
+
+    // Stops at first failure, even if later entries would match
+    let popped: Vec<_> = sgm.pop_first_while(|k, _| *k < 3 || *k == 5).collect();
+    assert_eq!(popped, [(0, 0), (1, 10), (2, 20)]);
+    assert_eq!(sgm.first_key(), Some(&3));
+
+    let popped: Vec<_> = sgm.pop_last_while(|_, v| *v >= 70).collect();
+    assert_eq!(popped, [(9, 90), (8, 80), (7, 70)]);
+    assert!(sgm.keys().cloned().eq(3..7));
+
+    // Lazy, unvisited entries remain
+    assert_eq!(sgm.pop_first_while(|_, _| true).next(), Some((3, 30)));
+    assert_eq!(sgm.len(), 3);
+
+    // Fused once the predicate fails
+    let mut iter = sgm.pop_first_while(|k, _| *k != 5);
+    assert_eq!(iter.next(), Some((4, 40)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(sgm.pop_last_while(|_, _| true).count(), 2);
+    assert!(sgm.is_empty());
+    assert_eq!(sgm.pop_first_while(|_, _| true).next(), None);
+}
+
+#[test]
+fn test_map_drain_front_back() {
+    let mut sgm: SgMap<u32, u32, DEFAULT_CAPACITY> = SgMap::from_iter((0..10).map(|k| (k, k * 10)));
+
+    let front = sgm.drain_front(3);
+    assert_eq!(front.len(), 3);
+    assert!(front.eq((0..3).map(|k| (k, k * 10))));
+
+    assert!(sgm.drain_back(2).eq((8..10).rev().map(|k| (k, k * 10))));
+    assert!(sgm.keys().cloned().eq(3..8));
+
+    // Remaining map stays usable
+    sgm.insert(0, 0);
+    assert_eq!(sgm.first_key_value(), Some((&0, &0)));
+
+    assert_eq!(sgm.drain_front(0).count(), 0);
+    assert_eq!(sgm.drain_back(100).count(), 6);
+    assert!(sgm.is_empty());
+}
+
+#[test]
+fn test_map_extract_if() {
+    let mut sgm: SgMap<u32, u32, DEFAULT_CAPACITY> = SgMap::from_iter((0..10).map(|k| (k, k * 10)));
+    let mut btm: BTreeMap<u32, u32> = BTreeMap::from_iter((0..10).map(|k| (k, k * 10)));
+
+    // Non-matching entries are kept, mutation persists
+    let extracted: Vec<_> = sgm
+        .extract_if(|k, v| {
+            *v += 1;
+            k % 3 == 0
+        })
+        .collect();
+    assert_eq!(extracted, [(0, 1), (3, 31), (6, 61), (9, 91)]);
+    btm.retain(|k, v| {
+        *v += 1;
+        k % 3 != 0
+    });
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Lazy, dropping early keeps unvisited entries
+    let mut iter = sgm.extract_if(|_, _| true);
+    assert_eq!(iter.size_hint(), (0, Some(6)));
+    assert_eq!(iter.next(), Some((1, 11)));
+    assert_eq!(iter.size_hint(), (0, Some(5)));
+    drop(iter);
+    btm.remove(&1);
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Remaining map stays usable
+    sgm.insert(1, 1);
+    assert_eq!(sgm.first_key_value(), Some((&1, &1)));
+    assert_eq!(sgm.extract_if(|_, _| true).count(), 6);
+    assert!(sgm.is_empty());
+    assert_eq!(sgm.extract_if(|_, _| true).next(), None);
+}
+
+#[test]
+fn test_map_cursor() {
+    let sgm: SgMap<u32, char, DEFAULT_CAPACITY> = SgMap::from_iter([(2, 'b'), (4, 'd'), (6, 'f')]);
+
+    assert_eq!(sgm.lower_bound(Included(&4)).key(), Some(&4));
+    assert_eq!(sgm.lower_bound(Excluded(&4)).key(), Some(&6));
+    assert_eq!(sgm.lower_bound(Unbounded).key(), Some(&2));
+    assert_eq!(sgm.lower_bound(Included(&7)).key(), None);
+    assert_eq!(sgm.upper_bound(Included(&4)).key(), Some(&4));
+    assert_eq!(sgm.upper_bound(Excluded(&4)).key(), Some(&2));
+    assert_eq!(sgm.upper_bound(Unbounded).key(), Some(&6));
+    assert_eq!(sgm.upper_bound(Excluded(&2)).key(), None);
+
+    // Full loop through the ghost position, both directions
+    let mut cursor = sgm.lower_bound(Unbounded);
+    let mut fwd = Vec::new();
+    for _ in 0..4 {
+        fwd.push(cursor.key().copied());
+        cursor.move_next();
+    }
+    assert_eq!(fwd, [Some(2), Some(4), Some(6), None]);
+    assert_eq!(cursor.key(), Some(&2));
+
+    let mut rev = Vec::new();
+    for _ in 0..4 {
+        cursor.move_prev();
+        rev.push(cursor.key().copied());
+    }
+    assert_eq!(rev, [None, Some(6), Some(4), Some(2)]);
+
+    // Peeks don't move, including from the ghost position
+    let ghost = sgm.lower_bound(Excluded(&6));
+    assert_eq!(ghost.peek_next(), Some((&2, &'b')));
+    assert_eq!(ghost.peek_prev(), Some((&6, &'f')));
+    assert_eq!(ghost.key(), None);
+
+    let empty = SgMap::<u32, char, DEFAULT_CAPACITY>::new();
+    let mut cursor = empty.lower_bound(Unbounded);
+    cursor.move_next();
+    assert_eq!(cursor.key_value(), None);
+    assert_eq!(cursor.peek_prev(), None);
+}
+
+#[test]
+fn test_map_cursor_mut() {
+    let mut sgm: SgMap<u32, u32, DEFAULT_CAPACITY> = SgMap::from_iter((0..5).map(|k| (k * 10, k)));
+
+    let mut cursor = sgm.lower_bound_mut(Included(&20));
+    *cursor.value_mut().unwrap() = 200;
+
+    // Ordering is enforced, map unchanged on error
+    assert_eq!(cursor.insert_after(30, 0), Err(SgError::UnsortedInput));
+    assert_eq!(cursor.insert_before(5, 0), Err(SgError::UnsortedInput));
+    assert!(cursor.insert_after(25, 250).is_ok());
+    assert!(cursor.insert_before(15, 150).is_ok());
+    assert_eq!(cursor.key(), Some(&20));
+    assert_eq!(cursor.peek_next(), Some((&25, &250)));
+    assert_eq!(cursor.peek_prev(), Some((&15, &150)));
+
+    // Removal moves to the neighbor
+    assert_eq!(cursor.remove_current(), Some((20, 200)));
+    assert_eq!(cursor.key(), Some(&25));
+    assert_eq!(cursor.remove_current_and_move_back(), Some((25, 250)));
+    assert_eq!(cursor.key(), Some(&15));
+    assert_eq!(cursor.as_cursor().peek_next(), Some((&30, &3)));
+
+    // Ghost position inserts at the ends
+    let mut cursor = sgm.upper_bound_mut(Excluded(&0));
+    assert_eq!(cursor.key(), None);
+    assert_eq!(cursor.remove_current(), None);
+    assert!(cursor.insert_after(1, 1).is_err());
+    assert!(cursor.insert_before(50, 5).is_ok());
+    assert!(cursor.insert_after(0, 0).is_err()); // Duplicate
+    cursor.move_next();
+    assert_eq!(cursor.key(), Some(&0));
+
+    assert!(sgm
+        .into_iter()
+        .eq([(0, 0), (10, 1), (15, 150), (30, 3), (40, 4), (50, 5)]));
+}
+
+#[test]
+fn test_map_cursor_fuzz() {
+    const CAPACITY: usize = 256;
+
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<u32, u32, CAPACITY>::new();
+    let mut btm = BTreeMap::new();
+
+    for _ in 0..(CAPACITY * 8) {
+        let key = rng.gen_range(0, (CAPACITY * 2) as u32);
+        let mut cursor = sgm.lower_bound_mut(Included(&key));
+        let expected = btm.range(key..).next().map(|(k, _)| *k);
+        assert_eq!(cursor.key().copied(), expected);
+
+        // Walk a few steps, verifying neighbors, then edit
+        for _ in 0..rng.gen_range(0, 4) {
+            cursor.move_next();
+        }
+
+        match cursor.key().copied() {
+            Some(k) if rng.gen_bool(0.5) => {
+                assert_eq!(cursor.remove_current(), btm.remove_entry(&k));
+                let expected = btm.range((Excluded(k), Unbounded)).next().map(|(k, _)| *k);
+                assert_eq!(cursor.key().copied(), expected);
+            }
+            opt_k => {
+                // Insert just after the cursor, in order only if below the next key
+                let new_key = opt_k.map_or(0, |k| k + 1);
+                let opt_next = match opt_k {
+                    Some(k) => btm.range((Excluded(k), Unbounded)).next(),
+                    None => btm.iter().next(),
+                };
+                let is_ordered = opt_next.into_iter().all(|(n, _)| new_key < *n);
+
+                let result = cursor.insert_after(new_key, new_key);
+                match (is_ordered, btm.len() < CAPACITY) {
+                    (false, _) => assert_eq!(result, Err(SgError::UnsortedInput)),
+                    (true, false) => assert_eq!(result, Err(SgError::StackCapacityExceeded)),
+                    (true, true) => {
+                        assert!(result.is_ok());
+                        assert_eq!(cursor.key().copied(), opt_k);
+                        assert_eq!(cursor.peek_next(), Some((&new_key, &new_key)));
+                        btm.insert(new_key, new_key);
+                    }
+                }
+            }
+        }
+
+        assert!(sgm.iter().eq(btm.iter()));
+    }
+}
+
+#[test]
+fn test_entry_single_traversal_insert() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    let mut sgm = SgMap::<u16, u32, 512>::new();
+    let mut btm = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0xe417);
+
+    // Mix of vacant inserts (incl. new min/max, rebalances) and occupied updates
+    for _ in 0..2_000 {
+        let key = rng.gen_range(0, 600);
+        if sgm.is_full() && !sgm.contains_key(&key) {
+            sgm.remove(&sgm.first_key().cloned().unwrap());
+            btm.pop_first();
+        }
+
+        *sgm.entry(key).and_modify(|v| *v += 1).or_insert(0) += 10;
+        *btm.entry(key).and_modify(|v| *v += 1).or_insert(0) += 10;
+
+        if rng.gen_bool(0.1) {
+            assert_eq!(sgm.entry(key).key(), &key);
+            if let scapegoat::map_types::Entry::Occupied(o) = sgm.entry(key) {
+                assert_eq!(o.remove(), btm.remove(&key).unwrap());
+            }
+        }
+
+        assert_eq!(sgm.first_key_value(), btm.first_key_value());
+        assert_eq!(sgm.last_key_value(), btm.last_key_value());
+    }
+
+    assert!(sgm.iter().eq(btm.iter()));
+}
+
+#[test]
+fn test_map_double_ended_iters() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    let mut rng = SmallRng::seed_from_u64(0xde1);
+
+    for len in [0, 1, 2, 7, 64] {
+        let mut sgm = SgMap::<u32, u32, 64>::new();
+        let mut btm = BTreeMap::new();
+        while sgm.len() < len {
+            let k = rng.gen_range(0, 1_000);
+            sgm.insert(k, k * 2);
+            btm.insert(k, k * 2);
+        }
+
+        // Leave holes in the arena
+        if let Some(k) = btm.keys().nth(len / 2).cloned() {
+            sgm.remove(&k);
+            btm.remove(&k);
+        }
+
+        // Random interleaving of both ends, until they meet
+        let pattern: Vec<bool> = (0..=len).map(|_| rng.gen_bool(0.5)).collect();
+        fn interleave<I: DoubleEndedIterator>(mut iter: I, pattern: &[bool]) -> Vec<I::Item> {
+            let mut out = Vec::new();
+            for from_back in pattern {
+                match from_back {
+                    true => out.extend(iter.next_back()),
+                    false => out.extend(iter.next()),
+                }
+            }
+            assert!(iter.next().is_none() && iter.next_back().is_none());
+            out
+        }
+
+        assert_eq!(
+            interleave(sgm.iter(), &pattern),
+            interleave(btm.iter(), &pattern)
+        );
+        assert_eq!(
+            interleave(sgm.keys(), &pattern),
+            interleave(btm.keys(), &pattern)
+        );
+        assert_eq!(
+            interleave(sgm.values(), &pattern),
+            interleave(btm.values(), &pattern)
+        );
+        assert_eq!(
+            interleave(sgm.iter_mut(), &pattern),
+            interleave(btm.iter_mut(), &pattern)
+        );
+        assert_eq!(
+            interleave(sgm.values_mut(), &pattern),
+            interleave(btm.values_mut(), &pattern)
+        );
+
+        assert!(sgm.iter().rev().eq(btm.iter().rev()));
+        assert_eq!(sgm.iter().len(), btm.len());
+
+        assert_eq!(
+            interleave(sgm.clone().into_keys(), &pattern),
+            interleave(btm.clone().into_keys(), &pattern)
+        );
+        assert_eq!(
+            interleave(sgm.clone().into_values(), &pattern),
+            interleave(btm.clone().into_values(), &pattern)
+        );
+        assert_eq!(
+            interleave(sgm.into_iter(), &pattern),
+            interleave(btm.into_iter(), &pattern)
+        );
+    }
+}
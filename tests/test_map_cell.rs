@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::thread;
+
+use scapegoat::{SgError, SgMapCell, StaticSgMap};
+
+const CAPACITY: usize = 64;
+
+static SHARED: SgMapCell<u32, u32, CAPACITY> = SgMapCell::new();
+
+#[test]
+fn test_map_cell_basic() {
+    let cell = SgMapCell::<u32, &str, 2>::new();
+    cell.init();
+    assert!(cell.with(|map| map.is_empty()));
+
+    cell.with(|map| {
+        map.insert(2, "b");
+        map.insert(1, "a");
+    });
+    // Already created, contents kept
+    cell.init();
+    assert_eq!(
+        cell.with(|map| map.try_insert(3, "c")),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(
+        critical_section::with(|cs| cell.with_cs(cs, |map| map.len())),
+        2
+    );
+
+    let mut cell = cell;
+    assert_eq!(cell.get_mut().remove(&2), Some("b"));
+    assert!(cell.into_inner().into_iter().eq([(1, "a")]));
+
+    // Never accessed
+    assert!(SgMapCell::<u32, u32, 1>::default().into_inner().is_empty());
+}
+
+#[test]
+fn test_map_cell_static_concurrent() {
+    thread::scope(|s| {
+        for t in 0..4 {
+            s.spawn(move || {
+                for i in 0..(CAPACITY as u32 / 4) {
+                    SHARED.with(|map| map.insert(t * 100 + i, t));
+                }
+            });
+        }
+    });
+
+    let expected: BTreeMap<_, _> = (0..4)
+        .flat_map(|t| (0..(CAPACITY as u32 / 4)).map(move |i| (t * 100 + i, t)))
+        .collect();
+    SHARED.with(|map| assert!(map.iter().eq(expected.iter())));
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn test_map_cell_reentrant() {
+    let cell = SgMapCell::<u32, u32, 1>::new();
+    cell.with(|_| cell.with(|map| map.len()));
+}
+
+#[test]
+fn test_static_map() {
+    static MAP: StaticSgMap<u32, u32, 2> = StaticSgMap::new();
+    MAP.init();
+    assert!(MAP.is_empty());
+
+    assert_eq!(MAP.try_insert(1, 10), Ok(None));
+    assert_eq!(MAP.try_insert(2, 20), Ok(None));
+    assert_eq!(MAP.try_insert(2, 21), Ok(Some(20)));
+    assert_eq!(MAP.try_insert(3, 30), Err(SgError::StackCapacityExceeded));
+    MAP.init();
+    assert_eq!(MAP.len(), 2);
+
+    assert_eq!(MAP.get(&2), Some(21));
+    assert!(MAP.contains_key(&1));
+    assert!(!MAP.contains_key(&3));
+    assert_eq!(
+        MAP.as_cell()
+            .with(|map| map.first_key_value().map(|(k, _)| *k)),
+        Some(1)
+    );
+
+    assert_eq!(MAP.remove(&1), Some(10));
+    assert_eq!(MAP.len(), 1);
+    MAP.clear();
+    assert!(MAP.is_empty());
+
+    let owned = StaticSgMap::<u8, u8, 1>::default();
+    assert_eq!(owned.try_insert(1, 1), Ok(None));
+    assert!(owned.into_inner().into_iter().eq([(1, 1)]));
+}
@@ -0,0 +1,100 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use scapegoat::model::{apply, apply_model, Op, OpOutput, RefModel};
+use scapegoat::SgMap;
+
+const CAPACITY: usize = 64;
+const KEY_SPACE: u16 = 128;
+
+// Generate a random operation, never one that would panic on overflow.
+fn rand_op<R: Rng>(rng: &mut R, model: &RefModel<u16, u32, CAPACITY>) -> Op<u16, u32> {
+    let key = rng.gen_range(0, KEY_SPACE);
+    let val = rng.gen();
+
+    match rng.gen_range(0, 21) {
+        0 if (model.map.len() < CAPACITY) || model.map.contains_key(&key) => {
+            Op::Insert { key, val }
+        }
+        0..=3 => Op::TryInsert { key, val },
+        4 => Op::Remove { key },
+        5 => Op::RemoveEntry { key },
+        6 => Op::Get { key },
+        7 => Op::GetKeyValue { key },
+        8 => Op::GetMut { key, val },
+        9 => Op::ContainsKey { key },
+        10 => Op::FirstKeyValue,
+        11 => Op::LastKeyValue,
+        12 => Op::PopFirst,
+        13 => Op::PopLast,
+        14 => Op::Len,
+        15 => Op::IsFull,
+        16 => match rng.gen_range(0, 20) {
+            0 => Op::Clear,
+            1 => Op::RetainLessThan { key },
+            _ => Op::IsEmpty,
+        },
+        17 => Op::SplitOff { key },
+        18 => Op::TakeSmallest {
+            k: rng.gen_range(0, 8),
+        },
+        19 => Op::TakeLargest {
+            k: rng.gen_range(0, 8),
+        },
+        _ => match rng.gen() {
+            true => Op::RangeInclusive {
+                start: key,
+                end: rng.gen_range(0, KEY_SPACE),
+            },
+            false => Op::Iter,
+        },
+    }
+}
+
+#[test]
+fn test_model_equivalence() {
+    let mut rng = SmallRng::seed_from_u64(0x5ca9_e90a);
+
+    for _ in 0..20 {
+        let mut map = SgMap::<u16, u32, CAPACITY>::new();
+        let mut model = RefModel::<u16, u32, CAPACITY>::new();
+
+        for _ in 0..2_000 {
+            let op = rand_op(&mut rng, &model);
+            assert_eq!(
+                apply(&mut map, op.clone()),
+                apply_model(&mut model, op.clone()),
+                "Divergence on {:?}",
+                op
+            );
+        }
+
+        assert_eq!(apply(&mut map, Op::Iter), apply_model(&mut model, Op::Iter));
+    }
+}
+
+#[test]
+fn test_model_capacity() {
+    let mut map = SgMap::<u16, u32, 2>::new();
+    let mut model = RefModel::<u16, u32, 2>::new();
+
+    for key in 0..3 {
+        let op = Op::TryInsert { key, val: 0 };
+        assert_eq!(apply(&mut map, op.clone()), apply_model(&mut model, op));
+    }
+
+    assert_eq!(
+        apply_model(&mut model, Op::TryInsert { key: 3, val: 0 }),
+        OpOutput::TryInsert(Err(scapegoat::SgError::StackCapacityExceeded))
+    );
+    assert_eq!(apply(&mut map, Op::IsFull), OpOutput::Bool(true));
+    assert_eq!(apply_model(&mut model, Op::IsFull), OpOutput::Bool(true));
+}
+
+#[should_panic]
+#[test]
+fn test_model_insert_panic() {
+    let mut model = RefModel::<u16, u32, 1>::new();
+    apply_model(&mut model, Op::Insert { key: 1, val: 0 });
+    apply_model(&mut model, Op::Insert { key: 2, val: 0 });
+}
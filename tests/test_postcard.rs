@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use scapegoat::persist::{PersistError, COMPACT_MAGIC, COMPACT_VERSION};
+use scapegoat::{SgMap, SgSet};
+
+const CAPACITY: usize = 256;
+
+#[test]
+fn test_postcard_round_trip() {
+    let mut map = SgMap::<u32, i64, CAPACITY>::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+    let mut buf = [0; 4096];
+
+    for _ in 0..8 {
+        while map.len() < CAPACITY {
+            map.insert(rng.gen_range(0, 1_000_000), rng.gen_range(-1_000, 1_000));
+        }
+        for _ in 0..rng.gen_range(0, CAPACITY) {
+            map.pop_first();
+        }
+
+        let written = map.to_compact_bytes(&mut buf).unwrap();
+        assert_eq!(written, map.compact_len().unwrap());
+        assert_eq!(
+            SgMap::<u32, i64, CAPACITY>::from_compact_bytes(&buf).as_ref(),
+            Ok(&map)
+        );
+
+        // Exact fit succeeds, one byte short fails
+        assert_eq!(map.to_compact_bytes(&mut buf[..written]), Ok(written));
+        assert_eq!(
+            map.to_compact_bytes(&mut buf[..(written - 1)]),
+            Err(PersistError::BufferTooSmall {
+                needed: written,
+                available: written - 1
+            })
+        );
+    }
+
+    // Non-integer items
+    let names =
+        SgSet::<(String, bool), 2>::from([("a".to_string(), true), ("b".to_string(), false)]);
+    let written = names.to_compact_bytes(&mut buf).unwrap();
+    assert_eq!(
+        SgSet::<(String, bool), 2>::from_compact_bytes(&buf[..written]),
+        Ok(names)
+    );
+}
+
+#[test]
+fn test_postcard_wire_format() {
+    let map = [(2, 300), (1, 7)]
+        .iter()
+        .copied()
+        .collect::<SgMap<u16, u32, 300>>();
+    let mut buf = [0; 32];
+    let written = map.to_compact_bytes(&mut buf).unwrap();
+
+    // Magic, version, varint capacity, varint length, then varint key-value pairs
+    assert_eq!(
+        &buf[..written],
+        &[b'S', b'G', b'P', b'C', 1, 0xac, 0x02, 2, 1, 7, 2, 0xac, 0x02]
+    );
+
+    // Readable by plain postcard, e.g. on a host without this library
+    let (magic, version, capacity, entries): ([u8; 4], u8, u32, BTreeMap<u16, u32>) =
+        postcard::from_bytes(&buf).unwrap();
+    assert_eq!(
+        (magic, version, capacity),
+        (COMPACT_MAGIC, COMPACT_VERSION, 300)
+    );
+    assert!(entries.iter().eq(map.iter()));
+
+    // Empty
+    let empty = SgSet::<u8, 1>::new();
+    assert_eq!(empty.to_compact_bytes(&mut buf), Ok(7));
+    assert_eq!(SgSet::<u8, 1>::from_compact_bytes(&buf), Ok(empty));
+}
+
+#[test]
+fn test_postcard_rejects_invalid() {
+    let map = SgMap::<u16, u32, 4>::from([(1, 10), (2, 20), (3, 30), (4, 40)]);
+    let mut buf = [0; 32];
+    let written = map.to_compact_bytes(&mut buf).unwrap();
+
+    assert_eq!(
+        SgMap::<u16, u32, 3>::from_compact_bytes(&buf),
+        Err(PersistError::CapacityExceeded {
+            image_capacity: 4,
+            image_len: 4,
+            capacity: 3
+        })
+    );
+    assert_eq!(
+        SgMap::<u16, u32, 4>::from_compact_bytes(&buf[..(written - 1)]),
+        Err(PersistError::Malformed)
+    );
+    assert_eq!(
+        SgMap::<u16, u32, 4>::from_compact_bytes(&buf[..3]),
+        Err(PersistError::BufferTooSmall {
+            needed: 5,
+            available: 3
+        })
+    );
+    assert_eq!(
+        SgMap::<u16, u32, 4>::from_compact_bytes(&[0xff; 32]),
+        Err(PersistError::BadMagic)
+    );
+
+    let mut future = buf;
+    future[4] = COMPACT_VERSION + 1;
+    assert_eq!(
+        SgMap::<u16, u32, 4>::from_compact_bytes(&future),
+        Err(PersistError::UnsupportedVersion {
+            found: COMPACT_VERSION + 1,
+            supported: COMPACT_VERSION
+        })
+    );
+
+    // Duplicate keys
+    let dup = (
+        COMPACT_MAGIC,
+        COMPACT_VERSION,
+        4_u32,
+        vec![(1_u16, 10_u32), (1, 20)],
+    );
+    postcard::to_slice(&dup, &mut buf).unwrap();
+    assert_eq!(
+        SgMap::<u16, u32, 4>::from_compact_bytes(&buf),
+        Err(PersistError::Malformed)
+    );
+}
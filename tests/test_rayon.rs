@@ -0,0 +1,74 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+use scapegoat::{SgMap, SgSet};
+
+const CAPACITY: usize = 2048;
+
+#[test]
+fn test_rayon_map() {
+    let mut map = SgMap::<u32, u64, CAPACITY>::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    // Irregular shape: random inserts, then removes
+    while map.len() < CAPACITY {
+        map.insert(rng.gen_range(0, 100_000), rng.gen_range(0, 1_000));
+    }
+    for _ in 0..(CAPACITY / 3) {
+        map.remove(&rng.gen_range(0, 100_000));
+    }
+
+    let par_entries: Vec<_> = map.par_iter().collect();
+    assert!(par_entries.into_iter().eq(map.iter()));
+    assert_eq!(map.par_iter().count(), map.len());
+    assert_eq!(
+        map.par_iter().map(|(_, v)| *v).sum::<u64>(),
+        map.values().sum::<u64>()
+    );
+
+    let first_odd = map.iter().find(|(_, v)| *v % 2 == 1);
+    assert_eq!(map.par_iter().find_first(|(_, v)| *v % 2 == 1), first_odd);
+
+    let expected: Vec<_> = map.iter().map(|(k, v)| (*k, v * 2)).collect();
+    map.par_iter_mut().for_each(|(_, v)| *v *= 2);
+    assert!(map
+        .iter()
+        .map(|(k, v)| (*k, *v))
+        .eq(expected.iter().copied()));
+
+    let keys: Vec<_> = map.par_iter_mut().map(|(k, _)| *k).collect();
+    assert!(keys.iter().eq(map.keys()));
+
+    let owned: Vec<_> = map.into_par_iter().collect();
+    assert_eq!(owned, expected);
+
+    // Empty and single-node trees
+    let mut empty = SgMap::<u32, u64, 1>::new();
+    assert_eq!(empty.par_iter().count(), 0);
+    assert_eq!(empty.par_iter_mut().count(), 0);
+    empty.insert(1, 2);
+    assert_eq!(empty.par_iter().collect::<Vec<_>>(), vec![(&1, &2)]);
+}
+
+#[test]
+fn test_rayon_set() {
+    let mut set = SgSet::<u32, CAPACITY>::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    while set.len() < CAPACITY {
+        set.insert(rng.gen_range(0, 100_000));
+    }
+    set.retain(|elem| elem % 3 != 0);
+
+    let par_elems: Vec<_> = set.par_iter().collect();
+    assert!(par_elems.into_iter().eq(set.iter()));
+    assert_eq!(
+        set.par_iter().map(|elem| *elem as u64).sum::<u64>(),
+        set.iter().map(|elem| *elem as u64).sum::<u64>()
+    );
+
+    let expected: Vec<_> = set.iter().copied().collect();
+    let owned: Vec<_> = set.into_par_iter().collect();
+    assert_eq!(owned, expected);
+}
@@ -0,0 +1,129 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use rkyv::api::low;
+use rkyv::rancor::{Error, Failure};
+use rkyv::rend::{u32_le, u64_le};
+use rkyv::ser::allocator::SubAllocator;
+use rkyv::ser::writer::Buffer;
+use rkyv::util::Align;
+
+use scapegoat::{SgMap, SgMapArchive, SgSet, SgSetArchive};
+
+const CAPACITY: usize = 512;
+
+#[test]
+fn test_rkyv_map() {
+    let mut map = SgMap::<u32, u64, CAPACITY>::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    // Irregular shape: random inserts, then removes
+    while map.len() < CAPACITY {
+        map.insert(rng.gen_range(0, 10_000), rng.gen_range(0, 1_000));
+    }
+    for _ in 0..(CAPACITY / 3) {
+        map.remove(&rng.gen_range(0, 10_000));
+    }
+
+    let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+    let archive = SgMapArchive::<u32, u64>::from_bytes::<Error>(&bytes).unwrap();
+    assert_eq!(archive.len(), map.len());
+    assert!(archive
+        .iter()
+        .map(|(k, v)| (k.to_native(), v.to_native()))
+        .eq(map.iter().map(|(k, v)| (*k, *v))));
+
+    for _ in 0..1_000 {
+        let key = rng.gen_range(0, 10_000);
+        assert_eq!(
+            archive
+                .get(&u32_le::from_native(key))
+                .map(|v| v.to_native()),
+            map.get(&key).copied()
+        );
+    }
+
+    let (lo, hi) = (u32_le::from_native(2_500), u32_le::from_native(7_500));
+    assert!(archive
+        .range(lo..hi)
+        .map(|(k, _)| k.to_native())
+        .eq(map.range(2_500..7_500).map(|(k, _)| *k)));
+    assert!(archive
+        .range(lo..=hi)
+        .rev()
+        .map(|(k, _)| k.to_native())
+        .eq(map.range(2_500..=7_500).rev().map(|(k, _)| *k)));
+    assert_eq!(archive.range(hi..lo).count(), 0);
+
+    let restored: SgMap<u32, u64, CAPACITY> = archive.to_map::<CAPACITY, Error>().unwrap();
+    assert_eq!(restored, map);
+
+    // Too small to restore
+    let err = archive.to_map::<16, Error>().unwrap_err();
+    assert_eq!(err.to_string(), "stack storage capacity exceeded");
+}
+
+#[test]
+fn test_rkyv_no_alloc() {
+    let map = SgMap::<u32, u64, 3>::from([(3, 30), (1, 10), (2, 20)]);
+
+    // Fixed buffers only, e.g. for a `no_std` target
+    let mut out = Align([0_u8; 256]);
+    let mut scratch = [core::mem::MaybeUninit::<u8>::uninit(); 256];
+    let bytes = low::to_bytes_in_with_alloc::<_, _, Failure>(
+        &map,
+        Buffer::from(&mut *out),
+        SubAllocator::new(&mut scratch),
+    )
+    .unwrap();
+
+    let archive = SgMapArchive::<u32, u64>::from_bytes::<Failure>(&bytes).unwrap();
+    assert_eq!(
+        archive.get(&u32_le::from_native(2)),
+        Some(&u64_le::from_native(20))
+    );
+    assert_eq!(archive.to_map::<3, Failure>().unwrap(), map);
+}
+
+#[test]
+fn test_rkyv_set() {
+    let set =
+        SgSet::<u32, CAPACITY>::from_sorted_iter((0..CAPACITY as u32).map(|i| i * 3)).unwrap();
+    let bytes = rkyv::to_bytes::<Error>(&set).unwrap();
+
+    let archive = SgSetArchive::<u32>::from_bytes::<Error>(&bytes).unwrap();
+    assert_eq!(archive.len(), set.len());
+    assert!(archive.contains(&u32_le::from_native(9)));
+    assert!(!archive.contains(&u32_le::from_native(10)));
+    assert!(archive
+        .range(u32_le::from_native(10)..)
+        .take(2)
+        .map(|elem| elem.to_native())
+        .eq([12, 15]));
+
+    let restored: SgSet<u32, CAPACITY> = archive.to_set::<CAPACITY, Error>().unwrap();
+    assert_eq!(restored, set);
+    assert!(archive.to_set::<1, Error>().is_err());
+
+    // Empty
+    let bytes = rkyv::to_bytes::<Error>(&SgSet::<u32, 1>::new()).unwrap();
+    let archive = SgSetArchive::<u32>::from_bytes::<Error>(&bytes).unwrap();
+    assert!(archive.is_empty());
+    assert!(archive.to_set::<1, Error>().unwrap().is_empty());
+}
+
+#[test]
+fn test_rkyv_rejects_invalid() {
+    // Same archived layout as a set, but unsorted
+    let bytes = rkyv::to_bytes::<Error>(&vec![1_u32, 3, 2]).unwrap();
+    let err = SgSetArchive::<u32>::from_bytes::<Error>(&bytes).unwrap_err();
+    assert_eq!(err.to_string(), "input not in strictly ascending order");
+
+    // Duplicates
+    let bytes = rkyv::to_bytes::<Error>(&vec![1_u32, 1]).unwrap();
+    assert!(SgSetArchive::<u32>::from_bytes::<Error>(&bytes).is_err());
+
+    // Truncated
+    let map = SgMap::<u32, u64, 2>::from([(1, 10), (2, 20)]);
+    let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+    assert!(SgMapArchive::<u32, u64>::from_bytes::<Error>(&bytes[..4]).is_err());
+}
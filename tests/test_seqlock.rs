@@ -0,0 +1,65 @@
+use std::thread;
+
+use scapegoat::{SgError, SgSeqLockMap};
+
+const CAPACITY: usize = 16;
+
+#[test]
+fn test_seqlock_basic() {
+    let map = SgSeqLockMap::<u32, &str, 2>::new();
+    assert_eq!(map.version(), 0);
+    assert!(map.read(|map| map.is_empty()));
+
+    assert_eq!(map.write(|map| map.insert(2, "b")), None);
+    assert_eq!(map.write(|map| map.insert(1, "a")), None);
+    assert_eq!(
+        map.write(|map| map.try_insert(3, "c")),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(map.write(|map| map.remove(&2)), Some("b"));
+    assert_eq!(map.version(), 4);
+
+    // Uncontended reads succeed on the first attempt
+    assert_eq!(map.try_read(0, |map| map.len()), Some(1));
+    assert!(map.into_inner().into_iter().eq([(1, "a")]));
+}
+
+#[test]
+fn test_seqlock_consistent_snapshots() {
+    let map = SgSeqLockMap::<u32, u32, CAPACITY>::new();
+    let writes = 200;
+
+    thread::scope(|s| {
+        // Each write sets every key to the same value
+        s.spawn(|| {
+            for val in 1..=writes {
+                map.write(|map| {
+                    for key in 0..(CAPACITY as u32) {
+                        map.insert(key, val);
+                    }
+                });
+            }
+        });
+
+        for _ in 0..4 {
+            s.spawn(|| {
+                let mut last_seen = 0;
+                while last_seen < writes {
+                    let opt_snapshot = map.try_read(8, |map| {
+                        let first = map.get(&0).copied().unwrap_or(0);
+                        assert!(map.values().all(|val| *val == first));
+                        first
+                    });
+
+                    if let Some(seen) = opt_snapshot {
+                        assert!(seen >= last_seen);
+                        last_seen = seen;
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.version(), writes as usize);
+    assert!(map.read(|map| map.values().all(|val| *val == writes)));
+}
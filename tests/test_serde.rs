@@ -0,0 +1,54 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter::FromIterator;
+
+use scapegoat::{Pairs, SgMap, SgSet};
+
+const DEFAULT_CAPACITY: usize = 10;
+
+#[test]
+fn test_serde_map_matches_btree_map() {
+    let btm: BTreeMap<String, u32> = (0..5).map(|i| (format!("k{}", i), i)).collect();
+    let sgm: SgMap<String, u32, DEFAULT_CAPACITY> = btm.clone().into_iter().collect();
+
+    let json = serde_json::to_string(&sgm).unwrap();
+    assert_eq!(json, serde_json::to_string(&btm).unwrap());
+
+    let round_trip: SgMap<String, u32, DEFAULT_CAPACITY> = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_trip, sgm);
+}
+
+#[test]
+fn test_serde_set_matches_btree_set() {
+    let bts: BTreeSet<i64> = [5, -1, 3, 9].iter().copied().collect();
+    let sgs: SgSet<i64, DEFAULT_CAPACITY> = bts.iter().copied().collect();
+
+    let json = serde_json::to_string(&sgs).unwrap();
+    assert_eq!(json, serde_json::to_string(&bts).unwrap());
+
+    let round_trip: SgSet<i64, DEFAULT_CAPACITY> = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_trip, sgs);
+}
+
+#[test]
+fn test_serde_pairs() {
+    let sgm = SgMap::<u8, bool, DEFAULT_CAPACITY>::from_iter([(2, true), (1, false)]);
+
+    let json = serde_json::to_string(&Pairs(sgm.clone())).unwrap();
+    assert_eq!(json, "[[1,false],[2,true]]");
+
+    let Pairs(round_trip): Pairs<SgMap<u8, bool, DEFAULT_CAPACITY>> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(round_trip, sgm);
+}
+
+#[test]
+fn test_serde_capacity_exceeded() {
+    // Duplicates don't count against capacity
+    assert!(serde_json::from_str::<SgSet<u8, 2>>("[1, 2, 2, 1]").is_ok());
+
+    let err = serde_json::from_str::<SgSet<u8, 2>>("[1, 2, 3]").unwrap_err();
+    assert!(err.to_string().contains("at most 2"));
+
+    assert!(serde_json::from_str::<SgMap<String, u8, 1>>(r#"{"a": 1, "b": 2}"#).is_err());
+    assert!(serde_json::from_str::<Pairs<SgMap<u8, u8, 1>>>("[[1, 1], [2, 2]]").is_err());
+}
@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 use std::iter::FromIterator;
 use std::ops::Bound::{Excluded, Included};
 
-use scapegoat::{sgset, SgError, SgSet};
+use scapegoat::{sgset, SgError, SgMap, SgSet};
 
 const DEFAULT_CAPACITY: usize = 10;
 
@@ -92,7 +92,7 @@ fn test_basic_set_functionality() {
 #[test]
 fn test_set_from_iter() {
     let keys = vec![1, 10, 100];
-    let sgs = SgSet::<_, 3>::from_iter(keys.into_iter());
+    let sgs = SgSet::<_, 3>::from_iter(keys);
 
     assert!(sgs.len() == 3);
     assert_eq!(sgs.into_iter().collect::<Vec<usize>>(), vec![1, 10, 100]);
@@ -107,7 +107,7 @@ fn test_set_from_iter_panic() {
 #[test]
 fn test_set_iter() {
     let keys = vec![1, 2, 3];
-    let sgs = SgSet::<_, 3>::from_iter(keys.into_iter());
+    let sgs = SgSet::<_, 3>::from_iter(keys);
     let mut sgs_iter = sgs.iter();
 
     assert_eq!(sgs_iter.next(), Some(&1));
@@ -227,6 +227,191 @@ fn test_set_is_disjoint() {
     assert!(!a.is_disjoint(&c));
 }
 
+#[test]
+fn test_set_replace_take_get_or_insert() {
+    use std::cmp::Ordering;
+
+    // Ordered by `id` only, `name` is payload
+    #[derive(Debug, Default, Clone)]
+    struct Sensor {
+        id: u32,
+        name: &'static str,
+    }
+
+    impl PartialEq for Sensor {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Sensor {}
+
+    impl PartialOrd for Sensor {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Sensor {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.id.cmp(&other.id)
+        }
+    }
+
+    let sensor = |id, name| Sensor { id, name };
+    let mut sgs = SgSet::<Sensor, 3>::new();
+
+    // Equal value swapped in place
+    assert_eq!(sgs.replace(sensor(1, "old")), None);
+    assert_eq!(sgs.replace(sensor(1, "new")).unwrap().name, "old");
+    assert_eq!(sgs.get(&sensor(1, "")).unwrap().name, "new");
+    assert_eq!(sgs.len(), 1);
+
+    // Existing value kept, missing inserted
+    assert_eq!(sgs.get_or_insert(sensor(1, "other")).name, "new");
+    assert_eq!(sgs.get_or_insert(sensor(2, "two")).name, "two");
+    assert_eq!(
+        sgs.get_or_insert_with(&sensor(3, ""), |s| sensor(s.id, "three"))
+            .name,
+        "three"
+    );
+    assert_eq!(sgs.len(), 3);
+
+    // Full: only new values fail
+    assert_eq!(
+        sgs.try_replace(sensor(2, "deux")).unwrap().unwrap().name,
+        "two"
+    );
+    assert_eq!(
+        sgs.try_replace(sensor(4, "")),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(
+        sgs.try_get_or_insert_with(&sensor(4, ""), |_| unreachable!()),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(sgs.try_get_or_insert(sensor(3, "")).unwrap().name, "three");
+
+    assert_eq!(sgs.take(&sensor(2, "")).unwrap().name, "deux");
+    assert_eq!(sgs.take(&sensor(2, "")), None);
+    assert!(sgs.iter().map(|s| s.name).eq(["new", "three"]));
+}
+
+#[test]
+#[should_panic(expected = "new value is not equal to the lookup value")]
+fn test_set_get_or_insert_with_unequal_panic() {
+    let mut sgs = SgSet::<u32, DEFAULT_CAPACITY>::new();
+    sgs.get_or_insert_with(&1, |_| 2);
+}
+
+#[test]
+fn test_set_operators() {
+    let a: SgSet<_, 6> = SgSet::from_iter([1, 2, 3, 4]);
+    let b: SgSet<_, 6> = SgSet::from_iter([3, 4, 5, 6]);
+
+    assert!((&a | &b).into_iter().eq(1..=6));
+    assert!((&a & &b).into_iter().eq(3..=4));
+    assert!((&a ^ &b).into_iter().eq([1, 2, 5, 6]));
+    assert!((&a - &b).into_iter().eq(1..=2));
+    assert!((&b - &a).into_iter().eq(5..=6));
+}
+
+#[test]
+#[should_panic(expected = "Stack-storage capacity exceeded!")]
+fn test_set_operator_overflow_panic() {
+    let a: SgSet<_, 4> = SgSet::from_iter([1, 2, 3]);
+    let b: SgSet<_, 4> = SgSet::from_iter([4, 5, 6]);
+    let _ = &a | &b;
+}
+
+#[test]
+fn test_set_relations_rand() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(0x5e7);
+
+    for _ in 0..500 {
+        let mut sgs_a = SgSet::<u32, 16>::new();
+        let mut sgs_b = SgSet::<u32, 16>::new();
+        for _ in 0..rng.gen_range(0, 8) {
+            sgs_a.insert(rng.gen_range(0, 12));
+        }
+        for _ in 0..rng.gen_range(0, 16) {
+            sgs_b.insert(rng.gen_range(0, 12));
+        }
+        let bts_a = BTreeSet::from_iter(sgs_a.iter().cloned());
+        let bts_b = BTreeSet::from_iter(sgs_b.iter().cloned());
+
+        assert_eq!(sgs_a.is_subset(&sgs_b), bts_a.is_subset(&bts_b));
+        assert_eq!(sgs_a.is_superset(&sgs_b), bts_a.is_superset(&bts_b));
+        assert_eq!(sgs_a.is_disjoint(&sgs_b), bts_a.is_disjoint(&bts_b));
+        assert_eq!(sgs_b.is_subset(&sgs_a), bts_b.is_subset(&bts_a));
+    }
+}
+
+#[test]
+fn test_set_overlap_count() {
+    let a = SgSet::<_, DEFAULT_CAPACITY>::from_iter([1, 2, 3, 4, 5, 6]);
+    let b = SgSet::<_, DEFAULT_CAPACITY>::from_iter([0, 2, 4, 6, 8]);
+    let c = SgSet::<_, DEFAULT_CAPACITY>::new();
+
+    let bts_a = BTreeSet::from_iter([1, 2, 3, 4, 5, 6]);
+    let bts_b = BTreeSet::from_iter([0, 2, 4, 6, 8]);
+
+    assert_eq!(a.overlap_count(&b), bts_a.intersection(&bts_b).count());
+    assert_eq!(b.overlap_count(&a), 3);
+    assert_eq!(a.overlap_count(&a), a.len());
+    assert_eq!(a.overlap_count(&c), 0);
+    assert_eq!(c.overlap_count(&a), 0);
+}
+
+#[test]
+fn test_set_jaccard() {
+    let a = SgSet::<_, DEFAULT_CAPACITY>::from_iter(["x", "y", "z"]);
+    let b = SgSet::<_, DEFAULT_CAPACITY>::from_iter(["y", "z", "w"]);
+    let c = SgSet::<_, DEFAULT_CAPACITY>::from_iter(["u", "v"]);
+    let empty = SgSet::<&str, DEFAULT_CAPACITY>::new();
+
+    assert_eq!(a.jaccard_ratio(&b), (2, 4));
+    assert_eq!(a.jaccard_ratio(&c), (0, 5));
+    assert_eq!(empty.jaccard_ratio(&empty), (0, 0));
+
+    assert_eq!(a.jaccard_similarity(&b), 0.5);
+    assert_eq!(a.jaccard_similarity(&b), b.jaccard_similarity(&a));
+    assert_eq!(a.jaccard_similarity(&c), 0.0);
+    assert_eq!(a.jaccard_similarity(&a), 1.0);
+    assert_eq!(empty.jaccard_similarity(&empty), 1.0);
+    assert_eq!(a.jaccard_similarity(&empty), 0.0);
+}
+
+#[test]
+fn test_set_difference_lens() {
+    let a = SgSet::<_, DEFAULT_CAPACITY>::from_iter([1, 2, 3, 4, 5, 6]);
+    let b = SgSet::<_, DEFAULT_CAPACITY>::from_iter([0, 2, 4, 6, 8]);
+    let empty = SgSet::<_, DEFAULT_CAPACITY>::new();
+
+    let bts_a = BTreeSet::from_iter([1, 2, 3, 4, 5, 6]);
+    let bts_b = BTreeSet::from_iter([0, 2, 4, 6, 8]);
+
+    assert_eq!(a.difference_len(&b), bts_a.difference(&bts_b).count());
+    assert_eq!(b.difference_len(&a), bts_b.difference(&bts_a).count());
+    assert_eq!(
+        a.symmetric_difference_len(&b),
+        bts_a.symmetric_difference(&bts_b).count()
+    );
+    assert_eq!(
+        a.symmetric_difference_len(&b),
+        b.symmetric_difference_len(&a)
+    );
+
+    assert_eq!(a.difference_len(&a), 0);
+    assert_eq!(a.symmetric_difference_len(&a), 0);
+    assert_eq!(a.difference_len(&empty), a.len());
+    assert_eq!(empty.difference_len(&a), 0);
+    assert_eq!(empty.symmetric_difference_len(&a), a.len());
+}
+
 // Fallible APIs -------------------------------------------------------------------------------------------------------
 
 #[test]
@@ -286,6 +471,26 @@ fn test_set_append_fallible() {
     );
 }
 
+#[test]
+fn test_set_extend_fallible() {
+    let mut a = SgSet::<_, 4>::new();
+    let batch = [2, 1, 2, 3, 4, 5, 6];
+
+    // Duplicates within the batch count as updates
+    let (report, rejected) = a.try_extend(batch.iter().copied()).unwrap_err();
+    assert_eq!(rejected, 5);
+    assert_eq!(report.inserted(), 4);
+    assert_eq!(report.updated(), 1);
+    assert_eq!(report.consumed(), 5);
+    assert!(a.is_full());
+
+    // Resume after making room
+    a.clear();
+    let report = a.try_extend(batch[report.consumed()..].iter().copied());
+    assert_eq!(report.map(|r| r.inserted()), Ok(2));
+    assert_eq!(a.into_iter().collect::<Vec<usize>>(), vec![5, 6]);
+}
+
 #[should_panic]
 #[test]
 fn test_set_insert_panic() {
@@ -393,3 +598,394 @@ fn test_set_macro_panic() {
         "d", // Capacity exceeded!
     };
 }
+
+#[test]
+fn test_set_map_conversion() {
+    let mut set = SgSet::<usize, DEFAULT_CAPACITY>::from_iter(0..5);
+    assert!(set.set_max_len(6).is_ok());
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    assert!(set.set_rebal_param(0.75, 1.0).is_ok());
+
+    // Tree config and contents survive the round trip
+    let map: SgMap<usize, (), DEFAULT_CAPACITY> = set.clone().into();
+    assert_eq!(map.max_len(), 6);
+    #[cfg(not(any(feature = "weight_balanced", feature = "avl")))]
+    assert_eq!(map.rebal_param(), (0.75, 1.0));
+    assert!(map.keys().eq(set.iter()));
+
+    let round_trip = SgSet::from(map);
+    assert_eq!(round_trip, set);
+    assert_eq!(round_trip.max_len(), 6);
+}
+
+#[test]
+fn test_range_set() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use scapegoat::SgRangeSet;
+
+    let mut ranges = SgRangeSet::<u32, DEFAULT_CAPACITY>::new();
+    let mut points = BTreeSet::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for _ in 0..2_000 {
+        let start = rng.gen_range(0, 64);
+        let end = rng.gen_range(0, 64);
+        if rng.gen_bool(0.5) {
+            if ranges.try_insert(start..end).is_ok() {
+                points.extend(start..end);
+            }
+        } else if ranges.try_remove(start..end).is_ok() {
+            (start..end).for_each(|p| {
+                points.remove(&p);
+            });
+        }
+
+        // Same points, as disjoint, non-adjoining, ascending ranges
+        assert!((0..64).all(|p| ranges.contains(&p) == points.contains(&p)));
+        assert!(ranges.iter().all(|r| r.start < r.end));
+        assert!(ranges
+            .iter()
+            .zip(ranges.iter().skip(1))
+            .all(|(a, b)| a.end < b.start));
+    }
+
+    // Full: disjoint insert and splitting remove fail, merging and trimming ones don't
+    ranges.clear();
+    (0..DEFAULT_CAPACITY as u32).for_each(|i| ranges.insert((i * 4)..(i * 4 + 3)));
+    assert_eq!(ranges.len(), ranges.capacity());
+    assert_eq!(
+        ranges.try_insert(100..101),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(ranges.try_remove(5..6), Err(SgError::StackCapacityExceeded));
+    assert_eq!(ranges.get(&5), Some(4..7));
+    assert!(ranges.try_remove(4..5).is_ok());
+    assert!(ranges.try_insert(3..5).is_ok());
+    assert_eq!(ranges.get(&0), Some(0..7));
+    assert!(ranges.try_remove(5..6).is_ok());
+    assert!(ranges.iter().take(3).eq([0..5, 6..7, 8..11]));
+    assert_eq!(format!("{:?}", ranges).get(..12), Some("{0..5, 6..7,"));
+}
+
+#[test]
+fn test_multi_set() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use scapegoat::SgMultiSet;
+    use std::collections::BTreeMap;
+
+    let mut multi = SgMultiSet::<u8, DEFAULT_CAPACITY>::new();
+    let mut model: BTreeMap<u8, usize> = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5ca9e);
+
+    for _ in 0..2_000 {
+        let elem = rng.gen_range(0, 2 * DEFAULT_CAPACITY as u8);
+        match rng.gen_range(0, 4) {
+            0 | 1 => match multi.try_insert(elem) {
+                Ok(cnt) => {
+                    *model.entry(elem).or_default() += 1;
+                    assert_eq!(cnt, model[&elem]);
+                }
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(!model.contains_key(&elem));
+                    assert_eq!(multi.distinct_len(), multi.capacity());
+                }
+            },
+            2 => {
+                let expected = match model.get_mut(&elem) {
+                    Some(cnt) => {
+                        *cnt -= 1;
+                        *cnt
+                    }
+                    None => 0,
+                };
+                model.retain(|_, cnt| *cnt > 0);
+                assert_eq!(multi.remove(&elem), expected);
+            }
+            _ => assert_eq!(multi.remove_all(&elem), model.remove(&elem).unwrap_or(0)),
+        }
+
+        assert_eq!(multi.len(), model.values().sum::<usize>());
+        assert_eq!(multi.distinct_len(), model.len());
+        assert_eq!(multi.is_empty(), model.is_empty());
+        assert!(multi.iter().eq(model.iter().map(|(e, cnt)| (e, *cnt))));
+        assert!((0..2 * DEFAULT_CAPACITY as u8)
+            .all(|e| (multi.count(&e) == model.get(&e).copied().unwrap_or(0))
+                && (multi.contains(&e) == model.contains_key(&e))));
+    }
+
+    // Borrowed lookups, collection, and formatting
+    let words: SgMultiSet<String, DEFAULT_CAPACITY> =
+        ["b", "a", "b"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(words.count("b"), 2);
+    assert_eq!(format!("{:?}", words), r#"{"a": 1, "b": 2}"#);
+
+    multi.clear();
+    assert!(multi.is_empty());
+    assert_eq!(multi.len(), 0);
+}
+
+#[test]
+fn test_set_ops_with_iter() {
+    use scapegoat::set_types::Merged;
+
+    let sgs: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter([1, 3, 5, 7, 9]);
+    let bts = BTreeSet::from_iter(sgs.iter().cloned());
+
+    for (lo, step) in [(0, 1), (2, 2), (3, 3), (10, 1)] {
+        let other = BTreeSet::from_iter((lo..20).step_by(step));
+        let stream = || (lo..20).step_by(step);
+
+        assert!(sgs
+            .intersection_with_iter(stream())
+            .map(|m| *m.get())
+            .eq(bts.intersection(&other).cloned()));
+        assert!(sgs
+            .difference_with_iter(stream())
+            .map(|m| *m.get())
+            .eq(bts.difference(&other).cloned()));
+        assert!(sgs
+            .symmetric_difference_with_iter(stream())
+            .map(|m| *m.get())
+            .eq(bts.symmetric_difference(&other).cloned()));
+        assert!(sgs
+            .union_with_iter(stream())
+            .map(|m| *m.get())
+            .eq(bts.union(&other).cloned()));
+    }
+
+    // Variants record provenance
+    assert!(sgs.union_with_iter([0, 1]).take(3).eq([
+        Merged::Iter(0),
+        Merged::Both(&1, 1),
+        Merged::Set(&3)
+    ]));
+
+    // Unbounded streams are only consumed as needed
+    assert_eq!(sgs.intersection_with_iter(9..).count(), 1);
+    assert_eq!(sgs.difference_with_iter(2..).count(), 1);
+    assert_eq!(sgs.union_with_iter(0..).nth(100), Some(Merged::Iter(100)));
+
+    // Borrowed items
+    let other = [3, 4, 5];
+    assert_eq!(sgs.intersection_with_iter(other.iter()).count(), 2);
+}
+
+#[test]
+fn test_set_pop_while() {
+    let mut sgs: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter(0..10);
+
+    assert!(sgs.pop_first_while(|n| n % 4 != 3).eq(0..3));
+    assert!(sgs.pop_last_while(|n| *n > 6).eq((7..10).rev()));
+    assert!(sgs.iter().cloned().eq(3..7));
+
+    assert_eq!(sgs.pop_last_while(|_| false).next(), None);
+    assert_eq!(sgs.len(), 4);
+}
+
+#[test]
+fn test_set_drain_front_back() {
+    let mut sgs: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter(0..10);
+
+    assert!(sgs.drain_front(4).eq(0..4));
+    assert!(sgs.drain_back(4).eq((6..10).rev()));
+    assert!(sgs.iter().cloned().eq(4..6));
+    assert!(sgs.drain_front(10).eq(4..6));
+    assert!(sgs.is_empty());
+}
+
+#[test]
+fn test_set_extract_if() {
+    let mut sgs: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter(0..10);
+
+    assert!(sgs.extract_if(|n| n % 2 == 1).eq([1, 3, 5, 7, 9]));
+    assert!(sgs.iter().cloned().eq([0, 2, 4, 6, 8]));
+
+    assert_eq!(sgs.extract_if(|n| *n > 2).next(), Some(4));
+    assert!(sgs.iter().cloned().eq([0, 2, 6, 8]));
+
+    sgs.insert(5);
+    assert_eq!(sgs.extract_if(|_| true).count(), 5);
+    assert!(sgs.is_empty());
+}
+
+#[test]
+fn test_set_cursor() {
+    let mut sgs: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter([10, 20, 30, 40]);
+
+    let cursor = sgs.lower_bound(Excluded(&20));
+    assert_eq!(cursor.get(), Some(&30));
+    assert_eq!(cursor.peek_prev(), Some(&20));
+    assert_eq!(sgs.upper_bound(Included(&35)).get(), Some(&30));
+    assert_eq!(sgs.upper_bound(Excluded(&10)).get(), None);
+
+    let mut cursor = sgs.lower_bound_mut(Included(&20));
+    assert_eq!(cursor.insert_after(30), Err(SgError::UnsortedInput));
+    assert!(cursor.insert_after(25).is_ok());
+    assert_eq!(cursor.remove_current(), Some(20));
+    assert_eq!(cursor.get(), Some(&25));
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.get(), None);
+    assert!(cursor.insert_before(50).is_ok());
+    assert_eq!(cursor.remove_current_and_move_back(), None);
+
+    assert!(sgs.into_iter().eq([10, 25, 30, 40, 50]));
+}
+
+#[test]
+fn test_set_double_ended_iters() {
+    let sgs_a: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter([1, 3, 5, 7, 9]);
+    let sgs_b: SgSet<u32, DEFAULT_CAPACITY> = SgSet::from_iter([2, 3, 4, 5]);
+    let bts_a = BTreeSet::from_iter(sgs_a.iter().cloned());
+    let bts_b = BTreeSet::from_iter(sgs_b.iter().cloned());
+
+    assert!(sgs_a.iter().rev().eq(bts_a.iter().rev()));
+    assert!(sgs_a
+        .clone()
+        .into_iter()
+        .rev()
+        .eq(bts_a.clone().into_iter().rev()));
+
+    let mut iter = sgs_a.iter();
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&9));
+    assert_eq!(iter.len(), 3);
+    assert!(iter.eq([3, 5, 7].iter()));
+
+    assert!(sgs_a.intersection(&sgs_b).rev().eq(bts_a
+        .intersection(&bts_b)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()));
+    assert!(sgs_a.difference(&sgs_b).rev().eq(bts_a
+        .difference(&bts_b)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()));
+    assert!(sgs_a.symmetric_difference(&sgs_b).rev().eq(bts_a
+        .symmetric_difference(&bts_b)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()));
+    assert!(sgs_a.union(&sgs_b).rev().eq(bts_a
+        .union(&bts_b)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()));
+
+    let mut union = sgs_a.union(&sgs_b);
+    assert_eq!(union.next_back(), Some(&9));
+    assert_eq!(union.next(), Some(&1));
+    assert_eq!(union.size_hint(), (4, Some(7)));
+    assert!(union.eq([2, 3, 4, 5, 7].iter()));
+}
+
+#[test]
+fn test_set_ops_lazy() {
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(0x5e70b);
+
+    for _ in 0..200 {
+        let mut sgs_a = SgSet::<u32, 64>::new();
+        let mut sgs_b = SgSet::<u32, 64>::new();
+        for _ in 0..rng.gen_range(0, 64) {
+            sgs_a.insert(rng.gen_range(0, 96));
+        }
+        for _ in 0..rng.gen_range(0, 64) {
+            sgs_b.insert(rng.gen_range(0, 96));
+        }
+        let bts_a = BTreeSet::from_iter(sgs_a.iter().cloned());
+        let bts_b = BTreeSet::from_iter(sgs_b.iter().cloned());
+
+        let expected: [Vec<&u32>; 4] = [
+            bts_a.intersection(&bts_b).collect(),
+            bts_a.difference(&bts_b).collect(),
+            bts_a.symmetric_difference(&bts_b).collect(),
+            bts_a.union(&bts_b).collect(),
+        ];
+
+        // Consume from randomly alternating ends
+        for (op, expected) in expected.iter().enumerate() {
+            let mut iter: Box<dyn DoubleEndedIterator<Item = &u32>> = match op {
+                0 => Box::new(sgs_a.intersection(&sgs_b)),
+                1 => Box::new(sgs_a.difference(&sgs_b)),
+                2 => Box::new(sgs_a.symmetric_difference(&sgs_b)),
+                _ => Box::new(sgs_a.union(&sgs_b)),
+            };
+
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            loop {
+                let (lo, hi) = iter.size_hint();
+                let remaining = expected.len() - front.len() - back.len();
+                assert!(lo <= remaining && hi.unwrap() >= remaining);
+
+                let opt_item = match rng.gen_bool(0.5) {
+                    true => iter.next().map(|item| front.push(item)),
+                    false => iter.next_back().map(|item| back.push(item)),
+                };
+
+                if opt_item.is_none() {
+                    break;
+                }
+            }
+
+            assert!(iter.next().is_none() && iter.next_back().is_none());
+            front.extend(back.into_iter().rev());
+            assert_eq!(&front, expected);
+        }
+    }
+
+    // No `2 * N` sized buffer: disjoint sets of capacity over 2048
+    const LARGE_CAPACITY: u32 = 2_080;
+    type LargeSet = SgSet<u32, { LARGE_CAPACITY as usize }>;
+
+    // Large sets may exceed the default 2MB test thread stack (e.g. with feature `cacheline_nodes`)
+    let result = std::thread::Builder::new()
+        .stack_size(16 * core::mem::size_of::<LargeSet>())
+        .spawn(|| {
+            let sgs_a: LargeSet = (0..LARGE_CAPACITY).map(|i| i * 2).collect();
+            let sgs_b: LargeSet = (0..LARGE_CAPACITY).map(|i| i * 2 + 1).collect();
+            assert!(sgs_a.union(&sgs_b).cloned().eq(0..(LARGE_CAPACITY * 2)));
+            assert!(sgs_a
+                .symmetric_difference(&sgs_b)
+                .rev()
+                .cloned()
+                .eq((0..(LARGE_CAPACITY * 2)).rev()));
+            assert_eq!(sgs_a.intersection(&sgs_b).next(), None);
+            assert_eq!(sgs_a.difference(&sgs_b).count(), LARGE_CAPACITY as usize);
+        })
+        .unwrap()
+        .join();
+
+    if let Err(panic) = result {
+        std::panic::resume_unwind(panic);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_set_with_capacity() {
+    const RUNTIME_CAPACITY: u32 = 3_000;
+
+    let mut sgs = SgSet::<u32, 0>::with_capacity(RUNTIME_CAPACITY as usize);
+    assert_eq!(sgs.capacity(), RUNTIME_CAPACITY as usize);
+
+    (0..RUNTIME_CAPACITY)
+        .rev()
+        .for_each(|i| assert!(sgs.insert(i)));
+    assert!(sgs.is_full());
+    assert_eq!(
+        sgs.try_insert(RUNTIME_CAPACITY),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert!(sgs.iter().copied().eq(0..RUNTIME_CAPACITY));
+
+    sgs.retain(|i| i % 2 == 0);
+    assert_eq!(sgs.len(), (RUNTIME_CAPACITY / 2) as usize);
+    assert!(sgs.iter().copied().eq((0..RUNTIME_CAPACITY).step_by(2)));
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::thread;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use scapegoat::{SgError, SgShardedMap};
+
+const SHARD_CAPACITY: usize = 256;
+const SHARDS: usize = 4;
+
+#[test]
+fn test_sharded_basic() {
+    let map = SgShardedMap::<u32, &str, SHARD_CAPACITY, SHARDS>::new();
+    assert!(map.is_empty());
+    assert_eq!(map.shard_count(), SHARDS);
+    assert_eq!(map.capacity(), SHARD_CAPACITY * SHARDS);
+
+    assert_eq!(map.insert(2, "b"), None);
+    assert_eq!(map.insert(1, "a"), None);
+    assert_eq!(map.try_insert(2, "B"), Ok(Some("b")));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&2), Some("B"));
+    assert!(map.contains_key(&1));
+
+    assert_eq!(map.remove(&1), Some("a"));
+    assert!(!map.contains_key(&1));
+
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_sharded_shard_full() {
+    let map = SgShardedMap::<u32, u32, 1, 2>::new();
+
+    // Pigeonhole: 3 keys, 2 single-slot shards
+    let results: Vec<_> = (0..3).map(|k| map.try_insert(k, k)).collect();
+    assert!(results.contains(&Err(SgError::StackCapacityExceeded)));
+    assert!(map.len() <= 2);
+}
+
+#[test]
+fn test_sharded_ordered_iter() {
+    let map = SgShardedMap::<u32, u32, SHARD_CAPACITY, SHARDS>::new();
+    let mut reference = BTreeMap::new();
+    let mut rng = SmallRng::seed_from_u64(0x5a4d);
+
+    for _ in 0..(SHARD_CAPACITY / 2) {
+        let (k, v) = (rng.gen_range(0, 1_000), rng.gen());
+        assert_eq!(map.insert(k, v), reference.insert(k, v));
+    }
+
+    let guard = map.read();
+    assert_eq!(guard.len(), reference.len());
+    assert!(guard.iter().eq(reference.iter()));
+}
+
+#[test]
+fn test_sharded_concurrent() {
+    let map = SgShardedMap::<usize, usize, SHARD_CAPACITY, SHARDS>::new();
+    let threads = 4;
+    let per_thread = 100;
+
+    thread::scope(|s| {
+        for t in 0..threads {
+            let map = &map;
+            s.spawn(move || {
+                for i in 0..per_thread {
+                    let k = i * threads + t;
+                    assert!(map.try_insert(k, t).is_ok());
+                    assert_eq!(map.get(&k), Some(t));
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.len(), threads * per_thread);
+    assert!(map
+        .read()
+        .iter()
+        .map(|(k, _)| *k)
+        .eq(0..(threads * per_thread)));
+}
+
+#[test]
+fn test_sharded_poison() {
+    use std::cmp::Ordering;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[derive(PartialEq, Eq, Hash)]
+    struct Key(u32);
+
+    impl PartialOrd for Key {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Key {
+        fn cmp(&self, other: &Self) -> Ordering {
+            assert!(self.0 != 13 && other.0 != 13, "Unlucky key!");
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let map = SgShardedMap::<Key, u32, SHARD_CAPACITY, 1>::new();
+    map.insert(Key(1), 1);
+
+    // Panic while the shard is write-locked
+    assert!(catch_unwind(AssertUnwindSafe(|| map.insert(Key(13), 13))).is_err());
+
+    // Poison is propagated, not ignored
+    assert!(catch_unwind(AssertUnwindSafe(|| map.get(&Key(1)))).is_err());
+}
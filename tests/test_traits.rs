@@ -22,3 +22,27 @@ fn test_default_map() {
 fn test_default_set() {
     is_default::<SgSet<usize, 10>>();
 }
+
+#[test]
+fn test_error_display() {
+    let mut map = SgMap::<usize, usize, 1>::new();
+    map.insert(1, 1);
+    let err = map.try_insert(2, 2).unwrap_err();
+    assert_eq!(err.to_string(), "stack storage capacity exceeded");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_error_trait() {
+    use scapegoat::SgError;
+
+    fn fill() -> Result<(), Box<dyn std::error::Error>> {
+        let mut set = SgSet::<usize, 1>::new();
+        set.try_insert(1)?;
+        set.try_insert(2)?;
+        Ok(())
+    }
+
+    let err = fill().unwrap_err();
+    assert_eq!(err.downcast_ref(), Some(&SgError::StackCapacityExceeded));
+}